@@ -93,3 +93,8 @@ buff-text-over_seconds = over { $dur_secs } seconds
 buff-text-for_seconds = for { $dur_secs } seconds
 buff-mysterious = Mysterious effect
 buff-remove = Click to remove
+buff-stat-learn_recipes =
+    Teaches { $count ->
+        [1] a recipe
+       *[other] { $count } recipes
+    }
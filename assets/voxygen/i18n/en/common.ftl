@@ -111,5 +111,9 @@ common-material-wood = Wood
 common-material-stone = Stone
 common-material-cloth = Cloth
 common-material-hide = Hide
+common-craft_quality-crude = Crude
+common-craft_quality-standard = Standard
+common-craft_quality-fine = Fine
+common-craft_quality-masterwork = Masterwork
 common-sprite-chest = Chest
 common-sprite-chair = Chair
@@ -0,0 +1,14 @@
+hud-achievement_unlocked = Achievement unlocked: { $achievement }
+hud-achievement-first_blood = First Blood
+hud-achievement-hunter = Hunter
+hud-achievement-slayer = Slayer
+hud-achievement-giant_slayer = Giant Slayer
+hud-achievement-back_from_the_dead = Back from the Dead
+hud-achievement-wanderer = Wanderer
+hud-achievement-voyager = Voyager
+hud-achievement-explorer = Explorer
+hud-achievement-cartographer = Cartographer
+hud-achievement-apprentice_crafter = Apprentice Crafter
+hud-achievement-master_crafter = Master Crafter
+hud-achievement-dungeon_delver = Dungeon Delver
+hud-achievement-dungeon_master = Dungeon Master
@@ -0,0 +1 @@
+hud-bank-title = Bank
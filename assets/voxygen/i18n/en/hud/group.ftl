@@ -1,6 +1,8 @@
 hud-group = Group
 hud-group-invite_to_join = [{ $name }] invited you to their group!
+hud-group-invite_to_join_guild = [{ $name }] invited you to join their guild!
 hud-group-invite_to_trade = [{ $name }] would like to trade with you.
+hud-group-invite_to_duel = [{ $name }] challenged you to a duel!
 hud-group-invite = Invite
 hud-group-kick = Kick
 hud-group-assign_leader = Assign Leader
@@ -10,4 +12,8 @@ hud-group-out_of_range = Out of range
 hud-group-add_friend = Add to Friends
 hud-group-link_group = Link Groups
 hud-group-in_menu = In Menu
-hud-group-members = Group Members
\ No newline at end of file
+hud-group-members = Group Members
+hud-group-aggro = { $count ->
+    [one] { $count } enemy
+    *[other] { $count } enemies
+}
\ No newline at end of file
@@ -0,0 +1,11 @@
+hud-mail-title = Mailbox
+hud-mail-empty = You have no mail.
+hud-mail-from = { $attachments ->
+    [0] From { $sender }
+    [one] From { $sender } (1 item)
+   *[other] From { $sender } ({ $attachments } items)
+}
+hud-mail-claim = Take
+hud-mail-recipient = To
+hud-mail-body = Message
+hud-mail-send = Send
@@ -34,4 +34,8 @@ hud-map-zoom_minimap_explanation =
 hud-map-gnarling = Gnarling Fortification
 hud-map-chapel_site = Sea Chapel
 hud-map-adlet = Adlet Stronghold
-hud-map-placed_by = Placed by { $name }
\ No newline at end of file
+hud-map-placed_by = Placed by { $name }
+hud-map-faction_capital = Capital of its faction
+hud-map-faction_held_by = Held by the faction of { $capital }
+hud-map-faction_allies = Allied with { $factions }
+hud-map-faction_enemies = At war with { $factions }
\ No newline at end of file
@@ -0,0 +1,11 @@
+hud-market-title = { $site } Market
+hud-market-empty = There are no open orders.
+hud-market-selling = Selling { $quantity } × { $item } for { $price } coins each
+hud-market-buying = Buying { $quantity } × { $item } for { $price } coins each
+hud-market-owner = by { $owner }
+hud-market-reference = Merchants: { $buy }, { $sell }
+hud-market-quantity = Quantity
+hud-market-price = Price
+hud-market-sell = Sell
+hud-market-buy = Buy
+hud-market-withdraw = Withdraw
@@ -33,6 +33,22 @@ hud-temp_quest_text =
     Whenever you feel ready, try to get even better equipment from the many challenges marked on your map!
 hud-spell = Spells
 hud-diary = Diary
+hud-diary-reputation-none = Nobody knows who you are... yet.
+hud-diary-reputation-faction = Faction of { $capital }
+hud-diary-reputation-hated = Hated
+hud-diary-reputation-hostile = Hostile
+hud-diary-reputation-unfriendly = Unfriendly
+hud-diary-reputation-neutral = Neutral
+hud-diary-reputation-friendly = Friendly
+hud-diary-reputation-honored = Honored
+hud-diary-reputation-revered = Revered
+hud-diary-statistics-kills = Kills
+hud-diary-statistics-deaths = Deaths
+hud-diary-statistics-distance_travelled = Distance Travelled
+hud-diary-statistics-items_crafted = Items Crafted
+hud-diary-statistics-sites_visited = Places Visited
+hud-diary-statistics-dungeons_cleared = Dungeons Cleared
+hud-diary-achievement-unlocked = Unlocked
 hud-free_look_indicator = Free look active. Press { $key } to disable.
 hud-camera_clamp_indicator = Camera vertical clamp active. Press { $key } to disable.
 hud-auto_walk_indicator = Auto walk/swim active
@@ -40,6 +56,7 @@ hud-zoom_lock_indicator-remind = Zoom locked
 hud-zoom_lock_indicator-enable = Camera zoom locked
 hud-zoom_lock_indicator-disable = Camera zoom unlocked
 hud-collect = Collect
+hud-plant = Plant seeds
 hud-pick_up = Pick up
 hud-open = Open
 hud-use = Use
@@ -142,7 +142,7 @@ hud-settings-messages = Messages
 hud-settings-activity = Activity
 hud-settings-death = Death
 hud-settings-group = Group
-hud-settings-faction = Faction
+hud-settings-guild = Guild
 hud-settings-world = World
 hud-settings-region = Region
 hud-settings-say = Say
@@ -53,6 +53,7 @@ main-login-kicked = You have been kicked with the following reason
 main-login-select_language = Select a language
 main-login-client_version = Client Version
 main-login-server_version = Server Version
+main-login-replay_error = Failed to read the recording
 main-login-client_init_failed = Client failed to initialize: { $init_fail_reason }
 main-login-username_bad_characters = Username contains invalid characters! (Only alphanumeric, '_' and '-' are allowed)
 main-login-username_too_long = Username is too long! Max length is: { $max_len }
@@ -64,6 +65,10 @@ main-servers-stream_error = Client connection/compression/(de)serialization erro
 main-servers-database_error = Server database error: { $raw_error }
 main-servers-persistence_error = Server persistence error (Probably Asset/Character Data related): { $raw_error }
 main-servers-other_error = Server general error: { $raw_error }
+main-replays = Replays
+main-replays-select_replay = Select a recording
+main-replays-watch = Watch
+main-replays-none = There are no recordings yet. Type /record while playing to make one.
 main-credits = Credits
 main-credits-created_by = created by
 main-credits-music = Music
@@ -236,6 +236,10 @@ npc-speech-day_time =
     .a1 = I never liked waking up...
 npc-speech-start_hunting =
     .a0 = Time to go hunting!
+npc-speech-rain_shelter =
+    .a0 = It's pouring, I'd better get inside!
+    .a1 = Not this rain again...
+    .a2 = I'm getting soaked!
 npc-speech-guard_thought =
     .a0 = My brother's out fighting ogres. What do I get? Guard duty...
     .a1 = Just one more patrol, then I can head home.
@@ -269,6 +273,29 @@ npc-speech-witness_death =
     .a0 = No!
     .a1 = This is terrible!
     .a2 = Oh my goodness!
+npc-speech-start_raid =
+    .a0 = To arms! We march on the enemy!
+    .a1 = Their walls will fall before nightfall.
+    .a2 = For the glory of our people!
+npc-speech-site_captured_victory =
+    .a0 = Victory! The town is ours!
+    .a1 = Another banner raised in our name.
+npc-speech-site_captured_defeat =
+    .a0 = We've been conquered...
+    .a1 = Those invaders will pay for this.
+    .a2 = I never thought I'd see our town fall.
+npc-speech-reputation_hostile =
+    .a0 = You're not welcome here.
+    .a1 = I know what you've done. Stay away from me.
+    .a2 = We don't want your kind around here.
+npc-speech-reputation_friendly =
+    .a0 = Always good to see you, friend!
+    .a1 = Everyone's been talking about you. Good things, mind!
+    .a2 = Welcome back! You're always welcome here.
+npc-speech-market_day =
+    .a0 = Fresh goods, fine prices! Come and see!
+    .a1 = Market's open! Get them while they last!
+    .a2 = Best deals in town, right here!
 npc-speech-dir_north = north
 npc-speech-dir_north_east = north-east
 npc-speech-dir_east = east
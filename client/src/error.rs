@@ -1,4 +1,5 @@
 use authc::AuthClientError;
+use common_net::replay::ReplayError;
 pub use network::{InitProtocolError, NetworkConnectError, NetworkError};
 use network::{ParticipantError, StreamError};
 use specs::error::Error as SpecsError;
@@ -24,6 +25,8 @@ pub enum Error {
     //TODO: InvalidAlias,
     Other(String),
     SpecsErr(SpecsError),
+    /// Failed to read or write a recording
+    Replay(ReplayError),
 }
 
 impl From<SpecsError> for Error {
@@ -45,3 +48,7 @@ impl From<StreamError> for Error {
 impl From<AuthClientError> for Error {
     fn from(err: AuthClientError) -> Self { Self::AuthClientError(err) }
 }
+
+impl From<ReplayError> for Error {
+    fn from(err: ReplayError) -> Self { Self::Replay(err) }
+}
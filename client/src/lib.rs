@@ -4,6 +4,7 @@
 
 pub mod addr;
 pub mod error;
+pub mod recording;
 
 // Reexports
 pub use crate::error::Error;
@@ -15,9 +16,13 @@ pub use specs::{
     Builder, DispatcherBuilder, Entity as EcsEntity, ReadStorage, World, WorldExt,
 };
 
-use crate::addr::ConnectionArgs;
+use crate::{
+    addr::ConnectionArgs,
+    recording::{Playback, Recorder, RecordingHeader, RecordingSnapshot},
+};
 use byteorder::{ByteOrder, LittleEndian};
 use common::{
+    calendar::Calendar,
     character::{CharacterId, CharacterItem},
     comp::{
         self,
@@ -58,12 +63,12 @@ use common_base::{prof_span, span};
 use common_net::{
     msg::{
         self,
-        world_msg::{EconomyInfo, PoiInfo, SiteId, SiteInfo},
+        world_msg::{EconomyInfo, PoiInfo, SiteFactions, SiteId, SiteInfo},
         ChatTypeContext, ClientGeneral, ClientMsg, ClientRegister, ClientType, DisconnectReason,
         InviteAnswer, Notification, PingMsg, PlayerInfo, PlayerListUpdate, RegisterError,
         ServerGeneral, ServerInit, ServerRegisterAnswer,
     },
-    sync::WorldSyncExt,
+    sync::{CompSyncPackage, WorldSyncExt},
 };
 use common_state::State;
 use common_systems::add_local_systems;
@@ -73,10 +78,12 @@ use image::DynamicImage;
 use network::{ConnectAddr, Network, Participant, Pid, Stream};
 use num::traits::FloatConst;
 use rayon::prelude::*;
+use serde::de::DeserializeOwned;
 use specs::Component;
 use std::{
     collections::{BTreeMap, VecDeque},
     mem,
+    path::{Path, PathBuf},
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -209,11 +216,26 @@ impl Default for WeatherLerp {
     }
 }
 
+/// The connection to the server, which clients that play back a recording
+/// don't have
+struct Connection {
+    network: Network,
+    participant: Participant,
+    general_stream: Stream,
+    ping_stream: Stream,
+    register_stream: Stream,
+    character_screen_stream: Stream,
+    in_game_stream: Stream,
+    terrain_stream: Stream,
+}
+
 pub struct Client {
     registered: bool,
     presence: Option<PresenceKind>,
     runtime: Arc<Runtime>,
     server_info: ServerInfo,
+    // Kept to be able to start recordings, which need it for playback
+    server_init: ServerInit,
     world_data: WorldData,
     weather: WeatherLerp,
     player_list: HashMap<Uid, PlayerInfo>,
@@ -221,11 +243,18 @@ pub struct Client {
     sites: HashMap<SiteId, SiteInfoRich>,
     possible_starting_sites: Vec<SiteId>,
     pois: Vec<PoiInfo>,
+    site_factions: SiteFactions,
     pub chat_mode: ChatMode,
     recipe_book: RecipeBook,
     component_recipe_book: ComponentRecipeBook,
     repair_recipe_book: RepairRecipeBook,
     available_recipes: HashMap<String, Option<SpriteKind>>,
+    // The recipes that the player's character has learned
+    known_recipes: comp::KnownRecipes,
+    // The lifetime statistics of the player's character
+    statistics: comp::Statistics,
+    // The achievements that the player has unlocked
+    achievements: comp::Achievements,
     lod_zones: HashMap<Vec2<i32>, lod::Zone>,
     lod_last_requested: Option<Instant>,
     force_update_counter: u64,
@@ -240,15 +269,20 @@ pub struct Client {
     pending_invites: HashSet<Uid>,
     // The pending trade the client is involved in, and it's id
     pending_trade: Option<(TradeId, PendingTrade, Option<SitePrices>)>,
-
-    network: Option<Network>,
-    participant: Option<Participant>,
-    general_stream: Stream,
-    ping_stream: Stream,
-    register_stream: Stream,
-    character_screen_stream: Stream,
-    in_game_stream: Stream,
-    terrain_stream: Stream,
+    // The standings of the player's character with sites and factions
+    reputation: comp::Reputation,
+    guild: Option<comp::GuildRoster>,
+    // The bank the player is currently using, and the position of its sprite
+    bank: Option<(Vec3<i32>, comp::Bank)>,
+    // The mailbox the player is currently using, and the position of its sprite
+    mailbox: Option<(Vec3<i32>, comp::Mailbox)>,
+    // The market board the player is currently using, and the position of its
+    // sprite
+    market: Option<(Vec3<i32>, comp::MarketBoard)>,
+
+    connection: Option<Connection>,
+    recorder: Option<Recorder>,
+    playback: Option<Playback>,
 
     client_timeout: Duration,
     last_server_ping: f64,
@@ -354,6 +388,61 @@ impl Client {
 
         // Wait for initial sync
         let mut ping_interval = tokio::time::interval(Duration::from_secs(1));
+        let init = loop {
+            tokio::select! {
+                res = register_stream.recv() => break res?,
+                _ = ping_interval.tick() => ping_stream.send(PingMsg::Ping)?,
+            }
+        };
+
+        Self::from_init(
+            runtime,
+            server_info,
+            init,
+            Some(Connection {
+                network,
+                participant,
+                general_stream: stream,
+                ping_stream,
+                register_stream,
+                character_screen_stream,
+                in_game_stream,
+                terrain_stream,
+            }),
+        )
+        .await
+    }
+
+    /// Creates a client that plays back a recording made with
+    /// [`Client::start_recording`], as a spectator.
+    pub async fn new_playback(path: PathBuf, runtime: Arc<Runtime>) -> Result<Self, Error> {
+        let (playback, header, snapshot) = tokio::task::block_in_place(|| Playback::open(path))?;
+        let mut client = Self::from_init(runtime, header.server_info, header.init, None).await?;
+
+        // Watch the recording through an entity that only exists on this client
+        let spectator = client
+            .state
+            .ecs_mut()
+            .create_entity()
+            .with(comp::Pos(Vec3::zero()))
+            .with(comp::Vel(Vec3::zero()))
+            .with(comp::Ori::default())
+            .build();
+        *client.state.ecs_mut().write_resource() = PlayerEntity(Some(spectator));
+        client.presence = Some(PresenceKind::Spectator);
+        client.playback = Some(playback);
+        client.load_recording_snapshot(snapshot);
+
+        Ok(client)
+    }
+
+    async fn from_init(
+        runtime: Arc<Runtime>,
+        server_info: ServerInfo,
+        init: ServerInit,
+        connection: Option<Connection>,
+    ) -> Result<Self, Error> {
+        let server_init = init.clone();
         let ServerInit::GameSync {
             entity_package,
             time_of_day,
@@ -366,13 +455,10 @@ impl Client {
             ability_map,
             server_constants,
             repair_recipe_book,
-        } = loop {
-            tokio::select! {
-                // Spawn in a blocking thread (leaving the network thread free).  This is mostly
-                // useful for bots.
-                res = register_stream.recv() => break res?,
-                _ = ping_interval.tick() => ping_stream.send(PingMsg::Ping)?,
-            }
+        } = init;
+        let ping = || match &connection {
+            Some(connection) => connection.ping_stream.send(PingMsg::Ping),
+            None => Ok(()),
         };
 
         // Spawn in a blocking thread (leaving the network thread free).  This is mostly
@@ -658,6 +744,7 @@ impl Client {
                 world_map.sites,
                 world_map.possible_starting_sites,
                 world_map.pois,
+                world_map.factions,
                 recipe_book,
                 component_recipe_book,
                 repair_recipe_book,
@@ -675,26 +762,31 @@ impl Client {
             sites,
             possible_starting_sites,
             pois,
+            site_factions,
             recipe_book,
             component_recipe_book,
             repair_recipe_book,
             max_group_size,
             client_timeout,
-        ) = loop {
-            tokio::select! {
-                res = &mut task => break res.expect("Client thread should not panic")?,
-                _ = ping_interval.tick() => ping_stream.send(PingMsg::Ping)?,
+        ) = {
+            let mut ping_interval = tokio::time::interval(Duration::from_secs(1));
+            loop {
+                tokio::select! {
+                    res = &mut task => break res.expect("Client thread should not panic")?,
+                    _ = ping_interval.tick() => ping()?,
+                }
             }
         };
-        ping_stream.send(PingMsg::Ping)?;
+        ping()?;
 
         debug!("Initial sync done");
 
         Ok(Self {
-            registered: true,
+            registered: connection.is_some(),
             presence: None,
             runtime,
             server_info,
+            server_init,
             world_data: WorldData {
                 lod_base,
                 lod_alt,
@@ -715,10 +807,14 @@ impl Client {
                 .collect(),
             possible_starting_sites,
             pois,
+            site_factions,
             recipe_book,
             component_recipe_book,
             repair_recipe_book,
             available_recipes: HashMap::default(),
+            known_recipes: comp::KnownRecipes::default(),
+            statistics: comp::Statistics::default(),
+            achievements: comp::Achievements::default(),
             chat_mode: ChatMode::default(),
 
             lod_zones: HashMap::new(),
@@ -732,15 +828,15 @@ impl Client {
             group_members: HashMap::new(),
             pending_invites: HashSet::new(),
             pending_trade: None,
+            reputation: comp::Reputation::default(),
+            guild: None,
+            bank: None,
+            mailbox: None,
+            market: None,
 
-            network: Some(network),
-            participant: Some(participant),
-            general_stream: stream,
-            ping_stream,
-            register_stream,
-            character_screen_stream,
-            in_game_stream,
-            terrain_stream,
+            connection,
+            recorder: None,
+            playback: None,
 
             client_timeout,
 
@@ -829,6 +925,10 @@ impl Client {
         S: Into<ClientMsg>,
     {
         prof_span!("send_msg_err");
+        // Messages have nowhere to go while playing back a recording
+        let Some(connection) = self.connection.as_mut() else {
+            return Ok(());
+        };
         let msg: ClientMsg = msg.into();
         #[cfg(debug_assertions)]
         {
@@ -848,8 +948,8 @@ impl Client {
             }
         }
         match msg {
-            ClientMsg::Type(msg) => self.register_stream.send(msg),
-            ClientMsg::Register(msg) => self.register_stream.send(msg),
+            ClientMsg::Type(msg) => connection.register_stream.send(msg),
+            ClientMsg::Register(msg) => connection.register_stream.send(msg),
             ClientMsg::General(msg) => {
                 #[cfg(feature = "tracy")]
                 let (mut ingame, mut terrain) = (0.0, 0.0);
@@ -859,7 +959,7 @@ impl Client {
                     | ClientGeneral::EditCharacter { .. }
                     | ClientGeneral::DeleteCharacter(_)
                     | ClientGeneral::Character(_, _)
-                    | ClientGeneral::Spectate(_) => &mut self.character_screen_stream,
+                    | ClientGeneral::Spectate(_) => &mut connection.character_screen_stream,
                     //Only in game
                     ClientGeneral::ControllerInputs(_)
                     | ClientGeneral::ControlEvent(_)
@@ -879,7 +979,7 @@ impl Client {
                         {
                             ingame = 1.0;
                         }
-                        &mut self.in_game_stream
+                        &mut connection.in_game_stream
                     },
                     //Only in game, terrain
                     ClientGeneral::TerrainChunkRequest { .. }
@@ -888,12 +988,12 @@ impl Client {
                         {
                             terrain = 1.0;
                         }
-                        &mut self.terrain_stream
+                        &mut connection.terrain_stream
                     },
                     //Always possible
                     ClientGeneral::ChatMsg(_)
                     | ClientGeneral::Command(_, _)
-                    | ClientGeneral::Terminate => &mut self.general_stream,
+                    | ClientGeneral::Terminate => &mut connection.general_stream,
                 };
                 #[cfg(feature = "tracy")]
                 {
@@ -902,7 +1002,7 @@ impl Client {
                 }
                 stream.send(msg)
             },
-            ClientMsg::Ping(msg) => self.ping_stream.send(msg),
+            ClientMsg::Ping(msg) => connection.ping_stream.send(msg),
         }
     }
 
@@ -1010,6 +1110,10 @@ impl Client {
     /// state.
     pub fn request_remove_character(&mut self) {
         self.chat_mode = ChatMode::World;
+        // There are no characters to go back to when playing back a recording
+        if self.playback.is_some() {
+            self.presence = None;
+        }
         self.send_msg(ClientGeneral::ExitInGame);
     }
 
@@ -1164,17 +1268,25 @@ impl Client {
         &self.available_recipes
     }
 
+    pub fn known_recipes(&self) -> &comp::KnownRecipes { &self.known_recipes }
+
+    pub fn statistics(&self) -> &comp::Statistics { &self.statistics }
+
+    pub fn achievements(&self) -> &comp::Achievements { &self.achievements }
+
     pub fn lod_zones(&self) -> &HashMap<Vec2<i32>, lod::Zone> { &self.lod_zones }
 
     /// Returns whether the specified recipe can be crafted and the sprite, if
-    /// any, that is required to do so.
-    pub fn can_craft_recipe(&self, recipe: &str, amount: u32) -> (bool, Option<SpriteKind>) {
+    /// any, that is required to do so. Recipes that the character has not
+    /// learned can never be crafted.
+    pub fn can_craft_recipe(&self, recipe_key: &str, amount: u32) -> (bool, Option<SpriteKind>) {
         self.recipe_book
-            .get(recipe)
+            .get(recipe_key)
             .zip(self.inventories().get(self.entity()))
             .map(|(recipe, inv)| {
                 (
-                    recipe.inventory_contains_ingredients(inv, amount).is_ok(),
+                    self.known_recipes.knows(recipe_key)
+                        && recipe.inventory_contains_ingredients(inv, amount).is_ok(),
                     recipe.craft_sprite,
                 )
             })
@@ -1360,6 +1472,8 @@ impl Client {
     /// Unstable, likely to be removed in a future release
     pub fn pois(&self) -> &Vec<PoiInfo> { &self.pois }
 
+    pub fn site_factions(&self) -> &SiteFactions { &self.site_factions }
+
     pub fn sites_mut(&mut self) -> &mut HashMap<SiteId, SiteInfoRich> { &mut self.sites }
 
     pub fn enable_lantern(&mut self) {
@@ -1402,6 +1516,125 @@ impl Client {
 
     pub fn is_trading(&self) -> bool { self.pending_trade.is_some() }
 
+    pub fn reputation(&self) -> &comp::Reputation { &self.reputation }
+
+    /// The roster of the guild that the player's character belongs to, if any.
+    pub fn guild(&self) -> Option<&comp::GuildRoster> { self.guild.as_ref() }
+
+    /// The bank that the player has opened, along with the position of its
+    /// sprite.
+    pub fn bank(&self) -> Option<(Vec3<i32>, &comp::Bank)> {
+        self.bank.as_ref().map(|(pos, bank)| (*pos, bank))
+    }
+
+    pub fn close_bank(&mut self) { self.bank = None; }
+
+    pub fn bank_deposit(&mut self, slot: comp::slot::InvSlotId) {
+        if let Some((bank_sprite, _)) = self.bank {
+            self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+                InventoryEvent::BankDeposit { bank_sprite, slot },
+            )));
+        }
+    }
+
+    pub fn bank_withdraw(&mut self, bank_slot: usize) {
+        if let Some((bank_sprite, _)) = self.bank {
+            self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+                InventoryEvent::BankWithdraw {
+                    bank_sprite,
+                    bank_slot,
+                },
+            )));
+        }
+    }
+
+    /// The mailbox that the player has opened, along with the position of its
+    /// sprite.
+    pub fn mailbox(&self) -> Option<(Vec3<i32>, &comp::Mailbox)> {
+        self.mailbox.as_ref().map(|(pos, mailbox)| (*pos, mailbox))
+    }
+
+    pub fn close_mailbox(&mut self) { self.mailbox = None; }
+
+    /// Send a mail from the open mailbox, attaching the items in the given
+    /// inventory slots.
+    pub fn send_mail(
+        &mut self,
+        recipient: String,
+        body: String,
+        attachments: Vec<comp::slot::InvSlotId>,
+    ) {
+        if let Some((mailbox_sprite, _)) = self.mailbox {
+            self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+                InventoryEvent::SendMail {
+                    mailbox_sprite,
+                    recipient,
+                    body,
+                    attachments,
+                },
+            )));
+        }
+    }
+
+    pub fn claim_mail(&mut self, mail_id: comp::mail::MailId) {
+        if let Some((mailbox_sprite, _)) = self.mailbox {
+            self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+                InventoryEvent::ClaimMail {
+                    mailbox_sprite,
+                    mail_id,
+                },
+            )));
+        }
+    }
+
+    /// The market board that the player has opened, along with the position of
+    /// its sprite.
+    pub fn market(&self) -> Option<(Vec3<i32>, &comp::MarketBoard)> {
+        self.market.as_ref().map(|(pos, market)| (*pos, market))
+    }
+
+    pub fn close_market(&mut self) { self.market = None; }
+
+    /// Put up some of the stack in an inventory slot for sale on the open
+    /// market board.
+    pub fn post_sell_order(&mut self, slot: comp::slot::InvSlotId, quantity: u32, unit_price: u32) {
+        if let Some((market_sprite, _)) = self.market {
+            self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+                InventoryEvent::PostSellOrder {
+                    market_sprite,
+                    slot,
+                    quantity,
+                    unit_price,
+                },
+            )));
+        }
+    }
+
+    /// Offer to buy some of an item on the open market board.
+    pub fn post_buy_order(&mut self, item_definition_id: String, quantity: u32, unit_price: u32) {
+        if let Some((market_sprite, _)) = self.market {
+            self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+                InventoryEvent::PostBuyOrder {
+                    market_sprite,
+                    item_definition_id,
+                    quantity,
+                    unit_price,
+                },
+            )));
+        }
+    }
+
+    pub fn cancel_market_order(&mut self, order_id: comp::market::MarketOrderId) {
+        if let Some((market_sprite, _)) = self.market {
+            self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InventoryEvent(
+                InventoryEvent::CancelMarketOrder {
+                    market_sprite,
+                    order_id,
+                },
+            )));
+        }
+    }
+
     pub fn send_invite(&mut self, invitee: Uid, kind: InviteKind) {
         self.send_msg(ClientGeneral::ControlEvent(ControlEvent::InitiateInvite(
             invitee, kind,
@@ -1515,6 +1748,214 @@ impl Client {
         write
     }
 
+    /// Starts recording what the client receives about the world to a file,
+    /// which can be played back with [`Client::new_playback`].
+    pub fn start_recording(&mut self, path: PathBuf) -> Result<(), Error> {
+        if self.playback.is_some() {
+            return Err(Error::Other(
+                "Can't record while playing back a recording".into(),
+            ));
+        }
+        self.stop_recording()?;
+
+        let ecs = self.state.ecs();
+        let header = RecordingHeader {
+            version: recording::RECORDING_VERSION,
+            server_info: self.server_info.clone(),
+            init: self.server_init.clone(),
+        };
+        let snapshot = RecordingSnapshot {
+            time: self.state.get_time(),
+            time_of_day: *ecs.read_resource::<TimeOfDay>(),
+            calendar: ecs.read_resource::<Calendar>().clone(),
+            player: self.uid(),
+            entities: recording::entity_packages(ecs),
+            chunks: self
+                .state
+                .terrain()
+                .iter()
+                .map(|(key, chunk)| (key, TerrainChunk::clone(chunk)))
+                .collect(),
+            player_list: self.player_list.clone(),
+        };
+        self.recorder = Some(Recorder::new(path, &header, &snapshot)?);
+        Ok(())
+    }
+
+    /// Stops the current recording, if there is one.
+    pub fn stop_recording(&mut self) -> Result<(), Error> {
+        if let Some(recorder) = self.recorder.take() {
+            recorder.finish()?;
+        }
+        Ok(())
+    }
+
+    /// The file that is being recorded to, if any.
+    pub fn recording(&self) -> Option<&Path> { self.recorder.as_ref().map(Recorder::path) }
+
+    fn record_msg(&mut self, msg: &ServerGeneral) {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(msg);
+        }
+    }
+
+    /// Writes the messages received during this tick to the recording, along
+    /// with the physics of the player's entity, which the server doesn't send
+    /// to the client.
+    fn finish_recording_frame(&mut self) {
+        if self.recorder.is_none() {
+            return;
+        }
+
+        let mut player_physics = CompSyncPackage::<msg::EcsCompPacket>::new();
+        if let Some(uid) = self.uid() {
+            let entity = self.entity();
+            let ecs = self.state.ecs();
+            if let Some(pos) = ecs.read_storage::<comp::Pos>().get(entity) {
+                player_physics.comp_modified(uid, *pos);
+            }
+            if let Some(vel) = ecs.read_storage::<comp::Vel>().get(entity) {
+                player_physics.comp_modified(uid, *vel);
+            }
+            if let Some(ori) = ecs.read_storage::<comp::Ori>().get(entity) {
+                player_physics.comp_modified(uid, *ori);
+            }
+        }
+
+        let time = self.state.get_time();
+        let result = self.recorder.as_mut().map_or(Ok(()), |recorder| {
+            if !player_physics.is_empty() {
+                recorder.record(&ServerGeneral::CompSync(
+                    player_physics,
+                    self.force_update_counter,
+                ));
+            }
+            recorder.finish_frame(time)
+        });
+        if let Err(e) = result {
+            warn!(?e, "Failed to write recording, recording has been stopped");
+            self.recorder = None;
+        }
+    }
+
+    /// The recording that is being played back, if this client was created
+    /// with [`Client::new_playback`].
+    pub fn playback(&self) -> Option<&Playback> { self.playback.as_ref() }
+
+    pub fn playback_mut(&mut self) -> Option<&mut Playback> { self.playback.as_mut() }
+
+    /// Jumps to the given time of the recording that is being played back.
+    pub fn seek_playback(&mut self, time: f64) -> Result<(), Error> {
+        let Some(playback) = self.playback.as_mut() else {
+            return Ok(());
+        };
+        let time = time.clamp(playback.start(), playback.end());
+        if time < playback.time() {
+            let snapshot = playback.restart()?;
+            self.load_recording_snapshot(snapshot);
+        }
+        // The events of the part that was skipped, like outcomes, would otherwise
+        // all happen at once
+        self.play_recording_until(time, &mut Vec::new())
+    }
+
+    fn tick_playback(&mut self, dt: Duration) -> Result<Vec<Event>, Error> {
+        let mut frontend_events = Vec::new();
+        let Some(playback) = self.playback.as_mut() else {
+            return Ok(frontend_events);
+        };
+        if let Some(focus) = playback.take_focus() {
+            frontend_events.push(Event::SpectatePosition(focus));
+        }
+        if !playback.paused() {
+            let time =
+                (playback.time() + dt.as_secs_f64() * playback.speed() as f64).min(playback.end());
+            self.play_recording_until(time, &mut frontend_events)?;
+        }
+        Ok(frontend_events)
+    }
+
+    fn play_recording_until(
+        &mut self,
+        time: f64,
+        frontend_events: &mut Vec<Event>,
+    ) -> Result<(), Error> {
+        while let Some(frame) = match self.playback.as_mut() {
+            Some(playback) => playback.next_frame_until(time)?,
+            None => None,
+        } {
+            for msg in frame.messages {
+                self.handle_recorded_msg(frontend_events, msg)?;
+            }
+        }
+        if let Some(playback) = self.playback.as_mut() {
+            playback.set_time(time);
+        }
+        Ok(())
+    }
+
+    fn handle_recorded_msg(
+        &mut self,
+        frontend_events: &mut Vec<Event>,
+        msg: ServerGeneral,
+    ) -> Result<(), Error> {
+        match msg {
+            ServerGeneral::TerrainChunkUpdate { .. }
+            | ServerGeneral::LodZoneUpdate { .. }
+            | ServerGeneral::TerrainBlockUpdates(_) => self.handle_server_terrain_msg(msg),
+            ServerGeneral::Outcomes(_) | ServerGeneral::WeatherUpdate(_) => {
+                self.handle_server_in_game_msg(frontend_events, msg)
+            },
+            msg if recording::is_recorded(&msg) => self.handle_server_msg(frontend_events, msg),
+            // Ignore anything else a modified recording might contain
+            _ => Ok(()),
+        }
+    }
+
+    /// Resets the world to the snapshot of a recording. Only the spectator
+    /// entity of the client is kept.
+    fn load_recording_snapshot(&mut self, snapshot: RecordingSnapshot) {
+        let uids = (&self.state.ecs().read_storage::<Uid>())
+            .join()
+            .map(|uid| uid.0)
+            .collect::<Vec<_>>();
+        for uid in uids {
+            self.state
+                .ecs_mut()
+                .delete_entity_and_clear_from_uid_allocator(uid);
+        }
+        let keys = self
+            .state
+            .terrain()
+            .iter()
+            .map(|(key, _)| key)
+            .collect::<Vec<_>>();
+        for key in keys {
+            self.state.remove_chunk(key);
+        }
+
+        for (key, chunk) in snapshot.chunks {
+            self.state.insert_chunk(key, Arc::new(chunk));
+        }
+        for package in snapshot.entities {
+            self.state.ecs_mut().apply_entity_package(package);
+        }
+        let ecs = self.state.ecs_mut();
+        *ecs.write_resource() = Time(snapshot.time);
+        *ecs.write_resource() = snapshot.time_of_day;
+        *ecs.write_resource() = snapshot.calendar;
+        self.player_list = snapshot.player_list;
+
+        // Start watching from where the player who made the recording was
+        let focus = snapshot
+            .player
+            .and_then(|uid| self.state.ecs().entity_from_uid(uid.0))
+            .and_then(|entity| self.state.read_component_copied::<comp::Pos>(entity));
+        if let (Some(focus), Some(playback)) = (focus, self.playback.as_mut()) {
+            playback.set_focus(focus.0);
+        }
+    }
+
     /// Checks whether a player can swap their weapon+ability `Loadout` settings
     /// and sends the `ControlAction` event that signals to do the swap.
     pub fn swap_loadout(&mut self) { self.control_action(ControlAction::SwapEquippedWeapons) }
@@ -1846,8 +2287,13 @@ impl Client {
             }
         }
 
-        // Handle new messages from the server.
-        frontend_events.append(&mut self.handle_new_messages()?);
+        // Handle new messages from the server, or from the recording that is played
+        // back.
+        if self.playback.is_some() {
+            frontend_events.append(&mut self.tick_playback(dt)?);
+        } else {
+            frontend_events.append(&mut self.handle_new_messages()?);
+        }
 
         // 3) Update client local data
         // Check if the invite has timed out and remove if so
@@ -1868,17 +2314,24 @@ impl Client {
         }
 
         // 4) Tick the client's LocalState
-        self.state.tick(
-            Duration::from_secs_f64(dt.as_secs_f64() * self.dt_adjustment),
-            |dispatch_builder| {
-                add_local_systems(dispatch_builder);
-                add_foreign_systems(dispatch_builder);
-            },
-            true,
-            None,
-            &self.connected_server_constants,
-            |_, _| {},
-        );
+        let playback_speed = match &self.playback {
+            Some(playback) if playback.paused() => None,
+            Some(playback) => Some(playback.speed() as f64),
+            None => Some(1.0),
+        };
+        if let Some(speed) = playback_speed {
+            self.state.tick(
+                Duration::from_secs_f64(dt.as_secs_f64() * self.dt_adjustment * speed),
+                |dispatch_builder| {
+                    add_local_systems(dispatch_builder);
+                    add_foreign_systems(dispatch_builder);
+                },
+                true,
+                None,
+                &self.connected_server_constants,
+                |_, _| {},
+            );
+        }
         // TODO: avoid emitting these in the first place
         let _ = self
             .state
@@ -1894,7 +2347,11 @@ impl Client {
         let _ = self.state.ecs().fetch::<EventBus<Outcome>>().recv_all();
 
         // 5) Terrain
-        self.tick_terrain()?;
+        // Chunks only come from the recording during playback, so they are neither
+        // requested nor removed
+        if self.playback.is_none() {
+            self.tick_terrain()?;
+        }
 
         // Send a ping to the server once every second
         if self.state.get_time() - self.last_server_ping > 1. {
@@ -1903,20 +2360,8 @@ impl Client {
         }
 
         // 6) Update the server about the player's physics attributes.
-        if self.presence.is_some() {
-            if let (Some(pos), Some(vel), Some(ori)) = (
-                self.state.read_storage().get(self.entity()).cloned(),
-                self.state.read_storage().get(self.entity()).cloned(),
-                self.state.read_storage().get(self.entity()).cloned(),
-            ) {
-                self.in_game_stream.send(ClientGeneral::PlayerPhysics {
-                    pos,
-                    vel,
-                    ori,
-                    force_counter: self.force_update_counter,
-                })?;
-            }
-        }
+        self.send_player_physics()?;
+        self.finish_recording_frame();
 
         /*
         // Output debug metrics
@@ -1935,6 +2380,28 @@ impl Client {
         Ok(frontend_events)
     }
 
+    /// Sends the physics of the player's entity to the server, which it doesn't
+    /// simulate itself.
+    fn send_player_physics(&mut self) -> Result<(), Error> {
+        if let (Some(connection), Some(_)) = (&self.connection, self.presence) {
+            if let (Some(pos), Some(vel), Some(ori)) = (
+                self.state.read_storage().get(self.entity()).cloned(),
+                self.state.read_storage().get(self.entity()).cloned(),
+                self.state.read_storage().get(self.entity()).cloned(),
+            ) {
+                connection
+                    .in_game_stream
+                    .send(ClientGeneral::PlayerPhysics {
+                        pos,
+                        vel,
+                        ori,
+                        force_counter: self.force_update_counter,
+                    })?;
+            }
+        }
+        Ok(())
+    }
+
     /// Clean up the client after a tick.
     pub fn cleanup(&mut self) {
         // Cleanup the local state
@@ -2275,6 +2742,9 @@ impl Client {
             ServerGeneral::Notification(n) => {
                 frontend_events.push(Event::Notification(n));
             },
+            ServerGeneral::SiteFactionsUpdate(site_factions) => {
+                self.site_factions = site_factions;
+            },
             _ => unreachable!("Not a general msg"),
         }
         Ok(())
@@ -2477,6 +2947,31 @@ impl Client {
             ServerGeneral::WeatherUpdate(weather) => {
                 self.weather.weather_update(weather);
             },
+            ServerGeneral::ReputationUpdate(reputation) => {
+                self.reputation = reputation;
+            },
+            ServerGeneral::KnownRecipesUpdate(known_recipes) => {
+                self.known_recipes = known_recipes;
+                self.update_available_recipes();
+            },
+            ServerGeneral::StatisticsUpdate(statistics) => {
+                self.statistics = statistics;
+            },
+            ServerGeneral::AchievementsUpdate(achievements) => {
+                self.achievements = achievements;
+            },
+            ServerGeneral::GuildUpdate(guild) => {
+                self.guild = guild;
+            },
+            ServerGeneral::BankUpdate(pos, bank) => {
+                self.bank = Some((pos, bank));
+            },
+            ServerGeneral::MailboxUpdate(pos, mailbox) => {
+                self.mailbox = Some((pos, mailbox));
+            },
+            ServerGeneral::MarketUpdate(pos, market) => {
+                self.market = Some((pos, market));
+            },
             ServerGeneral::SpectatePosition(pos) => {
                 frontend_events.push(Event::SpectatePosition(pos));
             },
@@ -2573,6 +3068,16 @@ impl Client {
         Ok(())
     }
 
+    fn try_recv<M: DeserializeOwned>(
+        &mut self,
+        stream: impl FnOnce(&mut Connection) -> &mut Stream,
+    ) -> Result<Option<M>, Error> {
+        match self.connection.as_mut() {
+            Some(connection) => Ok(stream(connection).try_recv()?),
+            None => Ok(None),
+        }
+    }
+
     fn handle_messages(&mut self, frontend_events: &mut Vec<Event>) -> Result<u64, Error> {
         let mut cnt = 0;
         #[cfg(feature = "tracy")]
@@ -2580,27 +3085,29 @@ impl Client {
         loop {
             let cnt_start = cnt;
 
-            while let Some(msg) = self.general_stream.try_recv()? {
+            while let Some(msg) = self.try_recv(|c| &mut c.general_stream)? {
                 cnt += 1;
+                self.record_msg(&msg);
                 self.handle_server_msg(frontend_events, msg)?;
             }
-            while let Some(msg) = self.ping_stream.try_recv()? {
+            while let Some(msg) = self.try_recv(|c| &mut c.ping_stream)? {
                 cnt += 1;
                 self.handle_ping_msg(msg)?;
             }
-            while let Some(msg) = self.character_screen_stream.try_recv()? {
+            while let Some(msg) = self.try_recv(|c| &mut c.character_screen_stream)? {
                 cnt += 1;
                 self.handle_server_character_screen_msg(frontend_events, msg)?;
             }
-            while let Some(msg) = self.in_game_stream.try_recv()? {
+            while let Some(msg) = self.try_recv(|c| &mut c.in_game_stream)? {
                 cnt += 1;
                 #[cfg(feature = "tracy")]
                 {
                     ingame_cnt += 1;
                 }
+                self.record_msg(&msg);
                 self.handle_server_in_game_msg(frontend_events, msg)?;
             }
-            while let Some(msg) = self.terrain_stream.try_recv()? {
+            while let Some(msg) = self.try_recv(|c| &mut c.terrain_stream)? {
                 cnt += 1;
                 #[cfg(feature = "tracy")]
                 {
@@ -2608,6 +3115,7 @@ impl Client {
                         terrain_cnt += chunk.as_ref().map(|x| x.approx_len()).unwrap_or(0);
                     }
                 }
+                self.record_msg(&msg);
                 self.handle_server_terrain_msg(msg)?;
             }
 
@@ -2656,9 +3164,9 @@ impl Client {
 
         // ignore network events
         while let Some(res) = self
-            .participant
+            .connection
             .as_mut()
-            .and_then(|p| p.try_fetch_event().transpose())
+            .and_then(|c| c.participant.try_fetch_event().transpose())
         {
             let event = res?;
             trace!(?event, "received network event");
@@ -2738,6 +3246,16 @@ impl Client {
         // Clear pending trade
         self.pending_trade = None;
 
+        // Clear the standings of the previous character
+        self.reputation = comp::Reputation::default();
+        self.known_recipes = comp::KnownRecipes::default();
+        self.statistics = comp::Statistics::default();
+        self.achievements = comp::Achievements::default();
+        self.guild = None;
+        self.bank = None;
+        self.mailbox = None;
+        self.market = None;
+
         let client_uid = self
             .uid()
             .map(|u| u.into())
@@ -2803,7 +3321,7 @@ impl Client {
             },
             comp::ChatType::CommandError => (),
             comp::ChatType::CommandInfo => (),
-            comp::ChatType::FactionMeta(_) => (),
+            comp::ChatType::GuildMeta(_) => (),
             comp::ChatType::GroupMeta(_) => (),
             comp::ChatType::Kill(kill_source, victim) => {
                 alias_of_uid(victim);
@@ -2829,7 +3347,7 @@ impl Client {
             | comp::ChatType::NpcSay(uid) => {
                 alias_of_uid(uid);
             },
-            comp::ChatType::Group(uid, _) | comp::ChatType::Faction(uid, _) => {
+            comp::ChatType::Group(uid, _) | comp::ChatType::Guild(uid, _) => {
                 alias_of_uid(uid);
             },
             comp::ChatType::Npc(uid) => alias_of_uid(uid),
@@ -2885,20 +3403,7 @@ impl Client {
         }
 
         // 6) Update the server about the player's physics attributes.
-        if self.presence.is_some() {
-            if let (Some(pos), Some(vel), Some(ori)) = (
-                self.state.read_storage().get(self.entity()).cloned(),
-                self.state.read_storage().get(self.entity()).cloned(),
-                self.state.read_storage().get(self.entity()).cloned(),
-            ) {
-                self.in_game_stream.send(ClientGeneral::PlayerPhysics {
-                    pos,
-                    vel,
-                    ori,
-                    force_counter: self.force_update_counter,
-                })?;
-            }
-        }
+        self.send_player_physics()?;
 
         // 7) Finish the tick, pass control back to the frontend.
         self.tick += 1;
@@ -2922,16 +3427,26 @@ impl Drop for Client {
             trace!("no disconnect msg necessary as client wasn't registered")
         }
 
-        tokio::task::block_in_place(|| {
-            if let Err(e) = self
-                .runtime
-                .block_on(self.participant.take().unwrap().disconnect())
-            {
-                warn!(?e, "error when disconnecting, couldn't send all data");
+        if let Some(recorder) = self.recorder.take() {
+            if let Err(e) = recorder.finish() {
+                warn!(?e, "Failed to finish recording");
             }
-        });
-        //explicitly drop the network here while the runtime is still existing
-        drop(self.network.take());
+        }
+
+        if let Some(Connection {
+            network,
+            participant,
+            ..
+        }) = self.connection.take()
+        {
+            tokio::task::block_in_place(|| {
+                if let Err(e) = self.runtime.block_on(participant.disconnect()) {
+                    warn!(?e, "error when disconnecting, couldn't send all data");
+                }
+            });
+            //explicitly drop the network here while the runtime is still existing
+            drop(network);
+        }
     }
 }
 
@@ -0,0 +1,284 @@
+//! Recordings of what a client received from the server, which can be played
+//! back without a connection to watch a fight again from any angle.
+//!
+//! A recording starts with a [`RecordingHeader`] and a [`RecordingSnapshot`]
+//! of everything the client knew about the world when the recording started,
+//! which are followed by a [`RecordingFrame`] for every tick. Only messages
+//! about the world are recorded, so that the inventory, trades and group of
+//! the player aren't shared along with the recording.
+
+use crate::Error;
+use common::{
+    calendar::Calendar,
+    comp::{Ori, Pos, Vel},
+    resources::TimeOfDay,
+    terrain::TerrainChunk,
+    uid::Uid,
+};
+use common_net::{
+    msg::{EcsCompPacket, PlayerInfo, ServerGeneral, ServerInfo, ServerInit},
+    replay::{ReplayError, ReplayReader, ReplayWriter},
+    sync::EntityPackage,
+};
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use specs::{Join, World, WorldExt};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
+use vek::*;
+
+/// Changes whenever the layout of recordings does
+pub const RECORDING_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordingHeader {
+    pub version: u32,
+    pub server_info: ServerInfo,
+    /// What the server sent when the client connected, which is needed to set
+    /// up a client for playback
+    pub init: ServerInit,
+}
+
+/// What the client knew about the world when the recording started
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordingSnapshot {
+    pub time: f64,
+    pub time_of_day: TimeOfDay,
+    pub calendar: Calendar,
+    /// The entity of the player who made the recording
+    pub player: Option<Uid>,
+    pub entities: Vec<EntityPackage<EcsCompPacket>>,
+    pub chunks: Vec<(Vec2<i32>, TerrainChunk)>,
+    pub player_list: HashMap<Uid, PlayerInfo>,
+}
+
+/// The messages received during a tick
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RecordingFrame {
+    /// The time of the client at the end of the tick
+    pub time: f64,
+    pub messages: Vec<ServerGeneral>,
+}
+
+/// Whether a message concerns the world rather than only the player, and so
+/// belongs in a recording
+pub fn is_recorded(msg: &ServerGeneral) -> bool {
+    matches!(
+        msg,
+        ServerGeneral::PlayerListUpdate(_)
+            | ServerGeneral::ChatMsg(_)
+            | ServerGeneral::TimeOfDay(_, _, _)
+            | ServerGeneral::EntitySync(_)
+            | ServerGeneral::CompSync(_, _)
+            | ServerGeneral::CreateEntity(_)
+            | ServerGeneral::DeleteEntity(_)
+            | ServerGeneral::Outcomes(_)
+            | ServerGeneral::WeatherUpdate(_)
+            | ServerGeneral::SiteFactionsUpdate(_)
+            | ServerGeneral::TerrainChunkUpdate { .. }
+            | ServerGeneral::LodZoneUpdate { .. }
+            | ServerGeneral::TerrainBlockUpdates(_)
+    )
+}
+
+use common_net::synced_components::*;
+
+macro_rules! entity_packages {
+    ($($component_name:ident: $component_type:ident,)*) => {
+        /// Packages every synced component of the entities known to the client,
+        /// along with their physics
+        pub(crate) fn entity_packages(ecs: &World) -> Vec<EntityPackage<EcsCompPacket>> {
+            $(let $component_name = ecs.read_storage::<$component_type>();)*
+            let positions = ecs.read_storage::<Pos>();
+            let velocities = ecs.read_storage::<Vel>();
+            let orientations = ecs.read_storage::<Ori>();
+
+            (&ecs.entities(), &ecs.read_storage::<Uid>())
+                .join()
+                .map(|(entity, uid)| {
+                    let mut comps = Vec::new();
+                    $(
+                        if let Some(c) = $component_name.get(entity) {
+                            comps.push(c.clone().into());
+                        }
+                    )*
+                    if let Some(c) = positions.get(entity) {
+                        comps.push((*c).into());
+                    }
+                    if let Some(c) = velocities.get(entity) {
+                        comps.push((*c).into());
+                    }
+                    if let Some(c) = orientations.get(entity) {
+                        comps.push((*c).into());
+                    }
+
+                    EntityPackage { uid: uid.0, comps }
+                })
+                .collect()
+        }
+    };
+}
+
+common_net::synced_components!(entity_packages);
+
+/// A recording that is being written to
+pub(crate) struct Recorder {
+    path: PathBuf,
+    writer: ReplayWriter<BufWriter<File>>,
+    /// Messages received during this tick
+    messages: Vec<ServerGeneral>,
+}
+
+impl Recorder {
+    pub fn new(
+        path: PathBuf,
+        header: &RecordingHeader,
+        snapshot: &RecordingSnapshot,
+    ) -> Result<Self, ReplayError> {
+        let mut writer = ReplayWriter::new(BufWriter::new(File::create(&path)?));
+        writer.write(header)?;
+        writer.write(snapshot)?;
+
+        Ok(Self {
+            path,
+            writer,
+            messages: Vec::new(),
+        })
+    }
+
+    pub fn path(&self) -> &Path { &self.path }
+
+    pub fn record(&mut self, msg: &ServerGeneral) {
+        if is_recorded(msg) {
+            self.messages.push(msg.clone());
+        }
+    }
+
+    /// Writes the messages received during this tick to the recording
+    pub fn finish_frame(&mut self, time: f64) -> Result<(), ReplayError> {
+        let messages = std::mem::take(&mut self.messages);
+        self.writer.write(&RecordingFrame { time, messages })
+    }
+
+    pub fn finish(mut self) -> Result<(), ReplayError> { self.writer.flush() }
+}
+
+/// A recording that is being played back
+pub struct Playback {
+    path: PathBuf,
+    reader: ReplayReader<BufReader<File>>,
+    /// The frame that was read ahead of time because it isn't due yet
+    next_frame: Option<RecordingFrame>,
+    start: f64,
+    end: f64,
+    time: f64,
+    speed: f32,
+    paused: bool,
+    /// Where the camera should be moved to, after the world has been reset
+    focus: Option<Vec3<f32>>,
+}
+
+impl Playback {
+    /// Opens a recording, returning what's needed to set up a client for it
+    pub(crate) fn open(path: PathBuf) -> Result<(Self, RecordingHeader, RecordingSnapshot), Error> {
+        let (mut reader, header) = Self::read_header(&path)?;
+        let snapshot = reader
+            .read::<RecordingSnapshot>()?
+            .ok_or_else(|| Error::Other("Recording has no snapshot".into()))?;
+
+        // Find out how long the recording is, so that it can be seeked through
+        let mut end = snapshot.time;
+        while let Some(frame) = reader.read::<RecordingFrame>()? {
+            end = frame.time;
+        }
+
+        let (mut reader, _) = Self::read_header(&path)?;
+        reader.read::<RecordingSnapshot>()?;
+
+        let playback = Self {
+            path,
+            reader,
+            next_frame: None,
+            start: snapshot.time,
+            end,
+            time: snapshot.time,
+            speed: 1.0,
+            paused: false,
+            focus: None,
+        };
+        Ok((playback, header, snapshot))
+    }
+
+    fn read_header(path: &Path) -> Result<(ReplayReader<BufReader<File>>, RecordingHeader), Error> {
+        let mut reader =
+            ReplayReader::new(BufReader::new(File::open(path).map_err(ReplayError::Io)?));
+        let header = reader
+            .read::<RecordingHeader>()?
+            .ok_or_else(|| Error::Other("Recording is empty".into()))?;
+        if header.version != RECORDING_VERSION {
+            return Err(Error::Other(format!(
+                "Recording has version {}, but only version {} is supported",
+                header.version, RECORDING_VERSION
+            )));
+        }
+        Ok((reader, header))
+    }
+
+    /// Goes back to the start of the recording, returning the snapshot to
+    /// reset the world to
+    pub(crate) fn restart(&mut self) -> Result<RecordingSnapshot, Error> {
+        let (mut reader, _) = Self::read_header(&self.path)?;
+        let snapshot = reader
+            .read::<RecordingSnapshot>()?
+            .ok_or_else(|| Error::Other("Recording has no snapshot".into()))?;
+        self.reader = reader;
+        self.next_frame = None;
+        self.time = snapshot.time;
+        Ok(snapshot)
+    }
+
+    /// Reads the next frame if it happened at or before the given time
+    pub(crate) fn next_frame_until(&mut self, time: f64) -> Result<Option<RecordingFrame>, Error> {
+        let frame = match self.next_frame.take() {
+            Some(frame) => frame,
+            None => match self.reader.read::<RecordingFrame>()? {
+                Some(frame) => frame,
+                None => return Ok(None),
+            },
+        };
+        if frame.time <= time {
+            Ok(Some(frame))
+        } else {
+            self.next_frame = Some(frame);
+            Ok(None)
+        }
+    }
+
+    pub(crate) fn set_time(&mut self, time: f64) { self.time = time; }
+
+    pub(crate) fn set_focus(&mut self, focus: Vec3<f32>) { self.focus = Some(focus); }
+
+    pub(crate) fn take_focus(&mut self) -> Option<Vec3<f32>> { self.focus.take() }
+
+    pub fn path(&self) -> &Path { &self.path }
+
+    /// The time at which the recording starts, in the time of the recording
+    /// client
+    pub fn start(&self) -> f64 { self.start }
+
+    pub fn end(&self) -> f64 { self.end }
+
+    /// How far the playback has got, in the time of the recording client
+    pub fn time(&self) -> f64 { self.time }
+
+    pub fn speed(&self) -> f32 { self.speed }
+
+    pub fn set_speed(&mut self, speed: f32) { self.speed = speed.clamp(0.1, 16.0); }
+
+    pub fn paused(&self) -> bool { self.paused }
+
+    pub fn set_paused(&mut self, paused: bool) { self.paused = paused; }
+}
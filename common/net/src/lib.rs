@@ -1,5 +1,6 @@
 #![allow(incomplete_features)]
 #![feature(generic_const_exprs, const_fn_floating_point_arithmetic)]
 pub mod msg;
+pub mod replay;
 pub mod sync;
 pub mod synced_components;
@@ -1,6 +1,7 @@
 use super::{
-    world_msg::EconomyInfo, ClientType, CompressedData, EcsCompPacket, PingMsg, QuadPngEncoding,
-    TriPngEncoding, WidePacking, WireChonk,
+    world_msg::{EconomyInfo, SiteFactions},
+    ClientType, CompressedData, EcsCompPacket, PingMsg, QuadPngEncoding, TriPngEncoding,
+    WidePacking, WireChonk,
 };
 use crate::sync;
 use common::{
@@ -207,8 +208,30 @@ pub enum ServerGeneral {
     FinishedTrade(TradeResult),
     /// Economic information about sites
     SiteEconomy(EconomyInfo),
+    /// Which factions hold the sites of the world, and how they get along
+    SiteFactionsUpdate(SiteFactions),
     MapMarker(comp::MapMarkerUpdate),
     WeatherUpdate(WeatherGrid),
+    /// The standings of the player's character with the sites and factions of
+    /// the world
+    ReputationUpdate(comp::Reputation),
+    /// The recipes that the player's character knows how to craft
+    KnownRecipesUpdate(comp::KnownRecipes),
+    /// The lifetime statistics of the player's character
+    StatisticsUpdate(comp::Statistics),
+    /// The achievements that the player has unlocked
+    AchievementsUpdate(comp::Achievements),
+    /// The roster of the player's guild, or `None` if they are not in one
+    GuildUpdate(Option<comp::GuildRoster>),
+    /// The contents of the player's bank, along with the position of the bank
+    /// sprite that it was opened at
+    BankUpdate(Vec3<i32>, comp::Bank),
+    /// The contents of the player's mailbox, along with the position of the
+    /// mailbox sprite that it was opened at
+    MailboxUpdate(Vec3<i32>, comp::Mailbox),
+    /// The open orders on a market board, along with the position of the
+    /// market board sprite that it was opened at
+    MarketUpdate(Vec3<i32>, comp::MarketBoard),
     /// Suggest the client to spectate a position. Called after client has
     /// requested teleport etc.
     SpectatePosition(Vec3<f32>),
@@ -269,6 +292,8 @@ pub enum InviteAnswer {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Notification {
     WaypointSaved,
+    /// The player unlocked the achievement with the given id
+    AchievementUnlocked(String),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -334,6 +359,14 @@ impl ServerMsg {
                         | ServerGeneral::SiteEconomy(_)
                         | ServerGeneral::MapMarker(_)
                         | ServerGeneral::WeatherUpdate(_)
+                        | ServerGeneral::ReputationUpdate(_)
+                        | ServerGeneral::KnownRecipesUpdate(_)
+                        | ServerGeneral::StatisticsUpdate(_)
+                        | ServerGeneral::AchievementsUpdate(_)
+                        | ServerGeneral::GuildUpdate(_)
+                        | ServerGeneral::BankUpdate(_, _)
+                        | ServerGeneral::MailboxUpdate(_, _)
+                        | ServerGeneral::MarketUpdate(_, _)
                         | ServerGeneral::SpectatePosition(_) => {
                             c_type == ClientType::Game && presence.is_some()
                         },
@@ -348,7 +381,8 @@ impl ServerMsg {
                         | ServerGeneral::CreateEntity(_)
                         | ServerGeneral::DeleteEntity(_)
                         | ServerGeneral::Disconnect(_)
-                        | ServerGeneral::Notification(_) => true,
+                        | ServerGeneral::Notification(_)
+                        | ServerGeneral::SiteFactionsUpdate(_) => true,
                     }
             },
             ServerMsg::Ping(_) => true,
@@ -123,6 +123,8 @@ pub struct WorldMapMsg {
     pub sites: Vec<SiteInfo>,
     pub possible_starting_sites: Vec<SiteId>,
     pub pois: Vec<PoiInfo>,
+    /// Which factions hold the sites of the world, and how they get along
+    pub factions: SiteFactions,
     /// Default chunk (representing the ocean outside the map bounds).  Sea
     /// level (used to provide a base altitude) is the lower bound of this
     /// chunk.
@@ -153,6 +155,38 @@ pub enum SiteKind {
     Adlet,
 }
 
+/// Which faction holds each site, and how the factions get along. Factions
+/// are identified by their capital, which is the most populous site they hold.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SiteFactions {
+    /// The capital of the faction holding each site
+    pub owners: HashMap<SiteId, SiteId>,
+    /// The stance that one faction holds toward another, for factions that
+    /// aren't neutral toward one-another
+    pub relations: Vec<(SiteId, SiteId, FactionStance)>,
+}
+
+impl SiteFactions {
+    /// The capitals of the factions that the faction with the given capital
+    /// holds the given stance toward
+    pub fn related(
+        &self,
+        capital: SiteId,
+        stance: FactionStance,
+    ) -> impl Iterator<Item = SiteId> + '_ {
+        self.relations
+            .iter()
+            .filter(move |(from, _, s)| *from == capital && *s == stance)
+            .map(|(_, to, _)| *to)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FactionStance {
+    Alliance,
+    War,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EconomyInfo {
     pub id: SiteId,
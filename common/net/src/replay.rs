@@ -0,0 +1,169 @@
+//! Recordings of the server's simulation, used to reproduce bugs and to check
+//! that the simulation is deterministic.
+//!
+//! A replay starts with a [`ReplayHeader`] and a [`ReplaySnapshot`] of the
+//! world, which are followed by a [`ReplayFrame`] for every tick. Each of
+//! these is compressed separately, so a replay that was cut short (e.g. by a
+//! crash) can still be read up to where it ends.
+//!
+//! Server events that move entities are recorded as [`ReplayEvent`]s, so that
+//! they can be applied again when re-simulating. The effects of all other
+//! server events are covered by the recorded changes to components, so only
+//! how many of them there were is kept.
+
+use crate::{
+    msg::{compression::CompressedData, ClientGeneral, EcsCompPacket},
+    sync::{CompSyncPackage, EntityPackage, EntitySyncPackage},
+};
+use common::{
+    comp::{Controller, Ori, Pos, Vel},
+    terrain::{Block, TerrainChunk},
+    uid::Uid,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{fmt, io};
+use vek::*;
+
+/// Changes whenever the layout of replays does
+pub const REPLAY_VERSION: u32 = 2;
+
+/// The physics of an entity, which are recorded separately from the rest of
+/// its components so that they can be compared against
+pub type EntityPhysics = (Uid, Pos, Vel, Ori);
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayHeader {
+    pub version: u32,
+    /// Size of the world in chunks, as a power of two
+    pub map_size_lg: Vec2<u32>,
+    /// Names of the kinds of server events, in the order used by
+    /// [`ReplayFrame::server_events`]
+    pub server_events: Vec<String>,
+}
+
+/// A server event that moves an entity, with its entities replaced by their
+/// [`Uid`]s
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum ReplayEvent {
+    Knockback {
+        entity: Uid,
+        impulse: Vec3<f32>,
+    },
+    TeleportTo {
+        entity: Uid,
+        target: Uid,
+        max_range: Option<f32>,
+    },
+}
+
+/// The state of the world when the recording started
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplaySnapshot {
+    pub tick: u64,
+    pub time: f64,
+    pub chunks: Vec<(Vec2<i32>, TerrainChunk)>,
+    /// The synced components of every entity, except for their physics
+    pub entities: Vec<EntityPackage<EcsCompPacket>>,
+    pub physics: Vec<EntityPhysics>,
+    pub controllers: Vec<(Uid, Controller)>,
+}
+
+/// Everything that happened during a tick
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    pub tick: u64,
+    /// Length of the tick in seconds
+    pub dt: f32,
+    /// The time at the end of the tick
+    pub time: f64,
+    /// Messages received from clients during the tick
+    pub client_inputs: Vec<(Uid, ClientGeneral)>,
+    /// How many server events of each kind were emitted during the tick
+    pub server_events: Vec<(u16, u32)>,
+    /// The server events that moved entities during the tick, in the order
+    /// they were handled
+    pub events: Vec<ReplayEvent>,
+    /// Entities that were created or deleted during the tick
+    pub entity_sync: EntitySyncPackage,
+    /// Changes made to the synced components of entities during the tick
+    pub comp_sync: CompSyncPackage<EcsCompPacket>,
+    /// The physics of every entity at the end of the tick
+    pub physics: Vec<EntityPhysics>,
+    /// The controllers of every entity at the end of the tick, which are
+    /// what entities act on during the next tick
+    pub controllers: Vec<(Uid, Controller)>,
+    pub new_chunks: Vec<(Vec2<i32>, TerrainChunk)>,
+    pub removed_chunks: Vec<Vec2<i32>>,
+    pub block_changes: Vec<(Vec3<i32>, Block)>,
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    Encoding(bincode::Error),
+    /// The data could be read, but not decompressed
+    Corrupt,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Failed to access replay: {}", err),
+            Self::Encoding(err) => write!(f, "Failed to encode replay: {}", err),
+            Self::Corrupt => write!(f, "Replay is corrupt"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<io::Error> for ReplayError {
+    fn from(err: io::Error) -> Self { Self::Io(err) }
+}
+
+impl From<bincode::Error> for ReplayError {
+    fn from(err: bincode::Error) -> Self {
+        match *err {
+            bincode::ErrorKind::Io(err) => Self::Io(err),
+            _ => Self::Encoding(err),
+        }
+    }
+}
+
+pub struct ReplayWriter<W: io::Write> {
+    writer: W,
+}
+
+impl<W: io::Write> ReplayWriter<W> {
+    pub fn new(writer: W) -> Self { Self { writer } }
+
+    /// Appends a header, snapshot or frame to the replay
+    pub fn write<T: Serialize>(&mut self, item: &T) -> Result<(), ReplayError> {
+        bincode::serialize_into(&mut self.writer, &CompressedData::compress(item, 1))?;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> Result<(), ReplayError> { Ok(self.writer.flush()?) }
+}
+
+pub struct ReplayReader<R: io::Read> {
+    reader: R,
+}
+
+impl<R: io::Read> ReplayReader<R> {
+    pub fn new(reader: R) -> Self { Self { reader } }
+
+    /// Reads the next header, snapshot or frame of the replay, or `None` once
+    /// the end of the replay is reached
+    pub fn read<T: DeserializeOwned>(&mut self) -> Result<Option<T>, ReplayError> {
+        match bincode::deserialize_from::<_, CompressedData<T>>(&mut self.reader) {
+            Ok(data) => data.decompress().map(Some).ok_or(ReplayError::Corrupt),
+            Err(err) => match *err {
+                bincode::ErrorKind::Io(err) if err.kind() == io::ErrorKind::UnexpectedEof => {
+                    Ok(None)
+                },
+                _ => Err(err.into()),
+            },
+        }
+    }
+}
@@ -46,6 +46,7 @@ macro_rules! synced_components {
             beam_segment: BeamSegment,
             alignment: Alignment,
             stance: Stance,
+            threat_list: ThreatList,
             // TODO: change this to `SyncFrom::ClientEntity` and sync the bare minimum
             // from other entities (e.g. just keys needed to show appearance
             // based on their loadout). Also, it looks like this actually has
@@ -237,6 +238,10 @@ impl NetSync for Stance {
     const SYNC_FROM: SyncFrom = SyncFrom::AnyEntity;
 }
 
+impl NetSync for ThreatList {
+    const SYNC_FROM: SyncFrom = SyncFrom::AnyEntity;
+}
+
 // These are synced only from the client's own entity.
 
 impl NetSync for Admin {
@@ -273,9 +273,9 @@ pub enum ServerChatCommand {
     DeleteLocation,
     DisconnectAllPlayers,
     DropAll,
+    Duel,
     Dummy,
     Explosion,
-    Faction,
     GiveItem,
     Goto,
     Group,
@@ -283,9 +283,17 @@ pub enum ServerChatCommand {
     GroupKick,
     GroupLeave,
     GroupPromote,
+    Guild,
+    GuildCreate,
+    GuildDemote,
+    GuildDisband,
+    GuildInvite,
+    GuildKick,
+    GuildLeader,
+    GuildLeave,
+    GuildPromote,
     Health,
     Help,
-    JoinFaction,
     Jump,
     Kick,
     Kill,
@@ -464,17 +472,17 @@ impl ServerChatCommand {
                 "Drops all your items on the ground",
                 Some(Moderator),
             ),
+            ServerChatCommand::Duel => cmd(
+                vec![PlayerName(Required)],
+                "Challenge a nearby player to a duel",
+                None,
+            ),
             ServerChatCommand::Dummy => cmd(vec![], "Spawns a training dummy", Some(Admin)),
             ServerChatCommand::Explosion => cmd(
                 vec![Float("radius", 5.0, Required)],
                 "Explodes the ground around you",
                 Some(Admin),
             ),
-            ServerChatCommand::Faction => cmd(
-                vec![Message(Optional)],
-                "Send messages to your faction",
-                None,
-            ),
             ServerChatCommand::GiveItem => cmd(
                 vec![
                     Enum("item", ITEM_SPECS.clone(), Required),
@@ -512,6 +520,41 @@ impl ServerChatCommand {
                 "Promote a player to group leader",
                 None,
             ),
+            ServerChatCommand::Guild => {
+                cmd(vec![Message(Optional)], "Send messages to your guild", None)
+            },
+            ServerChatCommand::GuildCreate => cmd(
+                vec![Any("name", Required)],
+                "Found a new guild, with yourself as its leader",
+                None,
+            ),
+            ServerChatCommand::GuildDemote => cmd(
+                vec![PlayerName(Required)],
+                "Demote a member of your guild by one rank",
+                None,
+            ),
+            ServerChatCommand::GuildDisband => cmd(vec![], "Disband the guild that you lead", None),
+            ServerChatCommand::GuildInvite => cmd(
+                vec![PlayerName(Required)],
+                "Invite a player to join your guild",
+                None,
+            ),
+            ServerChatCommand::GuildKick => cmd(
+                vec![PlayerName(Required)],
+                "Remove a player from your guild",
+                None,
+            ),
+            ServerChatCommand::GuildLeader => cmd(
+                vec![PlayerName(Required)],
+                "Hand leadership of your guild to another member",
+                None,
+            ),
+            ServerChatCommand::GuildLeave => cmd(vec![], "Leave your current guild", None),
+            ServerChatCommand::GuildPromote => cmd(
+                vec![PlayerName(Required)],
+                "Promote a member of your guild by one rank",
+                None,
+            ),
             ServerChatCommand::Health => cmd(
                 vec![Integer("hp", 100, Required)],
                 "Set your current health",
@@ -523,11 +566,6 @@ impl ServerChatCommand {
                 None,
             ),
             ServerChatCommand::Respawn => cmd(vec![], "Teleport to your waypoint", Some(Moderator)),
-            ServerChatCommand::JoinFaction => ChatCommandData::new(
-                vec![Any("faction", Optional)],
-                "Join/leave the specified faction",
-                None,
-            ),
             ServerChatCommand::Jump => cmd(
                 vec![
                     Float("x", 0.0, Required),
@@ -849,9 +887,9 @@ impl ServerChatCommand {
             ServerChatCommand::DebugWays => "debug_ways",
             ServerChatCommand::DisconnectAllPlayers => "disconnect_all_players",
             ServerChatCommand::DropAll => "dropall",
+            ServerChatCommand::Duel => "duel",
             ServerChatCommand::Dummy => "dummy",
             ServerChatCommand::Explosion => "explosion",
-            ServerChatCommand::Faction => "faction",
             ServerChatCommand::GiveItem => "give_item",
             ServerChatCommand::Goto => "goto",
             ServerChatCommand::Group => "group",
@@ -859,10 +897,18 @@ impl ServerChatCommand {
             ServerChatCommand::GroupKick => "group_kick",
             ServerChatCommand::GroupPromote => "group_promote",
             ServerChatCommand::GroupLeave => "group_leave",
+            ServerChatCommand::Guild => "guild",
+            ServerChatCommand::GuildCreate => "guild_create",
+            ServerChatCommand::GuildDemote => "guild_demote",
+            ServerChatCommand::GuildDisband => "guild_disband",
+            ServerChatCommand::GuildInvite => "guild_invite",
+            ServerChatCommand::GuildKick => "guild_kick",
+            ServerChatCommand::GuildLeader => "guild_leader",
+            ServerChatCommand::GuildLeave => "guild_leave",
+            ServerChatCommand::GuildPromote => "guild_promote",
             ServerChatCommand::Health => "health",
             ServerChatCommand::Help => "help",
             ServerChatCommand::Respawn => "respawn",
-            ServerChatCommand::JoinFaction => "join_faction",
             ServerChatCommand::Jump => "jump",
             ServerChatCommand::Kick => "kick",
             ServerChatCommand::Kill => "kill",
@@ -921,8 +967,8 @@ impl ServerChatCommand {
     /// Returns None if the command doesn't have a short keyword
     pub fn short_keyword(&self) -> Option<&'static str> {
         Some(match self {
-            ServerChatCommand::Faction => "f",
             ServerChatCommand::Group => "g",
+            ServerChatCommand::Guild => "gu",
             ServerChatCommand::Region => "r",
             ServerChatCommand::Say => "s",
             ServerChatCommand::Tell => "t",
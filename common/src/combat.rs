@@ -12,8 +12,8 @@ use crate::{
             slot::EquipSlot,
         },
         skillset::SkillGroupKind,
-        Alignment, Body, Buffs, CharacterState, Combo, Energy, Health, HealthChange, Inventory,
-        Ori, Player, Poise, PoiseChange, SkillSet, Stats,
+        Alignment, Body, Buffs, CharacterState, Combo, Duel, Energy, Guild, Health, HealthChange,
+        Inventory, Ori, Player, Poise, PoiseChange, SkillSet, Stats,
     },
     event::ServerEvent,
     outcome::Outcome,
@@ -509,6 +509,15 @@ impl Attack {
                                 });
                             }
                         },
+                        CombatEffect::Taunt(duration) => {
+                            if let Some(attacker) = attacker {
+                                emit(ServerEvent::Taunt {
+                                    entity: target.entity,
+                                    by: attacker.uid,
+                                    duration: *duration,
+                                });
+                            }
+                        },
                     }
                 }
             }
@@ -688,6 +697,15 @@ impl Attack {
                     // Only has an effect when attached to a damage
                     CombatEffect::BuffsVulnerable(_, _) => {},
                     CombatEffect::StunnedVulnerable(_) => {},
+                    CombatEffect::Taunt(duration) => {
+                        if let Some(attacker) = attacker {
+                            emit(ServerEvent::Taunt {
+                                entity: target.entity,
+                                by: attacker.uid,
+                                duration,
+                            });
+                        }
+                    },
                 }
             }
         }
@@ -708,12 +726,18 @@ impl Attack {
 /// e.g. if player with PvE mode will harm pets of other players
 /// or other players will do the same to such player.
 ///
+/// Members of the same guild are treated like members of the same group, and
+/// never harm one another regardless of their battle modes. Players that are
+/// duelling each other, on the other hand, may always harm one another.
+///
 /// If both players have PvP mode enabled, interact with NPC and
 /// in any other case, this function will return `true`
 // TODO: add parameter for doing self-harm?
 pub fn may_harm(
     alignments: &ReadStorage<Alignment>,
     players: &ReadStorage<Player>,
+    guilds: &ReadStorage<Guild>,
+    duels: &ReadStorage<Duel>,
     uid_allocator: &UidAllocator,
     attacker: Option<EcsEntity>,
     target: EcsEntity,
@@ -748,10 +772,24 @@ pub fn may_harm(
     let attacker_info = players.get(attacker);
     let target_info = players.get(target);
 
+    // Duellists may fight regardless of anything else
+    if duels
+        .get(attacker)
+        .map_or(false, |duel| duel.may_harm(target))
+    {
+        return true;
+    }
+
+    // Guild members can't harm each other
+    let same_guild = guilds
+        .get(attacker)
+        .zip(guilds.get(target))
+        .map_or(false, |(a, t)| a.id == t.id);
+
     // Return `true` if not players.
     attacker_info
         .zip(target_info)
-        .map_or(true, |(a, t)| a.may_harm(t))
+        .map_or(true, |(a, t)| !same_guild && a.may_harm(t))
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -842,6 +880,9 @@ pub enum CombatEffect {
     // TODO: Maybe try to make it do something if tied to attack, not sure if it should double
     // count in that instance?
     StunnedVulnerable(f32),
+    /// Forces the target to fight the attacker for this many seconds, if the
+    /// target is an NPC
+    Taunt(f32),
 }
 
 impl CombatEffect {
@@ -880,6 +921,7 @@ impl CombatEffect {
             CombatEffect::StunnedVulnerable(v) => {
                 CombatEffect::StunnedVulnerable(v * stats.effect_power)
             },
+            CombatEffect::Taunt(d) => CombatEffect::Taunt(d),
         }
     }
 }
@@ -1438,6 +1480,16 @@ pub fn perception_dist_multiplier_from_stealth(
     multiplier.clamp(0.0, 1.0)
 }
 
+/// Returns a value to be included as a multiplicative factor in perception
+/// distance checks, given how well lit the perceived entity is on a scale from
+/// 0.0 (pitch black) to 1.0 (daylight).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn perception_dist_multiplier_from_light(light_level: f32) -> f32 {
+    const DARKNESS_MULTIPLIER: f32 = 0.3;
+
+    DARKNESS_MULTIPLIER + (1.0 - DARKNESS_MULTIPLIER) * light_level.clamp(0.0, 1.0)
+}
+
 #[cfg(not(target_arch = "wasm32"))]
 pub fn stealth_multiplier_from_items(
     inventory: Option<&Inventory>,
@@ -470,6 +470,8 @@ impl From<&CharacterState> for CharacterAbilityType {
             | CharacterState::SpriteSummon(_)
             | CharacterState::UseItem(_)
             | CharacterState::SpriteInteract(_)
+            | CharacterState::Crafting(_)
+            | CharacterState::Fishing(_)
             | CharacterState::Skate(_)
             | CharacterState::Wallrun(_) => Self::Other,
         }
@@ -769,6 +771,15 @@ pub enum CharacterAbility {
         #[serde(default)]
         meta: AbilityMeta,
     },
+    Fishing {
+        cast_duration: f32,
+        cast_distance: f32,
+        min_wait_duration: f32,
+        max_wait_duration: f32,
+        reel_duration: f32,
+        #[serde(default)]
+        meta: AbilityMeta,
+    },
     FinisherMelee {
         energy_cost: f32,
         buildup_duration: f32,
@@ -928,6 +939,7 @@ impl CharacterAbility {
                 | CharacterAbility::BasicBeam { .. }
                 | CharacterAbility::Blink { .. }
                 | CharacterAbility::Music { .. }
+                | CharacterAbility::Fishing { .. }
                 | CharacterAbility::BasicSummon { .. }
                 | CharacterAbility::SpriteSummon { .. } => true,
             }
@@ -1438,6 +1450,18 @@ impl CharacterAbility {
             } => {
                 *play_duration /= stats.speed;
             },
+            Fishing {
+                ref mut cast_duration,
+                ref mut cast_distance,
+                min_wait_duration: _,
+                max_wait_duration: _,
+                ref mut reel_duration,
+                meta: _,
+            } => {
+                *cast_duration /= stats.speed;
+                *cast_distance *= stats.range;
+                *reel_duration /= stats.speed;
+            },
             FinisherMelee {
                 ref mut energy_cost,
                 ref mut buildup_duration,
@@ -1543,6 +1567,7 @@ impl CharacterAbility {
             | ComboMelee { .. }
             | Blink { .. }
             | Music { .. }
+            | Fishing { .. }
             | BasicSummon { .. }
             | SpriteSummon { .. } => 0.0,
         }
@@ -1585,6 +1610,7 @@ impl CharacterAbility {
             | ComboMelee { .. }
             | Blink { .. }
             | Music { .. }
+            | Fishing { .. }
             | BasicSummon { .. }
             | SpriteSummon { .. } => 0,
         }
@@ -1617,6 +1643,7 @@ impl CharacterAbility {
             | SpriteSummon { meta, .. }
             | FinisherMelee { meta, .. }
             | Music { meta, .. }
+            | Fishing { meta, .. }
             | DiveMelee { meta, .. }
             | RiposteMelee { meta, .. }
             | RapidMelee { meta, .. } => *meta,
@@ -2759,6 +2786,27 @@ impl From<(&CharacterAbility, AbilityInfo, &JoinData<'_>)> for CharacterState {
                 stage_section: StageSection::Action,
                 exhausted: false,
             }),
+            CharacterAbility::Fishing {
+                cast_duration,
+                cast_distance,
+                min_wait_duration,
+                max_wait_duration,
+                reel_duration,
+                meta: _,
+            } => CharacterState::Fishing(fishing::Data {
+                static_data: fishing::StaticData {
+                    cast_duration: Duration::from_secs_f32(*cast_duration),
+                    cast_distance: *cast_distance,
+                    min_wait_duration: Duration::from_secs_f32(*min_wait_duration),
+                    max_wait_duration: Duration::from_secs_f32(*max_wait_duration),
+                    reel_duration: Duration::from_secs_f32(*reel_duration),
+                    ability_info,
+                },
+                timer: Duration::default(),
+                stage_section: StageSection::Buildup,
+                bobber_pos: None,
+                wait_duration: Duration::default(),
+            }),
             CharacterAbility::FinisherMelee {
                 energy_cost: _,
                 buildup_duration,
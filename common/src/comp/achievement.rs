@@ -0,0 +1,148 @@
+use crate::{
+    assets::{self, AssetExt, AssetHandle},
+    comp::Body,
+    trade::SiteId,
+};
+use hashbrown::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use specs::Component;
+
+/// Lifetime statistics of a character, collected by the server.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Statistics {
+    /// Kills by the kind of body of the killed entity, e.g. `QuadrupedMedium`
+    pub kills: HashMap<String, u64>,
+    pub deaths: u64,
+    /// Distance travelled, in blocks
+    pub distance_travelled: f64,
+    pub items_crafted: u64,
+    pub sites_visited: HashSet<SiteId>,
+    pub dungeons_cleared: u64,
+}
+
+impl Statistics {
+    pub fn record_kill(&mut self, body: &Body) {
+        *self.kills.entry(body.to_string()).or_default() += 1;
+    }
+
+    pub fn total_kills(&self) -> u64 { self.kills.values().sum() }
+}
+
+impl Component for Statistics {
+    type Storage = specs::DenseVecStorage<Self>;
+}
+
+/// The achievements that a player has unlocked. Achievements are shared by all
+/// characters of the player's account.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Achievements {
+    unlocked: HashSet<String>,
+}
+
+impl Achievements {
+    pub fn new(unlocked: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            unlocked: unlocked.into_iter().collect(),
+        }
+    }
+
+    pub fn has_unlocked(&self, achievement: &str) -> bool { self.unlocked.contains(achievement) }
+
+    /// Unlocks the given achievement, returning whether it was previously
+    /// locked.
+    pub fn unlock(&mut self, achievement: String) -> bool { self.unlocked.insert(achievement) }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &String> { self.unlocked.iter() }
+
+    pub fn is_empty(&self) -> bool { self.unlocked.is_empty() }
+}
+
+impl Component for Achievements {
+    type Storage = specs::DenseVecStorage<Self>;
+}
+
+/// What a character has to accomplish to unlock an achievement.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum Requirement {
+    /// Kill entities, optionally only those with the given kind of body
+    Kills {
+        body: Option<String>,
+        count: u64,
+    },
+    Deaths(u64),
+    DistanceTravelled(f64),
+    ItemsCrafted(u64),
+    SitesVisited(u64),
+    DungeonsCleared(u64),
+}
+
+impl Requirement {
+    /// The progress of the character towards the requirement, as a pair of the
+    /// current and the required amount.
+    pub fn progress(&self, stats: &Statistics) -> (f64, f64) {
+        match self {
+            Self::Kills { body, count } => {
+                let kills = match body {
+                    Some(body) => stats.kills.get(body).copied().unwrap_or(0),
+                    None => stats.total_kills(),
+                };
+                (kills as f64, *count as f64)
+            },
+            Self::Deaths(count) => (stats.deaths as f64, *count as f64),
+            Self::DistanceTravelled(distance) => (stats.distance_travelled, *distance),
+            Self::ItemsCrafted(count) => (stats.items_crafted as f64, *count as f64),
+            Self::SitesVisited(count) => (stats.sites_visited.len() as f64, *count as f64),
+            Self::DungeonsCleared(count) => (stats.dungeons_cleared as f64, *count as f64),
+        }
+    }
+
+    pub fn is_met(&self, stats: &Statistics) -> bool {
+        let (current, required) = self.progress(stats);
+        current >= required
+    }
+}
+
+/// The definition of an achievement. Its title and description are localised
+/// by the client using its id.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AchievementDef {
+    pub id: String,
+    pub requirement: Requirement,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct AchievementManifest(pub Vec<AchievementDef>);
+
+impl AchievementManifest {
+    pub fn get(&self, id: &str) -> Option<&AchievementDef> {
+        self.0.iter().find(|achievement| achievement.id == id)
+    }
+}
+
+impl assets::Asset for AchievementManifest {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+pub fn default_achievements() -> AssetHandle<AchievementManifest> {
+    AchievementManifest::load_expect("common.achievements")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn achievement_ids_are_unique() {
+        let achievements = default_achievements().read();
+        let mut ids = HashSet::new();
+        for achievement in &achievements.0 {
+            assert!(
+                ids.insert(&achievement.id),
+                "Duplicate achievement {}",
+                achievement.id
+            );
+        }
+    }
+}
@@ -1,10 +1,12 @@
 use crate::{
+    assets::{AssetExt, AssetHandle},
     comp::{
         arthropod, biped_small, bird_medium, humanoid, quadruped_low, quadruped_medium,
         quadruped_small, ship, Body, UtteranceKind,
     },
     path::Chaser,
     rtsim::{NpcInput, RtSimController},
+    tactic::TacticSpec,
     trade::{PendingTrade, ReducedInventory, SiteId, SitePrices, TradeId, TradeResult},
     uid::Uid,
 };
@@ -14,7 +16,7 @@ use std::{collections::VecDeque, fmt};
 use strum::{EnumIter, IntoEnumIterator};
 use vek::*;
 
-use super::{dialogue::Subject, Pos};
+use super::{dialogue::Subject, threat::ThreatTable, Pos};
 
 pub const DEFAULT_INTERACTION_TIME: f32 = 3.0;
 pub const TRADE_INTERACTION_TIME: f32 = 300.0;
@@ -28,18 +30,18 @@ const SECONDS_BEFORE_FORGET_SOUNDS: f64 = 180.0;
 
 /// The number of timers that a single Action node can track concurrently
 /// Define constants within a given action node to index between them.
-const ACTIONSTATE_NUMBER_OF_CONCURRENT_TIMERS: usize = 5;
+pub const ACTIONSTATE_NUMBER_OF_CONCURRENT_TIMERS: usize = 5;
 /// The number of float counters that a single Action node can track
 /// concurrently Define constants within a given action node to index between
 /// them.
-const ACTIONSTATE_NUMBER_OF_CONCURRENT_COUNTERS: usize = 5;
+pub const ACTIONSTATE_NUMBER_OF_CONCURRENT_COUNTERS: usize = 5;
 /// The number of integer counters that a single Action node can track
 /// concurrently Define constants within a given action node to index between
 /// them.
 const ACTIONSTATE_NUMBER_OF_CONCURRENT_INT_COUNTERS: usize = 5;
 /// The number of booleans that a single Action node can track concurrently
 /// Define constants within a given action node to index between them.
-const ACTIONSTATE_NUMBER_OF_CONCURRENT_CONDITIONS: usize = 5;
+pub const ACTIONSTATE_NUMBER_OF_CONCURRENT_CONDITIONS: usize = 5;
 /// The number of positions that can be remembered by an agent
 const ACTIONSTATE_NUMBER_OF_CONCURRENT_POSITIONS: usize = 5;
 
@@ -410,6 +412,8 @@ pub struct Sound {
     pub pos: Vec3<f32>,
     pub vol: f32,
     pub time: f64,
+    /// The entity that made the sound, if it was made by one
+    pub source: Option<Uid>,
 }
 
 impl Sound {
@@ -419,9 +423,17 @@ impl Sound {
             pos,
             vol,
             time,
+            source: None,
         }
     }
 
+    #[must_use]
+    pub fn with_source(mut self, source: Uid) -> Self {
+        self.source = Some(source);
+
+        self
+    }
+
     #[must_use]
     pub fn with_new_vol(mut self, new_vol: f32) -> Self {
         self.vol = new_vol;
@@ -576,8 +588,21 @@ pub struct Agent {
     pub awareness: Awareness,
     /// Inputs sent up to rtsim
     pub rtsim_outbox: Option<VecDeque<NpcInput>>,
+    /// Declarative tactic that overrides the tactic picked from the agent's
+    /// weapon
+    pub tactic: Option<AssetHandle<TacticSpec>>,
+    /// The threat that other entities have built up against the agent, used
+    /// to decide who to fight
+    pub threat: ThreatTable,
+    /// The squad of NPCs that the agent was spawned with
+    pub squad: Option<SquadId>,
 }
 
+/// Identifies a group of agents that were spawned together and coordinate
+/// with each other in combat and while patrolling
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SquadId(pub u64);
+
 #[derive(Clone, Debug)]
 /// Always clamped between `0.0` and `1.0`.
 pub struct Awareness {
@@ -681,6 +706,9 @@ impl Agent {
             flee_from_pos: None,
             awareness: Awareness::new(0.0),
             rtsim_outbox: None,
+            tactic: None,
+            threat: ThreatTable::default(),
+            squad: None,
         }
     }
 
@@ -715,6 +743,18 @@ impl Agent {
         self
     }
 
+    #[must_use]
+    pub fn with_tactic(mut self, tactic: Option<&str>) -> Self {
+        self.tactic = tactic.map(TacticSpec::load_expect);
+        self
+    }
+
+    #[must_use]
+    pub fn with_squad(mut self, squad: Option<SquadId>) -> Self {
+        self.squad = squad;
+        self
+    }
+
     #[must_use]
     pub fn with_idle_wander_factor(mut self, idle_wander_factor: f32) -> Self {
         self.psyche.idle_wander_factor = idle_wander_factor;
@@ -0,0 +1,84 @@
+use crate::comp::Item;
+use serde::{Deserialize, Serialize};
+use specs::Component;
+
+/// The number of slots in a player's bank.
+pub const BANK_SLOTS: usize = 40;
+
+/// Storage for items that is shared by every character of a player's account,
+/// and which can only be accessed at a bank sprite.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Bank {
+    slots: Vec<Option<Item>>,
+}
+
+impl Default for Bank {
+    fn default() -> Self {
+        Self {
+            slots: std::iter::repeat_with(|| None).take(BANK_SLOTS).collect(),
+        }
+    }
+}
+
+impl Bank {
+    pub fn slots(&self) -> impl Iterator<Item = &Option<Item>> { self.slots.iter() }
+
+    pub fn slots_mut(&mut self) -> impl Iterator<Item = &mut Option<Item>> { self.slots.iter_mut() }
+
+    /// Iterate over the occupied slots of the bank, along with their index.
+    pub fn slots_with_id(&self) -> impl Iterator<Item = (usize, &Item)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(i, slot)| Some((i, slot.as_ref()?)))
+    }
+
+    pub fn get(&self, slot: usize) -> Option<&Item> { self.slots.get(slot)?.as_ref() }
+
+    pub fn get_mut(&mut self, slot: usize) -> Option<&mut Item> {
+        self.slots.get_mut(slot)?.as_mut()
+    }
+
+    pub fn is_empty(&self) -> bool { self.slots.iter().all(Option::is_none) }
+
+    /// Put an item into an empty slot, returning it if the slot does not exist
+    /// or is already occupied.
+    pub fn insert_at(&mut self, slot: usize, item: Item) -> Result<(), Item> {
+        match self.slots.get_mut(slot) {
+            Some(slot @ None) => {
+                *slot = Some(item);
+                Ok(())
+            },
+            _ => Err(item),
+        }
+    }
+
+    /// Remove the whole stack of items in a slot.
+    pub fn take(&mut self, slot: usize) -> Option<Item> { self.slots.get_mut(slot)?.take() }
+
+    /// Add an item to the bank, merging it into an existing stack if possible
+    /// and otherwise putting it in the first empty slot. The item is returned
+    /// if the bank is full.
+    pub fn push(&mut self, item: Item) -> Result<(), Item> {
+        let item = match self
+            .slots
+            .iter_mut()
+            .flatten()
+            .find(|stack| stack.can_merge(&item))
+        {
+            Some(stack) => return stack.try_merge(item),
+            None => item,
+        };
+        match self.slots.iter_mut().find(|slot| slot.is_none()) {
+            Some(slot) => {
+                *slot = Some(item);
+                Ok(())
+            },
+            None => Err(item),
+        }
+    }
+}
+
+impl Component for Bank {
+    type Storage = specs::DenseVecStorage<Self>;
+}
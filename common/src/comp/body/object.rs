@@ -106,6 +106,7 @@ make_case_elim!(
         LaserBeam = 91,
         AdletSpear = 92,
         AdletTrap = 93,
+        DungeonPortal = 94,
     }
 );
 
@@ -116,7 +117,7 @@ impl Body {
     }
 }
 
-pub const ALL_OBJECTS: [Body; 94] = [
+pub const ALL_OBJECTS: [Body; 95] = [
     Body::Arrow,
     Body::Bomb,
     Body::Scarecrow,
@@ -211,6 +212,7 @@ pub const ALL_OBJECTS: [Body; 94] = [
     Body::LaserBeam,
     Body::AdletSpear,
     Body::AdletTrap,
+    Body::DungeonPortal,
 ];
 
 impl From<Body> for super::Body {
@@ -314,6 +316,7 @@ impl Body {
             Body::LaserBeam => "laser_beam",
             Body::AdletSpear => "adlet_spear",
             Body::AdletTrap => "adlet_trap",
+            Body::DungeonPortal => "dungeon_portal",
         }
     }
 
@@ -437,6 +440,7 @@ impl Body {
             Body::LaserBeam => 80000.0,
             Body::AdletSpear => 1.5,
             Body::AdletTrap => 10.0,
+            Body::DungeonPortal => 1000.0,
         };
 
         Mass(m)
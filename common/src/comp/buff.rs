@@ -1,7 +1,9 @@
 #![allow(clippy::nonstandard_macro_braces)] //tmp as of false positive !?
+#[cfg(not(target_arch = "wasm32"))]
+use crate::assets::{self, AssetExt, AssetHandle};
 use crate::{
     combat::{AttackEffect, CombatBuff, CombatBuffStrength, CombatEffect},
-    comp::{aura::AuraKey, Health, Stats},
+    comp::{aura::AuraKey, CrowdControl, DiminishingReturns, Health, Stats},
     resources::{Secs, Time},
     uid::Uid,
 };
@@ -10,6 +12,8 @@ use core::cmp::Ordering;
 #[cfg(not(target_arch = "wasm32"))]
 use hashbrown::HashMap;
 use itertools::Either;
+#[cfg(not(target_arch = "wasm32"))]
+use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 #[cfg(not(target_arch = "wasm32"))]
 use specs::{Component, DerefFlaggedStorage, VecStorage};
@@ -163,9 +167,30 @@ impl BuffKind {
     /// tick.
     pub fn affects_subsequent_buffs(self) -> bool { matches!(self, BuffKind::PotionSickness) }
 
+    /// How buffs of this kind behave when applied to an entity already affected
+    /// by the kind, as configured in `common.buff_stacking`.
+    pub fn stacking(self) -> BuffStacking {
+        BUFF_STACKING
+            .read()
+            .0
+            .get(&self)
+            .copied()
+            .unwrap_or(BuffStacking::StrongestWins)
+    }
+
     /// Checks if multiple instances of the buff should be processed, instead of
     /// only the strongest.
-    pub fn stacks(self) -> bool { matches!(self, BuffKind::PotionSickness) }
+    pub fn stacks(self) -> bool { matches!(self.stacking(), BuffStacking::Stack(_)) }
+
+    /// The kind of crowd control the buff causes, if any, which is subject to
+    /// diminishing returns when applied by characters.
+    pub fn crowd_control(self) -> Option<CrowdControl> {
+        match self {
+            BuffKind::Ensnared => Some(CrowdControl::Root),
+            BuffKind::Frozen | BuffKind::Crippled => Some(CrowdControl::Slow),
+            _ => None,
+        }
+    }
 
     pub fn effects(
         &self,
@@ -341,6 +366,39 @@ impl BuffData {
     }
 }
 
+/// How a buff is applied to an entity that already has buffs of the same kind
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BuffStacking {
+    /// Replaces any existing buffs of the kind, resetting the duration
+    Refresh,
+    /// Up to this many buffs of the kind take effect at once (or any number if
+    /// `None`), the one closest to expiring is removed to make room for a new
+    /// one
+    Stack(Option<usize>),
+    /// Buffs of the kind are kept until they expire, but only the strongest
+    /// takes effect
+    StrongestWins,
+}
+
+/// The stacking rules of buff kinds that don't use
+/// [`BuffStacking::StrongestWins`]
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug, Deserialize)]
+pub struct BuffStackingManifest(pub HashMap<BuffKind, BuffStacking>);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl assets::Asset for BuffStackingManifest {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+lazy_static! {
+    static ref BUFF_STACKING: AssetHandle<BuffStackingManifest> =
+        BuffStackingManifest::load_expect("common.buff_stacking");
+}
+
 /// De/buff category ID.
 /// Similar to `BuffKind`, but to mark a category (for more generic usage, like
 /// positive/negative buffs).
@@ -569,6 +627,8 @@ pub struct Buffs {
     pub kinds: HashMap<BuffKind, (Vec<BuffId>, Time)>,
     // All currently applied buffs stored by Id
     pub buffs: HashMap<BuffId, Buff>,
+    /// Diminishing returns of crowd control caused by buffs
+    pub crowd_control: HashMap<CrowdControl, DiminishingReturns>,
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -612,9 +672,51 @@ impl Buffs {
         id
     }
 
-    pub fn insert(&mut self, buff: Buff, current_time: Time) -> BuffId {
+    /// Adds a buff according to the stacking rules of its kind. Returns `None`
+    /// if the entity is currently immune to the crowd control the buff causes.
+    pub fn insert(&mut self, mut buff: Buff, current_time: Time) -> Option<BuffId> {
+        // Only crowd control from characters diminishes, so that standing in
+        // e.g. ensnaring vines keeps working
+        if let (Some(cc), BuffSource::Character { .. }, Some(end_time)) =
+            (buff.kind.crowd_control(), buff.source, buff.end_time)
+        {
+            let duration = self.crowd_control.entry(cc).or_default().apply(
+                cc,
+                current_time,
+                end_time.0 - buff.start_time.0,
+            )?;
+            buff.end_time = Some(Time(buff.start_time.0 + duration));
+        }
+
+        match buff.kind.stacking() {
+            BuffStacking::Refresh => self.remove_kind(buff.kind),
+            BuffStacking::Stack(Some(max)) => self.make_room(buff.kind, max),
+            BuffStacking::Stack(None) | BuffStacking::StrongestWins => {},
+        }
+
         self.id_counter += 1;
-        self.force_insert(self.id_counter, buff, current_time)
+        Some(self.force_insert(self.id_counter, buff, current_time))
+    }
+
+    /// Removes the buffs of a kind that are closest to expiring, so that
+    /// another can be added without exceeding `max` buffs of the kind
+    fn make_room(&mut self, kind: BuffKind, max: usize) {
+        let mut ids = self
+            .kinds
+            .get(&kind)
+            .map_or_else(Vec::new, |ids| ids.0.clone());
+        if ids.len() >= max {
+            let excess = ids.len() + 1 - max;
+            let end_time = |id: &BuffId| self.buffs[id].end_time.map_or(f64::INFINITY, |t| t.0);
+            ids.sort_by(|a, b| {
+                end_time(a)
+                    .partial_cmp(&end_time(b))
+                    .unwrap_or(Ordering::Equal)
+            });
+            for id in ids.into_iter().take(excess) {
+                self.remove(id);
+            }
+        }
     }
 
     pub fn contains(&self, kind: BuffKind) -> bool { self.kinds.contains_key(&kind) }
@@ -711,6 +813,96 @@ pub mod tests {
         )
     }
 
+    fn create_test_buff(
+        kind: BuffKind,
+        buff_data: BuffData,
+        source: BuffSource,
+        time: Time,
+    ) -> Buff {
+        Buff::new(kind, buff_data, Vec::new(), source, time, None, None)
+    }
+
+    #[test]
+    /// Tests that reapplying a buff that refreshes replaces the old one
+    fn test_refresh_buff() {
+        assert_eq!(BuffKind::Wet.stacking(), BuffStacking::Refresh);
+        let mut buff_comp: Buffs = Default::default();
+        let time_a = Time(0.0);
+        let buff_data = BuffData::new(1.0, Some(Secs(10.0)), None);
+        buff_comp.insert(
+            create_test_buff(BuffKind::Wet, buff_data, BuffSource::World, time_a),
+            time_a,
+        );
+        let time_b = Time(5.0);
+        let buff_data = BuffData::new(0.5, Some(Secs(10.0)), None);
+        buff_comp.insert(
+            create_test_buff(BuffKind::Wet, buff_data, BuffSource::World, time_b),
+            time_b,
+        );
+        assert_eq!(buff_comp.buffs.len(), 1);
+        assert!(
+            buff_comp
+                .buffs
+                .values()
+                .all(|b| b.end_time.unwrap().0 > 14.99 && b.data.strength < 0.51)
+        );
+    }
+
+    #[test]
+    /// Tests that making room for a stacking buff removes the stacks closest to
+    /// expiring
+    fn test_stack_limit() {
+        assert!(matches!(
+            BuffKind::PotionSickness.stacking(),
+            BuffStacking::Stack(_)
+        ));
+        let mut buff_comp: Buffs = Default::default();
+        let buff_data = BuffData::new(0.33, Some(Secs(45.0)), None);
+        for i in 0..6 {
+            let time = Time(f64::from(i));
+            buff_comp.insert(
+                create_test_buff(BuffKind::PotionSickness, buff_data, BuffSource::Item, time),
+                time,
+            );
+        }
+        assert_eq!(buff_comp.buffs.len(), 6);
+        buff_comp.make_room(BuffKind::PotionSickness, 5);
+        assert_eq!(buff_comp.buffs.len(), 4);
+        assert!(buff_comp.buffs.values().all(|b| b.start_time.0 > 1.99));
+    }
+
+    #[test]
+    /// Tests that crowd control from characters diminishes until the target is
+    /// immune, while crowd control from the world does not
+    fn test_crowd_control_diminishes() {
+        let mut buff_comp: Buffs = Default::default();
+        let buff_data = BuffData::new(1.0, Some(Secs(4.0)), None);
+        let source = BuffSource::Character { by: Uid(1) };
+        let mut durations = Vec::new();
+        for i in 0..4 {
+            let time = Time(f64::from(i) * 5.0);
+            let id = buff_comp.insert(
+                create_test_buff(BuffKind::Ensnared, buff_data, source, time),
+                time,
+            );
+            durations.push(id.map(|id| {
+                let buff = &buff_comp.buffs[&id];
+                buff.end_time.unwrap().0 - buff.start_time.0
+            }));
+        }
+        assert_eq!(durations, vec![Some(4.0), Some(2.0), Some(1.0), None]);
+
+        let time = Time(15.0);
+        assert!(
+            buff_comp
+                .insert(
+                    create_test_buff(BuffKind::Ensnared, buff_data, BuffSource::World, time),
+                    time,
+                )
+                .is_some()
+        );
+    }
+
     #[test]
     /// Tests a number of buffs with various progresses that queue to ensure
     /// queue has correct total duration
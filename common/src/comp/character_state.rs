@@ -130,12 +130,16 @@ pub enum CharacterState {
     /// Handles logic for interacting with a sprite, e.g. using a chest or
     /// picking a plant
     SpriteInteract(sprite_interact::Data),
+    /// Crafts a recipe at a crafting station, possibly several times in a row
+    Crafting(crafting::Data),
     /// Runs on the wall
     Wallrun(wallrun::Data),
     /// Ice skating or skiing
     Skate(skate::Data),
     /// Play music instrument
     Music(music::Data),
+    /// Cast a fishing line into water, wait for a bite and reel in the catch
+    Fishing(fishing::Data),
     /// Melee attack that scales off and consumes combo
     FinisherMelee(finisher_melee::Data),
     /// State entered when diving, melee attack triggered upon landing on the
@@ -171,6 +175,7 @@ impl CharacterState {
                 | CharacterState::SelfBuff(_)
                 | CharacterState::Blink(_)
                 | CharacterState::Music(_)
+                | CharacterState::Fishing(_)
                 | CharacterState::BasicSummon(_)
                 | CharacterState::SpriteSummon(_)
                 | CharacterState::Roll(roll::Data {
@@ -193,6 +198,7 @@ impl CharacterState {
             CharacterState::Roll(data) => data.was_wielded,
             CharacterState::Stunned(data) => data.was_wielded,
             CharacterState::SpriteInteract(data) => data.static_data.was_wielded,
+            CharacterState::Crafting(data) => data.static_data.was_wielded,
             CharacterState::UseItem(data) => data.static_data.was_wielded,
             _ => false,
         }
@@ -405,7 +411,9 @@ impl CharacterState {
                 | CharacterState::SpriteSummon(_)
                 | CharacterState::UseItem(_)
                 | CharacterState::SpriteInteract(_)
+                | CharacterState::Crafting(_)
                 | CharacterState::Music(_)
+                | CharacterState::Fishing(_)
                 | CharacterState::RiposteMelee(_)
                 | CharacterState::RapidMelee(_)
         )
@@ -470,8 +478,10 @@ impl CharacterState {
             CharacterState::SpriteSummon(data) => data.behavior(j, output_events),
             CharacterState::UseItem(data) => data.behavior(j, output_events),
             CharacterState::SpriteInteract(data) => data.behavior(j, output_events),
+            CharacterState::Crafting(data) => data.behavior(j, output_events),
             CharacterState::Skate(data) => data.behavior(j, output_events),
             CharacterState::Music(data) => data.behavior(j, output_events),
+            CharacterState::Fishing(data) => data.behavior(j, output_events),
             CharacterState::FinisherMelee(data) => data.behavior(j, output_events),
             CharacterState::DiveMelee(data) => data.behavior(j, output_events),
             CharacterState::RiposteMelee(data) => data.behavior(j, output_events),
@@ -524,8 +534,10 @@ impl CharacterState {
             CharacterState::SpriteSummon(data) => data.handle_event(j, output_events, action),
             CharacterState::UseItem(data) => data.handle_event(j, output_events, action),
             CharacterState::SpriteInteract(data) => data.handle_event(j, output_events, action),
+            CharacterState::Crafting(data) => data.handle_event(j, output_events, action),
             CharacterState::Skate(data) => data.handle_event(j, output_events, action),
             CharacterState::Music(data) => data.handle_event(j, output_events, action),
+            CharacterState::Fishing(data) => data.handle_event(j, output_events, action),
             CharacterState::FinisherMelee(data) => data.handle_event(j, output_events, action),
             CharacterState::DiveMelee(data) => data.handle_event(j, output_events, action),
             CharacterState::RiposteMelee(data) => data.handle_event(j, output_events, action),
@@ -578,8 +590,10 @@ impl CharacterState {
             CharacterState::SpriteSummon(data) => Some(data.static_data.ability_info),
             CharacterState::UseItem(_) => None,
             CharacterState::SpriteInteract(_) => None,
+            CharacterState::Crafting(_) => None,
             CharacterState::FinisherMelee(data) => Some(data.static_data.ability_info),
             CharacterState::Music(data) => Some(data.static_data.ability_info),
+            CharacterState::Fishing(data) => Some(data.static_data.ability_info),
             CharacterState::DiveMelee(data) => Some(data.static_data.ability_info),
             CharacterState::RiposteMelee(data) => Some(data.static_data.ability_info),
             CharacterState::RapidMelee(data) => Some(data.static_data.ability_info),
@@ -623,8 +637,10 @@ impl CharacterState {
             CharacterState::SpriteSummon(data) => Some(data.stage_section),
             CharacterState::UseItem(data) => Some(data.stage_section),
             CharacterState::SpriteInteract(data) => Some(data.stage_section),
+            CharacterState::Crafting(_) => Some(StageSection::Action),
             CharacterState::FinisherMelee(data) => Some(data.stage_section),
             CharacterState::Music(data) => Some(data.stage_section),
+            CharacterState::Fishing(data) => Some(data.stage_section),
             CharacterState::DiveMelee(data) => Some(data.stage_section),
             CharacterState::RiposteMelee(data) => Some(data.stage_section),
             CharacterState::RapidMelee(data) => Some(data.stage_section),
@@ -794,6 +810,10 @@ impl CharacterState {
                 recover: Some(data.static_data.recover_duration),
                 ..Default::default()
             }),
+            CharacterState::Crafting(data) => Some(DurationsInfo {
+                action: Some(data.static_data.craft_duration),
+                ..Default::default()
+            }),
             CharacterState::FinisherMelee(data) => Some(DurationsInfo {
                 buildup: Some(data.static_data.buildup_duration),
                 action: Some(data.static_data.swing_duration),
@@ -804,6 +824,12 @@ impl CharacterState {
                 action: Some(data.static_data.play_duration),
                 ..Default::default()
             }),
+            CharacterState::Fishing(data) => Some(DurationsInfo {
+                buildup: Some(data.static_data.cast_duration),
+                action: Some(data.wait_duration),
+                recover: Some(data.static_data.reel_duration),
+                ..Default::default()
+            }),
             CharacterState::DiveMelee(data) => Some(DurationsInfo {
                 action: Some(data.static_data.swing_duration),
                 recover: Some(data.static_data.recover_duration),
@@ -862,8 +888,10 @@ impl CharacterState {
             CharacterState::SpriteSummon(data) => Some(data.timer),
             CharacterState::UseItem(data) => Some(data.timer),
             CharacterState::SpriteInteract(data) => Some(data.timer),
+            CharacterState::Crafting(data) => Some(data.timer),
             CharacterState::FinisherMelee(data) => Some(data.timer),
             CharacterState::Music(data) => Some(data.timer),
+            CharacterState::Fishing(data) => Some(data.timer),
             CharacterState::DiveMelee(data) => Some(data.timer),
             CharacterState::RiposteMelee(data) => Some(data.timer),
             CharacterState::RapidMelee(data) => Some(data.timer),
@@ -928,8 +956,10 @@ impl CharacterState {
             CharacterState::SpriteSummon(_) => None,
             CharacterState::UseItem(_) => None,
             CharacterState::SpriteInteract(_) => None,
+            CharacterState::Crafting(_) => None,
             CharacterState::FinisherMelee(_) => Some(AttackSource::Melee),
             CharacterState::Music(_) => None,
+            CharacterState::Fishing(_) => None,
             CharacterState::DiveMelee(_) => Some(AttackSource::Melee),
             CharacterState::RiposteMelee(_) => Some(AttackSource::Melee),
             CharacterState::RapidMelee(_) => Some(AttackSource::Melee),
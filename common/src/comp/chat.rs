@@ -19,8 +19,8 @@ pub enum ChatMode {
     Region,
     /// Talk to your current group of players
     Group(Group),
-    /// Talk to your faction
-    Faction(String),
+    /// Talk to your guild
+    Guild(String),
     /// Talk to every player on the server
     World,
 }
@@ -37,7 +37,7 @@ impl ChatMode {
             ChatMode::Say => ChatType::Say(from),
             ChatMode::Region => ChatType::Region(from),
             ChatMode::Group(group) => ChatType::Group(from, *group),
-            ChatMode::Faction(faction) => ChatType::Faction(from, faction.clone()),
+            ChatMode::Guild(guild) => ChatType::Guild(from, guild.clone()),
             ChatMode::World => ChatType::World(from),
         };
         UnresolvedChatMsg {
@@ -91,16 +91,16 @@ pub enum ChatType<G> {
     Kill(KillSource, Uid),
     /// Server notifications to a group, such as player join/leave
     GroupMeta(G),
-    /// Server notifications to a faction, such as player join/leave
-    FactionMeta(String),
+    /// Server notifications to a guild, such as player join/leave
+    GuildMeta(String),
     /// One-on-one chat (from, to)
     Tell(Uid, Uid),
     /// Chat with nearby players
     Say(Uid),
     /// Group chat
     Group(Uid, G),
-    /// Factional chat
-    Faction(Uid, String),
+    /// Guild chat
+    Guild(Uid, String),
     /// Regional chat
     Region(Uid),
     /// World chat
@@ -137,13 +137,13 @@ impl<G> ChatType<G> {
             ChatType::Offline(_) => None,
             ChatType::CommandInfo => None,
             ChatType::CommandError => None,
-            ChatType::FactionMeta(_) => None,
+            ChatType::GuildMeta(_) => None,
             ChatType::GroupMeta(_) => None,
             ChatType::Kill(_, _) => None,
             ChatType::Tell(u, _t) => Some(*u),
             ChatType::Say(u) => Some(*u),
             ChatType::Group(u, _s) => Some(*u),
-            ChatType::Faction(u, _s) => Some(*u),
+            ChatType::Guild(u, _s) => Some(*u),
             ChatType::Region(u) => Some(*u),
             ChatType::World(u) => Some(*u),
             ChatType::Npc(u) => Some(*u),
@@ -160,14 +160,14 @@ impl<G> ChatType<G> {
             | ChatType::Offline(_)
             | ChatType::CommandInfo
             | ChatType::CommandError
-            | ChatType::FactionMeta(_)
+            | ChatType::GuildMeta(_)
             | ChatType::GroupMeta(_)
             | ChatType::Npc(_)
             | ChatType::NpcSay(_)
             | ChatType::NpcTell(_, _)
             | ChatType::Meta
             | ChatType::Kill(_, _) => None,
-            ChatType::Tell(_, _) | ChatType::Group(_, _) | ChatType::Faction(_, _) => Some(true),
+            ChatType::Tell(_, _) | ChatType::Group(_, _) | ChatType::Guild(_, _) => Some(true),
             ChatType::Say(_) | ChatType::Region(_) | ChatType::World(_) => Some(false),
         }
     }
@@ -321,13 +321,13 @@ impl<G> GenericChatMsg<G> {
             ChatType::Offline(a) => ChatType::Offline(a),
             ChatType::CommandInfo => ChatType::CommandInfo,
             ChatType::CommandError => ChatType::CommandError,
-            ChatType::FactionMeta(a) => ChatType::FactionMeta(a),
+            ChatType::GuildMeta(a) => ChatType::GuildMeta(a),
             ChatType::GroupMeta(g) => ChatType::GroupMeta(f(g)),
             ChatType::Kill(a, b) => ChatType::Kill(a, b),
             ChatType::Tell(a, b) => ChatType::Tell(a, b),
             ChatType::Say(a) => ChatType::Say(a),
             ChatType::Group(a, g) => ChatType::Group(a, f(g)),
-            ChatType::Faction(a, b) => ChatType::Faction(a, b),
+            ChatType::Guild(a, b) => ChatType::Guild(a, b),
             ChatType::Region(a) => ChatType::Region(a),
             ChatType::World(a) => ChatType::World(a),
             ChatType::Npc(a) => ChatType::Npc(a),
@@ -361,13 +361,13 @@ impl<G> GenericChatMsg<G> {
             ChatType::Offline(_) => SpeechBubbleType::None,
             ChatType::CommandInfo => SpeechBubbleType::None,
             ChatType::CommandError => SpeechBubbleType::None,
-            ChatType::FactionMeta(_) => SpeechBubbleType::None,
+            ChatType::GuildMeta(_) => SpeechBubbleType::None,
             ChatType::GroupMeta(_) => SpeechBubbleType::None,
             ChatType::Kill(_, _) => SpeechBubbleType::None,
             ChatType::Tell(_u, _) => SpeechBubbleType::Tell,
             ChatType::Say(_u) => SpeechBubbleType::Say,
             ChatType::Group(_u, _s) => SpeechBubbleType::Group,
-            ChatType::Faction(_u, _s) => SpeechBubbleType::Faction,
+            ChatType::Guild(_u, _s) => SpeechBubbleType::Guild,
             ChatType::Region(_u) => SpeechBubbleType::Region,
             ChatType::World(_u) => SpeechBubbleType::World,
             ChatType::Npc(_u) => SpeechBubbleType::None,
@@ -386,19 +386,6 @@ impl<G> GenericChatMsg<G> {
     pub fn set_content(&mut self, content: Content) { self.content = content; }
 }
 
-/// Player factions are used to coordinate pvp vs hostile factions or segment
-/// chat from the world
-///
-/// Factions are currently just an associated String (the faction's name)
-#[derive(Clone, Debug)]
-pub struct Faction(pub String);
-impl Component for Faction {
-    type Storage = DenseVecStorage<Self>;
-}
-impl From<String> for Faction {
-    fn from(s: String) -> Self { Faction(s) }
-}
-
 /// List of chat types for players and NPCs. Each one has its own icon.
 ///
 /// This is a subset of `ChatType`, and a superset of `ChatMode`
@@ -408,7 +395,7 @@ pub enum SpeechBubbleType {
     Say,
     Region,
     Group,
-    Faction,
+    Guild,
     World,
     // For NPCs
     Quest, // TODO not implemented
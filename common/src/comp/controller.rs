@@ -7,6 +7,8 @@ use crate::{
             slot::{EquipSlot, InvSlotId, Slot},
         },
         invite::{InviteKind, InviteResponse},
+        mail::MailId,
+        market::MarketOrderId,
         BuffKind,
     },
     mounting::VolumePos,
@@ -31,6 +33,52 @@ pub enum InventoryEvent {
         craft_event: CraftEvent,
         craft_sprite: Option<VolumePos>,
     },
+    /// Move the whole stack in an inventory slot into the bank.
+    BankDeposit {
+        bank_sprite: Vec3<i32>,
+        slot: InvSlotId,
+    },
+    /// Move the whole stack in a bank slot into the inventory.
+    BankWithdraw {
+        bank_sprite: Vec3<i32>,
+        bank_slot: usize,
+    },
+    /// Send a mail to the character with the given name, moving the items in
+    /// the attached slots out of the inventory.
+    SendMail {
+        mailbox_sprite: Vec3<i32>,
+        recipient: String,
+        body: String,
+        attachments: Vec<InvSlotId>,
+    },
+    /// Move the attachments of a mail into the inventory, and remove the mail
+    /// from the mailbox.
+    ClaimMail {
+        mailbox_sprite: Vec3<i32>,
+        mail_id: MailId,
+    },
+    /// Put up some of the stack in an inventory slot for sale on a market
+    /// board, at the given price per item.
+    PostSellOrder {
+        market_sprite: Vec3<i32>,
+        slot: InvSlotId,
+        quantity: u32,
+        unit_price: u32,
+    },
+    /// Offer to buy some of an item on a market board, at the given price per
+    /// item. The coins for the whole order are taken from the inventory.
+    PostBuyOrder {
+        market_sprite: Vec3<i32>,
+        item_definition_id: String,
+        quantity: u32,
+        unit_price: u32,
+    },
+    /// Withdraw one of the character's own orders from a market board. Whatever
+    /// is left of the order is returned by mail.
+    CancelMarketOrder {
+        market_sprite: Vec3<i32>,
+        order_id: MarketOrderId,
+    },
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -60,7 +108,63 @@ pub enum InventoryManip {
         craft_event: CraftEvent,
         craft_sprite: Option<VolumePos>,
     },
+    /// Craft a simple recipe once, at the end of each craft of the
+    /// [`Crafting`](crate::states::crafting) character state. The ingredients
+    /// are taken from wherever they are in the inventory at the time.
+    FinishCraft {
+        recipe: String,
+        craft_sprite: VolumePos,
+    },
     SwapEquippedWeapons,
+    /// Plant the seed in the given slot on the tilled soil at the given
+    /// position.
+    Plant {
+        sprite_pos: Vec3<i32>,
+        seed: InvSlotId,
+    },
+    /// Open the bank after interacting with the bank sprite at the given
+    /// position.
+    OpenBank(Vec3<i32>),
+    BankDeposit {
+        bank_sprite: Vec3<i32>,
+        slot: InvSlotId,
+    },
+    BankWithdraw {
+        bank_sprite: Vec3<i32>,
+        bank_slot: usize,
+    },
+    /// Open the mailbox after interacting with the mailbox sprite at the given
+    /// position.
+    OpenMailbox(Vec3<i32>),
+    SendMail {
+        mailbox_sprite: Vec3<i32>,
+        recipient: String,
+        body: String,
+        attachments: Vec<InvSlotId>,
+    },
+    ClaimMail {
+        mailbox_sprite: Vec3<i32>,
+        mail_id: MailId,
+    },
+    /// Open the market board after interacting with the market board sprite at
+    /// the given position.
+    OpenMarket(Vec3<i32>),
+    PostSellOrder {
+        market_sprite: Vec3<i32>,
+        slot: InvSlotId,
+        quantity: u32,
+        unit_price: u32,
+    },
+    PostBuyOrder {
+        market_sprite: Vec3<i32>,
+        item_definition_id: String,
+        quantity: u32,
+        unit_price: u32,
+    },
+    CancelMarketOrder {
+        market_sprite: Vec3<i32>,
+        order_id: MarketOrderId,
+    },
 }
 
 impl From<InventoryEvent> for InventoryManip {
@@ -83,6 +187,63 @@ impl From<InventoryEvent> for InventoryManip {
                 craft_event,
                 craft_sprite,
             },
+            InventoryEvent::BankDeposit { bank_sprite, slot } => {
+                Self::BankDeposit { bank_sprite, slot }
+            },
+            InventoryEvent::BankWithdraw {
+                bank_sprite,
+                bank_slot,
+            } => Self::BankWithdraw {
+                bank_sprite,
+                bank_slot,
+            },
+            InventoryEvent::SendMail {
+                mailbox_sprite,
+                recipient,
+                body,
+                attachments,
+            } => Self::SendMail {
+                mailbox_sprite,
+                recipient,
+                body,
+                attachments,
+            },
+            InventoryEvent::ClaimMail {
+                mailbox_sprite,
+                mail_id,
+            } => Self::ClaimMail {
+                mailbox_sprite,
+                mail_id,
+            },
+            InventoryEvent::PostSellOrder {
+                market_sprite,
+                slot,
+                quantity,
+                unit_price,
+            } => Self::PostSellOrder {
+                market_sprite,
+                slot,
+                quantity,
+                unit_price,
+            },
+            InventoryEvent::PostBuyOrder {
+                market_sprite,
+                item_definition_id,
+                quantity,
+                unit_price,
+            } => Self::PostBuyOrder {
+                market_sprite,
+                item_definition_id,
+                quantity,
+                unit_price,
+            },
+            InventoryEvent::CancelMarketOrder {
+                market_sprite,
+                order_id,
+            } => Self::CancelMarketOrder {
+                market_sprite,
+                order_id,
+            },
         }
     }
 }
@@ -0,0 +1,122 @@
+use crate::resources::Time;
+use serde::{Deserialize, Serialize};
+
+/// Kinds of crowd control, each of which has its own diminishing returns so
+/// that chaining the same kind of crowd control on a target becomes less
+/// effective, until the target is briefly immune to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CrowdControl {
+    /// Caused by a poise state
+    Stun,
+    /// Prevents movement entirely, e.g. being ensnared
+    Root,
+    /// Reduces movement speed, e.g. being frozen or crippled
+    Slow,
+}
+
+impl CrowdControl {
+    /// How much the duration of each successive application is multiplied by,
+    /// the target becomes immune after the last one.
+    pub fn duration_multipliers(self) -> &'static [f64] {
+        match self {
+            // The length of a stun is determined by the poise state, so stuns only lead to
+            // immunity
+            CrowdControl::Stun => &[1.0, 1.0, 1.0],
+            CrowdControl::Root => &[1.0, 0.5, 0.25],
+            CrowdControl::Slow => &[1.0, 0.75, 0.5],
+        }
+    }
+}
+
+/// Tracks how often a kind of crowd control was applied to an entity recently
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DiminishingReturns {
+    /// How many times the crowd control was applied since the returns were
+    /// last reset
+    applications: u8,
+    /// When the most recently applied crowd control ends
+    last_end: Option<Time>,
+    /// When the entity stops being immune to the crowd control
+    immune_until: Option<Time>,
+}
+
+impl DiminishingReturns {
+    /// Immunity granted after the last diminished application ends
+    pub const IMMUNITY_TIME: f64 = 5.0;
+    /// Time after the last application ended before the returns reset
+    pub const RESET_TIME: f64 = 15.0;
+
+    pub fn is_immune(&self, time: Time) -> bool {
+        self.immune_until.map_or(false, |until| until.0 > time.0)
+    }
+
+    /// Returns `true` if nothing was applied for long enough that the returns
+    /// can be reset
+    pub fn is_expired(&self, time: Time) -> bool {
+        *self != Self::default()
+            && !self.is_immune(time)
+            && self
+                .last_end
+                .map_or(true, |end| time.0 - end.0 > Self::RESET_TIME)
+    }
+
+    /// Records an application of crowd control lasting `duration` seconds,
+    /// returning how long it should actually last, or `None` if the entity is
+    /// immune to it.
+    pub fn apply(&mut self, kind: CrowdControl, time: Time, duration: f64) -> Option<f64> {
+        if self.is_immune(time) {
+            return None;
+        }
+        if self.is_expired(time) {
+            *self = Self::default();
+        }
+
+        let multipliers = kind.duration_multipliers();
+        let index = usize::from(self.applications).min(multipliers.len() - 1);
+        let duration = duration * multipliers[index];
+        let end = time.0 + duration;
+        self.last_end = Some(Time(self.last_end.map_or(end, |last| last.0.max(end))));
+        self.applications += 1;
+
+        if usize::from(self.applications) >= multipliers.len() {
+            self.applications = 0;
+            self.immune_until = Some(Time(end + Self::IMMUNITY_TIME));
+        }
+
+        Some(duration)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_diminish_until_immune() {
+        let mut returns = DiminishingReturns::default();
+        assert_eq!(returns.apply(CrowdControl::Root, Time(0.0), 4.0), Some(4.0));
+        assert_eq!(returns.apply(CrowdControl::Root, Time(5.0), 4.0), Some(2.0));
+        assert_eq!(returns.apply(CrowdControl::Root, Time(8.0), 4.0), Some(1.0));
+        // The last root ends at 9 seconds, so the target is immune for 5 seconds after
+        assert!(returns.is_immune(Time(13.0)));
+        assert_eq!(returns.apply(CrowdControl::Root, Time(13.0), 4.0), None);
+        assert!(!returns.is_immune(Time(14.5)));
+        assert_eq!(
+            returns.apply(CrowdControl::Root, Time(14.5), 4.0),
+            Some(4.0)
+        );
+    }
+
+    #[test]
+    fn returns_reset_after_a_while() {
+        let mut returns = DiminishingReturns::default();
+        assert_eq!(returns.apply(CrowdControl::Slow, Time(0.0), 2.0), Some(2.0));
+        assert_eq!(returns.apply(CrowdControl::Slow, Time(1.0), 2.0), Some(1.5));
+        assert!(!returns.is_expired(Time(10.0)));
+        assert!(returns.is_expired(Time(20.0)));
+        assert_eq!(
+            returns.apply(CrowdControl::Slow, Time(20.0), 2.0),
+            Some(2.0)
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use crate::resources::Time;
+use specs::{Component, DenseVecStorage, Entity};
+use vek::*;
+
+/// How far, in blocks, a duellist may stray from the centre of the arena
+/// before forfeiting the duel.
+pub const DUEL_ARENA_RADIUS: f32 = 40.0;
+/// The number of seconds between a duel being accepted and the fight
+/// beginning.
+pub const DUEL_COUNTDOWN_SECS: f64 = 5.0;
+/// The longest that a duel may last, in seconds, before it is called a draw.
+pub const DUEL_TIME_LIMIT_SECS: f64 = 180.0;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DuelPhase {
+    /// The duel has been accepted, but the fight has not yet begun.
+    Countdown,
+    Fighting,
+    /// The duellist has been beaten by their opponent, and the duel is about
+    /// to end.
+    Defeated,
+}
+
+/// A consensual fight between two players, during which they may harm each
+/// other regardless of their battle modes. Both duellists have this
+/// component, each referring to the other.
+#[derive(Clone, Debug)]
+pub struct Duel {
+    pub opponent: Entity,
+    /// The centre of the arena that the duellists must stay within.
+    pub arena: Vec3<f32>,
+    /// When the fight begins.
+    pub start: Time,
+    pub phase: DuelPhase,
+}
+
+impl Duel {
+    /// Whether this duellist may currently harm `other` as part of the duel.
+    pub fn may_harm(&self, other: Entity) -> bool {
+        self.opponent == other && self.phase == DuelPhase::Fighting
+    }
+
+    pub fn within_arena(&self, pos: Vec3<f32>) -> bool {
+        self.arena.xy().distance_squared(pos.xy()) < DUEL_ARENA_RADIUS.powi(2)
+    }
+
+    pub fn timed_out(&self, time: Time) -> bool { time.0 > self.start.0 + DUEL_TIME_LIMIT_SECS }
+}
+
+impl Component for Duel {
+    type Storage = DenseVecStorage<Self>;
+}
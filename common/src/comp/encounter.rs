@@ -0,0 +1,160 @@
+//! Scripted boss encounters.
+//!
+//! An encounter splits a boss fight into phases, each with its own tactic.
+//! Phases begin once the boss' health drops low enough or a phase has gone on
+//! for long enough. While players are fighting the boss the arena can be
+//! sealed off with temporary blocks, and the whole encounter resets once no
+//! living player is left in the arena.
+
+use crate::{
+    assets::{self, AssetExt, AssetHandle},
+    comp::agent::ACTIONSTATE_NUMBER_OF_CONCURRENT_CONDITIONS,
+    resources::Time,
+    tactic::TacticSpec,
+    terrain::{Block, BlockKind},
+};
+use serde::Deserialize;
+use specs::{Component, DenseVecStorage};
+use vek::*;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct EncounterSpec {
+    /// How far from where the boss spawned the fight takes place. Living
+    /// players within this distance start the encounter and keep it going.
+    pub arena_radius: f32,
+    /// Seals the edge of the arena while the fight is going on
+    #[serde(default)]
+    pub lockdown: Option<LockdownSpec>,
+    /// The phases of the fight, in order. The first phase is used from the
+    /// start of the fight, so its trigger is ignored.
+    pub phases: Vec<PhaseSpec>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct LockdownSpec {
+    /// How many blocks high the wall around the arena is
+    pub height: u32,
+    pub block: BlockKind,
+    pub color: Rgb<u8>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct PhaseSpec {
+    /// What causes the fight to move on to this phase
+    #[serde(default)]
+    pub trigger: Option<PhaseTrigger>,
+    /// Asset specifier of the declarative tactic that decides which
+    /// abilities the boss uses during this phase. When `None`, the tactic of
+    /// the boss' weapon is used.
+    #[serde(default)]
+    pub tactic: Option<String>,
+    /// An `ActionState` condition that is set when the phase begins, and
+    /// cleared again once the boss casts a `BasicSummon`. The phase's tactic
+    /// should use it to summon adds.
+    #[serde(default)]
+    pub summon_condition: Option<usize>,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub enum PhaseTrigger {
+    /// The boss' health fraction drops below this value
+    HealthBelow(f32),
+    /// The previous phase has lasted this many seconds
+    Timer(f32),
+}
+
+impl PhaseTrigger {
+    pub fn is_triggered(&self, health_fraction: f32, phase_duration: f64) -> bool {
+        match self {
+            Self::HealthBelow(fraction) => health_fraction < *fraction,
+            Self::Timer(secs) => phase_duration > *secs as f64,
+        }
+    }
+}
+
+impl LockdownSpec {
+    pub fn barrier(&self) -> Block { Block::new(self.block, self.color) }
+}
+
+impl EncounterSpec {
+    /// Checks that the encounter has phases, that every phase but the first
+    /// can be triggered, and that its tactics exist and are valid.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.phases.is_empty() {
+            return Err("Encounter has no phases".to_string());
+        }
+        for (i, phase) in self.phases.iter().enumerate() {
+            if i > 0 && phase.trigger.is_none() {
+                return Err(format!("Phase {i} has no trigger"));
+            }
+            if let Some(condition) = phase.summon_condition
+                && condition >= ACTIONSTATE_NUMBER_OF_CONCURRENT_CONDITIONS
+            {
+                return Err(format!(
+                    "Summon condition {condition} of phase {i} is out of range"
+                ));
+            }
+            if let Some(tactic) = &phase.tactic {
+                TacticSpec::load(tactic)
+                    .map_err(|e| format!("Failed to load tactic {tactic}: {e:?}"))?
+                    .read()
+                    .validate()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl assets::Asset for EncounterSpec {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+/// The progress of a boss through its encounter
+#[derive(Clone, Debug)]
+pub struct Encounter {
+    /// The phases and arena of the encounter
+    pub spec: AssetHandle<EncounterSpec>,
+    /// The centre of the arena, which is where the boss was first seen
+    pub home: Option<Vec3<f32>>,
+    /// The index of the current phase
+    pub phase: usize,
+    /// When the fight began, or `None` if no fight is going on
+    pub started: Option<Time>,
+    /// When the current phase began
+    pub phase_started: Time,
+}
+
+impl Encounter {
+    pub fn new(spec: &str) -> Self {
+        Self {
+            spec: EncounterSpec::load_expect(spec),
+            home: None,
+            phase: 0,
+            started: None,
+            phase_started: Time(0.0),
+        }
+    }
+}
+
+impl Component for Encounter {
+    type Storage = DenseVecStorage<Self>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_encounters_are_valid() {
+        let encounters = assets::load_dir::<EncounterSpec>("common.encounters", true)
+            .expect("Failed to load encounters");
+        for id in encounters.ids() {
+            if let Err(e) = EncounterSpec::load_expect(id).read().validate() {
+                panic!("Invalid encounter {id}: {e}");
+            }
+        }
+    }
+}
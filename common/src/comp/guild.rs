@@ -0,0 +1,124 @@
+use crate::uid::Uid;
+use serde::{Deserialize, Serialize};
+use specs::{Component, DerefFlaggedStorage};
+
+/// The longest name that a guild may have.
+pub const MAX_GUILD_NAME_LEN: usize = 24;
+
+/// The persistent identifier of a guild.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+pub struct GuildId(pub i64);
+
+/// The rank of a member within their guild. Ranks are ordered, such that
+/// higher ranks have all of the permissions of lower ranks.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq, Ord, PartialOrd, Serialize, Deserialize)]
+pub enum GuildRank {
+    Recruit,
+    Member,
+    Officer,
+    Leader,
+}
+
+/// Actions that members of a guild may be permitted to take.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum GuildPermission {
+    /// Talk in guild chat.
+    Chat,
+    /// Invite new members to the guild.
+    Invite,
+    /// Remove members of a lower rank from the guild.
+    Kick,
+    /// Promote or demote members of a lower rank.
+    ChangeRank,
+    /// Disband the guild entirely.
+    Disband,
+}
+
+impl GuildRank {
+    pub fn has_permission(&self, permission: GuildPermission) -> bool {
+        match permission {
+            GuildPermission::Chat => true,
+            GuildPermission::Invite => *self >= GuildRank::Member,
+            GuildPermission::Kick | GuildPermission::ChangeRank => *self >= GuildRank::Officer,
+            GuildPermission::Disband => *self == GuildRank::Leader,
+        }
+    }
+
+    /// Whether a member with this rank may manage (kick, promote or demote) a
+    /// member with the given rank.
+    pub fn can_manage(&self, other: GuildRank) -> bool {
+        self.has_permission(GuildPermission::ChangeRank) && *self > other
+    }
+
+    /// The rank above this one, if any. Leadership is transferred rather than
+    /// granted by promotion, so this never returns [`GuildRank::Leader`].
+    pub fn promoted(&self) -> Option<Self> {
+        match self {
+            GuildRank::Recruit => Some(GuildRank::Member),
+            GuildRank::Member => Some(GuildRank::Officer),
+            GuildRank::Officer | GuildRank::Leader => None,
+        }
+    }
+
+    pub fn demoted(&self) -> Option<Self> {
+        match self {
+            GuildRank::Member => Some(GuildRank::Recruit),
+            GuildRank::Officer => Some(GuildRank::Member),
+            GuildRank::Recruit | GuildRank::Leader => None,
+        }
+    }
+}
+
+/// Membership of a player character in a guild.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Guild {
+    pub id: GuildId,
+    pub name: String,
+    pub rank: GuildRank,
+}
+
+impl Component for Guild {
+    type Storage = DerefFlaggedStorage<Self, specs::DenseVecStorage<Self>>;
+}
+
+/// A member of a guild, as presented to other members of the guild.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuildMember {
+    pub name: String,
+    pub rank: GuildRank,
+    /// Present if the member is currently online.
+    pub uid: Option<Uid>,
+}
+
+/// The full membership of a guild, sent to each of its online members.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuildRoster {
+    pub id: GuildId,
+    pub name: String,
+    pub members: Vec<GuildMember>,
+}
+
+impl GuildRoster {
+    pub fn leader(&self) -> Option<&GuildMember> {
+        self.members
+            .iter()
+            .find(|member| member.rank == GuildRank::Leader)
+    }
+
+    pub fn online(&self) -> impl Iterator<Item = &GuildMember> {
+        self.members.iter().filter(|member| member.uid.is_some())
+    }
+}
+
+/// Changes to guild membership requested by a player.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GuildManip {
+    Create(String),
+    Leave,
+    Kick(Uid),
+    Promote(Uid),
+    Demote(Uid),
+    /// Hand leadership of the guild to another member.
+    AssignLeader(Uid),
+    Disband,
+}
@@ -83,6 +83,56 @@ impl Quality {
     pub const MIN: Self = Self::Low;
 }
 
+/// How well an individual item was made. Unlike [`Quality`], which is the same
+/// for every item of a definition, this varies between crafted items and
+/// scales their stats.
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum CraftQuality {
+    Crude,
+    #[default]
+    Standard,
+    Fine,
+    Masterwork,
+}
+
+impl CraftQuality {
+    /// The multiplier applied to the stats of tools and armour of this quality
+    pub fn stats_multiplier(self) -> f32 {
+        match self {
+            Self::Crude => 0.9,
+            Self::Standard => 1.0,
+            Self::Fine => 1.1,
+            Self::Masterwork => 1.2,
+        }
+    }
+
+    /// A score that can be averaged across the ingredients of a recipe, where
+    /// standard quality is 0.
+    pub fn score(self) -> f32 {
+        match self {
+            Self::Crude => -1.0,
+            Self::Standard => 0.0,
+            Self::Fine => 1.0,
+            Self::Masterwork => 2.0,
+        }
+    }
+
+    /// The quality that a score rounds to
+    pub fn from_score(score: f32) -> Self {
+        if score < -0.5 {
+            Self::Crude
+        } else if score < 0.5 {
+            Self::Standard
+        } else if score < 1.5 {
+            Self::Fine
+        } else {
+            Self::Masterwork
+        }
+    }
+}
+
 pub trait TagExampleInfo {
     fn name(&self) -> &str;
     /// What item to show in the crafting hud if the player has nothing with the
@@ -317,6 +367,8 @@ pub enum ConsumableKind {
     Drink,
     Food,
     ComplexFood,
+    /// Read to learn the recipes that it teaches
+    Scroll,
 }
 
 impl ItemKind {
@@ -415,6 +467,9 @@ pub struct Item {
     /// converted into the items durability. Only tracked for tools and armor
     /// currently.
     durability_lost: Option<u32>,
+    /// How well the item was made, for items that were crafted
+    #[serde(default)]
+    craft_quality: CraftQuality,
 }
 
 use std::hash::{Hash, Hasher};
@@ -718,6 +773,7 @@ impl PartialEq for Item {
         {
             self_def.item_definition_id == other_def.item_definition_id
                 && self.components == other.components
+                && self.craft_quality == other.craft_quality
         } else {
             false
         }
@@ -809,6 +865,7 @@ impl Item {
             item_config: None,
             hash: 0,
             durability_lost: None,
+            craft_quality: CraftQuality::Standard,
         };
         item.durability_lost = item.has_durability().then_some(0);
         item.update_item_state(ability_map, msm);
@@ -910,6 +967,7 @@ impl Item {
             "`new_item` has the same `item_def` and as an invariant, \
              self.set_amount(self.amount()) should always succeed.",
         );
+        new_item.set_craft_quality(self.craft_quality, ability_map, msm);
         new_item.slots_mut().iter_mut().zip(self.slots()).for_each(
             |(new_item_slot, old_item_slot)| {
                 *new_item_slot = old_item_slot
@@ -1164,6 +1222,7 @@ impl Item {
         if self.is_stackable()
             && let ItemBase::Simple(other_item_def) = &other.item_base
             && self.is_same_item_def(other_item_def)
+            && self.craft_quality == other.craft_quality
             && u32::from(self.amount)
                 .checked_add(other.amount())
                 .filter(|&amount| amount <= self.max_amount())
@@ -1286,7 +1345,43 @@ impl Item {
                 / (Self::MAX_DURABILITY - DURABILITY_THRESHOLD) as f32)
             * (1.0 - MIN_FRAC)
             + MIN_FRAC;
-        DurabilityMultiplier(mult)
+        // Every stat that is weakened by durability is also scaled by how well the
+        // item was made
+        DurabilityMultiplier(mult * self.craft_quality.stats_multiplier())
+    }
+
+    pub fn craft_quality(&self) -> CraftQuality { self.craft_quality }
+
+    /// Whether the quality of this item can vary when it is crafted. This is
+    /// the case for items with stats, and for the materials that go into them.
+    pub fn has_craft_quality(&self) -> bool {
+        matches!(
+            &*self.kind(),
+            ItemKind::Tool(_) | ItemKind::Armor(_) | ItemKind::Ingredient { .. }
+        )
+    }
+
+    pub fn set_craft_quality(
+        &mut self,
+        craft_quality: CraftQuality,
+        ability_map: &AbilityMap,
+        msm: &MaterialStatManifest,
+    ) {
+        if self.has_craft_quality() {
+            self.craft_quality = craft_quality;
+            // Stats depend on the quality of the item
+            self.update_item_state(ability_map, msm);
+        }
+    }
+
+    pub fn persistence_craft_quality(&self) -> Option<CraftQuality> {
+        (self.craft_quality != CraftQuality::Standard).then_some(self.craft_quality)
+    }
+
+    pub fn persistence_set_craft_quality(&mut self, value: Option<CraftQuality>) {
+        self.craft_quality = value
+            .filter(|_| self.has_craft_quality())
+            .unwrap_or_default();
     }
 
     pub fn has_durability(&self) -> bool {
@@ -1365,6 +1460,7 @@ pub trait ItemDesc {
     fn has_durability(&self) -> bool;
     fn durability_lost(&self) -> Option<u32>;
     fn stats_durability_multiplier(&self) -> DurabilityMultiplier;
+    fn craft_quality(&self) -> CraftQuality;
 
     fn tool_info(&self) -> Option<ToolKind> {
         if let ItemKind::Tool(tool) = &*self.kind() {
@@ -1401,6 +1497,8 @@ impl ItemDesc for Item {
     fn stats_durability_multiplier(&self) -> DurabilityMultiplier {
         self.stats_durability_multiplier()
     }
+
+    fn craft_quality(&self) -> CraftQuality { self.craft_quality() }
 }
 
 impl ItemDesc for ItemDef {
@@ -1431,6 +1529,8 @@ impl ItemDesc for ItemDef {
     fn durability_lost(&self) -> Option<u32> { None }
 
     fn stats_durability_multiplier(&self) -> DurabilityMultiplier { DurabilityMultiplier(1.0) }
+
+    fn craft_quality(&self) -> CraftQuality { CraftQuality::Standard }
 }
 
 impl Component for Item {
@@ -1473,6 +1573,8 @@ impl<'a, T: ItemDesc + ?Sized> ItemDesc for &'a T {
     fn stats_durability_multiplier(&self) -> DurabilityMultiplier {
         (*self).stats_durability_multiplier()
     }
+
+    fn craft_quality(&self) -> CraftQuality { (*self).craft_quality() }
 }
 
 /// Returns all item asset specifiers
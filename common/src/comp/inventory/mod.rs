@@ -555,6 +555,34 @@ impl Inventory {
         }
     }
 
+    /// Takes the given number of items from a slot in the inventory. Nothing is
+    /// taken if the slot holds fewer items than that.
+    pub fn take_amount(
+        &mut self,
+        inv_slot_id: InvSlotId,
+        amount: u32,
+        ability_map: &AbilityMap,
+        msm: &MaterialStatManifest,
+    ) -> Option<Item> {
+        if let Some(Some(item)) = self.slot_mut(inv_slot_id) {
+            match amount.cmp(&item.amount()) {
+                Ordering::Less if amount > 0 => {
+                    let mut return_item = item.duplicate(ability_map, msm);
+                    item.decrease_amount(amount).ok()?;
+                    return_item.set_amount(amount).expect(
+                        "amount < item.amount() ≤ item.max_amount() = return_item.max_amount(), \
+                         since return_item is a duplicate of item",
+                    );
+                    Some(return_item)
+                },
+                Ordering::Equal => self.remove(inv_slot_id),
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+
     /// Takes all items from the inventory
     pub fn drain(&mut self) -> impl Iterator<Item = Item> + '_ {
         self.slots_mut()
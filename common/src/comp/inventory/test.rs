@@ -517,6 +517,33 @@ fn backpack_crash() {
     );
 }
 
+/// Crafted items of different quality should not be stacked together.
+#[test]
+fn push_does_not_stack_different_craft_qualities() {
+    let msm = &MaterialStatManifest::load().read();
+    let ability_map = &AbilityMap::load().read();
+    let mut inv = Inventory::with_empty();
+
+    let ingot = Item::new_from_asset_expect("common.items.mineral.ingot.bronze");
+    let mut fine_ingot = ingot.duplicate(ability_map, msm);
+    fine_ingot.set_craft_quality(item::CraftQuality::Fine, ability_map, msm);
+    assert_eq!(fine_ingot.craft_quality(), item::CraftQuality::Fine);
+
+    inv.push(ingot.duplicate(ability_map, msm)).unwrap();
+    inv.push(ingot).unwrap();
+    inv.push(fine_ingot.duplicate(ability_map, msm)).unwrap();
+    inv.push(fine_ingot).unwrap();
+
+    let stacks = inv
+        .slots()
+        .flatten()
+        .map(|item| (item.craft_quality(), item.amount()))
+        .collect::<Vec<_>>();
+    assert_eq!(stacks.len(), 2);
+    assert!(stacks.contains(&(item::CraftQuality::Standard, 2)));
+    assert!(stacks.contains(&(item::CraftQuality::Fine, 2)));
+}
+
 fn fill_inv_slots(inv: &mut Inventory, items: u16) {
     let msm = &MaterialStatManifest::load().read();
     let ability_map = &AbilityMap::load().read();
@@ -0,0 +1,30 @@
+use hashbrown::HashSet;
+use serde::{Deserialize, Serialize};
+use specs::Component;
+
+/// The recipes from the [`RecipeBook`](crate::recipe::RecipeBook) that a
+/// character has learned, either at character creation or from recipe
+/// scrolls. Characters may only craft the recipes that they know.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnownRecipes {
+    recipes: HashSet<String>,
+}
+
+impl KnownRecipes {
+    pub fn new(recipes: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            recipes: recipes.into_iter().collect(),
+        }
+    }
+
+    pub fn knows(&self, recipe: &str) -> bool { self.recipes.contains(recipe) }
+
+    /// Learns the given recipe, returning whether it was previously unknown.
+    pub fn learn(&mut self, recipe: String) -> bool { self.recipes.insert(recipe) }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &String> { self.recipes.iter() }
+}
+
+impl Component for KnownRecipes {
+    type Storage = specs::DenseVecStorage<Self>;
+}
@@ -0,0 +1,44 @@
+use crate::comp::Item;
+use serde::{Deserialize, Serialize};
+use specs::Component;
+
+/// The maximum number of items that can be attached to a single mail.
+pub const MAX_MAIL_ATTACHMENTS: usize = 8;
+/// The maximum length of the body of a mail, in bytes.
+pub const MAX_MAIL_BODY_LEN: usize = 512;
+
+/// Identifies a mail stored by the server.
+pub type MailId = i64;
+
+/// A message sent from one character to another, which may carry items.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Mail {
+    pub id: MailId,
+    /// The name of the character that sent the mail
+    pub sender: String,
+    pub body: String,
+    /// Unix timestamp of when the mail was sent
+    pub sent_at: i64,
+    pub attachments: Vec<Item>,
+}
+
+/// The mail received by a character, as of when they last opened their
+/// mailbox.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Mailbox {
+    pub mail: Vec<Mail>,
+}
+
+impl Mailbox {
+    pub fn get(&self, id: MailId) -> Option<&Mail> { self.mail.iter().find(|mail| mail.id == id) }
+
+    /// Remove a mail from the mailbox.
+    pub fn take(&mut self, id: MailId) -> Option<Mail> {
+        let index = self.mail.iter().position(|mail| mail.id == id)?;
+        Some(self.mail.remove(index))
+    }
+}
+
+impl Component for Mailbox {
+    type Storage = specs::DenseVecStorage<Self>;
+}
@@ -0,0 +1,59 @@
+use crate::{comp::Item, trade::SitePrices};
+use serde::{Deserialize, Serialize};
+
+/// The item that market orders are priced and paid in.
+pub const MARKET_CURRENCY: &str = "common.items.utility.coins";
+/// The maximum number of orders that a character can have open at once,
+/// across all markets.
+pub const MAX_MARKET_ORDERS: usize = 16;
+
+/// Identifies an order stored by the server.
+pub type MarketOrderId = i64;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderKind {
+    /// The items are held by the market until they are bought.
+    Sell,
+    /// The coins for the whole order are held by the market until the items
+    /// are sold to it.
+    Buy,
+}
+
+/// An open order on the market board of a site. Orders of opposite kinds for
+/// the same item are matched as soon as their prices overlap, and the proceeds
+/// of each sale are sent to the mailboxes of both characters.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MarketOrder {
+    pub id: MarketOrderId,
+    pub kind: OrderKind,
+    /// The name of the character that placed the order
+    pub owner: String,
+    /// Whether the order was placed by the character viewing the market
+    pub is_own: bool,
+    /// For sell orders, the stack of items being sold. For buy orders, a
+    /// single one of the item being bought.
+    pub item: Item,
+    /// How many of the item are left to be bought or sold
+    pub quantity: u32,
+    /// The price of each item, in coins
+    pub unit_price: u32,
+    /// Unix timestamp of when the order was placed
+    pub created_at: i64,
+}
+
+/// The open orders on the market board of a site, as of when it was opened.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MarketBoard {
+    /// The name of the site that the market belongs to
+    pub site_name: String,
+    pub orders: Vec<MarketOrder>,
+    /// The prices of goods in the economy of the site, which are shown as a
+    /// reference for what an item is worth.
+    pub prices: Option<SitePrices>,
+}
+
+impl MarketBoard {
+    pub fn get(&self, id: MarketOrderId) -> Option<&MarketOrder> {
+        self.orders.iter().find(|order| order.id == id)
+    }
+}
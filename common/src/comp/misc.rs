@@ -22,3 +22,12 @@ pub enum Object {
 impl Component for Object {
     type Storage = DerefFlaggedStorage<Self, specs::VecStorage<Self>>;
 }
+
+/// Marks the boss of a dungeon, which is considered cleared once its boss has
+/// been killed.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Boss;
+
+impl Component for Boss {
+    type Storage = specs::NullStorage<Self>;
+}
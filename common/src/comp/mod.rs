@@ -1,10 +1,13 @@
 #[cfg(not(target_arch = "wasm32"))]
 pub mod ability;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod achievement;
 #[cfg(not(target_arch = "wasm32"))] mod admin;
 #[cfg(not(target_arch = "wasm32"))] pub mod agent;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod anchor;
 #[cfg(not(target_arch = "wasm32"))] pub mod aura;
+#[cfg(not(target_arch = "wasm32"))] pub mod bank;
 #[cfg(not(target_arch = "wasm32"))] pub mod beam;
 #[cfg(not(target_arch = "wasm32"))] pub mod body;
 pub mod buff;
@@ -16,20 +19,31 @@ pub mod compass;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod controller;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod crowd_control;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod dialogue;
+#[cfg(not(target_arch = "wasm32"))] pub mod duel;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod encounter;
 #[cfg(not(target_arch = "wasm32"))] mod energy;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod fluid_dynamics;
 #[cfg(not(target_arch = "wasm32"))] pub mod group;
+#[cfg(not(target_arch = "wasm32"))] pub mod guild;
 mod health;
 #[cfg(not(target_arch = "wasm32"))] mod inputs;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod inventory;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod invite;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod known_recipes;
 #[cfg(not(target_arch = "wasm32"))] mod last;
 #[cfg(not(target_arch = "wasm32"))] mod location;
 pub mod loot_owner;
+#[cfg(not(target_arch = "wasm32"))] pub mod mail;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod market;
 #[cfg(not(target_arch = "wasm32"))] pub mod melee;
 #[cfg(not(target_arch = "wasm32"))] mod misc;
 #[cfg(not(target_arch = "wasm32"))] pub mod ori;
@@ -42,11 +56,15 @@ pub mod presence;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod projectile;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod reputation;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod shockwave;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod skillset;
 #[cfg(not(target_arch = "wasm32"))] mod stats;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod threat;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod visual;
 
 // Reexports
@@ -56,6 +74,7 @@ pub use self::{
         Ability, AbilityInput, ActiveAbilities, CharacterAbility, CharacterAbilityType, Stance,
         MAX_ABILITIES,
     },
+    achievement::{Achievements, Statistics},
     admin::{Admin, AdminRole},
     agent::{
         Agent, Alignment, Behavior, BehaviorCapability, BehaviorState, PidController,
@@ -63,6 +82,7 @@ pub use self::{
     },
     anchor::Anchor,
     aura::{Aura, AuraChange, AuraKind, Auras},
+    bank::Bank,
     beam::{Beam, BeamSegment},
     body::{
         arthropod, biped_large, biped_small, bird_large, bird_medium, dragon, fish_medium,
@@ -70,22 +90,26 @@ pub use self::{
         quadruped_small, ship, theropod, AllBodies, Body, BodyData,
     },
     buff::{
-        Buff, BuffCategory, BuffChange, BuffData, BuffEffect, BuffId, BuffKind, BuffSource, Buffs,
-        ModifierKind,
+        Buff, BuffCategory, BuffChange, BuffData, BuffEffect, BuffId, BuffKind, BuffSource,
+        BuffStacking, Buffs, ModifierKind,
     },
     character_state::{CharacterActivity, CharacterState, StateUpdate},
     chat::{
-        ChatMode, ChatMsg, ChatType, Content, Faction, LocalizationArg, SpeechBubble,
-        SpeechBubbleType, UnresolvedChatMsg,
+        ChatMode, ChatMsg, ChatType, Content, LocalizationArg, SpeechBubble, SpeechBubbleType,
+        UnresolvedChatMsg,
     },
     combo::Combo,
     controller::{
         Climb, ControlAction, ControlEvent, Controller, ControllerInputs, GroupManip, InputAttr,
         InputKind, InventoryAction, InventoryEvent, InventoryManip, UtteranceKind,
     },
+    crowd_control::{CrowdControl, DiminishingReturns},
+    duel::Duel,
+    encounter::Encounter,
     energy::Energy,
     fluid_dynamics::Fluid,
     group::Group,
+    guild::{Guild, GuildManip, GuildRank, GuildRoster},
     inputs::CanBuild,
     inventory::{
         item::{
@@ -96,11 +120,14 @@ pub use self::{
         },
         slot, CollectFailedReason, Inventory, InventoryUpdate, InventoryUpdateEvent,
     },
+    known_recipes::KnownRecipes,
     last::Last,
     location::{MapMarker, MapMarkerChange, MapMarkerUpdate, Waypoint, WaypointArea},
     loot_owner::LootOwner,
+    mail::{Mail, Mailbox},
+    market::{MarketBoard, MarketOrder},
     melee::{Melee, MeleeConstructor, MeleeConstructorKind},
-    misc::Object,
+    misc::{Boss, Object},
     ori::Ori,
     pet::Pet,
     phys::{
@@ -112,12 +139,14 @@ pub use self::{
     poise::{Poise, PoiseChange, PoiseState},
     presence::{Presence, PresenceKind},
     projectile::{Projectile, ProjectileConstructor},
+    reputation::Reputation,
     shockwave::{Shockwave, ShockwaveHitEntities},
     skillset::{
         skills::{self, Skill},
         SkillGroup, SkillGroupKind, SkillSet,
     },
     stats::{Stats, StatsModifier},
+    threat::{ThreatList, ThreatTable},
     visual::{LightAnimation, LightEmitter},
 };
 
@@ -4,7 +4,7 @@ use crate::{
         self,
         ability::Capability,
         inventory::item::{armor::Protection, ItemKind, MaterialStatManifest},
-        CharacterState, Inventory, Stats,
+        CharacterState, CrowdControl, DiminishingReturns, Inventory, Stats,
     },
     resources::Time,
     states,
@@ -58,6 +58,9 @@ pub struct Poise {
     last_stun_time: Option<Time>,
     /// The previous poise state
     pub previous_state: PoiseState,
+    /// Diminishing returns of stuns, which make the entity briefly immune to
+    /// poise damage after being stunned repeatedly
+    stun_returns: DiminishingReturns,
 }
 
 /// States to define effects of a poise change
@@ -201,12 +204,14 @@ impl Poise {
             regen_rate: 0.0,
             last_stun_time: None,
             previous_state: PoiseState::Normal,
+            stun_returns: DiminishingReturns::default(),
         }
     }
 
     pub fn change(&mut self, change: PoiseChange) {
         match self.last_stun_time {
             Some(last_time) if last_time.0 + Poise::POISE_BUFFER_TIME > change.time.0 => {},
+            _ if change.amount < 0.0 && self.stun_returns.is_immune(change.time) => {},
             _ => {
                 // if self.previous_state != self.poise_state() {
                 self.previous_state = self.poise_state();
@@ -241,6 +246,8 @@ impl Poise {
     pub fn reset(&mut self, time: Time, poise_state_time: f64) {
         self.current = self.maximum;
         self.last_stun_time = Some(Time(time.0 + poise_state_time));
+        self.stun_returns
+            .apply(CrowdControl::Stun, time, poise_state_time);
     }
 
     /// Returns knockback as a Dir
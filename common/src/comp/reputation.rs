@@ -0,0 +1,83 @@
+use crate::trade::SiteId;
+use serde::{Deserialize, Serialize};
+use specs::Component;
+
+/// The standing of a player character with the sites and factions of the
+/// world, aggregated from the sentiments that rtsim NPCs hold toward them.
+///
+/// All values are in the range `-1.0..=1.0`, where negative values indicate
+/// that the character is disliked.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Reputation {
+    pub sites: Vec<SiteStanding>,
+    pub factions: Vec<FactionStanding>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SiteStanding {
+    pub site: SiteId,
+    pub name: String,
+    pub value: f32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FactionStanding {
+    /// The largest site owned by the faction, used to identify it to players
+    /// (factions do not yet have names of their own).
+    pub capital: Option<SiteId>,
+    pub capital_name: Option<String>,
+    pub value: f32,
+}
+
+/// A coarse description of a reputation value, used for presentation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Standing {
+    Hated,
+    Hostile,
+    Unfriendly,
+    Neutral,
+    Friendly,
+    Honored,
+    Revered,
+}
+
+impl Standing {
+    pub fn from_value(value: f32) -> Self {
+        match value {
+            v if v <= -0.6 => Self::Hated,
+            v if v <= -0.3 => Self::Hostile,
+            v if v <= -0.1 => Self::Unfriendly,
+            v if v < 0.1 => Self::Neutral,
+            v if v < 0.3 => Self::Friendly,
+            v if v < 0.6 => Self::Honored,
+            _ => Self::Revered,
+        }
+    }
+
+    pub fn i18n_key(&self) -> &'static str {
+        match self {
+            Self::Hated => "hud-diary-reputation-hated",
+            Self::Hostile => "hud-diary-reputation-hostile",
+            Self::Unfriendly => "hud-diary-reputation-unfriendly",
+            Self::Neutral => "hud-diary-reputation-neutral",
+            Self::Friendly => "hud-diary-reputation-friendly",
+            Self::Honored => "hud-diary-reputation-honored",
+            Self::Revered => "hud-diary-reputation-revered",
+        }
+    }
+}
+
+impl Reputation {
+    /// The character's standing with the given (world) site, or neutral if
+    /// they are unknown there.
+    pub fn toward_site(&self, site: SiteId) -> f32 {
+        self.sites
+            .iter()
+            .find(|standing| standing.site == site)
+            .map_or(0.0, |standing| standing.value)
+    }
+}
+
+impl Component for Reputation {
+    type Storage = specs::DenseVecStorage<Self>;
+}
@@ -0,0 +1,192 @@
+use crate::uid::Uid;
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use specs::{Component, DerefFlaggedStorage};
+
+/// Threat generated against an agent per point of damage dealt to it
+pub const DAMAGE_THREAT: f32 = 1.0;
+/// Threat generated per point of health healed, against every agent that is
+/// fighting the healed entity
+pub const HEAL_THREAT: f32 = 0.5;
+/// How much more threat than the current target another entity needs before an
+/// agent switches to it, so that agents don't flip between entities with
+/// similar threat
+pub const THREAT_SWITCH_RATIO: f32 = 1.1;
+/// The fraction of its threat that an entity loses every second
+const THREAT_DECAY_PER_SECOND: f32 = 0.05;
+/// Threat below this is forgotten
+const MIN_THREAT: f32 = 1.0;
+/// How many entities of a threat table are shown to players
+const MAX_LISTED_THREATS: usize = 5;
+
+/// The threat that entities have built up against an agent by hurting it,
+/// healing its enemies or taunting it.
+#[derive(Clone, Debug, Default)]
+pub struct ThreatTable {
+    threat: HashMap<Uid, f32>,
+    /// Who taunted the agent, and until when
+    taunt: Option<(Uid, f64)>,
+}
+
+impl ThreatTable {
+    pub fn add(&mut self, uid: Uid, amount: f32) {
+        if amount > 0.0 {
+            *self.threat.entry(uid).or_default() += amount;
+        }
+    }
+
+    pub fn get(&self, uid: Uid) -> f32 { self.threat.get(&uid).copied().unwrap_or(0.0) }
+
+    pub fn contains(&self, uid: Uid) -> bool { self.threat.contains_key(&uid) }
+
+    pub fn is_empty(&self) -> bool { self.threat.is_empty() }
+
+    /// Forgets the threat of every entity for which `f` returns `false`
+    pub fn retain(&mut self, mut f: impl FnMut(Uid) -> bool) {
+        self.threat.retain(|uid, _| f(*uid));
+        if self
+            .taunt
+            .map_or(false, |(uid, _)| !self.threat.contains_key(&uid))
+        {
+            self.taunt = None;
+        }
+    }
+
+    /// Forces the agent to fight `by` until `until`. The taunter is also
+    /// given as much threat as the agent's current top threat, so that the
+    /// agent doesn't switch straight back once the taunt ends.
+    pub fn taunt(&mut self, by: Uid, until: f64) {
+        let top = self.threat.values().copied().fold(MIN_THREAT, f32::max);
+        let threat = self.threat.entry(by).or_default();
+        *threat = threat.max(top);
+        self.taunt = Some((by, until));
+    }
+
+    /// The entity that taunted the agent, if the taunt is still in effect
+    pub fn taunted_by(&self, time: f64) -> Option<Uid> {
+        self.taunt
+            .filter(|(_, until)| time < *until)
+            .map(|(uid, _)| uid)
+    }
+
+    /// The entity the agent should be fighting: whoever taunted it, or else
+    /// whoever has the most threat against it
+    pub fn highest(&self, time: f64) -> Option<Uid> {
+        self.taunted_by(time).or_else(|| {
+            self.threat
+                .iter()
+                .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(uid, _)| *uid)
+        })
+    }
+
+    /// Lets threat fade over `dt` seconds, forgetting taunts that have ended
+    /// and entities whose threat got too low
+    pub fn decay(&mut self, dt: f32, time: f64) {
+        let taunter = self.taunted_by(time);
+        if taunter.is_none() {
+            self.taunt = None;
+        }
+        let factor = (1.0 - THREAT_DECAY_PER_SECOND).powf(dt);
+        self.threat.retain(|uid, threat| {
+            *threat *= factor;
+            *threat >= MIN_THREAT || Some(*uid) == taunter
+        });
+    }
+
+    /// The entities with the most threat, in the form shown to players
+    pub fn to_list(&self, time: f64) -> ThreatList {
+        let taunter = self.taunted_by(time);
+        let top = self.threat.values().copied().fold(0.0, f32::max);
+        let mut entries = self
+            .threat
+            .iter()
+            .map(|(uid, threat)| (*uid, *threat))
+            .collect::<Vec<_>>();
+        // The taunter comes first no matter their threat, since they are who
+        // the agent is fighting
+        entries.sort_by(|(a_uid, a), (b_uid, b)| {
+            (Some(*b_uid) == taunter)
+                .cmp(&(Some(*a_uid) == taunter))
+                .then(b.total_cmp(a))
+                .then(a_uid.0.cmp(&b_uid.0))
+        });
+        entries.truncate(MAX_LISTED_THREATS);
+
+        ThreatList {
+            entries: entries
+                .into_iter()
+                .map(|(uid, threat)| (uid, (threat / top * 100.0).round().clamp(0.0, 100.0) as u8))
+                .collect(),
+        }
+    }
+}
+
+/// The entities with the most threat against an agent. This is synced to
+/// clients so that groups can see who is holding the attention of enemies.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreatList {
+    /// Entities in the order the agent wants to fight them, along with their
+    /// threat as a percentage of the highest threat
+    pub entries: Vec<(Uid, u8)>,
+}
+
+impl ThreatList {
+    /// The entity the agent is focused on
+    pub fn top(&self) -> Option<Uid> { self.entries.first().map(|(uid, _)| *uid) }
+}
+
+impl Component for ThreatList {
+    type Storage = DerefFlaggedStorage<Self, specs::VecStorage<Self>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highest_threat_is_fought() {
+        let mut table = ThreatTable::default();
+        table.add(Uid(1), 10.0);
+        table.add(Uid(2), 30.0);
+        table.add(Uid(1), 5.0);
+        assert_eq!(table.highest(0.0), Some(Uid(2)));
+        assert_eq!(table.get(Uid(1)), 15.0);
+    }
+
+    #[test]
+    fn taunt_overrides_threat_until_it_ends() {
+        let mut table = ThreatTable::default();
+        table.add(Uid(1), 50.0);
+        table.taunt(Uid(2), 5.0);
+        assert_eq!(table.highest(1.0), Some(Uid(2)));
+        assert_eq!(table.to_list(1.0).top(), Some(Uid(2)));
+        assert!(table.get(Uid(2)) >= table.get(Uid(1)));
+
+        table.add(Uid(1), 1.0);
+        table.decay(0.0, 6.0);
+        assert_eq!(table.taunted_by(6.0), None);
+        assert_eq!(table.highest(6.0), Some(Uid(1)));
+    }
+
+    #[test]
+    fn threat_decays_and_is_forgotten() {
+        let mut table = ThreatTable::default();
+        table.add(Uid(1), 10.0);
+        table.decay(1.0, 0.0);
+        assert!(table.get(Uid(1)) < 10.0);
+        table.decay(100.0, 0.0);
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn list_is_relative_to_top_threat() {
+        let mut table = ThreatTable::default();
+        table.add(Uid(1), 20.0);
+        table.add(Uid(2), 40.0);
+        assert_eq!(table.to_list(0.0).entries, vec![
+            (Uid(2), 100),
+            (Uid(1), 50)
+        ]);
+    }
+}
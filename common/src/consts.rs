@@ -28,6 +28,8 @@ pub const MIN_RECOMMENDED_RAYON_THREADS: usize = 2;
 pub const MIN_RECOMMENDED_TOKIO_THREADS: usize = 2;
 
 pub const SOUND_TRAVEL_DIST_PER_VOLUME: f32 = 3.0;
+/// How much of its volume a sound keeps when it has to pass through walls
+pub const SOUND_OCCLUSION_DAMPENING: f32 = 0.4;
 
 // Stat increase per level (multiplied by 10 compared to what you'll see in UI)
 pub const ENERGY_PER_LEVEL: u16 = 5;
@@ -8,6 +8,8 @@ pub enum Effect {
     Poise(f32),
     Damage(combat::Damage),
     Buff(BuffEffect),
+    /// Teaches the entity the recipe with the given name from the recipe book
+    LearnRecipe(String),
 }
 
 /// A buff that may be applied to an entity
@@ -25,6 +27,7 @@ impl Effect {
             Effect::Poise(p) => format!("{:+} poise", p),
             Effect::Damage(d) => format!("{:+}", d.value),
             Effect::Buff(e) => format!("{:?} buff", e),
+            Effect::LearnRecipe(recipe) => format!("Teaches {}", recipe),
         }
     }
 
@@ -34,6 +37,7 @@ impl Effect {
             Effect::Poise(p) => *p < 0.0,
             Effect::Damage(_) => true,
             Effect::Buff(e) => !e.kind.is_buff(),
+            Effect::LearnRecipe(_) => false,
         }
     }
 
@@ -51,6 +55,7 @@ impl Effect {
             Effect::Buff(effect) => {
                 effect.data.strength *= modifier;
             },
+            Effect::LearnRecipe(_) => {},
         }
     }
 }
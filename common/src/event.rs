@@ -58,6 +58,8 @@ pub struct NpcBuilder {
     pub loot: LootSpec<String>,
     pub rtsim_entity: Option<RtSimEntity>,
     pub projectile: Option<comp::Projectile>,
+    pub boss: bool,
+    pub encounter: Option<comp::Encounter>,
 }
 
 impl NpcBuilder {
@@ -76,6 +78,8 @@ impl NpcBuilder {
             loot: LootSpec::Nothing,
             rtsim_entity: None,
             projectile: None,
+            boss: false,
+            encounter: None,
         }
     }
 
@@ -124,6 +128,16 @@ impl NpcBuilder {
         self
     }
 
+    pub fn with_boss(mut self, boss: bool) -> Self {
+        self.boss = boss;
+        self
+    }
+
+    pub fn with_encounter(mut self, encounter: impl Into<Option<comp::Encounter>>) -> Self {
+        self.encounter = encounter.into();
+        self
+    }
+
     pub fn with_loot(mut self, loot: LootSpec<String>) -> Self {
         self.loot = loot;
         self
@@ -160,6 +174,7 @@ pub enum ServerEvent {
     },
     InventoryManip(EcsEntity, comp::InventoryManip),
     GroupManip(EcsEntity, comp::GroupManip),
+    GuildManip(EcsEntity, comp::GuildManip),
     Respawn(EcsEntity),
     Shoot {
         entity: EcsEntity,
@@ -218,6 +233,11 @@ pub enum ServerEvent {
             Vec<(comp::Pet, comp::Body, comp::Stats)>,
             comp::ActiveAbilities,
             Option<comp::MapMarker>,
+            comp::Reputation,
+            comp::KnownRecipes,
+            comp::Statistics,
+            comp::Achievements,
+            comp::Bank,
         ),
         metadata: UpdateCharacterMetadata,
     },
@@ -237,6 +257,13 @@ pub enum ServerEvent {
         driver: Option<NpcBuilder>,
     },
     CreateWaypoint(Vec3<f32>),
+    /// Create a portal into or out of a private copy of the dungeon at
+    /// `dungeon`
+    CreateDungeonPortal {
+        pos: Vec3<f32>,
+        dungeon: Vec2<i32>,
+        exit: bool,
+    },
     ClientDisconnect(EcsEntity, DisconnectReason),
     ClientDisconnectWithoutPersistence(EcsEntity),
     Command(EcsEntity, String, Vec<String>),
@@ -272,6 +299,11 @@ pub enum ServerEvent {
         pos: Vec3<i32>,
         tool: Option<comp::tool::ToolKind>,
     },
+    // Attempt to catch something from the body of water at `pos`
+    Fish {
+        entity: EcsEntity,
+        pos: Vec3<i32>,
+    },
     TeleportTo {
         entity: EcsEntity,
         target: Uid,
@@ -296,6 +328,12 @@ pub enum ServerEvent {
     EntityAttackedHook {
         entity: EcsEntity,
     },
+    /// Forces an NPC to fight `by` for `duration` seconds
+    Taunt {
+        entity: EcsEntity,
+        by: Uid,
+        duration: f32,
+    },
     ChangeAbility {
         entity: EcsEntity,
         slot: usize,
@@ -0,0 +1,82 @@
+use crate::{
+    assets::{self, AssetExt, AssetHandle},
+    terrain::SpriteKind,
+};
+use serde::Deserialize;
+
+/// The number of growth stages a seedling passes through before it turns into
+/// its mature crop.
+pub const GROWTH_STAGES: u8 = 3;
+
+/// How much rain speeds up the growth of crops. At full rain, crops grow
+/// `1.0 + RAIN_GROWTH_BONUS` times as fast.
+pub const RAIN_GROWTH_BONUS: f32 = 1.0;
+
+/// A crop that can be planted on tilled soil.
+#[derive(Clone, Debug, Deserialize)]
+pub struct CropSpec {
+    /// The item that is consumed to plant the crop
+    pub seed: String,
+    /// The sprite of the mature crop
+    pub crop: SpriteKind,
+    /// The in-game time, in hours, it takes for a seedling to mature without
+    /// any rain
+    pub growth_hours: f32,
+    /// The range of seeds that are returned when harvesting the mature crop
+    pub seeds_returned: (u32, u32),
+}
+
+impl CropSpec {
+    /// The in-game time, in seconds, that it takes a seedling to advance by one
+    /// growth stage without any rain.
+    pub fn stage_duration(&self) -> f64 { self.growth_hours as f64 * 3600.0 / GROWTH_STAGES as f64 }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CropManifest(pub Vec<CropSpec>);
+
+impl CropManifest {
+    pub fn by_seed(&self, seed: &str) -> Option<&CropSpec> {
+        self.0.iter().find(|spec| spec.seed == seed)
+    }
+
+    pub fn by_crop(&self, crop: SpriteKind) -> Option<&CropSpec> {
+        self.0.iter().find(|spec| spec.crop == crop)
+    }
+}
+
+impl assets::Asset for CropManifest {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+pub fn default_crops() -> AssetHandle<CropManifest> { CropManifest::load_expect("common.crops") }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comp::Item;
+    use hashbrown::HashSet;
+
+    #[test]
+    fn crops_are_valid() {
+        let crops = default_crops().read();
+        let mut seeds = HashSet::new();
+        for spec in &crops.0 {
+            assert!(seeds.insert(&spec.seed), "Duplicate seed {}", spec.seed);
+            assert!(
+                Item::new_from_asset(&spec.seed).is_ok(),
+                "Invalid seed item {}",
+                spec.seed
+            );
+            assert!(
+                spec.crop.is_collectible(),
+                "Crop {:?} can't be harvested",
+                spec.crop
+            );
+            assert!(spec.growth_hours > 0.0);
+            assert!(spec.seeds_returned.0 <= spec.seeds_returned.1);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use crate::{
+    assets::{self, AssetExt, AssetHandle},
+    lottery::LootSpec,
+    terrain::{BiomeKind, TerrainChunkMeta},
+};
+use serde::Deserialize;
+
+/// The kind of body of water that a line was cast into.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+pub enum WaterKind {
+    Ocean,
+    Lake,
+    River,
+}
+
+impl WaterKind {
+    /// Determines the kind of water in a chunk. Small bodies of water that
+    /// aren't part of a river are treated as lakes.
+    pub fn from_chunk_meta(meta: &TerrainChunkMeta) -> Self {
+        match meta.biome() {
+            BiomeKind::Ocean => Self::Ocean,
+            BiomeKind::Lake => Self::Lake,
+            _ if meta.contains_river() => Self::River,
+            _ => Self::Lake,
+        }
+    }
+}
+
+/// The loot that can be caught in a kind of water.
+#[derive(Clone, Debug, Deserialize)]
+pub struct FishingSpot {
+    pub water: WaterKind,
+    /// The biome surrounding the water, or `None` if this spot applies to any
+    /// biome without a more specific spot
+    #[serde(default)]
+    pub biome: Option<BiomeKind>,
+    pub loot: LootSpec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct FishingManifest(pub Vec<FishingSpot>);
+
+impl FishingManifest {
+    /// The loot for fishing in the given water, preferring a spot for the
+    /// surrounding biome over the generic spot for the water.
+    pub fn loot(&self, water: WaterKind, biome: BiomeKind) -> Option<&LootSpec<String>> {
+        let mut spots = self.0.iter().filter(|spot| spot.water == water);
+        spots
+            .clone()
+            .find(|spot| spot.biome == Some(biome))
+            .or_else(|| spots.find(|spot| spot.biome.is_none()))
+            .map(|spot| &spot.loot)
+    }
+}
+
+impl assets::Asset for FishingManifest {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+pub fn default_fishing_spots() -> AssetHandle<FishingManifest> {
+    FishingManifest::load_expect("common.fishing")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lottery::tests::validate_loot_spec;
+
+    #[test]
+    fn fishing_spots_are_valid() {
+        let spots = default_fishing_spots().read();
+        for spot in &spots.0 {
+            validate_loot_spec(&spot.loot);
+        }
+        // Every kind of water needs something to catch in it
+        for water in [WaterKind::Ocean, WaterKind::Lake, WaterKind::River] {
+            assert!(
+                spots.loot(water, BiomeKind::Void).is_some(),
+                "No fishing loot for {:?}",
+                water
+            );
+        }
+    }
+}
@@ -44,6 +44,8 @@ pub struct AgentConfig {
     pub has_agency: Option<bool>,
     pub no_flee: Option<bool>,
     pub idle_wander_factor: Option<f32>,
+    /// Asset specifier of the declarative tactic used in combat
+    pub tactic: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -63,6 +65,10 @@ pub struct InventorySpec {
 #[derive(Debug, Deserialize, Clone)]
 pub enum Meta {
     SkillSetAsset(String),
+    /// The entity is the boss of a dungeon
+    Boss,
+    /// Asset specifier of the scripted encounter that the boss follows
+    Encounter(String),
 }
 
 // FIXME: currently this is used for both base definition
@@ -172,6 +178,12 @@ pub struct EntityInfo {
     pub agent_mark: Option<agent::Mark>,
     pub no_flee: bool,
     pub idle_wander_factor: f32,
+    pub tactic: Option<String>,
+    pub is_boss: bool,
+    pub encounter: Option<String>,
+    /// Where the group this entity was spawned with was generated, if it was
+    /// spawned as part of one. Entities sharing a squad origin fight together.
+    pub squad: Option<Vec2<i32>>,
     // Stats
     pub body: Body,
     pub name: Option<String>,
@@ -205,6 +217,10 @@ impl EntityInfo {
             agent_mark: None,
             no_flee: false,
             idle_wander_factor: 1.0,
+            tactic: None,
+            is_boss: false,
+            encounter: None,
+            squad: None,
 
             body: Body::Humanoid(humanoid::Body::random()),
             name: None,
@@ -292,16 +308,24 @@ impl EntityInfo {
             has_agency,
             no_flee,
             idle_wander_factor,
+            tactic,
         } = agent;
         self.has_agency = has_agency.unwrap_or(self.has_agency);
         self.no_flee = no_flee.unwrap_or(self.no_flee);
         self.idle_wander_factor = idle_wander_factor.unwrap_or(self.idle_wander_factor);
+        self.tactic = tactic.or(self.tactic);
 
         for field in meta {
             match field {
                 Meta::SkillSetAsset(asset) => {
                     self = self.with_skillset_asset(asset);
                 },
+                Meta::Boss => {
+                    self.is_boss = true;
+                },
+                Meta::Encounter(encounter) => {
+                    self.encounter = Some(encounter);
+                },
             }
         }
 
@@ -407,6 +431,12 @@ impl EntityInfo {
         self
     }
 
+    #[must_use]
+    pub fn with_squad(mut self, origin: Vec2<i32>) -> Self {
+        self.squad = Some(origin);
+        self
+    }
+
     #[must_use]
     pub fn with_loot_drop(mut self, loot_drop: LootSpec<String>) -> Self {
         self.loot = loot_drop;
@@ -507,6 +537,21 @@ impl ChunkSupplement {
     pub fn add_entity(&mut self, entity: EntityInfo) { self.entities.push(entity); }
 }
 
+/// Where a dungeon is and how much space it takes up, used to make private
+/// copies of it for groups
+#[derive(Clone, Debug)]
+pub struct DungeonLayout {
+    /// The position of the dungeon, which also identifies it
+    pub origin: Vec2<i32>,
+    pub name: String,
+    /// Where players enter the dungeon from the surface
+    pub entrance: Vec3<f32>,
+    /// Where players arrive on the top floor of the dungeon
+    pub top_floor: Vec3<f32>,
+    /// The underground part of the dungeon, excluding the entrance
+    pub bounds: Aabb<i32>,
+}
+
 pub fn get_npc_name<
     'a,
     Species,
@@ -521,18 +566,22 @@ pub fn get_npc_name<
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::SkillSetBuilder;
+    use crate::{comp::encounter::EncounterSpec, tactic::TacticSpec, SkillSetBuilder};
     use hashbrown::HashMap;
 
     #[derive(Debug, Eq, Hash, PartialEq)]
     enum MetaId {
         SkillSetAsset,
+        Boss,
+        Encounter,
     }
 
     impl Meta {
         fn id(&self) -> MetaId {
             match self {
                 Meta::SkillSetAsset(_) => MetaId::SkillSetAsset,
+                Meta::Boss => MetaId::Boss,
+                Meta::Encounter(_) => MetaId::Encounter,
             }
         }
     }
@@ -629,6 +678,15 @@ mod tests {
                 Meta::SkillSetAsset(asset) => {
                     drop(SkillSetBuilder::from_asset_expect(&asset));
                 },
+                Meta::Boss => {},
+                Meta::Encounter(encounter) => {
+                    let spec = EncounterSpec::load(&encounter).unwrap_or_else(|e| {
+                        panic!("Failed to load encounter {encounter} of {config_asset}: {e:?}")
+                    });
+                    if let Err(e) = spec.read().validate() {
+                        panic!("Invalid encounter of {config_asset}: {e}");
+                    }
+                },
             }
         }
         for (meta_id, counter) in meta_counter {
@@ -638,6 +696,18 @@ mod tests {
         }
     }
 
+    #[cfg(test)]
+    fn validate_agent(agent: AgentConfig, config_asset: &str) {
+        if let Some(tactic) = agent.tactic {
+            let tactic = TacticSpec::load(&tactic).unwrap_or_else(|e| {
+                panic!("Failed to load tactic {tactic} of {config_asset}: {e:?}")
+            });
+            if let Err(e) = tactic.read().validate() {
+                panic!("Invalid tactic of {config_asset}: {e}");
+            }
+        }
+    }
+
     #[test]
     fn test_all_entity_assets() {
         // Get list of entity configs, load everything, validate content.
@@ -646,7 +716,7 @@ mod tests {
         for config_asset in entity_configs {
             let EntityConfig {
                 body,
-                agent: _,
+                agent,
                 inventory,
                 name,
                 loot,
@@ -659,6 +729,7 @@ mod tests {
             validate_inventory(inventory, &body, &config_asset);
             validate_name(name, body, &config_asset);
             // misc
+            validate_agent(agent, &config_asset);
             validate_loot(loot, &config_asset);
             validate_meta(meta, &config_asset);
         }
@@ -46,7 +46,9 @@ cfg_if! { if #[cfg(not(target_arch = "wasm32"))] {
     pub mod effect;
     pub mod event;
     pub mod explosion;
+    pub mod farming;
     pub mod figure;
+    pub mod fishing;
     pub mod generation;
     pub mod grid;
     pub mod link;
@@ -65,6 +67,7 @@ cfg_if! { if #[cfg(not(target_arch = "wasm32"))] {
     pub mod spiral;
     pub mod states;
     pub mod store;
+    pub mod tactic;
     pub mod terrain;
     pub mod time;
     pub mod trade;
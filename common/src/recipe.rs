@@ -5,16 +5,17 @@ use crate::{
         item::{
             modular,
             tool::{AbilityMap, ToolKind},
-            ItemBase, ItemDef, ItemDefinitionId, ItemDefinitionIdOwned, ItemKind, ItemTag,
-            MaterialStatManifest,
+            CraftQuality, ItemBase, ItemDef, ItemDefinitionId, ItemDefinitionIdOwned, ItemDesc,
+            ItemKind, ItemTag, MaterialStatManifest,
         },
+        skillset::{SkillGroupKind, SkillSet},
         Inventory, Item,
     },
     terrain::SpriteKind,
 };
 use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
-use std::{borrow::Cow, sync::Arc};
+use std::{borrow::Cow, sync::Arc, time::Duration};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum RecipeInput {
@@ -90,11 +91,16 @@ pub struct Recipe {
 
 impl Recipe {
     /// Perform a recipe, returning a list of missing items on failure
+    ///
+    /// The quality of the output is the average quality of the ingredients,
+    /// plus `craft_bonus`, which accounts for the skill and luck of the
+    /// crafter.
     pub fn craft_simple(
         &self,
         inv: &mut Inventory,
         // Vec tying an input to a slot
         slots: Vec<(u32, InvSlotId)>,
+        craft_bonus: f32,
         ability_map: &AbilityMap,
         msm: &MaterialStatManifest,
     ) -> Result<Vec<Item>, Vec<(&RecipeInput, u32)>> {
@@ -154,6 +160,23 @@ impl Recipe {
         // recipe in the necessary quantity and remove the items that the recipe
         // consumes
         if unsatisfied_requirements.is_empty() {
+            // Only ingredients that can vary in quality affect the quality of the output
+            let (quality_sum, quality_count) = slot_claims
+                .iter()
+                .filter_map(|(slot, claimed)| Some((inv.get(*slot)?, *claimed)))
+                .filter(|(item, _)| item.has_craft_quality())
+                .fold((0.0, 0), |(sum, count), (item, claimed)| {
+                    (
+                        sum + item.craft_quality().score() * claimed as f32,
+                        count + claimed,
+                    )
+                });
+            let ingredient_quality = if quality_count > 0 {
+                quality_sum / quality_count as f32
+            } else {
+                0.0
+            };
+
             let mut components = Vec::new();
             for slot in component_slots.iter() {
                 let component = inv
@@ -174,12 +197,17 @@ impl Recipe {
             }
             let (item_def, quantity) = &self.output;
 
-            let crafted_item = Item::new_from_item_base(
+            let mut crafted_item = Item::new_from_item_base(
                 ItemBase::Simple(Arc::clone(item_def)),
                 components,
                 ability_map,
                 msm,
             );
+            crafted_item.set_craft_quality(
+                CraftQuality::from_score(ingredient_quality + craft_bonus),
+                ability_map,
+                msm,
+            );
             let mut crafted_items = Vec::with_capacity(*quantity as usize);
             for _ in 0..*quantity {
                 crafted_items.push(crafted_item.duplicate(ability_map, msm));
@@ -190,6 +218,27 @@ impl Recipe {
         }
     }
 
+    /// How long it takes to craft the recipe once at a crafting station
+    pub fn craft_duration(&self) -> Duration {
+        let ingredients = self
+            .inputs
+            .iter()
+            .map(|(_, amount, _)| *amount)
+            .sum::<u32>();
+        Duration::from_secs_f32((1.0 + 0.25 * ingredients as f32).min(6.0))
+    }
+
+    /// How much the skills of a character improve the quality of what they
+    /// craft with this recipe. Tools benefit from experience with that kind of
+    /// tool, everything else from general experience.
+    pub fn skill_bonus(&self, skill_set: &SkillSet) -> f32 {
+        let skill_group = match &*self.output.0.kind() {
+            ItemKind::Tool(tool) => SkillGroupKind::Weapon(tool.kind),
+            _ => SkillGroupKind::General,
+        };
+        (skill_set.earned_sp(skill_group) as f32 * 0.05).min(1.0)
+    }
+
     pub fn inputs(&self) -> impl ExactSizeIterator<Item = (&RecipeInput, u32, bool)> {
         self.inputs
             .iter()
@@ -455,6 +504,23 @@ mod tests {
             |input: &str| input.chars().any(|c| c.is_uppercase() || c.is_whitespace());
         assert!(!recipe_book.iter().any(|(k, _)| is_invalid_key(k)));
     }
+
+    #[test]
+    fn starter_recipes_exist() {
+        let recipe_book = default_recipe_book().read();
+        let starter_recipes = default_starter_recipes().read();
+        for recipe in starter_recipes
+            .common
+            .iter()
+            .chain(starter_recipes.mainhand.values().flatten())
+        {
+            assert!(
+                recipe_book.get(recipe).is_some(),
+                "Starter recipe {} is not in the recipe book",
+                recipe
+            );
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -1104,6 +1170,38 @@ pub fn default_recipe_book() -> AssetHandle<RecipeBook> {
     RecipeBook::load_expect("common.recipe_book")
 }
 
+/// The recipes that a character knows when it is created, see
+/// [`KnownRecipes`](crate::comp::KnownRecipes).
+#[derive(Clone, Debug, Deserialize)]
+pub struct StarterRecipes {
+    /// Known by every new character
+    common: Vec<String>,
+    /// Additionally known by new characters that chose the given starting
+    /// mainhand weapon
+    mainhand: HashMap<String, Vec<String>>,
+}
+
+impl StarterRecipes {
+    pub fn for_mainhand(&self, mainhand: Option<&str>) -> impl Iterator<Item = &String> {
+        self.common.iter().chain(
+            mainhand
+                .and_then(|mainhand| self.mainhand.get(mainhand))
+                .into_iter()
+                .flatten(),
+        )
+    }
+}
+
+impl assets::Asset for StarterRecipes {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+pub fn default_starter_recipes() -> AssetHandle<StarterRecipes> {
+    StarterRecipes::load_expect("common.starter_recipes")
+}
+
 pub fn default_component_recipe_book() -> AssetHandle<ComponentRecipeBook> {
     ComponentRecipeBook::load_expect("common.component_recipe_book")
 }
@@ -0,0 +1,103 @@
+use super::utils::*;
+use crate::{
+    comp::{
+        character_state::OutputEvents, controller::InputKind, CharacterState, InventoryManip,
+        StateUpdate,
+    },
+    consts::MAX_PICKUP_RANGE,
+    event::ServerEvent,
+    mounting::{Volume, VolumePos},
+    states::behavior::{CharacterBehavior, JoinData},
+    util::Dir,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use vek::Vec3;
+
+/// Separated out to condense update portions of character state
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StaticData {
+    /// The recipe being crafted
+    pub recipe: String,
+    /// The crafting station that the recipe is crafted at
+    pub craft_sprite: VolumePos,
+    /// How long each craft takes
+    pub craft_duration: Duration,
+    /// Had weapon wielded
+    pub was_wielded: bool,
+    /// Was sneaking
+    pub was_sneak: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Data {
+    /// Struct containing data that does not change over the course of the
+    /// character state
+    pub static_data: StaticData,
+    /// How many more times the recipe will be crafted, including the craft in
+    /// progress
+    pub remaining: u32,
+    /// Timer for the craft in progress
+    pub timer: Duration,
+}
+
+impl CharacterBehavior for Data {
+    fn behavior(&self, data: &JoinData, output_events: &mut OutputEvents) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+
+        let sprite_pos = self.static_data.craft_sprite.pos.map(|x| x as f32 + 0.5);
+        // Stations on ships move with the ship, so only the server can tell whether
+        // they are still in range
+        let in_range = match self.static_data.craft_sprite.kind {
+            Volume::Terrain => {
+                let range = MAX_PICKUP_RANGE + data.body.max_radius();
+                data.pos.0.distance_squared(sprite_pos) < range.powi(2)
+            },
+            Volume::Entity(_) => true,
+        };
+
+        if matches!(self.static_data.craft_sprite.kind, Volume::Terrain) {
+            let ori_dir = Dir::from_unnormalized(Vec3::from((sprite_pos - data.pos.0).xy()));
+            handle_orientation(data, &mut update, 1.0, ori_dir);
+        }
+        // The player may shuffle around the station, but crafting stops once they
+        // walk away from it
+        handle_move(data, &mut update, 0.4);
+
+        if !in_range {
+            end_ability(data, &mut update);
+        } else if self.timer < self.static_data.craft_duration {
+            if let CharacterState::Crafting(c) = &mut update.character {
+                c.timer = tick_attack_or_default(data, self.timer, None);
+            }
+        } else {
+            output_events.emit_server(ServerEvent::InventoryManip(
+                data.entity,
+                InventoryManip::FinishCraft {
+                    recipe: self.static_data.recipe.clone(),
+                    craft_sprite: self.static_data.craft_sprite,
+                },
+            ));
+            if self.remaining > 1 {
+                // Start on the next craft of the batch
+                if let CharacterState::Crafting(c) = &mut update.character {
+                    c.remaining -= 1;
+                    c.timer = Duration::default();
+                }
+            } else {
+                // Done
+                end_ability(data, &mut update);
+            }
+        }
+
+        // Allow attacks and abilities to interrupt
+        handle_wield(data, &mut update);
+
+        // At end of state logic so an interrupt isn't overwritten
+        if input_is_pressed(data, InputKind::Roll) {
+            handle_input(data, output_events, &mut update, InputKind::Roll);
+        }
+
+        update
+    }
+}
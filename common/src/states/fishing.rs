@@ -0,0 +1,162 @@
+use crate::{
+    comp::{character_state::OutputEvents, CharacterState, StateUpdate},
+    event::ServerEvent,
+    states::{
+        behavior::{CharacterBehavior, JoinData},
+        utils::*,
+    },
+    terrain::BlockKind,
+    vol::ReadVol,
+};
+use rand::{thread_rng, Rng};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use vek::*;
+
+/// How far below the end of the cast the line can sink while looking for
+/// water
+const MAX_LINE_DROP: f32 = 16.0;
+
+/// Separated out to condense update portions of character state
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StaticData {
+    /// How long it takes to cast the line
+    pub cast_duration: Duration,
+    /// How far the line can be cast
+    pub cast_distance: f32,
+    /// The shortest time that can be spent waiting for a bite
+    pub min_wait_duration: Duration,
+    /// The longest time that can be spent waiting for a bite
+    pub max_wait_duration: Duration,
+    /// How long it takes to reel in the catch
+    pub reel_duration: Duration,
+    /// What key is used to press ability
+    pub ability_info: AbilityInfo,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Data {
+    /// Struct containing data that does not change over the course of the
+    /// character state
+    pub static_data: StaticData,
+    /// Timer for each stage
+    pub timer: Duration,
+    /// What section the character stage is in
+    pub stage_section: StageSection,
+    /// The water block that the line was cast into
+    pub bobber_pos: Option<Vec3<i32>>,
+    /// How long until a fish bites, rolled once the line lands in water
+    pub wait_duration: Duration,
+}
+
+impl CharacterBehavior for Data {
+    fn behavior(&self, data: &JoinData, output_events: &mut OutputEvents) -> StateUpdate {
+        let mut update = StateUpdate::from(data);
+
+        match self.stage_section {
+            StageSection::Buildup => {
+                handle_orientation(data, &mut update, 1.0, None);
+                if self.timer < self.static_data.cast_duration {
+                    // Cast
+                    update.character = CharacterState::Fishing(Data {
+                        timer: tick_attack_or_default(data, self.timer, None),
+                        ..*self
+                    });
+                } else if let Some(bobber_pos) = find_water(data, self.static_data.cast_distance) {
+                    // The line landed in water, so wait for a bite
+                    let wait_duration = thread_rng().gen_range(
+                        self.static_data.min_wait_duration.as_secs_f32()
+                            ..=self
+                                .static_data
+                                .max_wait_duration
+                                .as_secs_f32()
+                                .max(self.static_data.min_wait_duration.as_secs_f32()),
+                    );
+                    update.character = CharacterState::Fishing(Data {
+                        timer: Duration::default(),
+                        stage_section: StageSection::Action,
+                        bobber_pos: Some(bobber_pos),
+                        wait_duration: Duration::from_secs_f32(wait_duration),
+                        ..*self
+                    });
+                } else {
+                    // Nothing to fish in
+                    end_ability(data, &mut update);
+                }
+            },
+            StageSection::Action => {
+                let still_in_water = self.bobber_pos.map_or(false, |pos| {
+                    data.terrain
+                        .get(pos)
+                        .map_or(false, |block| block.kind() == BlockKind::Water)
+                });
+                // Moving around scares the fish away
+                if !still_in_water || data.inputs.move_dir.magnitude_squared() > 0.0 {
+                    end_ability(data, &mut update);
+                } else if self.timer < self.wait_duration {
+                    // Wait for a bite
+                    update.character = CharacterState::Fishing(Data {
+                        timer: tick_attack_or_default(data, self.timer, None),
+                        ..*self
+                    });
+                } else {
+                    // A fish bit, start reeling it in
+                    update.character = CharacterState::Fishing(Data {
+                        timer: Duration::default(),
+                        stage_section: StageSection::Recover,
+                        ..*self
+                    });
+                }
+            },
+            StageSection::Recover => {
+                if self.timer < self.static_data.reel_duration {
+                    // Reel in
+                    update.character = CharacterState::Fishing(Data {
+                        timer: tick_attack_or_default(data, self.timer, None),
+                        ..*self
+                    });
+                } else {
+                    if let Some(pos) = self.bobber_pos {
+                        output_events.emit_server(ServerEvent::Fish {
+                            entity: data.entity,
+                            pos,
+                        });
+                    }
+                    end_ability(data, &mut update);
+                }
+            },
+            _ => {
+                // If it somehow ends up in an incorrect stage section
+                end_ability(data, &mut update);
+            },
+        }
+
+        update
+    }
+}
+
+/// Finds the water block that a line cast in the direction the character is
+/// looking would land in, if any
+fn find_water(data: &JoinData, cast_distance: f32) -> Option<Vec3<i32>> {
+    let start = data.pos.0 + Vec3::unit_z() * data.body.eye_height(data.scale.map_or(1.0, |s| s.0));
+    let end = start + *data.inputs.look_dir * cast_distance;
+    let (dist, _) = data
+        .terrain
+        .ray(start, end)
+        .until(|block| block.is_filled() || block.is_liquid())
+        .cast();
+    // Step back slightly so the line doesn't start inside of the block it hit
+    let landing = start + *data.inputs.look_dir * (dist - 0.1).max(0.0);
+    let (drop, _) = data
+        .terrain
+        .ray(landing, landing - Vec3::unit_z() * MAX_LINE_DROP)
+        .until(|block| block.is_filled() || block.is_liquid())
+        .cast();
+
+    let pos = (landing - Vec3::unit_z() * (drop + 0.01)).map(|e| e.floor() as i32);
+    data.terrain
+        .get(pos)
+        .ok()
+        .filter(|block| block.kind() == BlockKind::Water)
+        .map(|_| pos)
+}
@@ -12,11 +12,13 @@ pub mod charged_ranged;
 pub mod climb;
 pub mod combo_melee;
 pub mod combo_melee2;
+pub mod crafting;
 pub mod dance;
 pub mod dash_melee;
 pub mod dive_melee;
 pub mod equipping;
 pub mod finisher_melee;
+pub mod fishing;
 pub mod glide;
 pub mod glide_wield;
 pub mod idle;
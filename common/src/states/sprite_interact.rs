@@ -113,12 +113,31 @@ impl CharacterBehavior for Data {
                             (has_item, has_item.then_some((slot, consume)))
                         });
                     if has_required_item {
-                        let inv_manip = InventoryManip::Collect {
-                            sprite_pos: self.static_data.sprite_pos,
-                            required_item: inv_slot,
+                        let inv_manip = match self.static_data.sprite_kind {
+                            SpriteInteractKind::Bank => {
+                                Some(InventoryManip::OpenBank(self.static_data.sprite_pos))
+                            },
+                            SpriteInteractKind::Mailbox => {
+                                Some(InventoryManip::OpenMailbox(self.static_data.sprite_pos))
+                            },
+                            SpriteInteractKind::MarketBoard => {
+                                Some(InventoryManip::OpenMarket(self.static_data.sprite_pos))
+                            },
+                            SpriteInteractKind::Plant => {
+                                inv_slot.map(|(seed, _)| InventoryManip::Plant {
+                                    sprite_pos: self.static_data.sprite_pos,
+                                    seed,
+                                })
+                            },
+                            _ => Some(InventoryManip::Collect {
+                                sprite_pos: self.static_data.sprite_pos,
+                                required_item: inv_slot,
+                            }),
                         };
-                        output_events
-                            .emit_server(ServerEvent::InventoryManip(data.entity, inv_manip));
+                        if let Some(inv_manip) = inv_manip {
+                            output_events
+                                .emit_server(ServerEvent::InventoryManip(data.entity, inv_manip));
+                        }
                         if matches!(self.static_data.sprite_kind, SpriteInteractKind::Unlock) {
                             output_events.emit_local(LocalEvent::CreateOutcome(
                                 Outcome::SpriteUnlocked {
@@ -156,6 +175,10 @@ pub enum SpriteInteractKind {
     Harvestable,
     Collectible,
     Unlock,
+    Bank,
+    Mailbox,
+    MarketBoard,
+    Plant,
     Fallback,
 }
 
@@ -180,7 +203,11 @@ impl From<SpriteKind> for Option<SpriteInteractKind> {
             | SpriteKind::Hardwood
             | SpriteKind::Ironwood
             | SpriteKind::Frostwood
-            | SpriteKind::Eldwood => Some(SpriteInteractKind::Harvestable),
+            | SpriteKind::Eldwood
+            | SpriteKind::Carrot
+            | SpriteKind::Tomato
+            | SpriteKind::Cabbage
+            | SpriteKind::Flax => Some(SpriteInteractKind::Harvestable),
             SpriteKind::Stones
             | SpriteKind::Twigs
             | SpriteKind::VialEmpty
@@ -191,6 +218,10 @@ impl From<SpriteKind> for Option<SpriteInteractKind> {
             SpriteKind::Keyhole | SpriteKind::BoneKeyhole | SpriteKind::GlassKeyhole => {
                 Some(SpriteInteractKind::Unlock)
             },
+            SpriteKind::Bank => Some(SpriteInteractKind::Bank),
+            SpriteKind::Mailbox => Some(SpriteInteractKind::Mailbox),
+            SpriteKind::MarketBoard => Some(SpriteInteractKind::MarketBoard),
+            SpriteKind::TilledSoil => Some(SpriteInteractKind::Plant),
             // Collectible checked in addition to container for case that sprite requires a tool to
             // collect and cannot be collected by hand, yet still meets the container check
             _ if sprite_kind.is_container() && sprite_kind.is_collectible() => {
@@ -231,6 +262,16 @@ impl SpriteInteractKind {
                 Duration::from_secs_f32(0.5),
                 Duration::from_secs_f32(0.3),
             ),
+            Self::Bank | Self::Mailbox | Self::MarketBoard => (
+                Duration::from_secs_f32(0.3),
+                Duration::from_secs_f32(0.3),
+                Duration::from_secs_f32(0.2),
+            ),
+            Self::Plant => (
+                Duration::from_secs_f32(0.5),
+                Duration::from_secs_f32(0.5),
+                Duration::from_secs_f32(0.3),
+            ),
         }
     }
 }
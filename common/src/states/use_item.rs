@@ -61,13 +61,19 @@ impl CharacterBehavior for Data {
                 handle_orientation(data, &mut update, 0.0, None);
                 handle_move(data, &mut update, 0.0);
             },
+            ItemUseKind::Consumable(ConsumableKind::Scroll) => {
+                handle_orientation(data, &mut update, 0.5, None);
+                handle_move(data, &mut update, 0.2);
+            },
         }
 
         let use_point = match self.static_data.item_kind {
             ItemUseKind::Consumable(ConsumableKind::Drink | ConsumableKind::Food) => {
                 UsePoint::BuildupUse
             },
-            ItemUseKind::Consumable(ConsumableKind::ComplexFood) => UsePoint::UseRecover,
+            ItemUseKind::Consumable(ConsumableKind::ComplexFood | ConsumableKind::Scroll) => {
+                UsePoint::UseRecover
+            },
         };
 
         match self.stage_section {
@@ -187,6 +193,11 @@ impl ItemUseKind {
                 Duration::from_secs_f32(5.0),
                 Duration::from_secs_f32(0.5),
             ),
+            Self::Consumable(ConsumableKind::Scroll) => (
+                Duration::from_secs_f32(0.5),
+                Duration::from_secs_f32(3.0),
+                Duration::from_secs_f32(0.5),
+            ),
         }
     }
 }
@@ -19,6 +19,7 @@ use crate::{
     },
     consts::{FRIC_GROUND, GRAVITY, MAX_PICKUP_RANGE},
     event::{LocalEvent, ServerEvent},
+    farming,
     outcome::Outcome,
     states::{behavior::JoinData, utils::CharacterState::Idle, *},
     terrain::{Block, TerrainGrid, UnlockKind},
@@ -1060,13 +1061,24 @@ pub fn handle_manipulate_loadout(
                     // None: An required items exist but no available
                     // Some(None): No required items
                     // Some(Some(_)): Required items satisfied, contains info about them
-                    let has_required_items = match required_item {
+                    let has_required_items = match (sprite_interact, required_item) {
+                        // Planting consumes the first seed that can be found in the inventory
+                        (sprite_interact::SpriteInteractKind::Plant, _) => {
+                            let crops = farming::default_crops().read();
+                            data.inventory.and_then(|inv| {
+                                inv.slots_with_id().find_map(|(slot, item)| {
+                                    let item_id = item.as_ref()?.item_definition_id();
+                                    crops.by_seed(item_id.itemdef_id()?)?;
+                                    Some(Some((item_id.to_owned(), slot, true)))
+                                })
+                            })
+                        },
                         // Produces `None` if we can't find the item or `Some(Some(_))` if we can
-                        Some((item_id, consume)) => data
+                        (_, Some((item_id, consume))) => data
                             .inventory
                             .and_then(|inv| inv.get_slot_of_item_by_def_id(&item_id))
                             .map(|slot| Some((item_id, slot, consume))),
-                        None => Some(None),
+                        (_, None) => Some(None),
                     };
                     if let Some(required_item) = has_required_items {
                         // If the sprite is collectible, enter the sprite interaction character
@@ -0,0 +1,207 @@
+//! Declarative combat tactics for NPCs.
+//!
+//! A tactic is a list of rules that are checked in order every tick while an
+//! agent is fighting. The first rule whose conditions all hold decides which
+//! ability the agent uses and how it moves. Rules can keep state between ticks
+//! using the timers, counters and conditions of the agent's `ActionState`.
+
+use crate::{
+    assets,
+    comp::{
+        agent::{
+            ACTIONSTATE_NUMBER_OF_CONCURRENT_CONDITIONS, ACTIONSTATE_NUMBER_OF_CONCURRENT_COUNTERS,
+            ACTIONSTATE_NUMBER_OF_CONCURRENT_TIMERS,
+        },
+        InputKind,
+    },
+};
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct TacticSpec {
+    /// Timers that tick up every tick while the tactic is in use, e.g. to be
+    /// used as cooldowns
+    #[serde(default)]
+    pub ticking_timers: Vec<usize>,
+    /// The rules of the tactic, in order of priority
+    pub rules: Vec<TacticRule>,
+    /// How the agent moves when none of the rules apply
+    #[serde(default = "TacticMovement::approach")]
+    pub fallback: TacticMovement,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct TacticRule {
+    /// Conditions that must all hold for the rule to apply
+    #[serde(default)]
+    pub conditions: Vec<TacticCondition>,
+    /// The input that is pressed when the rule applies, if any
+    #[serde(default)]
+    pub input: Option<InputKind>,
+    #[serde(default)]
+    pub movement: TacticMovement,
+    /// Changes made to the agent's `ActionState` when the rule applies
+    #[serde(default)]
+    pub effects: Vec<TacticEffect>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub enum TacticCondition {
+    /// The target is closer than this multiple of the agent's minimum attack
+    /// distance
+    InRange(f32),
+    /// The target is further than this multiple of the agent's minimum attack
+    /// distance
+    OutOfRange(f32),
+    /// The angle between where the agent is looking and the target is less
+    /// than this many degrees
+    Facing(f32),
+    /// The agent's health fraction is below this value
+    HealthBelow(f32),
+    /// The agent's health fraction is above this value
+    HealthAbove(f32),
+    /// The target's health fraction is below this value
+    TargetHealthBelow(f32),
+    /// The agent can see the target
+    LineOfSight,
+    /// The ability bound to the rule's input could currently be used on the
+    /// target
+    CanUse,
+    /// The timer with this index is below the given value, in seconds
+    TimerBelow(usize, f32),
+    /// The timer with this index is above the given value, in seconds
+    TimerAbove(usize, f32),
+    /// The counter with this index is below the given value
+    CounterBelow(usize, f32),
+    /// The counter with this index is above the given value
+    CounterAbove(usize, f32),
+    /// The condition with this index is set
+    Condition(usize),
+    /// Holds with the given probability, rolled every tick
+    Chance(f32),
+    Not(Box<TacticCondition>),
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub enum TacticMovement {
+    /// Stand still
+    #[default]
+    Stop,
+    /// Path towards the target
+    Approach,
+    /// Move around the target, at this many degrees away from facing it
+    Circle(f32),
+    /// Move directly away from the target
+    Retreat,
+}
+
+impl TacticMovement {
+    fn approach() -> Self { Self::Approach }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub enum TacticEffect {
+    /// Advances the timer with this index by the length of the tick
+    TickTimer(usize),
+    ResetTimer(usize),
+    /// Adds the given amount to the counter with this index
+    AddToCounter(usize, f32),
+    ResetCounter(usize),
+    SetCondition(usize, bool),
+}
+
+fn check_index(kind: &str, index: usize, max: usize) -> Result<(), String> {
+    if index < max {
+        Ok(())
+    } else {
+        Err(format!(
+            "{kind} {index} is out of range, there are only {max}"
+        ))
+    }
+}
+
+impl TacticCondition {
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::TimerBelow(i, _) | Self::TimerAbove(i, _) => {
+                check_index("Timer", *i, ACTIONSTATE_NUMBER_OF_CONCURRENT_TIMERS)
+            },
+            Self::CounterBelow(i, _) | Self::CounterAbove(i, _) => {
+                check_index("Counter", *i, ACTIONSTATE_NUMBER_OF_CONCURRENT_COUNTERS)
+            },
+            Self::Condition(i) => {
+                check_index("Condition", *i, ACTIONSTATE_NUMBER_OF_CONCURRENT_CONDITIONS)
+            },
+            Self::Not(condition) => condition.validate(),
+            Self::InRange(_)
+            | Self::OutOfRange(_)
+            | Self::Facing(_)
+            | Self::HealthBelow(_)
+            | Self::HealthAbove(_)
+            | Self::TargetHealthBelow(_)
+            | Self::LineOfSight
+            | Self::CanUse
+            | Self::Chance(_) => Ok(()),
+        }
+    }
+}
+
+impl TacticEffect {
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::TickTimer(i) | Self::ResetTimer(i) => {
+                check_index("Timer", *i, ACTIONSTATE_NUMBER_OF_CONCURRENT_TIMERS)
+            },
+            Self::AddToCounter(i, _) | Self::ResetCounter(i) => {
+                check_index("Counter", *i, ACTIONSTATE_NUMBER_OF_CONCURRENT_COUNTERS)
+            },
+            Self::SetCondition(i, _) => {
+                check_index("Condition", *i, ACTIONSTATE_NUMBER_OF_CONCURRENT_CONDITIONS)
+            },
+        }
+    }
+}
+
+impl TacticSpec {
+    /// Checks that every timer, counter and condition that the tactic uses
+    /// exists in an `ActionState`.
+    pub fn validate(&self) -> Result<(), String> {
+        for timer in &self.ticking_timers {
+            check_index("Timer", *timer, ACTIONSTATE_NUMBER_OF_CONCURRENT_TIMERS)?;
+        }
+        for rule in &self.rules {
+            for condition in &rule.conditions {
+                condition.validate()?;
+            }
+            for effect in &rule.effects {
+                effect.validate()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl assets::Asset for TacticSpec {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::AssetExt;
+
+    #[test]
+    fn all_tactics_are_valid() {
+        let tactics =
+            assets::load_dir::<TacticSpec>("common.tactics", true).expect("Failed to load tactics");
+        for id in tactics.ids() {
+            let tactic = TacticSpec::load_expect(id).read();
+            if let Err(e) = tactic.validate() {
+                panic!("Invalid tactic {id}: {e}");
+            }
+        }
+    }
+}
@@ -410,6 +410,9 @@ impl Block {
                 | SpriteKind::DismantlingBench
                 | SpriteKind::RepairBench
                 | SpriteKind::TanningRack
+                | SpriteKind::Bank
+                | SpriteKind::Mailbox
+                | SpriteKind::MarketBoard
                 | SpriteKind::Chest
                 | SpriteKind::DungeonChest0
                 | SpriteKind::DungeonChest1
@@ -539,6 +542,37 @@ impl Block {
         }
     }
 
+    /// Create a seedling that will grow into the given crop sprite.
+    #[inline]
+    pub const fn seedling(crop: SpriteKind) -> Self {
+        Self {
+            kind: BlockKind::Air,
+            attr: [SpriteKind::Seedling as u8, 0, crop as u8],
+        }
+    }
+
+    /// If this block is a seedling, get its growth stage and the crop that it
+    /// will grow into.
+    #[inline]
+    pub fn get_growth(&self) -> Option<(u8, SpriteKind)> {
+        match self.get_sprite()? {
+            SpriteKind::Seedling => Some((self.attr[1], SpriteKind::from_u8(self.attr[2])?)),
+            _ => None,
+        }
+    }
+
+    /// If this block is a seedling, give it a new growth stage.
+    #[inline]
+    #[must_use]
+    pub fn with_growth_stage(mut self, stage: u8) -> Option<Self> {
+        if self.get_sprite() == Some(SpriteKind::Seedling) {
+            self.attr[1] = stage;
+            Some(self)
+        } else {
+            None
+        }
+    }
+
     /// Remove the terrain sprite or solid aspects of a block
     #[inline]
     #[must_use]
@@ -594,4 +628,18 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn seedling_growth() {
+        let seedling = Block::seedling(SpriteKind::Carrot);
+        assert_eq!(seedling.get_growth(), Some((0, SpriteKind::Carrot)));
+        let grown = seedling.with_growth_stage(2).unwrap();
+        assert_eq!(grown.get_growth(), Some((2, SpriteKind::Carrot)));
+        assert_eq!(Block::from_u32(grown.to_u32()), Some(grown));
+        assert!(
+            Block::air(SpriteKind::Carrot)
+                .with_growth_stage(1)
+                .is_none()
+        );
+    }
 }
@@ -253,6 +253,12 @@ make_case_elim!(
         GlowIceCrystal = 0xE1,
         OneWayWall = 0xE2,
         GlassKeyhole = 0xE3,
+        Bank = 0xE4,
+        Mailbox = 0xE5,
+        MarketBoard = 0xE6,
+        TilledSoil = 0xE7,
+        // Growth stage and crop stored in the block attributes, see `Block::get_growth`
+        Seedling = 0xE8,
     }
 );
 
@@ -272,6 +278,9 @@ impl SpriteKind {
             SpriteKind::Pumpkin => 0.81,
             SpriteKind::Cabbage => 0.45,
             SpriteKind::Chest => 1.09,
+            SpriteKind::Bank => 1.09,
+            SpriteKind::Mailbox => 1.0,
+            SpriteKind::MarketBoard => 1.0,
             SpriteKind::CommonLockedChest => 1.09,
             SpriteKind::DungeonChest0 => 1.09,
             SpriteKind::DungeonChest1 => 1.09,
@@ -484,6 +493,10 @@ impl SpriteKind {
             SpriteKind::Silver => item("common.items.mineral.ore.silver"),
             SpriteKind::Gold => item("common.items.mineral.ore.gold"),
             SpriteKind::Cotton => item("common.items.crafting_ing.cotton_boll"),
+            SpriteKind::Carrot => item("common.items.food.carrot"),
+            SpriteKind::Tomato => item("common.items.food.tomato"),
+            SpriteKind::Cabbage => item("common.items.food.lettuce"),
+            SpriteKind::Flax => item("common.items.flowers.wild_flax"),
             SpriteKind::Moonbell => item("common.items.flowers.moonbell"),
             SpriteKind::Pyrebloom => item("common.items.flowers.pyrebloom"),
             SpriteKind::WildFlax => item("common.items.flowers.wild_flax"),
@@ -512,7 +525,13 @@ impl SpriteKind {
             SpriteKind::Frostwood => item("common.items.log.frostwood"),
             SpriteKind::Eldwood => item("common.items.log.eldwood"),
             SpriteKind::MagicalBarrier => table("common.loot_tables.sprite.chest"),
-            SpriteKind::Keyhole | SpriteKind::BoneKeyhole | SpriteKind::GlassKeyhole => {
+            SpriteKind::Keyhole
+            | SpriteKind::BoneKeyhole
+            | SpriteKind::GlassKeyhole
+            | SpriteKind::Bank
+            | SpriteKind::Mailbox
+            | SpriteKind::MarketBoard
+            | SpriteKind::TilledSoil => {
                 return Some(None);
             },
             _ => return None,
@@ -654,6 +673,9 @@ impl SpriteKind {
                 | SpriteKind::WardrobeDouble
                 | SpriteKind::Pot
                 | SpriteKind::Chest
+                | SpriteKind::Bank
+                | SpriteKind::Mailbox
+                | SpriteKind::MarketBoard
                 | SpriteKind::DungeonChest0
                 | SpriteKind::DungeonChest1
                 | SpriteKind::DungeonChest2
@@ -30,3 +30,13 @@ impl DayPeriod {
 
     pub fn is_light(&self) -> bool { !self.is_dark() }
 }
+
+/// How brightly the sun lights the world at the given time of day, from 0.0
+/// while it is below the horizon to 1.0 once it is well above it
+pub fn daylight(time_of_day: f64) -> f32 {
+    let day_fraction = time_of_day.rem_euclid(60.0 * 60.0 * 24.0) / (60.0 * 60.0 * 24.0);
+    // The sun is lowest at midnight and highest at noon
+    let sun_height = -(day_fraction * std::f64::consts::TAU).cos() as f32;
+
+    (sun_height * 2.0).clamp(0.0, 1.0)
+}
@@ -372,9 +372,35 @@ pub struct SiteInformation {
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct SitePrices {
     pub values: HashMap<Good, f32>,
+    /// How much more (or less) the goods offered to the site's merchants are
+    /// worth, depending on the reputation of the customer with the site. The
+    /// goods offered by the merchants are made cheaper (or dearer) to match.
+    #[serde(default)]
+    pub reputation_bonus: f32,
 }
 
 impl SitePrices {
+    /// The largest bonus (or penalty) that reputation can apply to prices.
+    pub const MAX_REPUTATION_BONUS: f32 = 0.2;
+
+    /// Adjust the prices for a customer with the given reputation (in the range
+    /// `-1.0..=1.0`) with the site.
+    #[must_use]
+    pub fn with_reputation(mut self, reputation: f32) -> Self {
+        self.reputation_bonus = reputation.clamp(-1.0, 1.0) * Self::MAX_REPUTATION_BONUS;
+        self
+    }
+
+    /// The factor by which reputation scales the value of goods offered by the
+    /// customer (`reduce`) or by the merchant.
+    fn reputation_factor(&self, reduce: bool) -> f32 {
+        if reduce {
+            1.0 + self.reputation_bonus
+        } else {
+            1.0 / (1.0 + self.reputation_bonus)
+        }
+    }
+
     pub fn balance(
         &self,
         offers: &[HashMap<InvSlotId, u32>; 2],
@@ -398,7 +424,8 @@ impl SitePrices {
                                         * (if reduce { material.trade_margin() } else { 1.0 })
                                 })
                                 .sum::<f32>()
-                                * (*amount as f32),
+                                * (*amount as f32)
+                                * self.reputation_factor(reduce),
                         )
                     })
                     .unwrap_or(Some(0.0))
@@ -211,6 +211,8 @@ impl State {
         ecs.register::<comp::CharacterState>();
         ecs.register::<comp::CharacterActivity>();
         ecs.register::<comp::Object>();
+        ecs.register::<comp::Boss>();
+        ecs.register::<comp::Encounter>();
         ecs.register::<comp::Group>();
         ecs.register::<comp::Shockwave>();
         ecs.register::<comp::ShockwaveHitEntities>();
@@ -219,6 +221,7 @@ impl State {
         ecs.register::<comp::LootOwner>();
         ecs.register::<comp::Admin>();
         ecs.register::<comp::Stance>();
+        ecs.register::<comp::ThreatList>();
 
         // Register components send from clients -> server
         ecs.register::<comp::Controller>();
@@ -248,17 +251,25 @@ impl State {
         ecs.register::<comp::Last<comp::Pos>>();
         ecs.register::<comp::Last<comp::Vel>>();
         ecs.register::<comp::Last<comp::Ori>>();
+        ecs.register::<comp::Last<comp::Statistics>>();
         ecs.register::<comp::Agent>();
         ecs.register::<comp::WaypointArea>();
         ecs.register::<comp::ForceUpdate>();
         ecs.register::<comp::InventoryUpdate>();
         ecs.register::<comp::Waypoint>();
         ecs.register::<comp::MapMarker>();
+        ecs.register::<comp::Reputation>();
+        ecs.register::<comp::KnownRecipes>();
+        ecs.register::<comp::Statistics>();
+        ecs.register::<comp::Achievements>();
+        ecs.register::<comp::Bank>();
+        ecs.register::<comp::Mailbox>();
         ecs.register::<comp::Projectile>();
         ecs.register::<comp::Melee>();
         ecs.register::<comp::ItemDrops>();
         ecs.register::<comp::ChatMode>();
-        ecs.register::<comp::Faction>();
+        ecs.register::<comp::Guild>();
+        ecs.register::<comp::Duel>();
         ecs.register::<comp::invite::Invite>();
         ecs.register::<comp::invite::PendingInvites>();
         ecs.register::<comp::Beam>();
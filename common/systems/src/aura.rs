@@ -4,7 +4,8 @@ use common::{
         aura::{AuraChange, AuraKey, AuraKind, AuraTarget},
         buff::{Buff, BuffCategory, BuffChange, BuffSource},
         group::Group,
-        Alignment, Aura, Auras, BuffKind, Buffs, CharacterState, Health, Player, Pos, Stats,
+        Alignment, Aura, Auras, BuffKind, Buffs, CharacterState, Duel, Guild, Health, Player, Pos,
+        Stats,
     },
     event::{Emitter, EventBus, ServerEvent},
     resources::Time,
@@ -20,6 +21,8 @@ use specs::{
 pub struct ReadData<'a> {
     entities: Entities<'a>,
     players: ReadStorage<'a, Player>,
+    guilds: ReadStorage<'a, Guild>,
+    duels: ReadStorage<'a, Duel>,
     time: Read<'a, Time>,
     server_bus: Read<'a, EventBus<ServerEvent>>,
     uid_allocator: Read<'a, UidAllocator>,
@@ -196,6 +199,8 @@ fn activate_aura(
                 combat::may_harm(
                     &read_data.alignments,
                     &read_data.players,
+                    &read_data.guilds,
+                    &read_data.duels,
                     &read_data.uid_allocator,
                     owner,
                     target,
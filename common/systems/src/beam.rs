@@ -2,8 +2,8 @@ use common::{
     combat::{self, AttackOptions, AttackSource, AttackerInfo, TargetInfo},
     comp::{
         agent::{Sound, SoundKind},
-        Alignment, Beam, BeamSegment, Body, Buffs, CharacterState, Combo, Energy, Group, Health,
-        Inventory, Ori, Player, Pos, Scale, Stats,
+        Alignment, Beam, BeamSegment, Body, Buffs, CharacterState, Combo, Duel, Energy, Group,
+        Guild, Health, Inventory, Ori, Player, Pos, Scale, Stats,
     },
     event::{EventBus, ServerEvent},
     outcome::Outcome,
@@ -27,6 +27,8 @@ use vek::*;
 pub struct ReadData<'a> {
     entities: Entities<'a>,
     players: ReadStorage<'a, Player>,
+    guilds: ReadStorage<'a, Guild>,
+    duels: ReadStorage<'a, Duel>,
     server_bus: Read<'a, EventBus<ServerEvent>>,
     time: Read<'a, Time>,
     dt: Read<'a, DeltaTime>,
@@ -243,6 +245,8 @@ impl<'a> System<'a> for Sys {
                             let may_harm = combat::may_harm(
                                 &read_data.alignments,
                                 &read_data.players,
+                                &read_data.guilds,
+                                &read_data.duels,
                                 &read_data.uid_allocator,
                                 beam_owner,
                                 target,
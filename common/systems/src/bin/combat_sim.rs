@@ -0,0 +1,472 @@
+#![feature(let_chains)]
+//! Simulates an attacker using an ability rotation against a target, to help
+//! with balancing abilities without having to log in and hit training dummies.
+//!
+//! The simulation runs the real character state, melee and buff systems in a
+//! headless `State`. The server events that those systems emit are applied
+//! here in the same way as the server would, except for the ones that create
+//! new entities (projectiles, beams, shockwaves and summons), so ranged
+//! abilities aren't simulated. The target is held in place and is given full
+//! health again whenever it dies.
+
+use clap::Parser;
+use common::{
+    comp::{
+        self,
+        buff::BuffChange,
+        inventory::{item::MaterialStatManifest, loadout_builder::LoadoutBuilder},
+        skills::{GeneralSkill, Skill},
+        tool::AbilityMap,
+        ActiveAbilities, Auras, Body, Buffs, CharacterActivity, CharacterState, Collider, Combo,
+        ControlAction, Controller, Energy, Health, InputKind, Inventory, Item, Ori, Poise, Pos,
+        SkillSet, Stats, Vel,
+    },
+    event::{EventBus, ServerEvent},
+    generation::EntityInfo,
+    resources::{GameMode, Time},
+    shared_server_config::ServerConstants,
+    terrain::{
+        Block, BlockKind, MapSizeLg, SpriteKind, TerrainChunk, TerrainChunkMeta, TerrainGrid,
+    },
+    util::Dir,
+    SkillSetBuilder,
+};
+use common_ecs::{dispatch, System};
+use common_net::sync::WorldSyncExt;
+use common_state::State;
+use specs::{Builder, Entity, WorldExt};
+use std::{error::Error, sync::Arc, time::Duration};
+use vek::{Rgb, Vec2, Vec3};
+use veloren_common_systems::{add_local_systems, melee, projectile};
+
+const WORLD_CHUNKS_LG: MapSizeLg = if let Ok(map_size_lg) = MapSizeLg::new(Vec2 { x: 1, y: 1 }) {
+    map_size_lg
+} else {
+    panic!("World chunk size does not satisfy required invariants.");
+};
+const GROUND_HEIGHT: i32 = 256;
+/// How long the attacker waits for an ability to start before moving on to the
+/// next one in the rotation, e.g. because it lacks the energy to use it
+const INPUT_TIMEOUT: f64 = 1.0;
+
+#[derive(Parser)]
+struct Cli {
+    /// Item asset of the weapon held in the main hand
+    #[arg(long)]
+    mainhand: String,
+    /// Item asset of the weapon held in the off hand
+    #[arg(long)]
+    offhand: Option<String>,
+    /// Loadout asset with the armor worn by the attacker
+    #[arg(long)]
+    loadout: Option<String>,
+    /// Skill set asset of the attacker
+    #[arg(long)]
+    skill_set: Option<String>,
+    /// Entity config asset of the target, which decides its body, armor and
+    /// poise
+    #[arg(long, default_value = "common.entity.village.dummy")]
+    target: String,
+    /// Inputs used in order by the attacker, repeating once the end is reached.
+    /// Available inputs: "primary", "secondary", "block", "roll" and
+    /// "ability1" to "ability5".
+    #[arg(long, value_delimiter = ',', default_value = "primary")]
+    rotation: Vec<String>,
+    /// How many seconds to simulate
+    #[arg(long, default_value_t = 60.0)]
+    duration: f64,
+    /// Distance between the attacker and the target
+    #[arg(long, default_value_t = 2.0)]
+    distance: f32,
+    /// Length of a tick, in seconds
+    #[arg(long, default_value_t = 1.0 / 30.0)]
+    dt: f64,
+    /// File the results are written to
+    #[arg(long, default_value = "combat_sim.csv")]
+    output: String,
+}
+
+/// What happened over the course of a simulation
+#[derive(Default)]
+struct Report {
+    damage: f32,
+    poise_damage: f32,
+    energy_used: f32,
+    first_kill: Option<f64>,
+    kills: u32,
+    unsimulated_events: u32,
+}
+
+fn parse_input(input: &str) -> Result<InputKind, String> {
+    Ok(match input {
+        "primary" => InputKind::Primary,
+        "secondary" => InputKind::Secondary,
+        "block" => InputKind::Block,
+        "roll" => InputKind::Roll,
+        _ => match input.strip_prefix("ability").map(str::parse::<usize>) {
+            Some(Ok(i @ 1..=comp::MAX_ABILITIES)) => InputKind::Ability(i - 1),
+            _ => return Err(format!("Invalid input {input:?}")),
+        },
+    })
+}
+
+fn setup() -> State {
+    let pools = State::pools(GameMode::Server);
+    let mut state = State::new(
+        GameMode::Server,
+        pools,
+        WORLD_CHUNKS_LG,
+        Arc::new(TerrainChunk::water(0)),
+    );
+    state
+        .ecs_mut()
+        .insert(MaterialStatManifest::load().cloned());
+    state.ecs_mut().insert(AbilityMap::load().cloned());
+    for x in 0..2 {
+        for y in 0..2 {
+            state.ecs().write_resource::<TerrainGrid>().insert(
+                Vec2::new(x, y),
+                Arc::new(TerrainChunk::new(
+                    GROUND_HEIGHT,
+                    Block::new(BlockKind::Grass, Rgb::new(11, 102, 35)),
+                    Block::air(SpriteKind::Empty),
+                    TerrainChunkMeta::void(),
+                )),
+            );
+        }
+    }
+
+    state
+}
+
+fn create_combatant(
+    state: &mut State,
+    pos: Vec3<f32>,
+    dir: Dir,
+    body: Body,
+    stats: Stats,
+    inventory: Inventory,
+    skill_set: SkillSet,
+) -> Entity {
+    let (p0, p1, radius) = body.sausage();
+    let health_level = skill_set
+        .skill_level(Skill::General(GeneralSkill::HealthIncrease))
+        .unwrap_or(0);
+    let energy_level = skill_set
+        .skill_level(Skill::General(GeneralSkill::EnergyIncrease))
+        .unwrap_or(0);
+
+    state
+        .ecs_mut()
+        .create_entity_synced()
+        .with(Pos(pos))
+        .with(Vel::default())
+        .with(Ori::from(dir))
+        .with(body.mass())
+        .with(body.density())
+        .with(Collider::CapsulePrism {
+            p0,
+            p1,
+            radius,
+            z_min: 0.0,
+            z_max: body.height(),
+        })
+        .with(body)
+        .with(Controller::default())
+        .with(CharacterState::default())
+        .with(CharacterActivity::default())
+        .with(Buffs::default())
+        .with(Combo::default())
+        .with(Auras::default())
+        .with(Energy::new(body, energy_level))
+        .with(Health::new(body, health_level))
+        .with(Poise::new(body))
+        .with(ActiveAbilities::default())
+        .with(inventory)
+        .with(skill_set)
+        .with(stats)
+        .build()
+}
+
+fn tick(state: &mut State, dt: Duration) {
+    state.tick(
+        dt,
+        |dispatch_builder| {
+            add_local_systems(dispatch_builder);
+            dispatch::<melee::Sys>(dispatch_builder, &[&projectile::Sys::sys_name()]);
+        },
+        false,
+        None,
+        &ServerConstants {
+            day_cycle_coefficient: 24.0,
+        },
+        |_, _| {},
+    );
+}
+
+/// Applies the events emitted during a tick, like the server would, and keeps
+/// track of those that hit the target
+fn handle_events(state: &State, target: Entity, report: &mut Report) {
+    let ecs = state.ecs();
+    let time = *ecs.read_resource::<Time>();
+    let events = ecs.read_resource::<EventBus<ServerEvent>>().recv_all();
+    for event in events {
+        match event {
+            ServerEvent::HealthChange { entity, change } => {
+                if entity == target && change.amount < 0.0 {
+                    report.damage -= change.amount;
+                }
+                if let Some(mut health) = ecs.write_storage::<Health>().get_mut(entity) {
+                    health.change_by(change);
+                }
+            },
+            ServerEvent::PoiseChange { entity, change } => {
+                let stunned = matches!(
+                    ecs.read_storage::<CharacterState>().get(entity),
+                    Some(CharacterState::Stunned(_))
+                );
+                if let Some(mut poise) = ecs.write_storage::<Poise>().get_mut(entity)
+                    && !stunned
+                {
+                    if entity == target && change.amount < 0.0 {
+                        report.poise_damage -= change.amount;
+                    }
+                    poise.change(change);
+                }
+            },
+            ServerEvent::EnergyChange { entity, change } => {
+                if let Some(mut energy) = ecs.write_storage::<Energy>().get_mut(entity) {
+                    energy.change_by(change);
+                }
+            },
+            ServerEvent::ComboChange { entity, change } => {
+                if let Some(mut combo) = ecs.write_storage::<Combo>().get_mut(entity) {
+                    combo.change_by(change, time.0);
+                }
+            },
+            ServerEvent::Buff {
+                entity,
+                buff_change,
+            } => {
+                let immune = |kind| {
+                    ecs.read_storage::<Body>()
+                        .get(entity)
+                        .map_or(false, |body| body.immune_to(kind))
+                };
+                if let Some(mut buffs) = ecs.write_storage::<Buffs>().get_mut(entity) {
+                    match buff_change {
+                        BuffChange::Add(buff) if !immune(buff.kind) => {
+                            buffs.insert(buff, time);
+                        },
+                        BuffChange::RemoveByKind(kind) => buffs.remove_kind(kind),
+                        BuffChange::RemoveFromController(kind) if kind.is_buff() => {
+                            buffs.remove_kind(kind)
+                        },
+                        _ => {},
+                    }
+                }
+            },
+            ServerEvent::Shoot { .. }
+            | ServerEvent::BeamSegment { .. }
+            | ServerEvent::Shockwave { .. }
+            | ServerEvent::CreateNpc { .. } => report.unsimulated_events += 1,
+            _ => {},
+        }
+    }
+}
+
+fn simulate(args: &Cli) -> Result<Report, Box<dyn Error>> {
+    let rotation = args
+        .rotation
+        .iter()
+        .map(|input| parse_input(input))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut rng = rand::thread_rng();
+    let mut state = setup();
+
+    let attacker_body = Body::Humanoid(comp::humanoid::Body::random_with(
+        &mut rng,
+        &comp::humanoid::Species::Human,
+    ));
+    let mut loadout = LoadoutBuilder::empty();
+    if let Some(asset) = &args.loadout {
+        loadout = loadout.with_asset_expect(asset, &mut rng);
+    }
+    let loadout = loadout
+        .active_mainhand(Some(Item::new_from_asset(&args.mainhand)?))
+        .active_offhand(
+            args.offhand
+                .as_deref()
+                .map(Item::new_from_asset)
+                .transpose()?,
+        )
+        .build();
+    let skill_set = match &args.skill_set {
+        Some(asset) => SkillSetBuilder::default().with_asset_expect(asset),
+        None => SkillSetBuilder::default(),
+    }
+    .build();
+    let center = Vec3::new(32.0, 32.0, GROUND_HEIGHT as f32);
+    let attacker = create_combatant(
+        &mut state,
+        center,
+        Dir::new(Vec3::unit_x()),
+        attacker_body,
+        Stats::new("Attacker".to_string(), attacker_body),
+        Inventory::with_loadout(loadout, attacker_body),
+        skill_set,
+    );
+
+    let target_info = EntityInfo::at(Vec3::zero()).with_asset_expect(&args.target, &mut rng);
+    let target_body = target_info.body;
+    let mut target_loadout = target_info.loadout;
+    if let Some(make_loadout) = target_info.make_loadout {
+        target_loadout = target_loadout.with_creator(make_loadout, None);
+    }
+    let target_skill_set = match &target_info.skillset_asset {
+        Some(asset) => SkillSetBuilder::default().with_asset_expect(asset),
+        None => SkillSetBuilder::default(),
+    }
+    .build();
+    let target_health_level = target_skill_set
+        .skill_level(Skill::General(GeneralSkill::HealthIncrease))
+        .unwrap_or(0);
+    let target = create_combatant(
+        &mut state,
+        center + Vec3::unit_x() * args.distance,
+        Dir::new(-Vec3::unit_x()),
+        target_body,
+        Stats::new(
+            target_info.name.unwrap_or_else(|| "Target".to_string()),
+            target_body,
+        ),
+        Inventory::with_loadout(target_loadout.build(), target_body),
+        target_skill_set,
+    );
+    let target_pos = center + Vec3::unit_x() * args.distance;
+
+    let dt = Duration::from_secs_f64(args.dt);
+    let mut report = Report::default();
+    let mut next_input = 0;
+    // The input being held, when it was pressed, and whether its ability has
+    // started yet
+    let mut held: Option<(InputKind, f64, bool)> = None;
+    let start = state.ecs().read_resource::<Time>().0;
+
+    if let Some(controller) = state.ecs().write_storage::<Controller>().get_mut(attacker) {
+        controller.push_action(ControlAction::Wield);
+    }
+    loop {
+        let time = state.ecs().read_resource::<Time>().0 - start;
+        if time >= args.duration {
+            break;
+        }
+
+        {
+            let ecs = state.ecs();
+            let char_states = ecs.read_storage::<CharacterState>();
+            let mut controllers = ecs.write_storage::<Controller>();
+            let ready = matches!(char_states.get(attacker), Some(CharacterState::Wielding(_)));
+            if let Some(controller) = controllers.get_mut(attacker) {
+                controller.inputs.look_dir = Dir::new(Vec3::unit_x());
+                controller.inputs.move_dir = Vec2::zero();
+                match held {
+                    None if ready && !rotation.is_empty() => {
+                        let input = rotation[next_input];
+                        controller.push_basic_input(input);
+                        held = Some((input, time, false));
+                    },
+                    None => {},
+                    Some((input, pressed_at, started)) => {
+                        if !ready {
+                            held = Some((input, pressed_at, true));
+                        } else if started || time - pressed_at > INPUT_TIMEOUT {
+                            controller.push_cancel_input(input);
+                            held = None;
+                            next_input = (next_input + 1) % rotation.len();
+                        }
+                    },
+                }
+            }
+        }
+
+        let energy_before = state
+            .ecs()
+            .read_storage::<Energy>()
+            .get(attacker)
+            .map_or(0.0, |energy| energy.current());
+        tick(&mut state, dt);
+        handle_events(&state, target, &mut report);
+        let energy_after = state
+            .ecs()
+            .read_storage::<Energy>()
+            .get(attacker)
+            .map_or(0.0, |energy| energy.current());
+        report.energy_used += (energy_before - energy_after).max(0.0);
+
+        let ecs = state.ecs();
+        // Hold the target in place, so knockback doesn't move it out of range
+        if let Some(pos) = ecs.write_storage::<Pos>().get_mut(target) {
+            pos.0 = target_pos;
+        }
+        let mut healths = ecs.write_storage::<Health>();
+        if healths.get(target).map_or(false, |health| health.is_dead) {
+            report.kills += 1;
+            report.first_kill.get_or_insert(time + args.dt);
+            let _ = healths.insert(target, Health::new(target_body, target_health_level));
+        }
+    }
+
+    Ok(report)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Cli::parse();
+    let report = simulate(&args)?;
+
+    if report.unsimulated_events > 0 {
+        eprintln!(
+            "Warning: the rotation created {} projectiles, beams, shockwaves or summons, which \
+             are not simulated",
+            report.unsimulated_events
+        );
+    }
+
+    let mut wtr = csv::Writer::from_path(&args.output)?;
+    wtr.write_record([
+        "Mainhand",
+        "Offhand",
+        "Rotation",
+        "Target",
+        "Duration (s)",
+        "Damage",
+        "DPS",
+        "Poise Damage",
+        "Poise DPS",
+        "Energy Used",
+        "Energy Per Second",
+        "Time To Kill (s)",
+        "Kills",
+    ])?;
+    wtr.write_record([
+        args.mainhand.as_str(),
+        args.offhand.as_deref().unwrap_or(""),
+        &args.rotation.join(","),
+        &args.target,
+        &args.duration.to_string(),
+        &report.damage.to_string(),
+        &(report.damage as f64 / args.duration).to_string(),
+        &report.poise_damage.to_string(),
+        &(report.poise_damage as f64 / args.duration).to_string(),
+        &report.energy_used.to_string(),
+        &(report.energy_used as f64 / args.duration).to_string(),
+        &report
+            .first_kill
+            .map_or_else(String::new, |ttk| ttk.to_string()),
+        &report.kills.to_string(),
+    ])?;
+    wtr.flush()?;
+    println!("Wrote results to {}", args.output);
+
+    Ok(())
+}
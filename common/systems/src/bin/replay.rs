@@ -0,0 +1,339 @@
+//! Re-simulates a replay recorded by a server, to find out whether the
+//! simulation is deterministic.
+//!
+//! The recorded world is loaded into a headless `State`, which is then ticked
+//! with the same controllers and tick lengths as the server. After every tick
+//! the positions of entities are compared against the recorded ones, and are
+//! then reset to them along with the rest of the recorded changes so that
+//! differences don't pile up over time.
+//!
+//! Server-only systems (like agents) and the handling of most server events
+//! aren't simulated, as their effects are part of the recorded changes. Server
+//! events that move entities, like knockbacks and teleports, are recorded with
+//! their payload and applied again before comparing.
+
+use clap::Parser;
+use common::{
+    comp::{
+        item::{tool::AbilityMap, MaterialStatManifest},
+        Controller, Mass, Ori, PhysicsState, Pos, Vel,
+    },
+    event::{EventBus, ServerEvent, ServerEventDiscriminants},
+    resources::{GameMode, Time},
+    shared_server_config::ServerConstants,
+    terrain::{MapSizeLg, TerrainChunk, TerrainGrid},
+    uid::Uid,
+    vol::WriteVol,
+};
+use common_ecs::{dispatch, System};
+use common_net::{
+    msg::ClientGeneral,
+    replay::{
+        EntityPhysics, ReplayEvent, ReplayFrame, ReplayHeader, ReplayReader, ReplaySnapshot,
+        REPLAY_VERSION,
+    },
+    sync::WorldSyncExt,
+};
+use common_state::State;
+use hashbrown::{HashMap, HashSet};
+use specs::WorldExt;
+use std::{error::Error, fs::File, io::BufReader, sync::Arc, time::Duration};
+use veloren_common_systems::{add_local_systems, melee, projectile};
+
+/// Re-simulates a replay recorded by a server and reports where entities end
+/// up somewhere other than where the server had them.
+///
+/// Only knockbacks and teleports are recorded in full and applied again. Other
+/// server events are only counted, so nondeterminism in how they are handled
+/// can't be found.
+#[derive(Parser)]
+struct Cli {
+    /// Replay file recorded by a server
+    replay: String,
+    /// How far an entity may end up from its recorded position before it
+    /// counts as a divergence
+    #[arg(long, default_value_t = 0.01)]
+    tolerance: f32,
+    /// How many divergences to print before only counting them
+    #[arg(long, default_value_t = 20)]
+    max_reports: usize,
+}
+
+/// What differed between the replay and the simulation
+#[derive(Default)]
+struct Report {
+    ticks: u64,
+    divergences: u64,
+    max_distance: f32,
+    /// Entities that diverged at least once
+    diverged: HashSet<Uid>,
+    /// Kinds of server events that were emitted more often by the simulation
+    /// than by the server, with how many extra there were
+    extra_events: HashMap<String, u32>,
+}
+
+fn setup(map_size_lg: MapSizeLg) -> State {
+    let pools = State::pools(GameMode::Server);
+    let mut state = State::new(
+        GameMode::Server,
+        pools,
+        map_size_lg,
+        Arc::new(TerrainChunk::water(0)),
+    );
+    state
+        .ecs_mut()
+        .insert(MaterialStatManifest::load().cloned());
+    state.ecs_mut().insert(AbilityMap::load().cloned());
+
+    state
+}
+
+fn tick(state: &mut State, dt: Duration) {
+    state.tick(
+        dt,
+        |dispatch_builder| {
+            add_local_systems(dispatch_builder);
+            dispatch::<melee::Sys>(dispatch_builder, &[&projectile::Sys::sys_name()]);
+        },
+        false,
+        None,
+        &ServerConstants {
+            day_cycle_coefficient: 24.0,
+        },
+        |_, _| {},
+    );
+}
+
+/// Puts entities back where the server had them at the end of a tick
+fn apply_physics(state: &State, physics: &[EntityPhysics]) {
+    let ecs = state.ecs();
+    let mut positions = ecs.write_storage::<Pos>();
+    let mut velocities = ecs.write_storage::<Vel>();
+    let mut orientations = ecs.write_storage::<Ori>();
+    for (uid, pos, vel, ori) in physics {
+        if let Some(entity) = ecs.entity_from_uid(uid.0) {
+            let _ = positions.insert(entity, *pos);
+            let _ = velocities.insert(entity, *vel);
+            let _ = orientations.insert(entity, *ori);
+        }
+    }
+}
+
+fn apply_controllers(state: &State, controllers: Vec<(Uid, Controller)>) {
+    let ecs = state.ecs();
+    let mut storage = ecs.write_storage::<Controller>();
+    for (uid, controller) in controllers {
+        if let Some(entity) = ecs.entity_from_uid(uid.0) {
+            let _ = storage.insert(entity, controller);
+        }
+    }
+}
+
+/// Applies the server events that moved entities during the tick, in the same
+/// way as the server handles them
+fn apply_events(state: &State, events: &[ReplayEvent]) {
+    let ecs = state.ecs();
+    let mut positions = ecs.write_storage::<Pos>();
+    let mut velocities = ecs.write_storage::<Vel>();
+    for event in events {
+        match event {
+            ReplayEvent::Knockback { entity, impulse } => {
+                let Some(entity) = ecs.entity_from_uid(entity.0) else {
+                    continue;
+                };
+                if let Some(physics) = ecs.read_storage::<PhysicsState>().get(entity) {
+                    let mut impulse = *impulse
+                        * if physics.on_surface().is_some() {
+                            1.0
+                        } else {
+                            0.4
+                        };
+                    if let Some(mass) = ecs.read_storage::<Mass>().get(entity) {
+                        impulse /= mass.0.max(40.0);
+                    }
+                    if let Some(vel) = velocities.get_mut(entity) {
+                        vel.0 += impulse;
+                    }
+                }
+            },
+            ReplayEvent::TeleportTo {
+                entity,
+                target,
+                max_range,
+            } => {
+                let target_pos = ecs
+                    .entity_from_uid(target.0)
+                    .and_then(|target| positions.get(target))
+                    .copied();
+                let pos = ecs
+                    .entity_from_uid(entity.0)
+                    .and_then(|entity| positions.get_mut(entity));
+                if let (Some(pos), Some(target_pos)) = (pos, target_pos) {
+                    if max_range.map_or(true, |r| pos.0.distance_squared(target_pos.0) < r.powi(2))
+                    {
+                        *pos = target_pos;
+                    }
+                }
+            },
+        }
+    }
+}
+
+fn load_snapshot(state: &mut State, snapshot: ReplaySnapshot) {
+    for (key, chunk) in snapshot.chunks {
+        state.insert_chunk(key, Arc::new(chunk));
+    }
+    for package in snapshot.entities {
+        state.ecs_mut().apply_entity_package(package);
+    }
+    apply_physics(state, &snapshot.physics);
+    apply_controllers(state, snapshot.controllers);
+    state.ecs().write_resource::<Time>().0 = snapshot.time;
+}
+
+/// Compares the simulated tick against the recorded one
+fn compare(
+    state: &State,
+    header: &ReplayHeader,
+    frame: &ReplayFrame,
+    args: &Cli,
+    report: &mut Report,
+) {
+    let ecs = state.ecs();
+
+    let mut events = HashMap::<String, i64>::new();
+    for event in ecs.read_resource::<EventBus<ServerEvent>>().recv_all() {
+        *events
+            .entry(format!("{:?}", ServerEventDiscriminants::from(&event)))
+            .or_default() += 1;
+    }
+    for (kind, count) in &frame.server_events {
+        if let Some(name) = header.server_events.get(*kind as usize) {
+            *events.entry(name.clone()).or_default() -= *count as i64;
+        }
+    }
+    for (name, extra) in events {
+        if extra > 0 {
+            *report.extra_events.entry(name).or_default() += extra as u32;
+        }
+    }
+
+    // Entities have no physics cache before their first tick, so they can't be
+    // expected to match until after it
+    if report.ticks == 0 {
+        return;
+    }
+
+    // The position of players is decided by their clients
+    let client_physics = frame
+        .client_inputs
+        .iter()
+        .filter(|(_, msg)| matches!(msg, ClientGeneral::PlayerPhysics { .. }))
+        .map(|(uid, _)| *uid)
+        .collect::<HashSet<_>>();
+    let positions = ecs.read_storage::<Pos>();
+    for (uid, recorded, _, _) in &frame.physics {
+        if client_physics.contains(uid) {
+            continue;
+        }
+        let Some(simulated) = ecs
+            .entity_from_uid(uid.0)
+            .and_then(|entity| positions.get(entity))
+        else {
+            continue;
+        };
+        let distance = simulated.0.distance(recorded.0);
+        if distance > args.tolerance {
+            if report.divergences < args.max_reports as u64 {
+                println!(
+                    "Tick {}: entity {} is at {:?}, but was recorded at {:?} ({} blocks away)",
+                    frame.tick, uid.0, simulated.0, recorded.0, distance
+                );
+            }
+            report.divergences += 1;
+            report.max_distance = report.max_distance.max(distance);
+            report.diverged.insert(*uid);
+        }
+    }
+}
+
+fn apply_frame(state: &mut State, frame: ReplayFrame) {
+    for (key, chunk) in frame.new_chunks {
+        state.insert_chunk(key, Arc::new(chunk));
+    }
+    for key in frame.removed_chunks {
+        state.remove_chunk(key);
+    }
+    {
+        let mut terrain = state.ecs().write_resource::<TerrainGrid>();
+        for (pos, block) in frame.block_changes {
+            let _ = terrain.set(pos, block);
+        }
+    }
+    state.ecs_mut().apply_entity_sync_package(frame.entity_sync);
+    state.ecs_mut().apply_comp_sync_package(frame.comp_sync);
+    apply_physics(state, &frame.physics);
+    apply_controllers(state, frame.controllers);
+}
+
+fn simulate(args: &Cli) -> Result<Report, Box<dyn Error>> {
+    let mut reader = ReplayReader::new(BufReader::new(File::open(&args.replay)?));
+    let header = reader.read::<ReplayHeader>()?.ok_or("Replay is empty")?;
+    if header.version != REPLAY_VERSION {
+        return Err(format!(
+            "Replay has version {}, but only version {} is supported",
+            header.version, REPLAY_VERSION
+        )
+        .into());
+    }
+    let map_size_lg = MapSizeLg::new(header.map_size_lg)
+        .map_err(|err| format!("Replay has an invalid map size: {:?}", err))?;
+    let snapshot = reader
+        .read::<ReplaySnapshot>()?
+        .ok_or("Replay has no snapshot")?;
+
+    let mut state = setup(map_size_lg);
+    load_snapshot(&mut state, snapshot);
+
+    let mut report = Report::default();
+    while let Some(frame) = reader.read::<ReplayFrame>()? {
+        state.ecs().write_resource::<Time>().0 = frame.time - frame.dt as f64;
+        tick(&mut state, Duration::from_secs_f32(frame.dt));
+        apply_events(&state, &frame.events);
+        compare(&state, &header, &frame, args, &mut report);
+        apply_frame(&mut state, frame);
+        state.cleanup();
+        report.ticks += 1;
+    }
+
+    Ok(report)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let args = Cli::parse();
+    let report = simulate(&args)?;
+
+    println!("Simulated {} ticks", report.ticks);
+    if report.divergences == 0 {
+        println!("No divergences within {} blocks", args.tolerance);
+    } else {
+        println!(
+            "{} divergences across {} entities, up to {} blocks",
+            report.divergences,
+            report.diverged.len(),
+            report.max_distance
+        );
+    }
+    for (name, extra) in &report.extra_events {
+        println!(
+            "{} more {} events were emitted than the server handled",
+            extra, name
+        );
+    }
+    println!(
+        "Note: only knockback and teleport events are replayed, so differences in how other \
+         server events are handled aren't checked"
+    );
+
+    Ok(())
+}
@@ -5,7 +5,7 @@ use common::{
         body::{object, Body},
         buff::{
             Buff, BuffCategory, BuffChange, BuffData, BuffEffect, BuffId, BuffKind, BuffSource,
-            Buffs,
+            BuffStacking, Buffs,
         },
         fluid_dynamics::{Fluid, LiquidKind},
         item::MaterialStatManifest,
@@ -354,14 +354,15 @@ impl<'a> System<'a> for Sys {
                 .collect::<Vec<(BuffKind, (Vec<BuffId>, Time))>>();
             buff_kinds.sort_by_key(|(kind, _)| !kind.affects_subsequent_buffs());
             for (buff_kind, (buff_ids, kind_start_time)) in buff_kinds.into_iter() {
-                let mut active_buff_ids = Vec::new();
-                if buff_kind.stacks() {
-                    // Process all the buffs of this kind
-                    active_buff_ids = buff_ids;
-                } else {
+                let active_buff_ids: Vec<BuffId> = match buff_kind.stacking() {
+                    // Process as many buffs of this kind as may stack
+                    BuffStacking::Stack(max) => buff_ids
+                        .into_iter()
+                        .take(max.unwrap_or(usize::MAX))
+                        .collect(),
                     // Only process the strongest of this buff kind
-                    active_buff_ids.push(buff_ids[0]);
-                }
+                    BuffStacking::Refresh | BuffStacking::StrongestWins => vec![buff_ids[0]],
+                };
                 for buff_id in active_buff_ids.into_iter() {
                     if let Some(buff) = buff_comp.buffs.get(&buff_id) {
                         // Skip the effect of buffs whose start delay hasn't expired.
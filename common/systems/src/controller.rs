@@ -6,7 +6,7 @@ use common::{
     },
     event::{EventBus, ServerEvent},
     terrain::TerrainGrid,
-    uid::UidAllocator,
+    uid::{Uid, UidAllocator},
 };
 use common_ecs::{Job, Origin, Phase, System};
 use specs::{
@@ -26,6 +26,7 @@ pub struct ReadData<'a> {
     bodies: ReadStorage<'a, Body>,
     scales: ReadStorage<'a, Scale>,
     colliders: ReadStorage<'a, Collider>,
+    uids: ReadStorage<'a, Uid>,
 }
 
 #[derive(Default)]
@@ -112,13 +113,16 @@ impl<'a> System<'a> for Sys {
                             read_data.bodies.get(entity),
                             read_data.scales.get(entity),
                         ) {
-                            let sound = Sound::new(
+                            let mut sound = Sound::new(
                                 SoundKind::Utterance(kind, *body),
                                 pos.0
                                     + Vec3::unit_z() * body.eye_height(scale.map_or(1.0, |s| s.0)),
                                 8.0, // TODO: Come up with a better way of determining this
                                 1.0,
                             );
+                            if let Some(uid) = read_data.uids.get(entity) {
+                                sound = sound.with_source(*uid);
+                            }
                             server_emitter.emit(ServerEvent::Sound { sound });
                         }
                     },
@@ -3,8 +3,8 @@ use common::{
     comp::{
         agent::{Sound, SoundKind},
         melee::MultiTarget,
-        Alignment, Body, Buffs, CharacterState, Combo, Energy, Group, Health, Inventory, Melee,
-        Ori, Player, Pos, Scale, Stats,
+        Alignment, Body, Buffs, CharacterState, Combo, Duel, Energy, Group, Guild, Health,
+        Inventory, Melee, Ori, Player, Pos, Scale, Stats,
     },
     event::{EventBus, ServerEvent},
     outcome::Outcome,
@@ -30,6 +30,8 @@ pub struct ReadData<'a> {
     uid_allocator: Read<'a, UidAllocator>,
     entities: Entities<'a>,
     players: ReadStorage<'a, Player>,
+    guilds: ReadStorage<'a, Guild>,
+    duels: ReadStorage<'a, Duel>,
     uids: ReadStorage<'a, Uid>,
     positions: ReadStorage<'a, Pos>,
     orientations: ReadStorage<'a, Ori>,
@@ -213,6 +215,8 @@ impl<'a> System<'a> for Sys {
                     let may_harm = combat::may_harm(
                         &read_data.alignments,
                         &read_data.players,
+                        &read_data.guilds,
+                        &read_data.duels,
                         &read_data.uid_allocator,
                         Some(attacker),
                         target,
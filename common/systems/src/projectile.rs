@@ -2,8 +2,8 @@ use common::{
     combat::{self, AttackOptions, AttackSource, AttackerInfo, TargetInfo},
     comp::{
         agent::{Sound, SoundKind},
-        projectile, Alignment, Body, Buffs, CharacterState, Combo, Energy, Group, Health,
-        Inventory, Ori, PhysicsState, Player, Pos, Projectile, Stats, Vel,
+        projectile, Alignment, Body, Buffs, CharacterState, Combo, Duel, Energy, Group, Guild,
+        Health, Inventory, Ori, PhysicsState, Player, Pos, Projectile, Stats, Vel,
     },
     event::{Emitter, EventBus, ServerEvent},
     outcome::Outcome,
@@ -30,6 +30,8 @@ pub struct ReadData<'a> {
     time: Read<'a, Time>,
     entities: Entities<'a>,
     players: ReadStorage<'a, Player>,
+    guilds: ReadStorage<'a, Guild>,
+    duels: ReadStorage<'a, Duel>,
     dt: Read<'a, DeltaTime>,
     uid_allocator: Read<'a, UidAllocator>,
     server_bus: Read<'a, EventBus<ServerEvent>>,
@@ -334,6 +336,8 @@ fn dispatch_hit(
             let may_harm = combat::may_harm(
                 &read_data.alignments,
                 &read_data.players,
+                &read_data.guilds,
+                &read_data.duels,
                 &read_data.uid_allocator,
                 owner,
                 target,
@@ -2,8 +2,8 @@ use common::{
     combat::{self, AttackOptions, AttackSource, AttackerInfo, TargetInfo},
     comp::{
         agent::{Sound, SoundKind},
-        Alignment, Body, Buffs, CharacterState, Combo, Energy, Group, Health, Inventory, Ori,
-        PhysicsState, Player, Pos, Scale, Shockwave, ShockwaveHitEntities, Stats,
+        Alignment, Body, Buffs, CharacterState, Combo, Duel, Energy, Group, Guild, Health,
+        Inventory, Ori, PhysicsState, Player, Pos, Scale, Shockwave, ShockwaveHitEntities, Stats,
     },
     event::{EventBus, ServerEvent},
     outcome::Outcome,
@@ -26,6 +26,8 @@ pub struct ReadData<'a> {
     server_bus: Read<'a, EventBus<ServerEvent>>,
     time: Read<'a, Time>,
     players: ReadStorage<'a, Player>,
+    guilds: ReadStorage<'a, Guild>,
+    duels: ReadStorage<'a, Duel>,
     dt: Read<'a, DeltaTime>,
     uid_allocator: Read<'a, UidAllocator>,
     uids: ReadStorage<'a, Uid>,
@@ -229,6 +231,8 @@ impl<'a> System<'a> for Sys {
                     let may_harm = combat::may_harm(
                         &read_data.alignments,
                         &read_data.players,
+                        &read_data.guilds,
+                        &read_data.duels,
                         &read_data.uid_allocator,
                         shockwave_owner,
                         target,
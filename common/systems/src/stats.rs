@@ -205,7 +205,9 @@ impl<'a> System<'a> for Sys {
                 | CharacterState::Stunned(_)
                 | CharacterState::BasicBlock(_)
                 | CharacterState::UseItem(_)
-                | CharacterState::SpriteInteract(_) => {},
+                | CharacterState::SpriteInteract(_)
+                | CharacterState::Crafting(_)
+                | CharacterState::Fishing(_) => {},
             }
         }
 
@@ -1,3 +1,5 @@
+pub mod schedule;
+
 use crate::{
     data::{
         npc::{Controller, Npc, NpcId},
@@ -0,0 +1,119 @@
+use common::{
+    assets::{self, AssetExt, AssetHandle},
+    resources::TimeOfDay,
+    rtsim::Profession,
+};
+use lazy_static::lazy_static;
+use serde::Deserialize;
+
+/// A place within (or near) a site that an NPC might go to in order to perform
+/// a scheduled activity.
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Eq)]
+pub enum Place {
+    /// The house that the NPC sleeps in.
+    Home,
+    /// Where NPCs go to eat and drink.
+    Tavern,
+    /// A plaza in the site.
+    Plaza,
+    /// A workshop, where smiths and other craftspeople ply their trade.
+    Forge,
+    /// A stall on one of the site's plazas.
+    Market,
+    /// Farmland on the outskirts of the site.
+    Field,
+    /// A nearby forest.
+    Forest,
+}
+
+/// An activity that an NPC may be scheduled to perform.
+#[derive(Copy, Clone, Debug, Deserialize, PartialEq, Eq)]
+pub enum Activity {
+    /// Go home and sleep.
+    Sleep,
+    /// Have a meal at the tavern.
+    Eat,
+    /// Perform the duties of the NPC's profession at the given place.
+    Work(Place),
+    /// Walk between the plazas of the site, keeping watch.
+    Patrol,
+    /// Spend time with other NPCs.
+    Socialize,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ScheduleEntry {
+    /// The hour of the day (`0.0..24.0`) at which the activity begins. The
+    /// activity continues until the start of the next entry.
+    pub start: f32,
+    pub activity: Activity,
+}
+
+/// The daily routine of an NPC, loaded from `common.rtsim.schedule.*`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Schedule {
+    pub entries: Vec<ScheduleEntry>,
+    /// Whether the NPC will drop what they are doing to head indoors when it
+    /// rains.
+    #[serde(default = "default_shelter_from_rain")]
+    pub shelter_from_rain: bool,
+}
+
+fn default_shelter_from_rain() -> bool { true }
+
+impl assets::Asset for Schedule {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+lazy_static! {
+    static ref FARMER: AssetHandle<Schedule> =
+        Schedule::load_expect("common.rtsim.schedule.farmer");
+    static ref HUNTER: AssetHandle<Schedule> =
+        Schedule::load_expect("common.rtsim.schedule.hunter");
+    static ref HERBALIST: AssetHandle<Schedule> =
+        Schedule::load_expect("common.rtsim.schedule.herbalist");
+    static ref MERCHANT: AssetHandle<Schedule> =
+        Schedule::load_expect("common.rtsim.schedule.merchant");
+    static ref GUARD: AssetHandle<Schedule> = Schedule::load_expect("common.rtsim.schedule.guard");
+    static ref BLACKSMITH: AssetHandle<Schedule> =
+        Schedule::load_expect("common.rtsim.schedule.blacksmith");
+    static ref CHEF: AssetHandle<Schedule> = Schedule::load_expect("common.rtsim.schedule.chef");
+    static ref DEFAULT: AssetHandle<Schedule> =
+        Schedule::load_expect("common.rtsim.schedule.default");
+}
+
+impl Schedule {
+    pub fn for_profession(profession: Option<&Profession>) -> AssetHandle<Self> {
+        match profession {
+            Some(Profession::Farmer) => *FARMER,
+            Some(Profession::Hunter) => *HUNTER,
+            Some(Profession::Herbalist) => *HERBALIST,
+            Some(Profession::Merchant | Profession::Alchemist) => *MERCHANT,
+            Some(Profession::Guard) => *GUARD,
+            Some(Profession::Blacksmith) => *BLACKSMITH,
+            Some(Profession::Chef) => *CHEF,
+            Some(
+                Profession::Adventurer(_)
+                | Profession::Pirate
+                | Profession::Cultist
+                | Profession::Captain,
+            )
+            | None => *DEFAULT,
+        }
+    }
+
+    /// The activity that should be performed at the given time of day.
+    pub fn activity_at(&self, time_of_day: TimeOfDay) -> Option<Activity> {
+        let hour = (time_of_day.0.rem_euclid(60.0 * 60.0 * 24.0) / (60.0 * 60.0)) as f32;
+        let by_start = |a: &&ScheduleEntry, b: &&ScheduleEntry| a.start.total_cmp(&b.start);
+        self.entries
+            .iter()
+            .filter(|entry| entry.start <= hour)
+            .max_by(by_start)
+            // Before the first entry of the day, we're still doing the last activity of yesterday
+            .or_else(|| self.entries.iter().max_by(by_start))
+            .map(|entry| entry.activity)
+    }
+}
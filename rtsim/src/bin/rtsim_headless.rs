@@ -0,0 +1,340 @@
+//! Runs rtsim without a server, simulating the world for a number of in-game
+//! days as fast as possible and periodically recording statistics about it.
+//!
+//! Statistics are written to the output directory as:
+//!
+//! - `summary.csv`: overall population, deaths and reports.
+//! - `sites.csv`: the population of each site.
+//! - `factions.csv`: the size of each faction and its feelings toward others.
+//! - `snapshots.jsonl`: all of the above, one JSON object per snapshot.
+//!
+//! Note that no chunks are ever loaded, so all NPCs are simulated and deaths
+//! only occur when a rule emits them.
+
+use clap::Parser;
+use common::{
+    resources::{Time, TimeOfDay},
+    rtsim::{Actor, WorldSettings},
+};
+use serde::Serialize;
+use std::{
+    error::Error,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use tracing::info;
+use veloren_rtsim::{
+    data::Data,
+    event::{EventCtx, OnDeath, OnSetup},
+    RtState, Rule, RuleError,
+};
+use world::{
+    sim::{FileOpts, WorldOpts, DEFAULT_WORLD_MAP},
+    IndexRef, World,
+};
+
+const DAY_SECONDS: f64 = 60.0 * 60.0 * 24.0;
+
+#[derive(Parser)]
+struct Cli {
+    /// The number of in-game days to simulate
+    #[arg(long, default_value_t = 7.0)]
+    days: f64,
+    /// The number of in-game hours between statistics snapshots
+    #[arg(long, default_value_t = 6.0)]
+    snapshot_hours: f64,
+    /// The real time, in seconds, that each rtsim tick represents. Larger
+    /// values simulate faster, at the cost of accuracy.
+    #[arg(long, default_value_t = 1.0 / 30.0)]
+    dt: f32,
+    /// The length of an in-game day in real minutes (as in the server
+    /// settings)
+    #[arg(long, default_value_t = 30.0)]
+    day_length: f64,
+    /// The world seed
+    #[arg(long, default_value_t = 0)]
+    seed: u32,
+    /// A world map file to load, instead of the default map
+    #[arg(long)]
+    map: Option<PathBuf>,
+    /// Existing rtsim data to load, instead of generating it from the world
+    #[arg(long)]
+    data: Option<PathBuf>,
+    /// Where to write the rtsim data at the end of the simulation
+    #[arg(long)]
+    save: Option<PathBuf>,
+    /// The directory that statistics are written to
+    #[arg(long, default_value = "rtsim_stats")]
+    out: PathBuf,
+}
+
+/// The number of deaths that have occurred since the simulation started.
+#[derive(Default)]
+struct Deaths(u64);
+
+/// Counts deaths so that they can be reported.
+struct TrackDeaths;
+
+impl Rule for TrackDeaths {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnDeath>(|ctx: EventCtx<Self, OnDeath>| {
+            if matches!(ctx.event.actor, Actor::Npc(_)) {
+                ctx.state.resource_mut::<Deaths>().0 += 1;
+            }
+        });
+
+        Ok(Self)
+    }
+}
+
+#[derive(Serialize)]
+struct Snapshot {
+    day: f64,
+    tick: u64,
+    population: usize,
+    deaths: u64,
+    reports: usize,
+    sites: Vec<SiteStats>,
+    factions: Vec<FactionStats>,
+}
+
+#[derive(Serialize)]
+struct SiteStats {
+    site: String,
+    faction: Option<String>,
+    population: usize,
+}
+
+#[derive(Serialize)]
+struct FactionStats {
+    faction: String,
+    members: usize,
+    sites: usize,
+    /// The average sentiment that the faction feels toward other factions.
+    mean_sentiment: f32,
+    /// The sentiment felt toward the faction it likes least.
+    min_sentiment: f32,
+}
+
+impl Snapshot {
+    fn take(data: &Data, index: IndexRef, deaths: u64) -> Self {
+        let sites = data
+            .sites
+            .iter()
+            .map(|(site_id, site)| SiteStats {
+                site: site
+                    .world_site
+                    .map(|ws| index.sites.get(ws).name().to_string())
+                    .unwrap_or_else(|| format!("{:?}", site_id)),
+                faction: site.faction.map(|faction| format!("{:?}", faction)),
+                population: site.population.len(),
+            })
+            .collect();
+        let factions = data
+            .factions
+            .iter()
+            .map(|(faction_id, faction)| {
+                let sentiments = faction
+                    .sentiments
+                    .factions()
+                    .filter(|(other, _)| *other != faction_id)
+                    .map(|(_, sentiment)| sentiment.value())
+                    .collect::<Vec<_>>();
+                FactionStats {
+                    faction: format!("{:?}", faction_id),
+                    members: data
+                        .npcs
+                        .values()
+                        .filter(|npc| !npc.is_dead && npc.faction == Some(faction_id))
+                        .count(),
+                    sites: data
+                        .sites
+                        .values()
+                        .filter(|site| site.faction == Some(faction_id))
+                        .count(),
+                    mean_sentiment: if sentiments.is_empty() {
+                        0.0
+                    } else {
+                        sentiments.iter().sum::<f32>() / sentiments.len() as f32
+                    },
+                    min_sentiment: sentiments.iter().copied().fold(0.0, f32::min),
+                }
+            })
+            .collect();
+
+        Self {
+            day: data.time_of_day.0 / DAY_SECONDS,
+            tick: data.tick,
+            population: data.npcs.values().filter(|npc| !npc.is_dead).count(),
+            deaths,
+            reports: data.reports.len(),
+            sites,
+            factions,
+        }
+    }
+}
+
+struct StatsWriter {
+    summary: csv::Writer<File>,
+    sites: csv::Writer<File>,
+    factions: csv::Writer<File>,
+    snapshots: BufWriter<File>,
+}
+
+impl StatsWriter {
+    fn create(dir: &Path) -> Result<Self, Box<dyn Error>> {
+        fs::create_dir_all(dir)?;
+        let mut summary = csv::Writer::from_path(dir.join("summary.csv"))?;
+        summary.write_record(["Day", "Tick", "Population", "Deaths", "Reports"])?;
+        let mut sites = csv::Writer::from_path(dir.join("sites.csv"))?;
+        sites.write_record(["Day", "Site", "Faction", "Population"])?;
+        let mut factions = csv::Writer::from_path(dir.join("factions.csv"))?;
+        factions.write_record([
+            "Day",
+            "Faction",
+            "Members",
+            "Sites",
+            "MeanSentiment",
+            "MinSentiment",
+        ])?;
+        let snapshots = BufWriter::new(File::create(dir.join("snapshots.jsonl"))?);
+
+        Ok(Self {
+            summary,
+            sites,
+            factions,
+            snapshots,
+        })
+    }
+
+    fn write(&mut self, snapshot: &Snapshot) -> Result<(), Box<dyn Error>> {
+        let day = format!("{:.3}", snapshot.day);
+        self.summary.write_record([
+            &day,
+            &snapshot.tick.to_string(),
+            &snapshot.population.to_string(),
+            &snapshot.deaths.to_string(),
+            &snapshot.reports.to_string(),
+        ])?;
+        for site in &snapshot.sites {
+            self.sites.write_record([
+                day.as_str(),
+                site.site.as_str(),
+                site.faction.as_deref().unwrap_or(""),
+                site.population.to_string().as_str(),
+            ])?;
+        }
+        for faction in &snapshot.factions {
+            self.factions.write_record([
+                &day,
+                &faction.faction,
+                &faction.members.to_string(),
+                &faction.sites.to_string(),
+                &faction.mean_sentiment.to_string(),
+                &faction.min_sentiment.to_string(),
+            ])?;
+        }
+        serde_json::to_writer(&mut self.snapshots, snapshot)?;
+        writeln!(self.snapshots)?;
+
+        // Flush as we go so that partial results survive an interrupted run
+        self.summary.flush()?;
+        self.sites.flush()?;
+        self.factions.flush()?;
+        self.snapshots.flush()?;
+        Ok(())
+    }
+}
+
+fn load_data(path: &Path) -> Result<Data, Box<dyn Error>> {
+    let file = io::BufReader::new(File::open(path)?);
+    match Data::from_reader(file) {
+        Ok(data) => Ok(*data),
+        Err(err) => Err(format!("Failed to load rtsim data: {:?}", err).into()),
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let _guards = common_frontend::init_stdout(None);
+    let args = Cli::parse();
+
+    info!("Generating world...");
+    let threadpool = rayon::ThreadPoolBuilder::new().build()?;
+    let (world, index) = World::generate(
+        args.seed,
+        WorldOpts {
+            seed_elements: true,
+            world_file: match &args.map {
+                Some(path) => FileOpts::Load(path.clone()),
+                None => FileOpts::LoadAsset(DEFAULT_WORLD_MAP.into()),
+            },
+            calendar: None,
+        },
+        &threadpool,
+    );
+    let index = index.as_index_ref();
+
+    let data = match &args.data {
+        Some(path) => {
+            info!("Loading rtsim data from {}...", path.display());
+            load_data(path)?
+        },
+        None => {
+            info!("Generating rtsim data...");
+            Data::generate(&WorldSettings { start_time: 0.0 }, &world, index)
+        },
+    };
+
+    let mut state = RtState::new(data).with_resource(Deaths::default());
+    state.start_rule::<TrackDeaths>();
+    state.emit(OnSetup, &world, index);
+
+    let day_cycle_coefficient = 1440.0 / args.day_length;
+    let start_time_of_day = state.data().time_of_day.0;
+    let end_time_of_day = start_time_of_day + args.days * DAY_SECONDS;
+    let snapshot_interval = args.snapshot_hours * 60.0 * 60.0;
+
+    let mut stats = StatsWriter::create(&args.out)?;
+    let mut time = Time(0.0);
+    let mut time_of_day = TimeOfDay(start_time_of_day);
+    let mut next_snapshot = start_time_of_day;
+    let started = Instant::now();
+
+    info!(
+        "Simulating {} days ({} ticks)...",
+        args.days,
+        ((end_time_of_day - start_time_of_day) / (args.dt as f64 * day_cycle_coefficient)).ceil()
+    );
+    loop {
+        if time_of_day.0 >= next_snapshot {
+            let snapshot = Snapshot::take(&state.data(), index, state.resource::<Deaths>().0);
+            info!(
+                "Day {:.2}: {} NPCs, {} deaths, {} reports",
+                snapshot.day, snapshot.population, snapshot.deaths, snapshot.reports
+            );
+            stats.write(&snapshot)?;
+            next_snapshot += snapshot_interval;
+        }
+
+        if time_of_day.0 >= end_time_of_day {
+            break;
+        }
+
+        time.0 += args.dt as f64;
+        time_of_day.0 += args.dt as f64 * day_cycle_coefficient;
+        state.tick(&world, index, time_of_day, time, args.dt);
+    }
+    info!(
+        "Simulation finished in {:.1}s",
+        started.elapsed().as_secs_f32()
+    );
+
+    if let Some(path) = &args.save {
+        info!("Saving rtsim data to {}...", path.display());
+        state.data().write_to(BufWriter::new(File::create(path)?))?;
+    }
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
-use crate::data::Sentiments;
-use common::rtsim::Actor;
+use crate::data::{Sentiment, Sentiments};
+use common::rtsim::{Actor, NpcId, SiteId};
 pub use common::rtsim::FactionId;
+use hashbrown::{HashMap, HashSet};
 use serde::{Deserialize, Serialize};
 use slotmap::HopSlotMap;
 use std::ops::{Deref, DerefMut};
@@ -14,6 +15,23 @@ pub struct Faction {
 
     #[serde(default)]
     pub sentiments: Sentiments,
+
+    /// The diplomatic stance that this faction currently holds toward other
+    /// factions. Factions that are not present are considered to be
+    /// [`Stance::Neutral`].
+    #[serde(default)]
+    pub relations: HashMap<FactionId, Stance>,
+
+    /// Raiding parties that the faction has sent out toward enemy sites.
+    #[serde(default)]
+    pub raids: Vec<Raid>,
+
+    /// The sites that are currently owned by this faction.
+    ///
+    /// This is derived from [`crate::data::Site::faction`] and so does not need
+    /// persisting.
+    #[serde(skip)]
+    pub territory: HashSet<SiteId>,
 }
 
 impl Faction {
@@ -21,6 +39,59 @@ impl Faction {
         self.sentiments
             .cleanup(crate::data::sentiment::FACTION_MAX_SENTIMENTS);
     }
+
+    /// The stance that this faction holds toward another faction.
+    pub fn stance_toward(&self, other: FactionId) -> Stance {
+        self.relations.get(&other).copied().unwrap_or_default()
+    }
+
+    pub fn is_at_war_with(&self, other: FactionId) -> bool {
+        matches!(self.stance_toward(other), Stance::War)
+    }
+
+    /// Determine the stance that the faction *should* hold toward another
+    /// faction, given its current stance and its sentiment toward that faction.
+    ///
+    /// Note that there is some hysteresis here: it takes a lot of bad blood to
+    /// start a war, but a war only ends when sentiment has recovered somewhat.
+    pub fn desired_stance_toward(&self, other: FactionId) -> Stance {
+        let sentiment = self.sentiments.toward(other);
+        match self.stance_toward(other) {
+            Stance::War if sentiment.is(Sentiment::RIVAL) => Stance::War,
+            Stance::Alliance if sentiment.is(Sentiment::POSITIVE) => Stance::Alliance,
+            _ if sentiment.is(Sentiment::ENEMY) => Stance::War,
+            _ if sentiment.is(Sentiment::ALLY) => Stance::Alliance,
+            _ => Stance::Neutral,
+        }
+    }
+
+    /// Returns the raid (if any) that the given NPC is taking part in.
+    pub fn raid_of(&self, npc: NpcId) -> Option<&Raid> {
+        self.raids.iter().find(|raid| raid.members.contains(&npc))
+    }
+}
+
+/// The diplomatic relationship between two factions.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Stance {
+    #[serde(rename = "0")]
+    #[default]
+    Neutral,
+    #[serde(rename = "1")]
+    Alliance,
+    #[serde(rename = "2")]
+    War,
+}
+
+/// A party of NPCs sent out by a faction to capture a site owned by an enemy
+/// faction.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Raid {
+    pub target: SiteId,
+    pub members: Vec<NpcId>,
+    /// The time of day at which the raid was launched, used to give up on raids
+    /// that take too long.
+    pub started_at: f64,
 }
 
 #[derive(Clone, Default, Serialize, Deserialize)]
@@ -30,6 +101,15 @@ pub struct Factions {
 
 impl Factions {
     pub fn create(&mut self, faction: Faction) -> FactionId { self.factions.insert(faction) }
+
+    /// Returns `true` if the two (optional) factions are at war with one
+    /// another.
+    pub fn are_at_war(&self, a: Option<FactionId>, b: Option<FactionId>) -> bool {
+        match (a, b) {
+            (Some(a), Some(b)) if a != b => self.get(a).map_or(false, |a| a.is_at_war_with(b)),
+            _ => false,
+        }
+    }
 }
 
 impl Deref for Factions {
@@ -2,14 +2,16 @@ pub mod faction;
 pub mod nature;
 pub mod npc;
 pub mod report;
+pub mod reputation;
 pub mod sentiment;
 pub mod site;
 
 pub use self::{
-    faction::{Faction, FactionId, Factions},
+    faction::{Faction, FactionId, Factions, Raid, Stance},
     nature::Nature,
     npc::{Npc, NpcId, Npcs},
     report::{Report, ReportId, ReportKind, Reports},
+    reputation::{Reputation, Reputations},
     sentiment::{Sentiment, Sentiments},
     site::{Site, SiteId, Sites},
 };
@@ -46,6 +48,8 @@ pub struct Data {
     pub factions: Factions,
     #[serde(default)]
     pub reports: Reports,
+    #[serde(skip)]
+    pub reputations: Reputations,
 
     #[serde(default)]
     pub tick: u64,
@@ -1,4 +1,7 @@
-use common::{resources::TimeOfDay, rtsim::Actor};
+use common::{
+    resources::TimeOfDay,
+    rtsim::{Actor, FactionId, SiteId},
+};
 use serde::{Deserialize, Serialize};
 use slotmap::HopSlotMap;
 use std::ops::Deref;
@@ -37,6 +40,8 @@ impl Report {
                     DAYS * 5.0
                 }
             },
+            // Changes in who controls a site are remembered for a long time
+            ReportKind::SiteCaptured { .. } => DAYS * 30.0,
         }
     }
 }
@@ -44,6 +49,12 @@ impl Report {
 #[derive(Copy, Clone, Serialize, Deserialize)]
 pub enum ReportKind {
     Death { actor: Actor, killer: Option<Actor> },
+    /// A site was captured by a raiding party from another faction.
+    SiteCaptured {
+        site: SiteId,
+        by: FactionId,
+        from: Option<FactionId>,
+    },
 }
 
 #[derive(Clone, Default, Serialize, Deserialize)]
@@ -0,0 +1,49 @@
+use common::{
+    character::CharacterId,
+    rtsim::{FactionId, SiteId},
+};
+use hashbrown::HashMap;
+use std::ops::{Deref, DerefMut};
+
+/// The standing of a player character with the factions and sites of the
+/// world, in the range `-1.0..=1.0`.
+#[derive(Clone, Default)]
+pub struct Reputation {
+    pub factions: HashMap<FactionId, f32>,
+    pub sites: HashMap<SiteId, f32>,
+}
+
+impl Reputation {
+    pub fn toward_faction(&self, faction: FactionId) -> f32 {
+        self.factions.get(&faction).copied().unwrap_or(0.0)
+    }
+
+    pub fn toward_site(&self, site: SiteId) -> f32 {
+        self.sites.get(&site).copied().unwrap_or(0.0)
+    }
+}
+
+/// The reputations of all player characters known to rtsim.
+///
+/// Reputations are derived from the sentiments of NPCs and factions (see
+/// [`crate::rule::reputation`]) so they do not need persisting.
+#[derive(Clone, Default)]
+pub struct Reputations {
+    pub characters: HashMap<CharacterId, Reputation>,
+}
+
+impl Reputations {
+    pub fn of(&self, character: CharacterId) -> Option<&Reputation> {
+        self.characters.get(&character)
+    }
+}
+
+impl Deref for Reputations {
+    type Target = HashMap<CharacterId, Reputation>;
+
+    fn deref(&self) -> &Self::Target { &self.characters }
+}
+
+impl DerefMut for Reputations {
+    fn deref_mut(&mut self) -> &mut Self::Target { &mut self.characters }
+}
@@ -65,6 +65,22 @@ impl Sentiments {
         self.map.entry(target.into()).or_default()
     }
 
+    /// Iterate over the sentiments that are felt toward player characters.
+    pub fn characters(&self) -> impl Iterator<Item = (CharacterId, &Sentiment)> + '_ {
+        self.map.iter().filter_map(|(target, sentiment)| match target {
+            Target::Character(character) => Some((*character, sentiment)),
+            _ => None,
+        })
+    }
+
+    /// Iterate over the sentiments that are felt toward factions.
+    pub fn factions(&self) -> impl Iterator<Item = (FactionId, &Sentiment)> + '_ {
+        self.map.iter().filter_map(|(target, sentiment)| match target {
+            Target::Faction(faction) => Some((*faction, sentiment)),
+            _ => None,
+        })
+    }
+
     /// Progressively decay the sentiment back to a neutral sentiment.
     ///
     /// Note that sentiment get decay gets slower the harsher the sentiment is.
@@ -158,7 +174,8 @@ impl Sentiment {
     /// generally try to harm the actor in any way they can.
     pub const VILLAIN: f32 = -0.8;
 
-    fn value(&self) -> f32 { self.positivity as f32 * (1.0 / 126.0) }
+    /// The sentiment as a value in the range -1 <= x <= 1.
+    pub fn value(&self) -> f32 { self.positivity as f32 * (1.0 / 126.0) }
 
     /// Change the sentiment toward the given target by the given amount,
     /// capping out at the given value.
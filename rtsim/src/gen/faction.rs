@@ -9,6 +9,9 @@ impl Faction {
             leader: None,
             good_or_evil: rng.gen(),
             sentiments: Default::default(),
+            relations: Default::default(),
+            raids: Vec::new(),
+            territory: Default::default(),
         }
     }
 }
@@ -6,7 +6,7 @@ use crate::data::{
     faction::Faction,
     npc::{Npc, Npcs, Profession, Vehicle},
     site::Site,
-    Data, Nature, CURRENT_VERSION,
+    Data, Nature, Sentiment, CURRENT_VERSION,
 };
 use common::{
     comp::{self, Body},
@@ -41,6 +41,7 @@ impl Data {
             sites: Default::default(),
             factions: Default::default(),
             reports: Default::default(),
+            reputations: Default::default(),
 
             tick: 0,
             time_of_day: TimeOfDay(settings.start_time),
@@ -61,6 +62,19 @@ impl Data {
             .collect::<Vec<_>>();
         info!("Generated {} rtsim factions.", this.factions.len());
 
+        // Factions with opposing ideals start out distrustful of one-another
+        let faction_ids = this.factions.keys().collect::<Vec<_>>();
+        for &a in &faction_ids {
+            for &b in &faction_ids {
+                if this.factions[a].good_or_evil != this.factions[b].good_or_evil {
+                    this.factions[a]
+                        .sentiments
+                        .toward_mut(b)
+                        .change_by(Sentiment::NEGATIVE, 1.0);
+                }
+            }
+        }
+
         // Register sites with rtsim
         for (world_site_id, _) in index.sites.iter() {
             let site = Site::generate(
@@ -23,12 +23,16 @@ pub use self::{
 };
 use anymap2::SendSyncAnyMap;
 use atomic_refcell::AtomicRefCell;
-use common::resources::{Time, TimeOfDay};
+use common::{
+    resources::{Time, TimeOfDay},
+    weather::WeatherGrid,
+};
 use std::{
     any::type_name,
     ops::{Deref, DerefMut},
 };
 use tracing::{error, info};
+use vek::*;
 use world::{IndexRef, World};
 
 pub struct RtState {
@@ -47,7 +51,9 @@ impl RtState {
             rules: SendSyncAnyMap::new(),
             event_handlers: SendSyncAnyMap::new(),
         }
-        .with_resource(data);
+        .with_resource(data)
+        // Updated by the server as the weather changes
+        .with_resource(WeatherGrid::new(Vec2::zero()));
 
         this.start_default_rules();
 
@@ -67,6 +73,9 @@ impl RtState {
         self.start_rule::<rule::sync_npcs::SyncNpcs>();
         self.start_rule::<rule::simulate_npcs::SimulateNpcs>();
         self.start_rule::<rule::npc_ai::NpcAi>();
+        self.start_rule::<rule::diplomacy::Diplomacy>();
+        self.start_rule::<rule::reputation::AggregateReputation>();
+        self.start_rule::<rule::scripted::ScriptedRules>();
         self.start_rule::<rule::cleanup::CleanUp>();
     }
 
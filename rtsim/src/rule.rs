@@ -1,8 +1,11 @@
 pub mod cleanup;
+pub mod diplomacy;
 pub mod migrate;
 pub mod npc_ai;
 pub mod replenish_resources;
 pub mod report;
+pub mod reputation;
+pub mod scripted;
 pub mod simulate_npcs;
 pub mod sync_npcs;
 
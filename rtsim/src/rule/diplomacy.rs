@@ -0,0 +1,389 @@
+use crate::{
+    data::{
+        faction::{Raid, Stance},
+        Data, Report, ReportKind, Sentiment,
+    },
+    event::{EventCtx, OnSetup, OnTick},
+    RtState, Rule, RuleError,
+};
+use common::rtsim::{FactionId, NpcId, Profession, SiteId};
+use hashbrown::HashMap;
+use rand::prelude::*;
+use rand_chacha::ChaChaRng;
+use tracing::info;
+
+/// How many ticks should pass between updates of faction relations.
+const DIPLOMACY_TICK_SKIP: u64 = 900;
+/// How quickly the collective feelings of a faction's members toward another
+/// faction influence the faction as a whole.
+const AGGREGATE_RATE: f32 = 0.1;
+/// Factions with sites closer than this (in blocks) are considered neighbours
+/// and will develop opinions of one-another.
+const BORDER_DIST: i32 = 2048;
+/// How much neighbouring factions of differing alignment grow to dislike
+/// one-another each diplomacy tick (and how much factions of like alignment
+/// grow to like one-another).
+const BORDER_FRICTION: f32 = 0.01;
+/// The maximum number of raids a faction may have in progress at once.
+const MAX_RAIDS: usize = 2;
+/// The chance that a faction at war will launch a new raid on a diplomacy tick.
+const RAID_CHANCE: f64 = 0.05;
+const MIN_RAID_SIZE: usize = 3;
+const MAX_RAID_SIZE: usize = 8;
+/// Raids will only target sites that are within this distance (in blocks) of
+/// the raiding faction's territory.
+const MAX_RAID_DIST: i32 = 4096;
+/// The in-game time after which a raid that has not reached its target is
+/// abandoned.
+const RAID_TIMEOUT: f64 = 60.0 * 60.0 * 24.0 * 2.0;
+
+/// A rule that manages the relationships between factions: maintaining their
+/// territory, moving them between peace and war according to the sentiments of
+/// their members, and sending raiding parties to capture enemy sites.
+pub struct Diplomacy;
+
+impl Rule for Diplomacy {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnSetup>(on_setup);
+        rtstate.bind::<Self, OnTick>(on_tick);
+
+        Ok(Self)
+    }
+}
+
+fn on_setup(ctx: EventCtx<Diplomacy, OnSetup>) {
+    let data = &mut *ctx.state.data_mut();
+    update_territory(data);
+}
+
+fn on_tick(ctx: EventCtx<Diplomacy, OnTick>) {
+    if ctx.event.tick % DIPLOMACY_TICK_SKIP != 0 {
+        return;
+    }
+
+    let data = &mut *ctx.state.data_mut();
+    let mut rng = ChaChaRng::from_seed(thread_rng().gen::<[u8; 32]>());
+    let dt = ctx.event.dt * DIPLOMACY_TICK_SKIP as f32;
+
+    update_territory(data);
+    update_sentiments(data, &mut rng, dt);
+    update_stances(data);
+    resolve_raids(data);
+    launch_raids(data, &mut rng);
+}
+
+/// Recalculate the sites owned by each faction.
+fn update_territory(data: &mut Data) {
+    for faction in data.factions.values_mut() {
+        faction.territory.clear();
+    }
+    for (site_id, site) in data.sites.iter() {
+        if let Some(faction) = site.faction.and_then(|f| data.factions.get_mut(f)) {
+            faction.territory.insert(site_id);
+        }
+    }
+}
+
+/// The distance between the closest pair of sites in two territories, if both
+/// have any territory at all.
+fn territory_dist(data: &Data, a: FactionId, b: FactionId) -> Option<i32> {
+    let (a, b) = (data.factions.get(a)?, data.factions.get(b)?);
+    a.territory
+        .iter()
+        .filter_map(|site| data.sites.get(*site))
+        .flat_map(|sa| {
+            b.territory
+                .iter()
+                .filter_map(|site| data.sites.get(*site))
+                .map(move |sb| sa.wpos.as_::<f32>().distance(sb.wpos.as_()) as i32)
+        })
+        .min()
+}
+
+fn update_sentiments(data: &mut Data, rng: &mut impl Rng, dt: f32) {
+    let faction_ids = data.factions.keys().collect::<Vec<_>>();
+
+    // Tally up what members of each faction think about other factions
+    let mut member_totals = HashMap::<(FactionId, FactionId), (f32, usize)>::new();
+    for npc in data.npcs.values().filter(|npc| !npc.is_dead) {
+        if let Some(faction) = npc.faction {
+            for other in faction_ids.iter().filter(|other| **other != faction) {
+                let total = member_totals.entry((faction, *other)).or_default();
+                total.0 += npc.sentiments.toward(*other).value();
+                total.1 += 1;
+            }
+        }
+    }
+
+    let mut changes = Vec::new();
+    for &faction in faction_ids.iter() {
+        for &other in faction_ids.iter().filter(|other| **other != faction) {
+            let mut change = 0.0;
+
+            // Members' opinions filter up to the faction as a whole
+            if let Some((total, count)) = member_totals.get(&(faction, other))
+                && *count > 0
+            {
+                change += (total / *count as f32) * AGGREGATE_RATE;
+            }
+
+            // Neighbours rub along well if they share ideals, and less well if they don't
+            if territory_dist(data, faction, other).map_or(false, |dist| dist < BORDER_DIST) {
+                let same_alignment = data.factions[faction].good_or_evil
+                    == data.factions[other].good_or_evil;
+                change += if same_alignment {
+                    BORDER_FRICTION
+                } else {
+                    -BORDER_FRICTION
+                };
+            }
+
+            changes.push((faction, other, change));
+        }
+    }
+
+    for faction in data.factions.values_mut() {
+        faction.sentiments.decay(rng, dt);
+    }
+    for (faction, other, change) in changes {
+        if change.abs() > 0.5 / 126.0 {
+            data.factions[faction]
+                .sentiments
+                .toward_mut(other)
+                .change_by(change, 1.0);
+        }
+    }
+}
+
+/// Move factions between war, peace and alliance. Relations are kept symmetric:
+/// it takes only one faction to start a war, but both to form an alliance.
+fn update_stances(data: &mut Data) {
+    let faction_ids = data.factions.keys().collect::<Vec<_>>();
+    let mut stances = Vec::new();
+    for (i, &a) in faction_ids.iter().enumerate() {
+        for &b in faction_ids.iter().skip(i + 1) {
+            let stance = match (
+                data.factions[a].desired_stance_toward(b),
+                data.factions[b].desired_stance_toward(a),
+            ) {
+                (Stance::War, _) | (_, Stance::War) => Stance::War,
+                (Stance::Alliance, Stance::Alliance) => Stance::Alliance,
+                _ => Stance::Neutral,
+            };
+            stances.push((a, b, stance));
+        }
+    }
+
+    for (a, b, stance) in stances {
+        if data.factions[a].stance_toward(b) != stance {
+            info!("Faction {:?} now has stance {:?} toward {:?}", a, stance, b);
+        }
+        for (this, other) in [(a, b), (b, a)] {
+            let relations = &mut data.factions[this].relations;
+            if stance == Stance::Neutral {
+                relations.remove(&other);
+            } else {
+                relations.insert(other, stance);
+            }
+        }
+    }
+}
+
+fn is_raider(data: &Data, npc_id: NpcId, faction: FactionId) -> bool {
+    data.npcs.get(npc_id).map_or(false, |npc| {
+        !npc.is_dead
+            && npc.faction == Some(faction)
+            && matches!(
+                npc.profession(),
+                Some(Profession::Guard | Profession::Adventurer(_))
+            )
+    })
+}
+
+fn launch_raids(data: &mut Data, rng: &mut impl Rng) {
+    let mut new_raids = Vec::new();
+    for (faction_id, faction) in data.factions.iter() {
+        if faction.raids.len() >= MAX_RAIDS || !rng.gen_bool(RAID_CHANCE) {
+            continue;
+        }
+
+        // Find the closest enemy site to our own territory
+        let Some((target, target_wpos)) = data
+            .sites
+            .iter()
+            .filter(|(_, site)| {
+                site.faction
+                    .map_or(false, |owner| faction.is_at_war_with(owner))
+            })
+            .filter_map(|(site_id, site)| {
+                let dist = faction
+                    .territory
+                    .iter()
+                    .filter_map(|s| data.sites.get(*s))
+                    .map(|own| own.wpos.as_::<f32>().distance(site.wpos.as_()) as i32)
+                    .min()?;
+                Some((site_id, site.wpos, dist))
+            })
+            .filter(|(_, _, dist)| *dist < MAX_RAID_DIST)
+            .min_by_key(|(_, _, dist)| *dist)
+            .map(|(site_id, wpos, _)| (site_id, wpos))
+        else { continue };
+
+        // Only NPCs that live in our territory and aren't already busy can raid
+        let mut members = data
+            .npcs
+            .iter()
+            .filter(|(npc_id, npc)| {
+                is_raider(data, *npc_id, faction_id)
+                    && npc.home.map_or(false, |home| faction.territory.contains(&home))
+                    && faction.raid_of(*npc_id).is_none()
+            })
+            .map(|(npc_id, npc)| (npc_id, npc.wpos.xy().distance(target_wpos.as_()) as i32))
+            .collect::<Vec<_>>();
+        members.sort_by_key(|(_, dist)| *dist);
+
+        if members.len() >= MIN_RAID_SIZE {
+            new_raids.push((faction_id, Raid {
+                target,
+                members: members
+                    .into_iter()
+                    .take(MAX_RAID_SIZE)
+                    .map(|(npc_id, _)| npc_id)
+                    .collect(),
+                started_at: data.time_of_day.0,
+            }));
+        }
+    }
+
+    for (faction_id, raid) in new_raids {
+        info!(
+            "Faction {:?} is sending a raid of {} NPCs toward {:?}",
+            faction_id,
+            raid.members.len(),
+            raid.target
+        );
+        data.factions[faction_id].raids.push(raid);
+    }
+}
+
+enum RaidOutcome {
+    Ongoing,
+    Captured,
+    Repelled,
+    Abandoned,
+}
+
+fn resolve_raids(data: &mut Data) {
+    let mut outcomes = Vec::new();
+    for (faction_id, faction) in data.factions.iter() {
+        for (i, raid) in faction.raids.iter().enumerate() {
+            let members = raid
+                .members
+                .iter()
+                .filter(|npc_id| is_raider(data, **npc_id, faction_id))
+                .collect::<Vec<_>>();
+            let Some(site) = data.sites.get(raid.target) else {
+                outcomes.push((faction_id, i, RaidOutcome::Abandoned));
+                continue;
+            };
+
+            let outcome = if members.is_empty() {
+                RaidOutcome::Repelled
+            } else if !site
+                .faction
+                .map_or(false, |owner| faction.is_at_war_with(owner))
+                || data.time_of_day.0 - raid.started_at > RAID_TIMEOUT
+            {
+                RaidOutcome::Abandoned
+            } else {
+                let arrived = members
+                    .iter()
+                    .filter(|npc_id| data.npcs[***npc_id].current_site == Some(raid.target))
+                    .count();
+                // Wait until most of the raiding party has arrived before fighting
+                if arrived * 2 < members.len() {
+                    RaidOutcome::Ongoing
+                } else {
+                    let defenders = site
+                        .population
+                        .iter()
+                        .filter(|npc_id| {
+                            site.faction
+                                .map_or(false, |owner| is_raider(data, **npc_id, owner))
+                                && matches!(
+                                    data.npcs[**npc_id].profession(),
+                                    Some(Profession::Guard)
+                                )
+                        })
+                        .count();
+                    if arrived > defenders {
+                        RaidOutcome::Captured
+                    } else {
+                        RaidOutcome::Repelled
+                    }
+                }
+            };
+            outcomes.push((faction_id, i, outcome));
+        }
+    }
+
+    // Iterate in reverse so that raid indices remain valid as raids are removed
+    for (faction_id, i, outcome) in outcomes.into_iter().rev() {
+        let target = data.factions[faction_id].raids[i].target;
+        match outcome {
+            RaidOutcome::Ongoing => continue,
+            RaidOutcome::Captured => capture_site(data, faction_id, target),
+            RaidOutcome::Repelled => {
+                // A failed raid breeds resentment
+                if let Some(owner) = data.sites.get(target).and_then(|site| site.faction) {
+                    data.factions[faction_id]
+                        .sentiments
+                        .toward_mut(owner)
+                        .change_by(-0.1, Sentiment::VILLAIN);
+                }
+            },
+            RaidOutcome::Abandoned => {},
+        }
+        data.factions[faction_id].raids.remove(i);
+    }
+}
+
+/// Transfer ownership of a site to the given faction.
+fn capture_site(data: &mut Data, by: FactionId, site_id: SiteId) {
+    let Some(site) = data.sites.get_mut(site_id) else { return };
+    let from = site.faction.replace(by);
+    info!("Faction {:?} captured {:?} from {:?}", by, site_id, from);
+
+    // The residents of the site now answer to their new masters
+    for npc_id in site.population.iter() {
+        if let Some(npc) = data.npcs.get_mut(*npc_id)
+            && npc.faction == from
+        {
+            npc.faction = Some(by);
+        }
+    }
+
+    // Losing a site is not easily forgiven
+    if let Some(from) = from.and_then(|from| data.factions.get_mut(from)) {
+        from.sentiments
+            .toward_mut(by)
+            .change_by(-0.3, Sentiment::VILLAIN);
+    }
+
+    // Let the sites of both factions know what happened
+    let report = data.reports.create(Report {
+        kind: ReportKind::SiteCaptured {
+            site: site_id,
+            by,
+            from,
+        },
+        at: data.time_of_day,
+    });
+    for site in data.sites.values_mut().filter(|site| {
+        site.faction == Some(by) || (from.is_some() && site.faction == from)
+    }) {
+        site.known_reports.insert(report);
+    }
+
+    update_territory(data);
+}
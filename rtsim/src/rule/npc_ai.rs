@@ -1,10 +1,14 @@
 use std::hash::BuildHasherDefault;
 
 use crate::{
-    ai::{casual, choose, finish, important, just, now, seq, until, Action, NpcCtx},
+    ai::{
+        casual, choose, finish, important, just, now,
+        schedule::{Activity, Place, Schedule},
+        seq, until, Action, NpcCtx,
+    },
     data::{
         npc::{Brain, PathData, SimulationMode},
-        ReportKind, Sentiment, Sites,
+        Data, Npc, ReportKind, Sentiment, Sites,
     },
     event::OnTick,
     RtState, Rule, RuleError,
@@ -21,7 +25,7 @@ use common::{
     spiral::Spiral2d,
     store::Id,
     terrain::{CoordinateConversions, SiteKindMeta, TerrainChunkSize},
-    time::DayPeriod,
+    weather::{WeatherGrid, WeatherKind},
 };
 use fxhash::FxHasher64;
 use itertools::{Either, Itertools};
@@ -552,6 +556,22 @@ fn timeout(time: f64) -> impl FnMut(&mut NpcCtx) -> bool + Clone + Send + Sync {
     move |ctx| ctx.time.0 > *timeout.get_or_insert(ctx.time.0 + time)
 }
 
+/// The reputation that an actor has with an NPC's home site or faction,
+/// whichever is held more strongly. Only player characters have a reputation.
+fn reputation_of(data: &Data, npc: &Npc, actor: Actor) -> f32 {
+    let Actor::Character(character) = actor else { return 0.0 };
+    let Some(reputation) = data.reputations.of(character) else { return 0.0 };
+    let with_site = npc.home.map_or(0.0, |site| reputation.toward_site(site));
+    let with_faction = npc
+        .faction
+        .map_or(0.0, |faction| reputation.toward_faction(faction));
+    if with_site.abs() >= with_faction.abs() {
+        with_site
+    } else {
+        with_faction
+    }
+}
+
 fn talk_to(tgt: Actor, _subject: Option<Subject>) -> impl Action {
     now(move |ctx| {
         if matches!(tgt, Actor::Npc(_)) && ctx.rng.gen_bool(0.2) {
@@ -560,8 +580,14 @@ fn talk_to(tgt: Actor, _subject: Option<Subject>) -> impl Action {
             // some sort of 'bored of conversation' system
             idle().l()
         } else {
+            let reputation = reputation_of(&ctx.state.data(), ctx.npc, tgt);
+            // Let characters know what we think of them
+            let comment = if reputation <= Sentiment::RIVAL {
+                Content::localized("npc-speech-reputation_hostile")
+            } else if reputation >= Sentiment::ALLY && ctx.rng.gen_bool(0.5) {
+                Content::localized("npc-speech-reputation_friendly")
             // Mention nearby sites
-            let comment = if ctx.rng.gen_bool(0.3)
+            } else if ctx.rng.gen_bool(0.3)
                 && let Some(current_site) = ctx.npc.current_site
                 && let Some(current_site) = ctx.state.data().sites.get(current_site)
                 && let Some(mention_site) = current_site.nearby_sites_by_size.choose(&mut ctx.rng)
@@ -732,6 +758,120 @@ fn choose_plaza(ctx: &mut NpcCtx, site: SiteId) -> Option<Vec2<f32>> {
         })
 }
 
+/// Find farmland on the outskirts of a site: open land that is neither
+/// forested nor part of the site itself.
+fn find_field(ctx: &mut NpcCtx, site: SiteId) -> Option<Vec2<f32>> {
+    let site_wpos = ctx.state.data().sites.get(site)?.wpos;
+    let chunk_pos = site_wpos.wpos_to_cpos();
+    Spiral2d::new()
+        .skip(ctx.rng.gen_range(1..=8))
+        .take(49)
+        .map(|rpos| chunk_pos + rpos)
+        .find(|cpos| {
+            ctx.world.sim().get(*cpos).map_or(false, |c| {
+                c.tree_density < 0.25 && c.sites.is_empty() && !c.is_underwater()
+            })
+        })
+        .map(|chunk| TerrainChunkSize::center_wpos(chunk).as_())
+}
+
+/// Find a place in (or near) a site at which to perform a scheduled activity.
+fn find_place(ctx: &mut NpcCtx, site: SiteId, place: Place) -> Option<Vec2<f32>> {
+    let index = ctx.index;
+    let site2 = ctx
+        .state
+        .data()
+        .sites
+        .get(site)
+        .and_then(|site| index.sites.get(site.world_site?).site2());
+    let plot_wpos = |site2: &site2::Site, plot: &site2::Plot| -> Vec2<f32> {
+        site2.tile_center_wpos(plot.root_tile()).as_()
+    };
+
+    match place {
+        Place::Home => {
+            let site2 = site2?;
+            // Always return to the same house, so that each NPC has a bed to call their own
+            let houses = site2
+                .plots()
+                .filter(|p| matches!(p.kind(), PlotKind::House(_)))
+                .collect::<Vec<_>>();
+            let house = houses.get(ctx.npc.seed as usize % houses.len().max(1))?;
+            Some(plot_wpos(site2, house))
+        },
+        Place::Tavern => {
+            let site2 = site2?;
+            // TODO: There are no taverns in sites yet, so we treat the house nearest to
+            // the main plaza as one
+            let plaza = site2.plot(site2.plazas().next()?).root_tile();
+            site2
+                .plots()
+                .filter(|p| matches!(p.kind(), PlotKind::House(_)))
+                .min_by_key(|p| p.root_tile().distance_squared(plaza))
+                .map(|house| plot_wpos(site2, house))
+        },
+        Place::Forge => site2
+            .and_then(|site2| {
+                let workshop = site2
+                    .plots()
+                    .filter(|p| matches!(p.kind(), PlotKind::Workshop(_)))
+                    .choose(&mut ctx.rng)?;
+                Some(plot_wpos(site2, workshop))
+            })
+            .or_else(|| choose_plaza(ctx, site)),
+        Place::Plaza | Place::Market => choose_plaza(ctx, site),
+        Place::Field => find_field(ctx, site),
+        Place::Forest => find_forest(ctx),
+    }
+}
+
+/// The activity that the NPC's schedule says they should be doing right now.
+fn scheduled_activity(ctx: &NpcCtx) -> Activity {
+    Schedule::for_profession(ctx.npc.profession().as_ref())
+        .read()
+        .activity_at(ctx.time_of_day)
+        .unwrap_or(Activity::Socialize)
+}
+
+fn is_raining(ctx: &NpcCtx) -> bool {
+    let weather = ctx
+        .state
+        .resource::<WeatherGrid>()
+        .get_interpolated(ctx.npc.wpos.xy());
+    matches!(weather.get_kind(), WeatherKind::Rain | WeatherKind::Storm)
+}
+
+fn gather_crops() -> impl Action {
+    just(|ctx| {
+        ctx.controller
+            .do_gather(&[ChunkResource::Plant, ChunkResource::Vegetable][..])
+    })
+    .debug(|| "gather crops")
+}
+
+fn sell_wares() -> impl Action {
+    just(|ctx| {
+        // Try to direct our speech at nearby actors, if there are any
+        let (target, phrase) = if ctx.rng.gen_bool(0.3) && let Some(other) = ctx
+            .state
+            .data()
+            .npcs
+            .nearby(Some(ctx.npc_id), ctx.npc.wpos, 8.0)
+            .choose(&mut ctx.rng)
+        {
+            (Some(other), "npc-speech-merchant_sell_directed")
+        } else {
+            // Otherwise, resort to generic expressions
+            (None, "npc-speech-merchant_sell_undirected")
+        };
+
+        ctx.controller.say(target, Content::localized(phrase));
+    })
+    .then(idle().repeat().stop_if(timeout(8.0)))
+    .debug(|| "sell wares")
+    .map(|_| ())
+}
+
 fn villager(visiting_site: SiteId) -> impl Action {
     choose(move |ctx| {
         // Consider moving home if the home site gets too full
@@ -778,119 +918,111 @@ fn villager(visiting_site: SiteId) -> impl Action {
                 .then(just(move |ctx| ctx.controller.set_new_home(new_home))));
         }
 
-        if DayPeriod::from(ctx.time_of_day.0).is_dark()
-            && !matches!(ctx.npc.profession(), Some(Profession::Guard))
+        let activity = scheduled_activity(ctx);
+
+        // Head indoors if it starts raining
+        if is_raining(ctx)
+            && Schedule::for_profession(ctx.npc.profession().as_ref()).read().shelter_from_rain
+            && !matches!(activity, Activity::Sleep)
         {
-            return important(
-                now(move |ctx| {
-                    if let Some(house_wpos) = ctx
-                        .state
-                        .data()
-                        .sites
-                        .get(visiting_site)
-                        .and_then(|site| ctx.index.sites.get(site.world_site?).site2())
-                        .and_then(|site2| {
-                            // Find a house in the site we're visiting
-                            let house = site2
-                                .plots()
-                                .filter(|p| matches!(p.kind(), PlotKind::House(_)))
-                                .choose(&mut ctx.rng)?;
-                            Some(site2.tile_center_wpos(house.root_tile()).as_())
-                        })
-                    {
+            if let Some(shelter_wpos) = find_place(ctx, visiting_site, Place::Home) {
+                return important(
+                    just(|ctx| {
+                        ctx.controller
+                            .say(None, Content::localized("npc-speech-rain_shelter"))
+                    })
+                    .then(travel_to_point(shelter_wpos, 0.8))
+                    .debug(|| "run for shelter")
+                    .then(socialize().repeat().debug(|| "wait out the rain"))
+                    .stop_if(|ctx| !is_raining(ctx))
+                    .map(|_| ()),
+                );
+            }
+        }
+
+        let action = match activity {
+            Activity::Sleep => {
+                // Sleep in our own house, even when we're visiting another site
+                let home_site = ctx.npc.home.unwrap_or(visiting_site);
+                if let Some(house_wpos) = find_place(ctx, home_site, Place::Home) {
+                    let go_home = if home_site == visiting_site {
+                        Either::Left(finish())
+                    } else {
+                        Either::Right(travel_to_site(home_site, 0.65))
+                    };
+                    return important(
                         just(|ctx| {
                             ctx.controller
                                 .say(None, Content::localized("npc-speech-night_time"))
                         })
+                        .then(go_home)
                         .then(travel_to_point(house_wpos, 0.65))
                         .debug(|| "walk to house")
-                        .then(socialize().repeat().debug(|| "wait in house"))
-                        .stop_if(|ctx| DayPeriod::from(ctx.time_of_day.0).is_light())
+                        .then(socialize().repeat().debug(|| "sleep in house"))
+                        .stop_if(move |ctx| scheduled_activity(ctx) != activity)
                         .then(just(|ctx| {
                             ctx.controller
                                 .say(None, Content::localized("npc-speech-day_time"))
                         }))
                         .map(|_| ())
-                        .boxed()
-                    } else {
-                        finish().boxed()
-                    }
-                })
-                .debug(|| "find somewhere to sleep"),
-            );
-        // Villagers with roles should perform those roles
-        } else if matches!(ctx.npc.profession(), Some(Profession::Herbalist)) && ctx.rng.gen_bool(0.8)
-        {
-            if let Some(forest_wpos) = find_forest(ctx) {
-                return casual(
-                    travel_to_point(forest_wpos, 0.5)
-                        .debug(|| "walk to forest")
-                        .then({
-                            let wait_time = ctx.rng.gen_range(10.0..30.0);
-                            gather_ingredients().repeat().stop_if(timeout(wait_time))
-                        })
-                        .map(|_| ()),
-                );
-            }
-        } else if matches!(ctx.npc.profession(), Some(Profession::Hunter)) && ctx.rng.gen_bool(0.8) {
-            if let Some(forest_wpos) = find_forest(ctx) {
-                return casual(
-                    just(|ctx| {
+                        .debug(|| "find somewhere to sleep"),
+                    );
+                }
+                None
+            },
+            Activity::Eat => find_place(ctx, visiting_site, Place::Tavern).map(|tavern_wpos| {
+                travel_to_point(tavern_wpos, 0.6)
+                    .debug(|| "walk to tavern")
+                    .then(socialize().repeat().debug(|| "eat at tavern"))
+                    .map(|_| ())
+                    .boxed()
+            }),
+            Activity::Work(place) => {
+                let work = match (place, ctx.npc.profession()) {
+                    (Place::Field, _) => gather_crops().repeat().map(|_| ()).boxed(),
+                    (Place::Forest, Some(Profession::Hunter)) => just(|ctx| {
                         ctx.controller
                             .say(None, Content::localized("npc-speech-start_hunting"))
                     })
-                    .then(travel_to_point(forest_wpos, 0.75))
-                    .debug(|| "walk to forest")
-                    .then({
-                        let wait_time = ctx.rng.gen_range(30.0..60.0);
-                        hunt_animals().repeat().stop_if(timeout(wait_time))
+                    .then(hunt_animals().repeat())
+                    .map(|_| ())
+                    .boxed(),
+                    (Place::Forest, _) => gather_ingredients().repeat().map(|_| ()).boxed(),
+                    (Place::Market, _) => sell_wares().repeat().map(|_| ()).boxed(),
+                    _ => socialize().repeat().map(|_| ()).boxed(),
+                };
+                find_place(ctx, visiting_site, place).map(|work_wpos| {
+                    travel_to_point(work_wpos, 0.6)
+                        .debug(move || format!("walk to work at {:?}", place))
+                        .then(work.debug(move || format!("work at {:?}", place)))
+                        .map(|_| ())
+                        .boxed()
+                })
+            },
+            Activity::Patrol => choose_plaza(ctx, visiting_site).map(|plaza_wpos| {
+                travel_to_point(plaza_wpos, 0.4)
+                    .debug(|| "patrol")
+                    .interrupt_with(|ctx| {
+                        if ctx.rng.gen_bool(0.0003) {
+                            Some(just(move |ctx| {
+                                ctx.controller
+                                    .say(None, Content::localized("npc-speech-guard_thought"))
+                            }))
+                        } else {
+                            None
+                        }
                     })
-                    .map(|_| ()),
-                );
-            }
-        } else if matches!(ctx.npc.profession(), Some(Profession::Guard)) && ctx.rng.gen_bool(0.7) {
-            if let Some(plaza_wpos) = choose_plaza(ctx, visiting_site) {
-                return casual(
-                    travel_to_point(plaza_wpos, 0.4)
-                        .debug(|| "patrol")
-                        .interrupt_with(|ctx| {
-                            if ctx.rng.gen_bool(0.0003) {
-                                Some(just(move |ctx| {
-                                    ctx.controller
-                                        .say(None, Content::localized("npc-speech-guard_thought"))
-                                }))
-                            } else {
-                                None
-                            }
-                        })
-                        .map(|_| ()),
-                );
-            }
-        } else if matches!(ctx.npc.profession(), Some(Profession::Merchant)) && ctx.rng.gen_bool(0.8)
-        {
-            return casual(
-                just(|ctx| {
-                    // Try to direct our speech at nearby actors, if there are any
-                    let (target, phrase) = if ctx.rng.gen_bool(0.3) && let Some(other) = ctx
-                        .state
-                        .data()
-                        .npcs
-                        .nearby(Some(ctx.npc_id), ctx.npc.wpos, 8.0)
-                        .choose(&mut ctx.rng)
-                    {
-                        (Some(other), "npc-speech-merchant_sell_directed")
-                    } else {
-                        // Otherwise, resort to generic expressions
-                        (None, "npc-speech-merchant_sell_undirected")
-                    };
+                    .map(|_| ())
+                    .boxed()
+            }),
+            Activity::Socialize => None,
+        };
 
-                    ctx.controller.say(target, Content::localized(phrase));
-                })
-                .then(idle().repeat().stop_if(timeout(8.0)))
-                .repeat()
-                .stop_if(timeout(60.0))
-                .debug(|| "sell wares")
-                .map(|_| ()),
+        if let Some(action) = action {
+            return casual(
+                action
+                    .stop_if(move |ctx| scheduled_activity(ctx) != activity)
+                    .map(|_| ()),
             );
         }
 
@@ -911,7 +1043,9 @@ fn villager(visiting_site: SiteId) -> impl Action {
                     .stop_if(timeout(ctx.rng.gen_range(30.0..90.0)))
                     .debug(|| "wait at plaza"))
                 .map(|_| ())
-        }))
+        })
+        .stop_if(move |ctx| scheduled_activity(ctx) != activity)
+        .map(|_| ()))
     })
     .debug(move || format!("villager at site {:?}", visiting_site))
 }
@@ -1013,9 +1147,25 @@ fn check_inbox(ctx: &mut NpcCtx) -> Option<impl Action> {
                     {
                         // TODO: Don't report self
                         let phrase = if let Some(killer) = killer {
-                            // TODO: For now, we don't make sentiment changes if the killer was an
-                            // NPC because NPCs can't hurt one-another.
-                            // This should be changed in the future.
+                            // The killing of a fellow faction member by a member of another
+                            // faction sours our opinion of that faction
+                            let data = ctx.state.data();
+                            let faction_of = |actor: Actor| actor.npc()
+                                .and_then(|npc| data.npcs.get(npc))
+                                .and_then(|npc| npc.faction);
+                            if let Some(our_faction) = ctx.npc.faction
+                                && faction_of(actor) == Some(our_faction)
+                                && let Some(killer_faction) = faction_of(killer)
+                                && killer_faction != our_faction
+                            {
+                                ctx.sentiments
+                                    .toward_mut(killer_faction)
+                                    .change_by(-0.2, Sentiment::VILLAIN);
+                            }
+
+                            // TODO: For now, we don't make sentiment changes toward individual
+                            // killers if the killer was an NPC because NPCs rarely hurt
+                            // one-another. This should be changed in the future.
                             if !matches!(killer, Actor::Npc(_)) {
                                 // TODO: Don't hard-code sentiment change
                                 let mut change = -0.7;
@@ -1048,15 +1198,40 @@ fn check_inbox(ctx: &mut NpcCtx) -> Option<impl Action> {
                         ctx.known_reports.insert(report_id);
                         break Some(
                             just(move |ctx| ctx.controller.say(killer, Content::localized(phrase)))
-                                .l(),
+                                .boxed(),
+                        );
+                    },
+                    Some(ReportKind::SiteCaptured { site, by, from })
+                        if ctx.npc.home == Some(site)
+                            || ctx
+                                .npc
+                                .faction
+                                .map_or(false, |f| f == by || Some(f) == from) =>
+                    {
+                        let phrase = if ctx.npc.faction == Some(by) {
+                            "npc-speech-site_captured_victory"
+                        } else {
+                            // Losing a site to another faction is a humiliation
+                            ctx.sentiments
+                                .toward_mut(by)
+                                .change_by(-0.3, Sentiment::VILLAIN);
+                            "npc-speech-site_captured_defeat"
+                        };
+                        ctx.known_reports.insert(report_id);
+                        break Some(
+                            just(move |ctx| ctx.controller.say(None, Content::localized(phrase)))
+                                .boxed(),
                         );
                     },
-                    Some(ReportKind::Death { .. }) => {}, // We don't care about death
-                    None => {},                           // Stale report, ignore
+                    Some(ReportKind::Death { .. }) => {},        // We don't care about death
+                    Some(ReportKind::SiteCaptured { .. }) => {}, // Not our concern
+                    None => {},                                  // Stale report, ignore
                 }
             },
             Some(NpcInput::Report(_)) => {}, // Reports we already know of are ignored
-            Some(NpcInput::Interaction(by, subject)) => break Some(talk_to(by, Some(subject)).r()),
+            Some(NpcInput::Interaction(by, subject)) => {
+                break Some(talk_to(by, Some(subject)).boxed());
+            },
             None => break None,
         }
     }
@@ -1069,12 +1244,29 @@ fn check_for_enemies(ctx: &mut NpcCtx) -> Option<impl Action> {
     // implementing this means accounting for changes in sentiment (that could
     // suddenly make a nearby actor an enemy) as well as variable NPC tick
     // rates!
-    ctx.state
-        .data()
+    let data = ctx.state.data();
+    // Fighters will also attack members of factions that we're at war with
+    let is_fighter = matches!(
+        ctx.npc.profession(),
+        Some(Profession::Guard | Profession::Adventurer(_))
+    );
+    // Guards will not tolerate characters that are disliked by those they protect
+    let is_guard = matches!(ctx.npc.profession(), Some(Profession::Guard));
+    let enemy = data
         .npcs
         .nearby(Some(ctx.npc_id), ctx.npc.wpos, 24.0)
-        .find(|actor| ctx.sentiments.toward(*actor).is(Sentiment::ENEMY))
-        .map(|enemy| just(move |ctx| ctx.controller.attack(enemy)))
+        .find(|actor| {
+            ctx.sentiments.toward(*actor).is(Sentiment::ENEMY)
+                || (is_fighter
+                    && actor
+                        .npc()
+                        .and_then(|npc| data.npcs.get(npc))
+                        .map_or(false, |other| {
+                            data.factions.are_at_war(ctx.npc.faction, other.faction)
+                        }))
+                || (is_guard && reputation_of(&data, ctx.npc, *actor) <= Sentiment::RIVAL)
+        });
+    enemy.map(|enemy| just(move |ctx| ctx.controller.attack(enemy)))
 }
 
 fn react_to_events(ctx: &mut NpcCtx) -> Option<impl Action> {
@@ -1083,6 +1275,35 @@ fn react_to_events(ctx: &mut NpcCtx) -> Option<impl Action> {
         .or_else(|| check_for_enemies(ctx).map(|action| action.boxed()))
 }
 
+/// The site that the NPC's faction has sent it to raid, if any.
+fn raid_target(ctx: &NpcCtx) -> Option<SiteId> {
+    let data = ctx.state.data();
+    let faction = data.factions.get(ctx.npc.faction?)?;
+    faction.raid_of(ctx.npc_id).map(|raid| raid.target)
+}
+
+fn raid(target: SiteId) -> impl Action {
+    just(|ctx| {
+        ctx.controller
+            .say(None, Content::localized("npc-speech-start_raid"))
+    })
+    .then(travel_to_site(target, 0.8))
+    // Once we've arrived, roam the site looking for trouble
+    .then(
+        now(move |ctx| {
+            if let Some(plaza_wpos) = choose_plaza(ctx, target) {
+                travel_to_point(plaza_wpos, 0.6).boxed()
+            } else {
+                idle().repeat().stop_if(timeout(10.0)).map(|_| ()).boxed()
+            }
+        })
+        .repeat(),
+    )
+    .stop_if(move |ctx| raid_target(ctx) != Some(target))
+    .debug(move || format!("raiding site {:?}", target))
+    .map(|_| ())
+}
+
 fn humanoid() -> impl Action {
     choose(|ctx| {
         if let Some(riding) = &ctx.npc.riding {
@@ -1102,6 +1323,8 @@ fn humanoid() -> impl Action {
             } else {
                 important(socialize())
             }
+        } else if let Some(target) = raid_target(ctx) {
+            important(raid(target).interrupt_with(react_to_events))
         } else {
             let action = if matches!(
                 ctx.npc.profession(),
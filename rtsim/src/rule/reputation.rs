@@ -0,0 +1,92 @@
+use crate::{
+    data::{Data, Reputation},
+    event::{EventCtx, OnTick},
+    RtState, Rule, RuleError,
+};
+use common::{
+    character::CharacterId,
+    rtsim::{FactionId, SiteId},
+};
+use hashbrown::HashMap;
+
+/// How many ticks should pass between recalculations of character reputations.
+const REPUTATION_TICK_SKIP: u64 = 150;
+/// A number of 'imaginary' neutral NPCs that are included when averaging the
+/// opinions of NPCs, such that the opinion of a single NPC does not make or
+/// break a character's reputation with an entire site.
+const NEUTRAL_PRIOR: f32 = 4.0;
+
+/// A rule that aggregates the sentiments that NPCs (and factions) feel toward
+/// player characters into a reputation with each faction and site.
+pub struct AggregateReputation;
+
+impl Rule for AggregateReputation {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnTick>(on_tick);
+
+        Ok(Self)
+    }
+}
+
+fn on_tick(ctx: EventCtx<AggregateReputation, OnTick>) {
+    if ctx.event.tick % REPUTATION_TICK_SKIP != 0 {
+        return;
+    }
+
+    let data = &mut *ctx.state.data_mut();
+    data.reputations.characters = calculate_reputations(data);
+}
+
+fn calculate_reputations(data: &Data) -> HashMap<CharacterId, Reputation> {
+    // Tally up the opinions that NPCs have of each character. Only a handful of
+    // the NPCs in a site will ever meet a given character, so only those that
+    // have an opinion are counted.
+    let mut faction_totals = HashMap::<(CharacterId, FactionId), (f32, usize)>::new();
+    let mut site_totals = HashMap::<(CharacterId, SiteId), (f32, usize)>::new();
+    for npc in data.npcs.values().filter(|npc| !npc.is_dead) {
+        for (character, sentiment) in npc.sentiments.characters() {
+            if let Some(faction) = npc.faction {
+                let total = faction_totals.entry((character, faction)).or_default();
+                total.0 += sentiment.value();
+                total.1 += 1;
+            }
+            if let Some(home) = npc.home {
+                let total = site_totals.entry((character, home)).or_default();
+                total.0 += sentiment.value();
+                total.1 += 1;
+            }
+        }
+    }
+
+    let average = |(total, count): (f32, usize)| total / (count as f32 + NEUTRAL_PRIOR);
+    let mut reputations = HashMap::<CharacterId, Reputation>::new();
+    for ((character, site), total) in site_totals {
+        reputations
+            .entry(character)
+            .or_default()
+            .sites
+            .insert(site, average(total));
+    }
+    for ((character, faction), total) in faction_totals {
+        reputations
+            .entry(character)
+            .or_default()
+            .factions
+            .insert(faction, average(total));
+    }
+    // Factions also have a collective memory of characters, independent of that of
+    // their members
+    for (faction_id, faction) in data.factions.iter() {
+        for (character, sentiment) in faction.sentiments.characters() {
+            let value = reputations
+                .entry(character)
+                .or_default()
+                .factions
+                .entry(faction_id)
+                .or_default();
+            *value = (*value + sentiment.value()).clamp(-1.0, 1.0);
+        }
+    }
+
+    reputations
+}
@@ -0,0 +1,460 @@
+//! Rules that are described by assets rather than code, allowing world events
+//! to be added without recompiling.
+//!
+//! Each file in `common.rtsim.script` describes a single [`Script`]: when it
+//! should be run (its [`Trigger`]), which NPCs it applies to (its
+//! [`Condition`]s) and what it does (its [`Effect`]s). For example, the
+//! following script makes the neighbours of a murdered NPC resent the killer:
+//!
+//! ```ron
+//! (
+//!     trigger: Death,
+//!     conditions: [Role(Civilised), KilledByCharacter],
+//!     effects: [
+//!         ChangeSentiment(who: Residents, toward: Killer, change: -0.1, cap: 0.8),
+//!     ],
+//! )
+//! ```
+
+use crate::{
+    data::{npc::Npc, sentiment::Target, Data},
+    event::{EventCtx, OnDeath, OnSetup, OnTick},
+    RtState, Rule, RuleError,
+};
+use common::{
+    assets::{self, AssetExt, AssetHandle},
+    comp::{self, Content},
+    rtsim::{Actor, NpcId, Personality, Profession, Role},
+};
+use rand::prelude::*;
+use rand_chacha::ChaChaRng;
+use serde::Deserialize;
+use tracing::{error, info};
+use world::{IndexRef, World};
+
+/// The asset directory from which scripts are loaded.
+const SCRIPT_DIR: &str = "common.rtsim.script";
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Script {
+    pub trigger: Trigger,
+    /// Conditions that the subject of the script must meet for its effects to
+    /// take place. All conditions must hold.
+    #[serde(default)]
+    pub conditions: Vec<Condition>,
+    pub effects: Vec<Effect>,
+}
+
+impl assets::Asset for Script {
+    type Loader = assets::RonLoader;
+
+    const EXTENSION: &'static str = "ron";
+}
+
+/// When a script is run, and for which NPC (the 'subject' of the script).
+#[derive(Clone, Debug, Deserialize)]
+pub enum Trigger {
+    /// Run once when rtsim starts, for every living NPC.
+    Setup,
+    /// Run every `every` ticks, for every living NPC.
+    Tick { every: u64 },
+    /// Run when an NPC dies, for the NPC that died.
+    Death,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub enum Condition {
+    /// The subject has the given role.
+    Role(RoleKind),
+    /// The subject has the given profession.
+    Profession(ProfessionKind),
+    /// The subject's home is the site with the given name.
+    HomeNamed(String),
+    /// The subject is currently within their home site.
+    AtHome,
+    /// Fewer than the given number of NPCs live in the subject's home site.
+    HomePopulationBelow(usize),
+    /// The time of day is between the given hours (`0.0..24.0`). The range may
+    /// wrap around midnight.
+    Hours(f32, f32),
+    /// The subject was killed by a player character.
+    KilledByCharacter,
+    /// Holds with the given probability.
+    Chance(f64),
+    Not(Box<Condition>),
+    Any(Vec<Condition>),
+}
+
+/// Something that a script does to the world.
+#[derive(Clone, Debug, Deserialize)]
+pub enum Effect {
+    /// The subject says something (given as a localisation key).
+    Say(String),
+    /// Change the sentiment that some NPCs feel toward a target.
+    ChangeSentiment {
+        who: Feelers,
+        toward: SentimentTarget,
+        change: f32,
+        cap: f32,
+    },
+    /// Spawn new NPCs at the subject's home site, belonging to the subject's
+    /// faction.
+    SpawnNpc {
+        profession: Option<ProfessionKind>,
+        count: u32,
+    },
+}
+
+/// Those whose feelings are changed by [`Effect::ChangeSentiment`].
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub enum Feelers {
+    Subject,
+    /// The subject's faction, as a whole.
+    Faction,
+    /// The other residents of the subject's home site.
+    Residents,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub enum SentimentTarget {
+    Subject,
+    /// The faction of the subject.
+    SubjectFaction,
+    /// Whoever killed the subject.
+    Killer,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub enum RoleKind {
+    Civilised,
+    Wild,
+    Monster,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub enum ProfessionKind {
+    Farmer,
+    Hunter,
+    Merchant,
+    Guard,
+    Adventurer,
+    Blacksmith,
+    Chef,
+    Alchemist,
+    Pirate,
+    Cultist,
+    Herbalist,
+    Captain,
+}
+
+impl RoleKind {
+    fn matches(&self, role: &Role) -> bool {
+        matches!(
+            (self, role),
+            (Self::Civilised, Role::Civilised(_))
+                | (Self::Wild, Role::Wild)
+                | (Self::Monster, Role::Monster)
+        )
+    }
+}
+
+impl ProfessionKind {
+    fn matches(&self, profession: &Profession) -> bool {
+        matches!(
+            (self, profession),
+            (Self::Farmer, Profession::Farmer)
+                | (Self::Hunter, Profession::Hunter)
+                | (Self::Merchant, Profession::Merchant)
+                | (Self::Guard, Profession::Guard)
+                | (Self::Adventurer, Profession::Adventurer(_))
+                | (Self::Blacksmith, Profession::Blacksmith)
+                | (Self::Chef, Profession::Chef)
+                | (Self::Alchemist, Profession::Alchemist)
+                | (Self::Pirate, Profession::Pirate)
+                | (Self::Cultist, Profession::Cultist)
+                | (Self::Herbalist, Profession::Herbalist)
+                | (Self::Captain, Profession::Captain)
+        )
+    }
+
+    fn to_profession(self, rng: &mut impl Rng) -> Profession {
+        match self {
+            Self::Farmer => Profession::Farmer,
+            Self::Hunter => Profession::Hunter,
+            Self::Merchant => Profession::Merchant,
+            Self::Guard => Profession::Guard,
+            Self::Adventurer => Profession::Adventurer(rng.gen_range(0..=3)),
+            Self::Blacksmith => Profession::Blacksmith,
+            Self::Chef => Profession::Chef,
+            Self::Alchemist => Profession::Alchemist,
+            Self::Pirate => Profession::Pirate,
+            Self::Cultist => Profession::Cultist,
+            Self::Herbalist => Profession::Herbalist,
+            Self::Captain => Profession::Captain,
+        }
+    }
+}
+
+/// The NPC that a script is being run for, along with details of the event
+/// that triggered it.
+#[derive(Copy, Clone)]
+struct Subject {
+    npc_id: NpcId,
+    killer: Option<Actor>,
+}
+
+struct ScriptCtx<'a, R> {
+    data: &'a mut Data,
+    world: &'a World,
+    index: IndexRef<'a>,
+    rng: &'a mut R,
+}
+
+impl Condition {
+    fn holds(&self, ctx: &mut ScriptCtx<impl Rng>, subject: Subject) -> bool {
+        let Some(npc) = ctx.data.npcs.get(subject.npc_id) else { return false };
+        match self {
+            Self::Role(kind) => kind.matches(&npc.role),
+            Self::Profession(kind) => npc
+                .profession()
+                .map_or(false, |profession| kind.matches(&profession)),
+            Self::HomeNamed(name) => npc
+                .home
+                .and_then(|home| ctx.data.sites.get(home))
+                .and_then(|site| site.world_site)
+                .map_or(false, |ws| ctx.index.sites.get(ws).name() == name),
+            Self::AtHome => npc.home.is_some() && npc.current_site == npc.home,
+            Self::HomePopulationBelow(max) => npc
+                .home
+                .and_then(|home| ctx.data.sites.get(home))
+                .map_or(false, |site| site.population.len() < *max),
+            Self::Hours(from, to) => {
+                let hour =
+                    (ctx.data.time_of_day.0.rem_euclid(60.0 * 60.0 * 24.0) / (60.0 * 60.0)) as f32;
+                if from <= to {
+                    (*from..*to).contains(&hour)
+                } else {
+                    hour >= *from || hour < *to
+                }
+            },
+            Self::KilledByCharacter => matches!(subject.killer, Some(Actor::Character(_))),
+            Self::Chance(chance) => ctx.rng.gen_bool(chance.clamp(0.0, 1.0)),
+            Self::Not(condition) => !condition.holds(ctx, subject),
+            Self::Any(conditions) => conditions
+                .iter()
+                .any(|condition| condition.holds(ctx, subject)),
+        }
+    }
+}
+
+impl Effect {
+    fn apply(&self, ctx: &mut ScriptCtx<impl Rng>, subject: Subject) {
+        let Some(npc) = ctx.data.npcs.get(subject.npc_id) else { return };
+        let (home, faction) = (npc.home, npc.faction);
+        match self {
+            Self::Say(key) => {
+                if let Some(npc) = ctx.data.npcs.get_mut(subject.npc_id)
+                    && !npc.is_dead
+                {
+                    npc.controller.say(None, Content::localized(key));
+                }
+            },
+            Self::ChangeSentiment {
+                who,
+                toward,
+                change,
+                cap,
+            } => {
+                let target = match toward {
+                    SentimentTarget::Subject => Some(Target::Npc(subject.npc_id)),
+                    SentimentTarget::SubjectFaction => faction.map(Target::Faction),
+                    SentimentTarget::Killer => subject.killer.map(Target::from),
+                };
+                let Some(target) = target else { return };
+                match who {
+                    Feelers::Subject => {
+                        if let Some(npc) = ctx.data.npcs.get_mut(subject.npc_id) {
+                            npc.sentiments.toward_mut(target).change_by(*change, *cap);
+                        }
+                    },
+                    Feelers::Faction => {
+                        if let Some(faction) = faction.and_then(|f| ctx.data.factions.get_mut(f)) {
+                            faction.sentiments.toward_mut(target).change_by(*change, *cap);
+                        }
+                    },
+                    Feelers::Residents => {
+                        let Some(home) = home else { return };
+                        ctx.data
+                            .npcs
+                            .iter_mut()
+                            .filter(|(npc_id, npc)| {
+                                *npc_id != subject.npc_id && !npc.is_dead && npc.home == Some(home)
+                            })
+                            .for_each(|(_, npc)| {
+                                npc.sentiments.toward_mut(target).change_by(*change, *cap)
+                            });
+                    },
+                }
+            },
+            Self::SpawnNpc { profession, count } => {
+                let Some(site_wpos) = home
+                    .and_then(|home| ctx.data.sites.get(home))
+                    .map(|site| site.wpos)
+                else { return };
+                for _ in 0..*count {
+                    let wpos2d = site_wpos.map(|e| e + ctx.rng.gen_range(-16..16));
+                    let wpos = wpos2d
+                        .map(|e| e as f32 + 0.5)
+                        .with_z(ctx.world.sim().get_alt_approx(wpos2d).unwrap_or(0.0));
+                    let species = comp::humanoid::ALL_SPECIES.choose(ctx.rng).unwrap();
+                    let body = comp::Body::Humanoid(comp::humanoid::Body::random_with(
+                        ctx.rng, species,
+                    ));
+                    let role =
+                        Role::Civilised(profession.map(|kind| kind.to_profession(ctx.rng)));
+                    let npc = Npc::new(ctx.rng.gen(), wpos, body, role)
+                        .with_faction(faction)
+                        .with_home(home)
+                        .with_personality(Personality::random(ctx.rng));
+                    ctx.data.spawn_npc(npc);
+                }
+            },
+        }
+    }
+}
+
+fn run_script(ctx: &mut ScriptCtx<impl Rng>, script: &Script, subject: Subject) {
+    if script
+        .conditions
+        .iter()
+        .all(|condition| condition.holds(ctx, subject))
+    {
+        for effect in &script.effects {
+            effect.apply(ctx, subject);
+        }
+    }
+}
+
+/// A rule that runs the scripts found in `common.rtsim.script`.
+pub struct ScriptedRules {
+    scripts: Vec<AssetHandle<Script>>,
+}
+
+impl ScriptedRules {
+    fn load_scripts() -> Vec<AssetHandle<Script>> {
+        let dir = match assets::load_dir::<Script>(SCRIPT_DIR, true) {
+            Ok(dir) => dir,
+            Err(err) => {
+                error!(?err, "Failed to read rtsim script directory");
+                return Vec::new();
+            },
+        };
+        let scripts = dir
+            .ids()
+            .filter_map(|id| {
+                Script::load(id)
+                    .map_err(|err| error!(?err, "Failed to load rtsim script '{}'", id))
+                    .ok()
+            })
+            .collect::<Vec<_>>();
+        info!("Loaded {} rtsim scripts.", scripts.len());
+        scripts
+    }
+
+    /// Run all scripts with a matching trigger for each of the given subjects.
+    fn run(
+        &self,
+        data: &mut Data,
+        world: &World,
+        index: IndexRef,
+        triggered: impl Fn(&Trigger) -> bool,
+        subjects: &[Subject],
+    ) {
+        let mut rng = ChaChaRng::from_seed(thread_rng().gen::<[u8; 32]>());
+        let mut ctx = ScriptCtx {
+            data,
+            world,
+            index,
+            rng: &mut rng,
+        };
+        for script in self.scripts.iter().map(|script| script.read()) {
+            if triggered(&script.trigger) {
+                for subject in subjects {
+                    run_script(&mut ctx, &script, *subject);
+                }
+            }
+        }
+    }
+}
+
+impl Rule for ScriptedRules {
+    fn start(rtstate: &mut RtState) -> Result<Self, RuleError> {
+        rtstate.bind::<Self, OnSetup>(on_setup);
+        rtstate.bind::<Self, OnTick>(on_tick);
+        rtstate.bind::<Self, OnDeath>(on_death);
+
+        Ok(Self {
+            scripts: Self::load_scripts(),
+        })
+    }
+}
+
+fn living_npcs(data: &Data) -> Vec<Subject> {
+    data.npcs
+        .iter()
+        .filter(|(_, npc)| !npc.is_dead)
+        .map(|(npc_id, _)| Subject {
+            npc_id,
+            killer: None,
+        })
+        .collect()
+}
+
+fn on_setup(ctx: EventCtx<ScriptedRules, OnSetup>) {
+    let data = &mut *ctx.state.data_mut();
+    let subjects = living_npcs(data);
+    ctx.rule.run(
+        data,
+        ctx.world,
+        ctx.index,
+        |trigger| matches!(trigger, Trigger::Setup),
+        &subjects,
+    );
+}
+
+fn on_tick(ctx: EventCtx<ScriptedRules, OnTick>) {
+    let tick = ctx.event.tick;
+    let triggered = |trigger: &Trigger| match trigger {
+        Trigger::Tick { every } => tick % (*every).max(1) == 0,
+        _ => false,
+    };
+    // Avoid collecting NPCs on ticks when no scripts will run
+    if !ctx
+        .rule
+        .scripts
+        .iter()
+        .any(|script| triggered(&script.read().trigger))
+    {
+        return;
+    }
+
+    let data = &mut *ctx.state.data_mut();
+    let subjects = living_npcs(data);
+    ctx.rule
+        .run(data, ctx.world, ctx.index, triggered, &subjects);
+}
+
+fn on_death(ctx: EventCtx<ScriptedRules, OnDeath>) {
+    let Actor::Npc(npc_id) = ctx.event.actor else { return };
+    let data = &mut *ctx.state.data_mut();
+    ctx.rule.run(
+        data,
+        ctx.world,
+        ctx.index,
+        |trigger| matches!(trigger, Trigger::Death),
+        &[Subject {
+            npc_id,
+            killer: ctx.event.killer,
+        }],
+    );
+}
@@ -7,11 +7,12 @@ use crate::{
     util::{
         aim_projectile, are_our_owners_hostile, entities_have_line_of_sight, get_attacker,
         get_entity_by_id, is_dead_or_invulnerable, is_dressed_as_cultist, is_invulnerable,
-        is_village_guard, is_villager,
+        is_village_guard, is_villager, light_level_at,
     },
 };
 use common::{
-    combat::perception_dist_multiplier_from_stealth,
+    assets::{AssetExt, AssetHandle},
+    combat::{perception_dist_multiplier_from_light, perception_dist_multiplier_from_stealth},
     comp::{
         self,
         ability::MAX_ABILITIES,
@@ -32,12 +33,14 @@ use common::{
     path::TraversalConfig,
     rtsim::NpcActivity,
     states::basic_beam,
+    tactic::TacticSpec,
     terrain::Block,
     time::DayPeriod,
     util::Dir,
     vol::ReadVol,
 };
 use itertools::Itertools;
+use lazy_static::lazy_static;
 use rand::{thread_rng, Rng};
 use specs::{saveload::Marker, Entity as EcsEntity};
 use vek::*;
@@ -45,6 +48,15 @@ use vek::*;
 #[cfg(feature = "use-dyn-lib")]
 use {crate::LIB, std::ffi::CStr};
 
+lazy_static! {
+    static ref QUAD_MED_BASIC_TACTIC: AssetHandle<TacticSpec> =
+        TacticSpec::load_expect("common.tactics.quad_med_basic");
+    static ref QUAD_LOW_QUICK_TACTIC: AssetHandle<TacticSpec> =
+        TacticSpec::load_expect("common.tactics.quad_low_quick");
+    static ref QUAD_LOW_BASIC_TACTIC: AssetHandle<TacticSpec> =
+        TacticSpec::load_expect("common.tactics.quad_low_basic");
+}
+
 impl<'a> AgentData<'a> {
     ////////////////////////////////////////
     // Action Nodes
@@ -984,7 +996,7 @@ impl<'a> AgentData<'a> {
                                 radius: 6,
                                 circle_time: 1,
                             },
-                            "Quad Med Basic" => Tactic::QuadMedBasic,
+                            "Quad Med Basic" => Tactic::Declarative(*QUAD_MED_BASIC_TACTIC),
                             "Quad Med Hoof" => Tactic::QuadMedHoof,
                             "Roshwalr" => Tactic::Roshwalr,
                             "Asp" | "Maneater" => Tactic::QuadLowRanged,
@@ -993,8 +1005,8 @@ impl<'a> AgentData<'a> {
                             },
                             "Organ" => Tactic::OrganAura,
                             "Quad Low Tail" | "Husk Brute" => Tactic::TailSlap,
-                            "Quad Low Quick" => Tactic::QuadLowQuick,
-                            "Quad Low Basic" => Tactic::QuadLowBasic,
+                            "Quad Low Quick" => Tactic::Declarative(*QUAD_LOW_QUICK_TACTIC),
+                            "Quad Low Basic" => Tactic::Declarative(*QUAD_LOW_BASIC_TACTIC),
                             "Theropod Basic" | "Theropod Bird" | "Theropod Small" => {
                                 Tactic::Theropod
                             },
@@ -1065,6 +1077,11 @@ impl<'a> AgentData<'a> {
                 }
             })
             .unwrap_or(Tactic::SimpleMelee);
+        // A tactic given by the entity config takes priority over the weapon
+        let tactic = match &agent.tactic {
+            Some(tactic) => Tactic::Declarative(*tactic),
+            None => tactic,
+        };
 
         // Wield the weapon as running towards the target
         controller.push_action(ControlAction::Wield);
@@ -1095,7 +1112,7 @@ impl<'a> AgentData<'a> {
                    // of the target and not the ground around the target
                    // For the ranged it is to shoot at the feet and not
                    // the head to get splash damage
-                   if tactic == Tactic::QuadMedJump {
+                   if matches!(tactic, Tactic::QuadMedJump) {
                        1.0
                    } else if matches!(tactic, Tactic::QuadLowRanged) {
                        -1.0
@@ -1232,6 +1249,11 @@ impl<'a> AgentData<'a> {
             angle_xy,
         };
 
+        // Squad members move around the target before closing in
+        if self.flank_target(agent, controller, &attack_data, tgt_data, read_data) {
+            return;
+        }
+
         // Match on tactic. Each tactic has different controls depending on the distance
         // from the agent to the target.
         match tactic {
@@ -1295,20 +1317,6 @@ impl<'a> AgentData<'a> {
             Tactic::TailSlap => {
                 self.handle_tail_slap_attack(agent, controller, &attack_data, tgt_data, read_data)
             },
-            Tactic::QuadLowQuick => self.handle_quadlow_quick_attack(
-                agent,
-                controller,
-                &attack_data,
-                tgt_data,
-                read_data,
-            ),
-            Tactic::QuadLowBasic => self.handle_quadlow_basic_attack(
-                agent,
-                controller,
-                &attack_data,
-                tgt_data,
-                read_data,
-            ),
             Tactic::QuadMedJump => self.handle_quadmed_jump_attack(
                 agent,
                 controller,
@@ -1316,13 +1324,6 @@ impl<'a> AgentData<'a> {
                 tgt_data,
                 read_data,
             ),
-            Tactic::QuadMedBasic => self.handle_quadmed_basic_attack(
-                agent,
-                controller,
-                &attack_data,
-                tgt_data,
-                read_data,
-            ),
             Tactic::QuadMedHoof => self.handle_quadmed_hoof_attack(
                 agent,
                 controller,
@@ -1535,6 +1536,15 @@ impl<'a> AgentData<'a> {
             Tactic::AdletElder => {
                 self.handle_adlet_elder(agent, controller, &attack_data, tgt_data, read_data, rng)
             },
+            Tactic::Declarative(tactic) => self.handle_declarative_tactic(
+                agent,
+                controller,
+                &attack_data,
+                tgt_data,
+                read_data,
+                rng,
+                &tactic.read(),
+            ),
         }
     }
 
@@ -1724,38 +1734,6 @@ impl<'a> AgentData<'a> {
         self.damage.min(1.0) < agent.psyche.flee_health
     }
 
-    pub fn is_more_dangerous_than_target(
-        &self,
-        entity: EcsEntity,
-        target: Target,
-        read_data: &ReadData,
-    ) -> bool {
-        let entity_pos = read_data.positions.get(entity);
-        let target_pos = read_data.positions.get(target.target);
-
-        entity_pos.map_or(false, |entity_pos| {
-            target_pos.map_or(true, |target_pos| {
-                // Fuzzy factor that makes it harder for players to cheese enemies by making
-                // them quickly flip aggro between two players.
-                // It does this by only switching aggro if the entity is closer to the enemy by
-                // a specific proportional threshold.
-                const FUZZY_DIST_COMPARISON: f32 = 0.8;
-
-                let is_target_further = target_pos.0.distance(entity_pos.0)
-                    < target_pos.0.distance(entity_pos.0) * FUZZY_DIST_COMPARISON;
-                let is_entity_hostile = read_data
-                    .alignments
-                    .get(entity)
-                    .zip(self.alignment)
-                    .map_or(false, |(entity, me)| me.hostile_towards(*entity));
-
-                // Consider entity more dangerous than target if entity is closer or if target
-                // had not triggered aggro.
-                !target.aggro_on || (is_target_further && is_entity_hostile)
-            })
-        })
-    }
-
     pub fn is_enemy(&self, entity: EcsEntity, read_data: &ReadData) -> bool {
         let other_alignment = read_data.alignments.get(entity);
 
@@ -1822,28 +1800,34 @@ impl<'a> AgentData<'a> {
         other_scale: Option<&Scale>,
         read_data: &ReadData,
     ) -> bool {
+        let within_fov = (other_pos.0 - self.pos.0)
+            .try_normalized()
+            .map_or(false, |v| v.dot(*controller.inputs.look_dir) > 0.15);
+        if !within_fov {
+            return false;
+        }
+
         let other_stealth_multiplier = {
             let other_inventory = read_data.inventories.get(other);
             let other_char_state = read_data.char_states.get(other);
 
             perception_dist_multiplier_from_stealth(other_inventory, other_char_state, self.msm)
         };
+        let dist_sqrd = other_pos.0.distance_squared(self.pos.0);
+        let sight_dist = agent.psyche.sight_dist * other_stealth_multiplier;
+        // Check the distance before the lighting, which is more expensive to work out
+        if dist_sqrd >= sight_dist.powi(2) {
+            return false;
+        }
 
-        let within_sight_dist = {
-            let sight_dist = agent.psyche.sight_dist * other_stealth_multiplier;
-            let dist_sqrd = other_pos.0.distance_squared(self.pos.0);
-
-            dist_sqrd < sight_dist.powi(2)
-        };
-
-        let within_fov = (other_pos.0 - self.pos.0)
-            .try_normalized()
-            .map_or(false, |v| v.dot(*controller.inputs.look_dir) > 0.15);
+        // Entities in the dark are harder to spot, unless they carry a light
+        let light_multiplier =
+            perception_dist_multiplier_from_light(light_level_at(other_pos, read_data));
+        let within_sight_dist = dist_sqrd < (sight_dist * light_multiplier).powi(2);
 
         let other_body = read_data.bodies.get(other);
 
-        (within_sight_dist)
-            && within_fov
+        within_sight_dist
             && entities_have_line_of_sight(
                 self.pos,
                 self.body,
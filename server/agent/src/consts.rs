@@ -15,3 +15,19 @@ pub const HEALING_ITEM_THRESHOLD: f32 = 0.5;
 pub const IDLE_HEALING_ITEM_THRESHOLD: f32 = 0.999;
 pub const DEFAULT_ATTACK_RANGE: f32 = 2.0;
 pub const STD_AWARENESS_DECAY_RATE: f32 = -0.05;
+/// How far above a position terrain is checked for when working out whether
+/// sunlight reaches it
+pub const SKY_CHECK_DIST: f32 = 64.0;
+/// Light sources further away than this don't make entities more visible
+pub const MAX_LIGHT_DIST: f32 = 16.0;
+/// How far from their target squad members spread out before closing in
+pub const FLANK_RADIUS: f32 = 8.0;
+pub const FLANK_SLOT_TOLERANCE: f32 = 3.0;
+/// Distance between rows of a squad in formation
+pub const FORMATION_SPACING: f32 = 3.0;
+/// How far squad members may stray from their spot in formation
+pub const FORMATION_SLACK: f32 = 4.0;
+/// The morale below which a squad retreats from a fight
+pub const SQUAD_RETREAT_MORALE: f32 = 0.35;
+/// How long an empty squad is kept around in case its chunk is loaded again
+pub const SQUAD_DISBAND_DELAY: f64 = 60.0;
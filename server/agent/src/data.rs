@@ -1,5 +1,6 @@
-use crate::util::*;
+use crate::{squad::Squads, util::*};
 use common::{
+    assets::AssetHandle,
     comp::{
         ability::{CharacterAbility, MAX_ABILITIES},
         buff::{BuffKind, Buffs},
@@ -20,6 +21,7 @@ use common::{
     resources::{DeltaTime, Time, TimeOfDay},
     rtsim::{Actor, RtSimEntity},
     states::utils::{ForcedMovement, StageSection},
+    tactic::TacticSpec,
     terrain::TerrainGrid,
     uid::{Uid, UidAllocator},
 };
@@ -160,7 +162,6 @@ impl ActionMode {
     }
 }
 
-#[derive(Eq, PartialEq)]
 // When adding a new variant, first decide if it should instead fall under one
 // of the pre-existing tactics
 pub enum Tactic {
@@ -179,6 +180,8 @@ pub enum Tactic {
         secondary: u8,
         abilities: [u8; MAX_ABILITIES],
     },
+    // Rules loaded from a tactic asset
+    Declarative(AssetHandle<TacticSpec>),
 
     // Tool specific tactics
     Axe,
@@ -197,11 +200,8 @@ pub enum Tactic {
     },
     QuadLowRanged,
     TailSlap,
-    QuadLowQuick,
-    QuadLowBasic,
     QuadLowBeam,
     QuadMedJump,
-    QuadMedBasic,
     QuadMedHoof,
     Theropod,
     BirdLargeBreathe,
@@ -320,6 +320,7 @@ pub struct ReadData<'a> {
     pub poises: ReadStorage<'a, Poise>,
     pub stances: ReadStorage<'a, Stance>,
     pub presences: ReadStorage<'a, Presence>,
+    pub squads: Read<'a, Squads>,
 }
 
 impl<'a> ReadData<'a> {
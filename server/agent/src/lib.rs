@@ -7,6 +7,8 @@ pub mod action_nodes;
 pub mod attack;
 pub mod consts;
 pub mod data;
+pub mod squad;
+pub mod tactic;
 pub mod util;
 
 #[cfg(feature = "use-dyn-lib")]
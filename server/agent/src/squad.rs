@@ -0,0 +1,243 @@
+use crate::{
+    consts::{FLANK_RADIUS, FLANK_SLOT_TOLERANCE, FORMATION_SLACK, FORMATION_SPACING},
+    data::{AgentData, AttackData, Path, ReadData, TargetData},
+};
+use common::comp::{
+    agent::SquadId,
+    inventory::slot::EquipSlot,
+    item::{tool::ToolKind, ItemKind},
+    Agent, Controller,
+};
+use specs::Entity as EcsEntity;
+use std::collections::HashMap;
+use vek::*;
+
+/// A group of agents that were spawned together. Squad members fight the same
+/// target, spread out around it, retreat together once the fight turns against
+/// them and keep formation around their leader while patrolling.
+#[derive(Clone, Debug)]
+pub struct Squad {
+    /// Where the squad was generated
+    pub origin: Vec2<i32>,
+    /// The living members of the squad in the order they were spawned in, the
+    /// first of which leads it
+    pub members: Vec<EcsEntity>,
+    /// The most members the squad has had at once
+    pub size: usize,
+    /// Who the squad is fighting
+    pub target: Option<EcsEntity>,
+    /// The average health of the squad, with fallen members counting as
+    /// having none
+    pub morale: f32,
+    /// Whether the squad has lost its nerve and is fleeing from its target
+    pub retreating: bool,
+    /// When the squad lost its last member
+    pub empty_since: Option<f64>,
+}
+
+impl Squad {
+    fn new(origin: Vec2<i32>) -> Self {
+        Self {
+            origin,
+            members: Vec::new(),
+            size: 0,
+            target: None,
+            morale: 1.0,
+            retreating: false,
+            empty_since: None,
+        }
+    }
+
+    pub fn leader(&self) -> Option<EcsEntity> { self.members.first().copied() }
+
+    /// The position of `entity` within the squad, the leader being 0
+    pub fn rank(&self, entity: EcsEntity) -> Option<usize> {
+        self.members.iter().position(|member| *member == entity)
+    }
+}
+
+/// All squads of NPCs, kept up to date by the squad system
+#[derive(Default)]
+pub struct Squads {
+    squads: HashMap<SquadId, Squad>,
+    by_origin: HashMap<Vec2<i32>, SquadId>,
+    next_id: u64,
+}
+
+impl Squads {
+    /// The squad generated at `origin`, which is created if it doesn't exist
+    /// yet
+    pub fn join(&mut self, origin: Vec2<i32>) -> SquadId {
+        let Self {
+            squads,
+            by_origin,
+            next_id,
+        } = self;
+        *by_origin.entry(origin).or_insert_with(|| {
+            let id = SquadId(*next_id);
+            *next_id += 1;
+            squads.insert(id, Squad::new(origin));
+            id
+        })
+    }
+
+    pub fn get(&self, id: SquadId) -> Option<&Squad> { self.squads.get(&id) }
+
+    pub fn get_mut(&mut self, id: SquadId) -> Option<&mut Squad> { self.squads.get_mut(&id) }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Squad> { self.squads.values_mut() }
+
+    /// Disbands every squad for which `f` returns `false`
+    pub fn retain(&mut self, mut f: impl FnMut(&Squad) -> bool) {
+        let Self {
+            squads, by_origin, ..
+        } = self;
+        squads.retain(|_, squad| f(squad));
+        by_origin.retain(|_, id| squads.contains_key(id));
+    }
+}
+
+impl<'a> AgentData<'a> {
+    fn squad<'b>(&self, agent: &Agent, read_data: &'b ReadData) -> Option<&'b Squad> {
+        agent.squad.and_then(|id| read_data.squads.get(id))
+    }
+
+    /// Whether the agent's squad is fleeing from its target. Agents that never
+    /// flee hold their ground regardless.
+    pub fn squad_retreating(&self, agent: &Agent, read_data: &ReadData) -> bool {
+        agent.psyche.flee_health > 0.0
+            && self
+                .squad(agent, read_data)
+                .map_or(false, |squad| squad.retreating)
+    }
+
+    /// Whether `other` belongs to the same squad as the agent
+    pub fn is_squad_mate(&self, agent: &Agent, other: EcsEntity, read_data: &ReadData) -> bool {
+        other != *self.entity
+            && self
+                .squad(agent, read_data)
+                .map_or(false, |squad| squad.members.contains(&other))
+    }
+
+    /// Who the agent's squad is fighting, for when a squad mate calls for help
+    pub fn squad_target(&self, agent: &Agent, read_data: &ReadData) -> Option<EcsEntity> {
+        self.squad(agent, read_data)
+            .filter(|squad| !squad.retreating)
+            .and_then(|squad| squad.target)
+            .filter(|target| target != self.entity)
+    }
+
+    /// Moves towards a spot beside or behind the squad's target rather than
+    /// straight at it, so that the squad surrounds the target instead of
+    /// queueing up in front of it. The leader attacks head on and ranged
+    /// fighters are left to keep their distance.
+    ///
+    /// Returns `true` while the agent is still moving into position.
+    pub fn flank_target(
+        &self,
+        agent: &mut Agent,
+        controller: &mut Controller,
+        attack_data: &AttackData,
+        tgt_data: &TargetData,
+        read_data: &ReadData,
+    ) -> bool {
+        let Some(squad) = self.squad(agent, read_data) else {
+            return false;
+        };
+        let Some(rank) = squad.rank(*self.entity) else {
+            return false;
+        };
+        if rank == 0
+            || squad.target.is_none()
+            || squad.target != agent.target.map(|target| target.target)
+        {
+            return false;
+        }
+
+        let is_ranged = self
+            .inventory
+            .equipped(EquipSlot::ActiveMainhand)
+            .as_ref()
+            .map_or(false, |item| {
+                matches!(
+                    &*item.kind(),
+                    ItemKind::Tool(tool)
+                        if matches!(tool.kind, ToolKind::Bow | ToolKind::Staff | ToolKind::Sceptre)
+                )
+            });
+        if is_ranged {
+            return false;
+        }
+
+        // Spread the squad evenly around the target, starting from the side the
+        // leader attacks from
+        let front = squad
+            .leader()
+            .and_then(|leader| read_data.positions.get(leader))
+            .and_then(|pos| (pos.0.xy() - tgt_data.pos.0.xy()).try_normalized())
+            .or_else(|| (self.pos.0.xy() - tgt_data.pos.0.xy()).try_normalized())
+            .unwrap_or_else(Vec2::unit_y);
+        let angle = rank as f32 / squad.members.len() as f32 * std::f32::consts::TAU;
+        let slot = tgt_data.pos.0 + front.with_z(0.0).rotated_z(angle) * FLANK_RADIUS;
+
+        if attack_data.dist_sqrd > (FLANK_RADIUS * 0.75).powi(2)
+            && self.pos.0.xy().distance_squared(slot.xy()) > FLANK_SLOT_TOLERANCE.powi(2)
+        {
+            self.path_toward_target(agent, controller, slot, read_data, Path::Separate, None)
+        } else {
+            false
+        }
+    }
+
+    /// Keeps squad members in a wedge behind their leader while patrolling.
+    ///
+    /// Returns `true` while the agent is moving back into formation.
+    pub fn follow_squad_leader(
+        &self,
+        agent: &mut Agent,
+        controller: &mut Controller,
+        read_data: &ReadData,
+    ) -> bool {
+        let Some(squad) = self.squad(agent, read_data) else {
+            return false;
+        };
+        let (Some(rank), Some(leader)) = (squad.rank(*self.entity), squad.leader()) else {
+            return false;
+        };
+        if rank == 0 {
+            return false;
+        }
+        let (Some(leader_pos), Some(leader_ori)) = (
+            read_data.positions.get(leader),
+            read_data.orientations.get(leader),
+        ) else {
+            return false;
+        };
+
+        let forward = leader_ori
+            .look_vec()
+            .xy()
+            .try_normalized()
+            .unwrap_or_else(Vec2::unit_y);
+        let right = Vec2::new(forward.y, -forward.x);
+        let row = ((rank + 1) / 2) as f32;
+        let side = if rank % 2 == 1 { -1.0 } else { 1.0 };
+        let slot = leader_pos.0.xy() + (right * side - forward) * row * FORMATION_SPACING;
+
+        let dist_sqrd = self.pos.0.xy().distance_squared(slot);
+        if dist_sqrd > FORMATION_SLACK.powi(2) {
+            // Walk back into place, unless we've fallen far behind
+            let speed = (dist_sqrd < (FORMATION_SLACK * 3.0).powi(2)).then_some(0.5);
+            self.path_toward_target(
+                agent,
+                controller,
+                slot.with_z(leader_pos.0.z),
+                read_data,
+                Path::Partial,
+                speed,
+            )
+        } else {
+            false
+        }
+    }
+}
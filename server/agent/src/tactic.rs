@@ -0,0 +1,157 @@
+use crate::{
+    consts::MAX_PATH_DIST,
+    data::{AgentData, AttackData, Path, ReadData, TargetData},
+    util::entities_have_line_of_sight,
+};
+use common::{
+    comp::{AbilityInput, Agent, Controller, InputKind},
+    tactic::{TacticCondition, TacticEffect, TacticMovement, TacticSpec},
+};
+use rand::Rng;
+use vek::*;
+
+impl<'a> AgentData<'a> {
+    /// Fights using a tactic loaded from assets rather than one of the
+    /// hardcoded `Tactic`s. The first rule whose conditions all hold is used,
+    /// if none do the agent falls back to the tactic's default movement.
+    pub fn handle_declarative_tactic(
+        &self,
+        agent: &mut Agent,
+        controller: &mut Controller,
+        attack_data: &AttackData,
+        tgt_data: &TargetData,
+        read_data: &ReadData,
+        rng: &mut impl Rng,
+        tactic: &TacticSpec,
+    ) {
+        for timer in &tactic.ticking_timers {
+            agent.action_state.timers[*timer] += read_data.dt.0;
+        }
+
+        let rule = tactic.rules.iter().find(|rule| {
+            rule.conditions.iter().all(|condition| {
+                self.tactic_condition_holds(
+                    condition,
+                    rule.input,
+                    agent,
+                    attack_data,
+                    tgt_data,
+                    read_data,
+                    rng,
+                )
+            })
+        });
+
+        let movement = if let Some(rule) = rule {
+            if let Some(input) = rule.input {
+                controller.push_basic_input(input);
+            }
+            for effect in &rule.effects {
+                let action_state = &mut agent.action_state;
+                match effect {
+                    TacticEffect::TickTimer(i) => action_state.timers[*i] += read_data.dt.0,
+                    TacticEffect::ResetTimer(i) => action_state.timers[*i] = 0.0,
+                    TacticEffect::AddToCounter(i, amount) => action_state.counters[*i] += amount,
+                    TacticEffect::ResetCounter(i) => action_state.counters[*i] = 0.0,
+                    TacticEffect::SetCondition(i, value) => action_state.conditions[*i] = *value,
+                }
+            }
+            &rule.movement
+        } else {
+            &tactic.fallback
+        };
+
+        match movement {
+            TacticMovement::Stop => controller.inputs.move_dir = Vec2::zero(),
+            TacticMovement::Approach => {
+                let path = if attack_data.dist_sqrd < MAX_PATH_DIST.powi(2) {
+                    Path::Separate
+                } else {
+                    Path::Partial
+                };
+                self.path_toward_target(agent, controller, tgt_data.pos.0, read_data, path, None);
+            },
+            TacticMovement::Circle(angle) => {
+                controller.inputs.move_dir = (tgt_data.pos.0 - self.pos.0)
+                    .xy()
+                    .rotated_z(angle.to_radians())
+                    .try_normalized()
+                    .unwrap_or_else(Vec2::unit_y);
+            },
+            TacticMovement::Retreat => {
+                controller.inputs.move_dir = (self.pos.0 - tgt_data.pos.0)
+                    .xy()
+                    .try_normalized()
+                    .unwrap_or_else(Vec2::unit_y);
+            },
+        }
+    }
+
+    fn tactic_condition_holds(
+        &self,
+        condition: &TacticCondition,
+        input: Option<InputKind>,
+        agent: &Agent,
+        attack_data: &AttackData,
+        tgt_data: &TargetData,
+        read_data: &ReadData,
+        rng: &mut impl Rng,
+    ) -> bool {
+        let action_state = &agent.action_state;
+        match condition {
+            TacticCondition::InRange(factor) => {
+                attack_data.dist_sqrd < (factor * attack_data.min_attack_dist).powi(2)
+            },
+            TacticCondition::OutOfRange(factor) => {
+                attack_data.dist_sqrd > (factor * attack_data.min_attack_dist).powi(2)
+            },
+            TacticCondition::Facing(angle) => attack_data.angle < *angle,
+            TacticCondition::HealthBelow(fraction) => {
+                self.health.map_or(false, |h| h.fraction() < *fraction)
+            },
+            TacticCondition::HealthAbove(fraction) => {
+                self.health.map_or(false, |h| h.fraction() > *fraction)
+            },
+            TacticCondition::TargetHealthBelow(fraction) => {
+                tgt_data.health.map_or(false, |h| h.fraction() < *fraction)
+            },
+            TacticCondition::LineOfSight => entities_have_line_of_sight(
+                self.pos,
+                self.body,
+                self.scale,
+                tgt_data.pos,
+                tgt_data.body,
+                tgt_data.scale,
+                read_data,
+            ),
+            TacticCondition::CanUse => {
+                let ability_input = match input {
+                    Some(InputKind::Primary) => Some(AbilityInput::Primary),
+                    Some(InputKind::Secondary) => Some(AbilityInput::Secondary),
+                    Some(InputKind::Ability(i)) => Some(AbilityInput::Auxiliary(i)),
+                    _ => None,
+                };
+                ability_input
+                    .and_then(|ability_input| self.extract_ability(ability_input))
+                    .map_or(false, |ability| {
+                        ability.could_use(attack_data, self, tgt_data, read_data, 0.0)
+                    })
+            },
+            TacticCondition::TimerBelow(i, value) => action_state.timers[*i] < *value,
+            TacticCondition::TimerAbove(i, value) => action_state.timers[*i] > *value,
+            TacticCondition::CounterBelow(i, value) => action_state.counters[*i] < *value,
+            TacticCondition::CounterAbove(i, value) => action_state.counters[*i] > *value,
+            TacticCondition::Condition(i) => action_state.conditions[*i],
+            TacticCondition::Chance(chance) => rng.gen_bool(chance.clamp(0.0, 1.0) as f64),
+            TacticCondition::Not(condition) => !self.tactic_condition_holds(
+                condition,
+                input,
+                agent,
+                attack_data,
+                tgt_data,
+                read_data,
+                rng,
+            ),
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::data::{AbilityData, ActionMode, AgentData, AttackData, Path, ReadData, TargetData};
+use crate::{
+    consts::{MAX_LIGHT_DIST, SKY_CHECK_DIST},
+    data::{AbilityData, ActionMode, AgentData, AttackData, Path, ReadData, TargetData},
+};
 use common::{
     comp::{
         ability::AbilityInput,
@@ -9,6 +12,7 @@ use common::{
     },
     consts::GRAVITY,
     terrain::Block,
+    time::daylight,
     util::Dir,
     vol::ReadVol,
 };
@@ -179,6 +183,39 @@ pub fn positions_have_line_of_sight(pos_a: &Pos, pos_b: &Pos, read_data: &ReadDa
         >= (dist_sqrd - 0.01)
 }
 
+/// How well lit `pos` is, from 0.0 in complete darkness to 1.0 in daylight.
+/// Sunlight only reaches places that are open to the sky, while light sources
+/// such as lanterns light up their surroundings no matter where they are.
+pub fn light_level_at(pos: &Pos, read_data: &ReadData) -> f32 {
+    let under_cover = read_data
+        .terrain
+        .ray(pos.0, pos.0 + Vec3::unit_z() * SKY_CHECK_DIST)
+        .until(Block::is_opaque)
+        .cast()
+        .1
+        .map_or(false, |block| block.is_some());
+    let sunlight = if under_cover {
+        0.0
+    } else {
+        daylight(read_data.time_of_day.0)
+    };
+
+    let lamplight = read_data
+        .cached_spatial_grid
+        .0
+        .in_circle_aabr(pos.0.xy(), MAX_LIGHT_DIST)
+        .filter_map(|entity| {
+            let light = read_data.light_emitter.get(entity)?;
+            let light_pos = read_data.positions.get(entity)?;
+            let dist_sqrd = light_pos.0.distance_squared(pos.0);
+
+            (dist_sqrd < MAX_LIGHT_DIST.powi(2)).then(|| light.strength / (1.0 + dist_sqrd))
+        })
+        .sum::<f32>();
+
+    (sunlight + lamplight).min(1.0)
+}
+
 pub fn is_dressed_as_cultist(entity: EcsEntity, read_data: &ReadData) -> bool {
     read_data
         .inventories
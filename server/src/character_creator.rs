@@ -2,9 +2,10 @@ use crate::persistence::{character_updater::CharacterUpdater, PersistedComponent
 use common::{
     character::CharacterId,
     comp::{
-        inventory::loadout_builder::LoadoutBuilder, Body, Inventory, Item, SkillSet, Stats,
-        Waypoint,
+        inventory::loadout_builder::LoadoutBuilder, Body, Inventory, Item, KnownRecipes, SkillSet,
+        Stats, Waypoint,
     },
+    recipe::default_starter_recipes,
 };
 use specs::{Entity, WriteExpect};
 
@@ -48,6 +49,12 @@ pub fn create_character(
     {
         return Err(CreationError::InvalidWeapon);
     };
+    let known_recipes = KnownRecipes::new(
+        default_starter_recipes()
+            .read()
+            .for_mainhand(character_mainhand.as_deref())
+            .cloned(),
+    );
     // The client sends None if a weapon hand is empty
     let loadout = LoadoutBuilder::empty()
         .defaults()
@@ -78,6 +85,10 @@ pub fn create_character(
         pets: Vec::new(),
         active_abilities: Default::default(),
         map_marker,
+        known_recipes,
+        statistics: Default::default(),
+        achievements: Default::default(),
+        bank: Default::default(),
     });
     Ok(())
 }
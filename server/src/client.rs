@@ -189,6 +189,14 @@ impl Client {
                     | ServerGeneral::FinishedTrade(_)
                     | ServerGeneral::MapMarker(_)
                     | ServerGeneral::WeatherUpdate(_)
+                    | ServerGeneral::ReputationUpdate(_)
+                    | ServerGeneral::KnownRecipesUpdate(_)
+                    | ServerGeneral::StatisticsUpdate(_)
+                    | ServerGeneral::AchievementsUpdate(_)
+                    | ServerGeneral::GuildUpdate(_)
+                    | ServerGeneral::BankUpdate(_, _)
+                    | ServerGeneral::MailboxUpdate(_, _)
+                    | ServerGeneral::MarketUpdate(_, _)
                     | ServerGeneral::SpectatePosition(_) => {
                         PreparedMsg::new(2, &g, &self.in_game_stream_params)
                     },
@@ -209,7 +217,8 @@ impl Client {
                     | ServerGeneral::CreateEntity(_)
                     | ServerGeneral::DeleteEntity(_)
                     | ServerGeneral::Disconnect(_)
-                    | ServerGeneral::Notification(_) => {
+                    | ServerGeneral::Notification(_)
+                    | ServerGeneral::SiteFactionsUpdate(_) => {
                         PreparedMsg::new(3, &g, &self.general_stream_params)
                     },
                 }
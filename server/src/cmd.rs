@@ -141,20 +141,28 @@ fn do_command(
         ServerChatCommand::DebugWays => handle_debug_ways,
         ServerChatCommand::DisconnectAllPlayers => handle_disconnect_all_players,
         ServerChatCommand::DropAll => handle_drop_all,
+        ServerChatCommand::Duel => handle_duel,
         ServerChatCommand::Dummy => handle_spawn_training_dummy,
         ServerChatCommand::Explosion => handle_explosion,
-        ServerChatCommand::Faction => handle_faction,
         ServerChatCommand::GiveItem => handle_give_item,
         ServerChatCommand::Goto => handle_goto,
         ServerChatCommand::Group => handle_group,
         ServerChatCommand::GroupInvite => handle_group_invite,
         ServerChatCommand::GroupKick => handle_group_kick,
         ServerChatCommand::GroupLeave => handle_group_leave,
+        ServerChatCommand::Guild => handle_guild_chat,
+        ServerChatCommand::GuildCreate => handle_guild_create,
+        ServerChatCommand::GuildDemote => handle_guild_demote,
+        ServerChatCommand::GuildDisband => handle_guild_disband,
+        ServerChatCommand::GuildInvite => handle_guild_invite,
+        ServerChatCommand::GuildKick => handle_guild_kick,
+        ServerChatCommand::GuildLeader => handle_guild_leader,
+        ServerChatCommand::GuildLeave => handle_guild_leave,
+        ServerChatCommand::GuildPromote => handle_guild_promote,
         ServerChatCommand::GroupPromote => handle_group_promote,
         ServerChatCommand::Health => handle_health,
         ServerChatCommand::Help => handle_help,
         ServerChatCommand::Respawn => handle_respawn,
-        ServerChatCommand::JoinFaction => handle_join_faction,
         ServerChatCommand::Jump => handle_jump,
         ServerChatCommand::Kick => handle_kick,
         ServerChatCommand::Kill => handle_kill,
@@ -707,6 +715,9 @@ fn handle_make_npc(
                 alignment,
                 scale,
                 loot,
+                is_boss,
+                encounter,
+                squad: _,
             } => {
                 let mut entity_builder = server
                     .state
@@ -723,6 +734,14 @@ fn handle_make_npc(
                     entity_builder = entity_builder.with(comp::ItemDrops(drop_items));
                 }
 
+                if is_boss {
+                    entity_builder = entity_builder.with(comp::Boss);
+                }
+
+                if let Some(encounter) = encounter {
+                    entity_builder = entity_builder.with(encounter);
+                }
+
                 // Some would say it's a hack, some would say it's incomplete
                 // simulation. But this is what we do to avoid PvP between npc.
                 let npc_group = match alignment {
@@ -1292,9 +1311,9 @@ fn handle_rtsim_info(
         // TODO: Take some other identifier than an integer to this command.
         let rtsim = server.state.ecs().read_resource::<RtSim>();
         let data = rtsim.state().data();
-        let npc = data
+        let (npc_id, npc) = data
             .npcs
-            .values()
+            .iter()
             .nth(id as usize)
             .ok_or_else(|| format!("No NPC has index {}", id))?;
 
@@ -1306,6 +1325,21 @@ fn handle_rtsim_info(
         let _ = writeln!(&mut info, "Home: {:?}", npc.home);
         let _ = writeln!(&mut info, "Faction: {:?}", npc.faction);
         let _ = writeln!(&mut info, "Personality: {:?}", npc.personality);
+        if let Some(faction) = npc.faction.and_then(|f| data.factions.get(f)) {
+            let _ = writeln!(&mut info, "-- Faction --");
+            let _ = writeln!(&mut info, "Territory: {} sites", faction.territory.len());
+            for (other, stance) in faction.relations.iter() {
+                let _ = writeln!(&mut info, "Stance toward {:?}: {:?}", other, stance);
+            }
+            if let Some(raid) = faction.raid_of(npc_id) {
+                let _ = writeln!(
+                    &mut info,
+                    "Raiding: {:?} (with {} others)",
+                    raid.target,
+                    raid.members.len().saturating_sub(1)
+                );
+            }
+        }
         let _ = writeln!(&mut info, "-- Status --");
         let _ = writeln!(&mut info, "Current site: {:?}", npc.current_site);
         let _ = writeln!(&mut info, "Current mode: {:?}", npc.mode);
@@ -2855,33 +2889,6 @@ fn handle_tell(
     }
 }
 
-fn handle_faction(
-    server: &mut Server,
-    client: EcsEntity,
-    target: EcsEntity,
-    args: Vec<String>,
-    _action: &ServerChatCommand,
-) -> CmdResult<()> {
-    no_sudo(client, target)?;
-
-    let factions = server.state.ecs().read_storage();
-    if let Some(comp::Faction(faction)) = factions.get(target) {
-        let mode = comp::ChatMode::Faction(faction.to_string());
-        drop(factions);
-        insert_or_replace_component(server, target, mode.clone(), "target")?;
-        let msg = args.join(" ");
-        if !msg.is_empty() {
-            if let Some(uid) = server.state.ecs().read_storage().get(target) {
-                server.state.send_chat(mode.to_plain_msg(*uid, msg));
-            }
-        }
-        server.notify_client(target, ServerGeneral::ChatMode(mode));
-        Ok(())
-    } else {
-        Err("Please join a faction with /join_faction".into())
-    }
-}
-
 fn handle_group(
     server: &mut Server,
     client: EcsEntity,
@@ -3009,6 +3016,210 @@ fn handle_group_promote(
     }
 }
 
+fn handle_duel(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    no_sudo(client, target)?;
+
+    if let Some(target_alias) = parse_cmd_args!(args, String) {
+        let target_player = find_alias(server.state.ecs(), &target_alias)?.0;
+        let uid = uid(server, target_player, "player")?;
+
+        server
+            .state
+            .mut_resource::<EventBus<ServerEvent>>()
+            .emit_now(ServerEvent::InitiateInvite(target, uid, InviteKind::Duel));
+
+        server.notify_client(
+            client,
+            ServerGeneral::server_msg(
+                ChatType::CommandInfo,
+                format!("Challenged {} to a duel.", target_alias),
+            ),
+        );
+        Ok(())
+    } else {
+        Err(action.help_string())
+    }
+}
+
+fn handle_guild_chat(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    no_sudo(client, target)?;
+
+    let guilds = server.state.ecs().read_storage::<comp::Guild>();
+    if let Some(guild) = guilds.get(target) {
+        let mode = comp::ChatMode::Guild(guild.name.clone());
+        drop(guilds);
+        insert_or_replace_component(server, target, mode.clone(), "target")?;
+        let msg = args.join(" ");
+        if !msg.is_empty() {
+            if let Some(uid) = server.state.ecs().read_storage().get(target) {
+                server.state.send_chat(mode.to_plain_msg(*uid, msg));
+            }
+        }
+        server.notify_client(target, ServerGeneral::ChatMode(mode));
+        Ok(())
+    } else {
+        Err("Please join a guild with /guild_create or by accepting an invite".into())
+    }
+}
+
+fn handle_guild_create(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    no_sudo(client, target)?;
+
+    let name = args.join(" ");
+    if name.trim().is_empty() {
+        return Err(action.help_string());
+    }
+    server
+        .state
+        .mut_resource::<EventBus<ServerEvent>>()
+        .emit_now(ServerEvent::GuildManip(
+            target,
+            comp::GuildManip::Create(name.trim().to_string()),
+        ));
+    Ok(())
+}
+
+fn handle_guild_invite(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    if let Some(target_alias) = parse_cmd_args!(args, String) {
+        let target_player = find_alias(server.state.ecs(), &target_alias)?.0;
+        let uid = uid(server, target_player, "player")?;
+
+        server
+            .state
+            .mut_resource::<EventBus<ServerEvent>>()
+            .emit_now(ServerEvent::InitiateInvite(target, uid, InviteKind::Guild));
+
+        server.notify_client(
+            client,
+            ServerGeneral::server_msg(
+                ChatType::CommandInfo,
+                format!("Invited {} to the guild.", target_alias),
+            ),
+        );
+        Ok(())
+    } else {
+        Err(action.help_string())
+    }
+}
+
+/// Emit a guild manipulation that targets another player, named in the
+/// command arguments.
+fn guild_manip_player(
+    server: &mut Server,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+    manip: fn(Uid) -> comp::GuildManip,
+) -> CmdResult<()> {
+    // Permissions are checked in guild_manip
+    if let Some(target_alias) = parse_cmd_args!(args, String) {
+        let target_player = find_alias(server.state.ecs(), &target_alias)?.0;
+        let uid = uid(server, target_player, "player")?;
+
+        server
+            .state
+            .mut_resource::<EventBus<ServerEvent>>()
+            .emit_now(ServerEvent::GuildManip(target, manip(uid)));
+        Ok(())
+    } else {
+        Err(action.help_string())
+    }
+}
+
+fn handle_guild_kick(
+    server: &mut Server,
+    _client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    guild_manip_player(server, target, args, action, comp::GuildManip::Kick)
+}
+
+fn handle_guild_promote(
+    server: &mut Server,
+    _client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    guild_manip_player(server, target, args, action, comp::GuildManip::Promote)
+}
+
+fn handle_guild_demote(
+    server: &mut Server,
+    _client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    guild_manip_player(server, target, args, action, comp::GuildManip::Demote)
+}
+
+fn handle_guild_leader(
+    server: &mut Server,
+    _client: EcsEntity,
+    target: EcsEntity,
+    args: Vec<String>,
+    action: &ServerChatCommand,
+) -> CmdResult<()> {
+    guild_manip_player(server, target, args, action, comp::GuildManip::AssignLeader)
+}
+
+fn handle_guild_leave(
+    server: &mut Server,
+    _client: EcsEntity,
+    target: EcsEntity,
+    _args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    server
+        .state
+        .mut_resource::<EventBus<ServerEvent>>()
+        .emit_now(ServerEvent::GuildManip(target, comp::GuildManip::Leave));
+    Ok(())
+}
+
+fn handle_guild_disband(
+    server: &mut Server,
+    client: EcsEntity,
+    target: EcsEntity,
+    _args: Vec<String>,
+    _action: &ServerChatCommand,
+) -> CmdResult<()> {
+    no_sudo(client, target)?;
+
+    server
+        .state
+        .mut_resource::<EventBus<ServerEvent>>()
+        .emit_now(ServerEvent::GuildManip(target, comp::GuildManip::Disband));
+    Ok(())
+}
+
 fn handle_region(
     server: &mut Server,
     client: EcsEntity,
@@ -3072,58 +3283,6 @@ fn handle_world(
     Ok(())
 }
 
-fn handle_join_faction(
-    server: &mut Server,
-    _client: EcsEntity,
-    target: EcsEntity,
-    args: Vec<String>,
-    _action: &ServerChatCommand,
-) -> CmdResult<()> {
-    let players = server.state.ecs().read_storage::<comp::Player>();
-    if let Some(alias) = players.get(target).map(|player| player.alias.clone()) {
-        drop(players);
-        let (faction_leave, mode) = if let Some(faction) = parse_cmd_args!(args, String) {
-            let mode = comp::ChatMode::Faction(faction.clone());
-            insert_or_replace_component(server, target, mode.clone(), "target")?;
-            let faction_join = server
-                .state
-                .ecs()
-                .write_storage()
-                .insert(target, comp::Faction(faction.clone()))
-                .ok()
-                .flatten()
-                .map(|f| f.0);
-            server.state.send_chat(
-                // TODO: Localise
-                ChatType::FactionMeta(faction.clone())
-                    .into_plain_msg(format!("[{}] joined faction ({})", alias, faction)),
-            );
-            (faction_join, mode)
-        } else {
-            let mode = comp::ChatMode::default();
-            insert_or_replace_component(server, target, mode.clone(), "target")?;
-            let faction_leave = server
-                .state
-                .ecs()
-                .write_storage()
-                .remove(target)
-                .map(|comp::Faction(f)| f);
-            (faction_leave, mode)
-        };
-        if let Some(faction) = faction_leave {
-            server.state.send_chat(
-                // TODO: Localise
-                ChatType::FactionMeta(faction.clone())
-                    .into_plain_msg(format!("[{}] left faction ({})", alias, faction)),
-            );
-        }
-        server.notify_client(target, ServerGeneral::ChatMode(mode));
-        Ok(())
-    } else {
-        Err("Could not find your player alias".into())
-    }
-}
-
 #[cfg(not(feature = "worldgen"))]
 fn handle_debug_column(
     server: &mut Server,
@@ -0,0 +1,116 @@
+use crate::client::Client;
+use common::{
+    comp::{
+        self,
+        duel::{Duel, DuelPhase, DUEL_ARENA_RADIUS, DUEL_COUNTDOWN_SECS},
+        ChatType, Pos, Presence, PresenceKind,
+    },
+    resources::Time,
+};
+use common_net::msg::ServerGeneral;
+use common_state::State;
+use specs::{world::Entity, ReadStorage, WorldExt};
+
+fn in_game(state: &State, entity: Entity) -> bool {
+    matches!(
+        state.ecs().read_storage::<Presence>().get(entity),
+        Some(Presence {
+            kind: PresenceKind::Character(_),
+            ..
+        })
+    )
+}
+
+fn within_duel_range(state: &State, a: Entity, b: Entity) -> bool {
+    let positions = state.ecs().read_storage::<Pos>();
+    positions
+        .get(a)
+        .zip(positions.get(b))
+        .map_or(false, |(a, b)| {
+            a.0.distance_squared(b.0) < DUEL_ARENA_RADIUS.powi(2)
+        })
+}
+
+/// Why two entities can't duel, if they can't.
+fn duel_blocked_reason(state: &State, inviter: Entity, invitee: Entity) -> Option<&'static str> {
+    let duels = state.ecs().read_storage::<Duel>();
+    if !in_game(state, inviter) {
+        Some("You must be playing a character to duel")
+    } else if !in_game(state, invitee) {
+        Some("They are not playing a character")
+    } else if duels.contains(inviter) {
+        Some("You are already in a duel")
+    } else if duels.contains(invitee) {
+        Some("They are already in a duel")
+    } else if !within_duel_range(state, inviter, invitee) {
+        Some("They are too far away")
+    } else {
+        None
+    }
+}
+
+pub fn can_invite(
+    state: &State,
+    clients: &ReadStorage<'_, Client>,
+    inviter: Entity,
+    invitee: Entity,
+) -> bool {
+    match duel_blocked_reason(state, inviter, invitee) {
+        Some(reason) => {
+            if let Some(client) = clients.get(inviter) {
+                client.send_fallible(ServerGeneral::server_msg(
+                    ChatType::Meta,
+                    format!("Invite failed, {}.", reason.to_lowercase()),
+                ));
+            }
+            false
+        },
+        None => true,
+    }
+}
+
+/// Begin the countdown to a duel between the inviter and invitee, after the
+/// invite has been accepted.
+pub fn handle_duel_invite_accepted(state: &State, inviter: Entity, invitee: Entity) {
+    let clients = state.ecs().read_storage::<Client>();
+    // Things may have changed since the invite was sent
+    if let Some(reason) = duel_blocked_reason(state, inviter, invitee) {
+        for client in clients.get(inviter).into_iter().chain(clients.get(invitee)) {
+            client.send_fallible(ServerGeneral::server_msg(
+                ChatType::Meta,
+                format!("The duel could not start: {}.", reason.to_lowercase()),
+            ));
+        }
+        return;
+    }
+
+    let positions = state.ecs().read_storage::<Pos>();
+    let (Some(inviter_pos), Some(invitee_pos)) = (positions.get(inviter), positions.get(invitee))
+    else {
+        return;
+    };
+    let arena = (inviter_pos.0 + invitee_pos.0) / 2.0;
+    let start = Time(state.ecs().read_resource::<Time>().0 + DUEL_COUNTDOWN_SECS);
+
+    let stats = state.ecs().read_storage::<comp::Stats>();
+    let mut duels = state.ecs().write_storage::<Duel>();
+    for (entity, opponent) in [(inviter, invitee), (invitee, inviter)] {
+        let _ = duels.insert(entity, Duel {
+            opponent,
+            arena,
+            start,
+            phase: DuelPhase::Countdown,
+        });
+        if let (Some(client), Some(opponent)) = (clients.get(entity), stats.get(opponent)) {
+            // TODO: Localise
+            client.send_fallible(ServerGeneral::server_msg(
+                ChatType::Meta,
+                format!(
+                    "Your duel against [{}] begins in {} seconds. Leaving the arena forfeits the \
+                     duel.",
+                    opponent.name, DUEL_COUNTDOWN_SECS
+                ),
+            ));
+        }
+    }
+}
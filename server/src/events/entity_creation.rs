@@ -25,7 +25,7 @@ use common::{
 };
 use common_net::{msg::ServerGeneral, sync::WorldSyncExt};
 use specs::{Builder, Entity as EcsEntity, WorldExt};
-use vek::{Rgb, Vec3};
+use vek::{Rgb, Vec2, Vec3};
 
 use super::group_manip::update_map_markers;
 
@@ -146,6 +146,18 @@ pub fn handle_create_npc(server: &mut Server, pos: Pos, mut npc: NpcBuilder) ->
         entity
     };
 
+    let entity = if npc.boss {
+        entity.with(comp::Boss)
+    } else {
+        entity
+    };
+
+    let entity = if let Some(encounter) = npc.encounter {
+        entity.with(encounter)
+    } else {
+        entity
+    };
+
     let new_entity = entity.build();
 
     // Add to group system if a pet
@@ -401,3 +413,23 @@ pub fn handle_create_waypoint(server: &mut Server, pos: Vec3<f32>) {
         ]))
         .build();
 }
+
+pub fn handle_create_dungeon_portal(
+    server: &mut Server,
+    pos: Vec3<f32>,
+    dungeon: Vec2<i32>,
+    exit: bool,
+) {
+    server
+        .state
+        .create_object(Pos(pos), comp::object::Body::DungeonPortal)
+        .with(LightEmitter {
+            col: Rgb::new(0.6, 0.2, 1.0),
+            strength: 3.0,
+            flicker: 1.0,
+            animated: true,
+        })
+        .with(comp::Immovable)
+        .with(sys::instance::DungeonPortal { dungeon, exit })
+        .build();
+}
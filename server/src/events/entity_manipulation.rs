@@ -21,8 +21,8 @@ use common::{
         inventory::item::{AbilityMap, MaterialStatManifest},
         item::flatten_counted_items,
         loot_owner::LootOwnerKind,
-        Alignment, Auras, Body, CharacterState, Energy, Group, Health, HealthChange, Inventory,
-        Player, Poise, Pos, SkillSet, Stats,
+        threat, Alignment, Auras, Body, CharacterState, Energy, Group, Health, HealthChange,
+        Inventory, Player, Poise, Pos, SkillSet, Stats,
     },
     event::{EventBus, ServerEvent},
     lottery::distribute_many,
@@ -67,11 +67,27 @@ pub fn handle_poise(server: &Server, entity: EcsEntity, change: comp::PoiseChang
     }
 }
 
-pub fn handle_health_change(server: &Server, entity: EcsEntity, change: HealthChange) {
+pub fn handle_health_change(server: &Server, entity: EcsEntity, mut change: HealthChange) {
     let ecs = &server.state.ecs();
+    let mut changed = false;
     if let Some(mut health) = ecs.write_storage::<Health>().get_mut(entity) {
+        // Duels are fought until one duellist is beaten, not to the death, so the loser
+        // is left with a sliver of health. The duel itself is ended by the duel system,
+        // so damage from the opponent is still clamped after the loser was beaten.
+        if let Some(duel) = ecs.write_storage::<comp::Duel>().get_mut(entity) {
+            let by_opponent = change
+                .damage_by()
+                .and_then(|by| ecs.entity_from_uid(by.uid().0))
+                .map_or(false, |attacker| {
+                    duel.opponent == attacker && duel.phase != comp::duel::DuelPhase::Countdown
+                });
+            if by_opponent && health.current() + change.amount < 1.0 {
+                change.amount = (1.0 - health.current()).min(0.0);
+                duel.phase = comp::duel::DuelPhase::Defeated;
+            }
+        }
         // If the change amount was not zero
-        let changed = health.change_by(change);
+        changed = health.change_by(change);
         if let (Some(pos), Some(uid)) = (
             ecs.read_storage::<Pos>().get(entity),
             ecs.read_storage::<Uid>().get(entity),
@@ -92,6 +108,25 @@ pub fn handle_health_change(server: &Server, entity: EcsEntity, change: HealthCh
             }
         }
     }
+    // Damage builds up threat against whoever dealt it, and healing builds up
+    // threat against the healer for every agent fighting the healed entity
+    let target_uid = ecs.read_storage::<Uid>().get(entity).copied();
+    if changed && let Some(by) = change.by.map(|by| by.uid()) {
+        let mut agents = ecs.write_storage::<Agent>();
+        if change.amount < 0.0 {
+            if Some(by) != target_uid
+                && let Some(agent) = agents.get_mut(entity)
+            {
+                agent.threat.add(by, -change.amount * threat::DAMAGE_THREAT);
+            }
+        } else if let Some(target_uid) = target_uid {
+            for agent in (&mut agents).join() {
+                if agent.threat.contains(target_uid) {
+                    agent.threat.add(by, change.amount * threat::HEAL_THREAT);
+                }
+            }
+        }
+    }
     // This if statement filters out anything under 5 damage, for DOT ticks
     // TODO: Find a better way to separate direct damage from DOT here
     let damage = -change.amount;
@@ -387,6 +422,38 @@ pub fn handle_destroy(server: &mut Server, entity: EcsEntity, last_change: Healt
         });
     })();
 
+    // Record the death, and the kill by whoever dealt the final blow, in the
+    // statistics of any characters involved
+    {
+        let mut statistics = state.ecs().write_storage::<comp::Statistics>();
+        if let Some(entity_statistics) = statistics.get_mut(entity) {
+            entity_statistics.deaths += 1;
+        }
+        if let Some(body) = state.ecs().read_storage::<Body>().get(entity)
+            && let Some(killer) = last_change
+                .by
+                .and_then(|by| state.ecs().entity_from_uid(by.uid().into()))
+            && killer != entity
+            && let Some(killer_statistics) = statistics.get_mut(killer)
+        {
+            killer_statistics.record_kill(body);
+        }
+
+        // Everyone who was awarded exp for killing the boss of a dungeon has cleared
+        // it
+        if state.ecs().read_storage::<comp::Boss>().contains(entity) {
+            let clearers = exp_awards
+                .iter()
+                .map(|(attacker, _, _)| *attacker)
+                .collect::<HashSet<_>>();
+            for clearer in clearers {
+                if let Some(clearer_statistics) = statistics.get_mut(clearer) {
+                    clearer_statistics.dungeons_cleared += 1;
+                }
+            }
+        }
+    }
+
     let should_delete = if state
         .ecs()
         .write_storage::<Client>()
@@ -954,6 +1021,8 @@ pub fn handle_explosion(server: &Server, pos: Vec3<f32>, explosion: Explosion, o
                 let alignments = &ecs.read_storage::<Alignment>();
                 let uid_allocator = &ecs.read_resource::<UidAllocator>();
                 let players = &ecs.read_storage::<Player>();
+                let guilds = &ecs.read_storage::<comp::Guild>();
+                let duels = &ecs.read_storage::<comp::Duel>();
                 let buffs = &ecs.read_storage::<comp::Buffs>();
                 let stats = &ecs.read_storage::<comp::Stats>();
                 let terrain = ecs.read_resource::<TerrainGrid>();
@@ -1048,6 +1117,8 @@ pub fn handle_explosion(server: &Server, pos: Vec3<f32>, explosion: Explosion, o
                         let may_harm = combat::may_harm(
                             alignments,
                             players,
+                            guilds,
+                            duels,
                             uid_allocator,
                             owner_entity,
                             entity_b,
@@ -1079,6 +1150,8 @@ pub fn handle_explosion(server: &Server, pos: Vec3<f32>, explosion: Explosion, o
                 let alignments = &ecs.read_storage::<Alignment>();
                 let uid_allocator = &ecs.read_resource::<UidAllocator>();
                 let players = &ecs.read_storage::<Player>();
+                let guilds = &ecs.read_storage::<comp::Guild>();
+                let duels = &ecs.read_storage::<comp::Duel>();
                 for (entity_b, pos_b, body_b_maybe) in (
                     &ecs.entities(),
                     &ecs.read_storage::<Pos>(),
@@ -1109,8 +1182,15 @@ pub fn handle_explosion(server: &Server, pos: Vec3<f32>, explosion: Explosion, o
                     //
                     // This can be changed later.
                     let may_harm = || {
-                        combat::may_harm(alignments, players, uid_allocator, owner_entity, entity_b)
-                            || owner_entity.map_or(true, |entity_a| entity_a == entity_b)
+                        combat::may_harm(
+                            alignments,
+                            players,
+                            guilds,
+                            duels,
+                            uid_allocator,
+                            owner_entity,
+                            entity_b,
+                        ) || owner_entity.map_or(true, |entity_a| entity_a == entity_b)
                     };
                     if strength > 0.0 {
                         let is_alive = ecs
@@ -1435,6 +1515,14 @@ pub fn handle_teleport_to(server: &Server, entity: EcsEntity, target: Uid, max_r
     }
 }
 
+pub fn handle_taunt(server: &Server, entity: EcsEntity, by: Uid, duration: f32) {
+    let ecs = &server.state.ecs();
+    let time = ecs.read_resource::<Time>();
+    if let Some(agent) = ecs.write_storage::<Agent>().get_mut(entity) {
+        agent.threat.taunt(by, time.0 + duration as f64);
+    }
+}
+
 /// Intended to handle things that should happen for any successful attack,
 /// regardless of the damages and effects specific to that attack
 pub fn handle_entity_attacked_hook(server: &Server, entity: EcsEntity) {
@@ -1448,10 +1536,14 @@ pub fn handle_entity_attacked_hook(server: &Server, entity: EcsEntity) {
         ecs.write_storage::<Poise>().get_mut(entity),
         ecs.read_storage::<Pos>().get(entity),
     ) {
-        // Interrupt sprite interaction and item use if any attack is applied to entity
+        // Interrupt sprite interaction, crafting, fishing and item use if any attack is
+        // applied to entity
         if matches!(
             *char_state,
-            CharacterState::SpriteInteract(_) | CharacterState::UseItem(_)
+            CharacterState::SpriteInteract(_)
+                | CharacterState::Crafting(_)
+                | CharacterState::Fishing(_)
+                | CharacterState::UseItem(_)
         ) {
             let poise_state = comp::poise::PoiseState::Interrupted;
             let was_wielded = char_state.is_wield();
@@ -0,0 +1,360 @@
+use crate::{
+    client::Client,
+    guild::{GuildError, GuildManager},
+    persistence::character_updater::CharacterUpdater,
+    Server,
+};
+use common::{
+    character::CharacterId,
+    comp::{
+        self,
+        guild::{GuildId, GuildPermission},
+        invite::{InviteKind, PendingInvites},
+        ChatType, Guild, GuildManip, Presence, PresenceKind,
+    },
+    uid::Uid,
+};
+use common_net::{msg::ServerGeneral, sync::WorldSyncExt};
+use common_state::State;
+use hashbrown::{HashMap, HashSet};
+use specs::{world::Entity, Join, ReadStorage, WorldExt, WriteStorage};
+
+/// Find the character that the entity is playing, along with its name.
+fn character_of(state: &State, entity: Entity) -> Option<(CharacterId, String)> {
+    let presences = state.ecs().read_storage::<Presence>();
+    let stats = state.ecs().read_storage::<comp::Stats>();
+    match presences.get(entity)?.kind {
+        PresenceKind::Character(character_id) => {
+            Some((character_id, stats.get(entity)?.name.clone()))
+        },
+        PresenceKind::Spectator | PresenceKind::Possessor => None,
+    }
+}
+
+/// Entities and uids of all characters that are currently being played.
+fn online_characters(state: &State) -> HashMap<CharacterId, (Entity, Uid)> {
+    (
+        &state.ecs().entities(),
+        &state.ecs().read_storage::<Presence>(),
+        &state.ecs().read_storage::<Uid>(),
+    )
+        .join()
+        .filter_map(|(entity, presence, uid)| match presence.kind {
+            PresenceKind::Character(character_id) => Some((character_id, (entity, *uid))),
+            PresenceKind::Spectator | PresenceKind::Possessor => None,
+        })
+        .collect()
+}
+
+/// Bring the guild components and rosters of the given characters up to date,
+/// and persist any changes that have been made to guilds.
+pub fn sync_guild_members(state: &State, characters: impl IntoIterator<Item = CharacterId>) {
+    let online = online_characters(state);
+    let mut guild_manager = state.ecs().write_resource::<GuildManager>();
+    let clients = state.ecs().read_storage::<Client>();
+    let mut guilds = state.ecs().write_storage::<Guild>();
+    let mut chat_modes = state.ecs().write_storage::<comp::ChatMode>();
+
+    for character_id in characters {
+        let Some((entity, _)) = online.get(&character_id) else { continue };
+        let membership = guild_manager.membership(character_id);
+        let roster = membership.as_ref().and_then(|guild| {
+            guild_manager.roster(guild.id, |character_id| {
+                online.get(&character_id).map(|(_, uid)| *uid)
+            })
+        });
+        match membership {
+            Some(guild) => {
+                let _ = guilds.insert(*entity, guild);
+            },
+            None => {
+                guilds.remove(*entity);
+                // Stop talking in the chat of a guild that they're no longer a member of
+                if let Some(mode @ comp::ChatMode::Guild(_)) = chat_modes.get_mut(*entity) {
+                    *mode = comp::ChatMode::default();
+                    if let Some(client) = clients.get(*entity) {
+                        client.send_fallible(ServerGeneral::ChatMode(mode.clone()));
+                    }
+                }
+            },
+        }
+        if let Some(client) = clients.get(*entity) {
+            client.send_fallible(ServerGeneral::GuildUpdate(roster));
+        }
+    }
+
+    state
+        .ecs()
+        .write_resource::<CharacterUpdater>()
+        .update_guilds(guild_manager.take_pending());
+}
+
+/// Update every member of the guild.
+pub fn sync_guild(state: &State, guild_id: GuildId) {
+    let members = state
+        .ecs()
+        .read_resource::<GuildManager>()
+        .members(guild_id)
+        .collect::<Vec<_>>();
+    sync_guild_members(state, members);
+}
+
+/// Remove characters that have been deleted from their guilds, passing on
+/// leadership of the guild if needed.
+pub fn handle_characters_deleted(server: &mut Server, characters: Vec<CharacterId>) {
+    let state = server.state();
+    let guilds = {
+        let mut guild_manager = state.ecs().write_resource::<GuildManager>();
+        characters
+            .into_iter()
+            .filter_map(|character_id| guild_manager.leave(character_id))
+            .collect::<HashSet<_>>()
+    };
+    for guild_id in guilds {
+        sync_guild(state, guild_id);
+    }
+}
+
+fn send_guild_meta(state: &State, guild_id: GuildId, msg: String) {
+    let name = state
+        .ecs()
+        .read_resource::<GuildManager>()
+        .guild(guild_id)
+        .map(|guild| guild.name.clone());
+    if let Some(name) = name {
+        // TODO: Localise
+        for (client, guild) in (
+            &state.ecs().read_storage::<Client>(),
+            &state.ecs().read_storage::<Guild>(),
+        )
+            .join()
+        {
+            if guild.id == guild_id {
+                client.send_fallible(ServerGeneral::server_msg(
+                    ChatType::GuildMeta(name.clone()),
+                    msg.clone(),
+                ));
+            }
+        }
+    }
+}
+
+fn notify(state: &State, entity: Entity, msg: impl Into<String>) {
+    if let Some(client) = state.ecs().read_storage::<Client>().get(entity) {
+        client.send_fallible(ServerGeneral::server_msg(ChatType::Meta, msg.into()));
+    }
+}
+
+pub fn can_invite(
+    state: &State,
+    clients: &ReadStorage<'_, Client>,
+    pending_invites: &mut WriteStorage<'_, PendingInvites>,
+    inviter: Entity,
+    invitee: Entity,
+) -> bool {
+    let guild_manager = state.ecs().read_resource::<GuildManager>();
+    let permitted = character_of(state, inviter)
+        .ok_or(GuildError::NotInGuild)
+        .and_then(|(character_id, _)| {
+            guild_manager.check_permission(character_id, GuildPermission::Invite)
+        });
+    // Only one guild invite may be pending at a time, to avoid spam
+    let already_inviting = pending_invites.get(inviter).map_or(false, |p| {
+        p.0.iter().any(|(_, kind, _)| *kind == InviteKind::Guild)
+    });
+
+    let msg = match (permitted, character_of(state, invitee)) {
+        (Err(err), _) => format!("Invite failed: {}", err),
+        (Ok(_), None) => "Invite failed, they are not playing a character".to_string(),
+        (Ok(_), Some((character_id, _))) if guild_manager.guild_of(character_id).is_some() => {
+            "Invite failed, they are already a member of a guild".to_string()
+        },
+        (Ok(_), Some(_)) if already_inviting => {
+            "Invite failed, you already have a pending guild invite".to_string()
+        },
+        (Ok(_), Some(_)) => return true,
+    };
+    if let Some(client) = clients.get(inviter) {
+        client.send_fallible(ServerGeneral::server_msg(ChatType::Meta, msg));
+    }
+    false
+}
+
+/// Add the invitee to the guild of the inviter, after they accept an invite.
+pub fn handle_guild_invite_accepted(state: &State, inviter: Entity, invitee: Entity) {
+    let (Some((inviter_id, _)), Some((invitee_id, invitee_name))) =
+        (character_of(state, inviter), character_of(state, invitee))
+    else {
+        return;
+    };
+
+    let result = {
+        let mut guild_manager = state.ecs().write_resource::<GuildManager>();
+        guild_manager
+            .check_permission(inviter_id, GuildPermission::Invite)
+            .and_then(|guild_id| {
+                guild_manager
+                    .add_member(guild_id, invitee_id, &invitee_name)
+                    .map(|()| guild_id)
+            })
+    };
+    match result {
+        Ok(guild_id) => {
+            sync_guild(state, guild_id);
+            send_guild_meta(
+                state,
+                guild_id,
+                format!("[{}] joined the guild", invitee_name),
+            );
+        },
+        Err(err) => notify(state, invitee, format!("Could not join the guild: {}", err)),
+    }
+}
+
+// TODO: turn chat messages into enums
+pub fn handle_guild(server: &mut Server, entity: Entity, manip: GuildManip) {
+    let state = server.state();
+    let Some((character_id, name)) = character_of(state, entity) else {
+        notify(state, entity, "You must be playing a character to manage guilds.");
+        return;
+    };
+    let target_of = |uid: Uid| {
+        state
+            .ecs()
+            .entity_from_uid(uid.into())
+            .and_then(|target| character_of(state, target))
+    };
+
+    match manip {
+        GuildManip::Create(guild_name) => {
+            let result = state.ecs().write_resource::<GuildManager>().create(
+                character_id,
+                &name,
+                &guild_name,
+            );
+            match result {
+                Ok(guild_id) => {
+                    sync_guild(state, guild_id);
+                    send_guild_meta(state, guild_id, format!("[{}] founded the guild", name));
+                },
+                Err(err) => notify(state, entity, format!("Could not create guild: {}", err)),
+            }
+        },
+        GuildManip::Leave => {
+            let guild_id = state
+                .ecs()
+                .read_resource::<GuildManager>()
+                .guild_of(character_id);
+            let Some(guild_id) = guild_id else {
+                notify(state, entity, GuildError::NotInGuild.to_string());
+                return;
+            };
+            send_guild_meta(state, guild_id, format!("[{}] left the guild", name));
+            state
+                .ecs()
+                .write_resource::<GuildManager>()
+                .leave(character_id);
+            sync_guild_members(state, [character_id]);
+            sync_guild(state, guild_id);
+        },
+        GuildManip::Kick(uid) => {
+            let Some((target_id, target_name)) = target_of(uid) else {
+                notify(state, entity, "Kick failed, target does not exist.");
+                return;
+            };
+            let result = state
+                .ecs()
+                .write_resource::<GuildManager>()
+                .kick(character_id, target_id);
+            match result {
+                Ok(guild_id) => {
+                    send_guild_meta(
+                        state,
+                        guild_id,
+                        format!("[{}] was removed from the guild", target_name),
+                    );
+                    if let Some(target) = state.ecs().entity_from_uid(uid.into()) {
+                        notify(state, target, "You have been removed from the guild.");
+                    }
+                    sync_guild_members(state, [target_id]);
+                    sync_guild(state, guild_id);
+                },
+                Err(err) => notify(state, entity, format!("Kick failed: {}", err)),
+            }
+        },
+        GuildManip::Promote(uid) | GuildManip::Demote(uid) => {
+            let promote = matches!(manip, GuildManip::Promote(_));
+            let Some((target_id, target_name)) = target_of(uid) else {
+                notify(state, entity, "Failed to change rank, target does not exist.");
+                return;
+            };
+            let result = state.ecs().write_resource::<GuildManager>().change_rank(
+                character_id,
+                target_id,
+                promote,
+            );
+            match result {
+                Ok(rank) => {
+                    let guild_id = state
+                        .ecs()
+                        .read_resource::<GuildManager>()
+                        .guild_of(target_id);
+                    if let Some(guild_id) = guild_id {
+                        sync_guild(state, guild_id);
+                        send_guild_meta(
+                            state,
+                            guild_id,
+                            format!("[{}] is now a guild {:?}", target_name, rank),
+                        );
+                    }
+                },
+                Err(err) => notify(state, entity, format!("Failed to change rank: {}", err)),
+            }
+        },
+        GuildManip::AssignLeader(uid) => {
+            let Some((target_id, target_name)) = target_of(uid) else {
+                notify(state, entity, "Failed to assign leader, target does not exist.");
+                return;
+            };
+            let result = state
+                .ecs()
+                .write_resource::<GuildManager>()
+                .assign_leader(character_id, target_id);
+            match result {
+                Ok(guild_id) => {
+                    sync_guild(state, guild_id);
+                    send_guild_meta(
+                        state,
+                        guild_id,
+                        format!("[{}] is now the guild leader", target_name),
+                    );
+                },
+                Err(err) => notify(state, entity, format!("Failed to assign leader: {}", err)),
+            }
+        },
+        GuildManip::Disband => {
+            let guild_id = state
+                .ecs()
+                .read_resource::<GuildManager>()
+                .guild_of(character_id);
+            let permitted = state
+                .ecs()
+                .read_resource::<GuildManager>()
+                .check_permission(character_id, GuildPermission::Disband);
+            match (guild_id, permitted) {
+                (Some(guild_id), Ok(_)) => {
+                    send_guild_meta(state, guild_id, format!("[{}] disbanded the guild", name));
+                    let result = state
+                        .ecs()
+                        .write_resource::<GuildManager>()
+                        .disband(character_id);
+                    if let Ok((_, members)) = result {
+                        sync_guild_members(state, members);
+                    }
+                },
+                (_, Err(err)) => notify(state, entity, format!("Could not disband guild: {}", err)),
+                (None, Ok(_)) => {},
+            }
+        },
+    }
+}
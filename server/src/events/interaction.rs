@@ -12,14 +12,19 @@ use common::{
         loot_owner::LootOwnerKind,
         pet::is_mountable,
         tool::{AbilityMap, ToolKind},
-        Inventory, LootOwner, Pos, SkillGroupKind,
+        Inventory, InventoryUpdate, InventoryUpdateEvent, LootOwner, Pos, SkillGroupKind,
+    },
+    consts::{
+        MAX_MOUNT_RANGE, MAX_SPRITE_MOUNT_RANGE, SOUND_OCCLUSION_DAMPENING,
+        SOUND_TRAVEL_DIST_PER_VOLUME,
     },
-    consts::{MAX_MOUNT_RANGE, MAX_SPRITE_MOUNT_RANGE, SOUND_TRAVEL_DIST_PER_VOLUME},
     event::EventBus,
+    fishing::{default_fishing_spots, WaterKind},
     link::Is,
     mounting::{Mounting, Rider, VolumeMounting, VolumePos, VolumeRider},
     outcome::Outcome,
-    terrain::{Block, SpriteKind},
+    spiral::Spiral2d,
+    terrain::{BiomeKind, Block, BlockKind, SpriteKind, TerrainGrid},
     uid::Uid,
     vol::ReadVol,
 };
@@ -312,23 +317,138 @@ pub fn handle_mine_block(
     }
 }
 
+/// The furthest that a character can be from the water they are fishing in
+const MAX_FISHING_RANGE: f32 = 32.0;
+
+pub fn handle_fish(server: &mut Server, entity: EcsEntity, pos: Vec3<i32>) {
+    let state = server.state_mut();
+    let in_range = state
+        .ecs()
+        .read_storage::<Pos>()
+        .get(entity)
+        .map_or(false, |entity_pos| {
+            entity_pos.0.distance_squared(pos.as_()) < MAX_FISHING_RANGE.powi(2)
+        });
+    let terrain = state.terrain();
+    if !in_range
+        || !terrain
+            .get(pos)
+            .map_or(false, |block| block.kind() == BlockKind::Water)
+    {
+        return;
+    }
+    let Some(chunk) = terrain.get_key(terrain.pos_key(pos)) else { return };
+
+    let water = WaterKind::from_chunk_meta(chunk.meta());
+    // Oceans and lakes are their own biome, so use the biome of their shore instead
+    let biome = match chunk.meta().biome() {
+        BiomeKind::Ocean | BiomeKind::Lake => Spiral2d::new()
+            .take(25)
+            .filter_map(|offset| terrain.get_key(terrain.pos_key(pos) + offset))
+            .map(|chunk| chunk.meta().biome())
+            .find(|biome| !matches!(biome, BiomeKind::Ocean | BiomeKind::Lake))
+            .unwrap_or_default(),
+        biome => biome,
+    };
+    drop(terrain);
+
+    let Some(items) = default_fishing_spots()
+        .read()
+        .loot(water, biome)
+        .and_then(|loot| loot.to_items())
+    else {
+        return;
+    };
+
+    let msm = &MaterialStatManifest::load().read();
+    let ability_map = &AbilityMap::load().read();
+    let mut drop_items = Vec::new();
+    {
+        let mut inventories = state.ecs().write_storage::<Inventory>();
+        let mut inventory_updates = state.ecs().write_storage::<InventoryUpdate>();
+        if let (Some(inventory), Ok(entry)) =
+            (inventories.get_mut(entity), inventory_updates.entry(entity))
+        {
+            let inventory_update = entry.or_insert_with(InventoryUpdate::default);
+            for item in flatten_counted_items(&items, ability_map, msm) {
+                // NOTE: We dup the item for message purposes.
+                let item_msg = item.duplicate(ability_map, msm);
+                match inventory.push(item) {
+                    Ok(_) => inventory_update.push(InventoryUpdateEvent::Collected(item_msg)),
+                    Err(_) => drop_items.push(item_msg),
+                }
+            }
+        }
+    }
+
+    // Anything that doesn't fit in the inventory is dropped at the fisher's feet
+    let entity_pos = state.ecs().read_storage::<Pos>().get(entity).copied();
+    let loot_owner = state
+        .ecs()
+        .uid_from_entity(entity)
+        .map(LootOwnerKind::Player)
+        .map(LootOwner::new);
+    if let Some(entity_pos) = entity_pos {
+        for item in drop_items {
+            state.create_item_drop(entity_pos, comp::Vel(Vec3::zero()), item, loot_owner);
+        }
+    }
+}
+
 pub fn handle_sound(server: &mut Server, sound: &Sound) {
     let ecs = &server.state.ecs();
     let positions = &ecs.read_storage::<Pos>();
     let agents = &mut ecs.write_storage::<comp::Agent>();
 
+    // Utterances are passed on to the whole squad of whoever made them, so that
+    // squad members can call each other for help
+    let source_squad = match sound.kind {
+        SoundKind::Utterance(..) => sound
+            .source
+            .and_then(|source| ecs.entity_from_uid(source.0))
+            .and_then(|source| agents.get(source))
+            .and_then(|agent| agent.squad),
+        _ => None,
+    };
+
+    let terrain = ecs.read_resource::<TerrainGrid>();
+
     // TODO: Reduce the complexity of this problem by using spatial partitioning
     // system
     for (agent, agent_pos) in (agents, positions).join() {
+        if source_squad.is_some() && agent.squad == source_squad {
+            agent.inbox.push_back(AgentEvent::ServerSound(*sound));
+            continue;
+        }
+
         // TODO: Use pathfinding for more dropoff around obstacles
         let agent_dist_sqrd = agent_pos.0.distance_squared(sound.pos);
-        let sound_travel_dist_sqrd = (sound.vol * SOUND_TRAVEL_DIST_PER_VOLUME).powi(2);
+        let propagate = |vol: f32| {
+            let sound_travel_dist_sqrd = (vol * SOUND_TRAVEL_DIST_PER_VOLUME).powi(2);
+            let vol_dropoff = agent_dist_sqrd / sound_travel_dist_sqrd * vol;
+            sound.with_new_vol(vol - vol_dropoff)
+        };
+        let mut propagated_sound = propagate(sound.vol);
+
+        let should_hear_sound = agent_dist_sqrd < agent.psyche.listen_dist.powi(2);
 
-        let vol_dropoff = agent_dist_sqrd / sound_travel_dist_sqrd * sound.vol;
-        let propagated_sound = sound.with_new_vol(sound.vol - vol_dropoff);
+        // Walls muffle sounds. Casting rays is expensive, so only check for walls
+        // if the sound could be heard otherwise.
+        if propagated_sound.vol > 0.00 && should_hear_sound {
+            let listen_pos = agent_pos.0 + Vec3::unit_z();
+            let is_occluded = terrain
+                .ray(sound.pos, listen_pos)
+                .until(Block::is_opaque)
+                .cast()
+                .0
+                .powi(2)
+                < sound.pos.distance_squared(listen_pos) - 0.01;
+            if is_occluded {
+                propagated_sound = propagate(sound.vol * SOUND_OCCLUSION_DAMPENING);
+            }
+        }
 
         let can_hear_sound = propagated_sound.vol > 0.00;
-        let should_hear_sound = agent_dist_sqrd < agent.psyche.listen_dist.powi(2);
 
         if can_hear_sound && should_hear_sound {
             agent
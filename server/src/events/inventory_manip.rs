@@ -1,6 +1,7 @@
 use hashbrown::HashSet;
 use rand::{seq::IteratorRandom, Rng};
 use specs::{join::Join, world::WorldExt, Builder, Entity as EcsEntity, WriteStorage};
+use std::time::Duration;
 use tracing::{debug, error, warn};
 use vek::{Rgb, Vec3};
 
@@ -9,17 +10,22 @@ use common::{
         self,
         group::members,
         item::{self, flatten_counted_items, tool::AbilityMap, MaterialStatManifest},
+        mail::{MAX_MAIL_ATTACHMENTS, MAX_MAIL_BODY_LEN},
+        market::{OrderKind, MARKET_CURRENCY},
         slot::{self, Slot},
         InventoryUpdate,
     },
     consts::MAX_PICKUP_RANGE,
+    effect::Effect,
+    farming::default_crops,
     mounting::VolumePos,
     recipe::{
         self, default_component_recipe_book, default_recipe_book, default_repair_recipe_book,
     },
     resources::Time,
+    states::crafting,
     terrain::{Block, SpriteKind},
-    trade::Trades,
+    trade::{SiteId, Trades},
     uid::Uid,
     util::find_dist::{self, FindDist},
     vol::ReadVol,
@@ -28,7 +34,19 @@ use common_net::sync::WorldSyncExt;
 use common_state::State;
 use comp::LightEmitter;
 
-use crate::{client::Client, Server, StateExt};
+#[cfg(feature = "persistent_world")]
+use crate::TerrainPersistence;
+use crate::{
+    client::Client,
+    events::player::character_update_data,
+    persistence::{
+        character_updater::CharacterUpdater,
+        mail::{MailDelivery, MailResponse, OutgoingMail},
+        market::{MarketChange, MarketResponse, NewOrder},
+    },
+    sys::farming::PlantedCrops,
+    Server, StateExt,
+};
 use common::{
     comp::{
         pet::is_tameable, Alignment, Body, CollectFailedReason, Group, InventoryUpdateEvent, Player,
@@ -54,6 +72,15 @@ pub fn snuff_lantern(storage: &mut WriteStorage<LightEmitter>, entity: EcsEntity
 
 #[allow(clippy::blocks_in_if_conditions)]
 pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::InventoryManip) {
+    // Orders are placed on the market of the site that the market board is in
+    let market_site = match &manip {
+        comp::InventoryManip::OpenMarket(market_sprite)
+        | comp::InventoryManip::PostSellOrder { market_sprite, .. }
+        | comp::InventoryManip::PostBuyOrder { market_sprite, .. } => {
+            market_site(&server.index, *market_sprite)
+        },
+        _ => None,
+    };
     let state = server.state_mut();
 
     let uid = if let Some(uid) = state.ecs().uid_from_entity(entity) {
@@ -94,6 +121,60 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
         })
     };
 
+    let get_craft_sprite = |state: &State, sprite_pos: Option<VolumePos>| {
+        sprite_pos
+            .filter(|pos| {
+                let entity_cylinder = get_cylinder(state, entity);
+                let in_range = within_pickup_range(entity_cylinder, || {
+                    pos.get_block_and_transform(
+                        &state.terrain(),
+                        &state.ecs().read_resource(),
+                        |e| {
+                            state
+                                .read_storage()
+                                .get(e)
+                                .copied()
+                                .zip(state.read_storage().get(e).copied())
+                        },
+                        &state.read_storage(),
+                    )
+                    .map(|(mat, _, _)| mat.mul_point(Vec3::broadcast(0.5)))
+                });
+                if !in_range {
+                    debug!(
+                        ?entity_cylinder,
+                        "Failed to craft recipe as not within range of required sprite, sprite \
+                         pos: {:?}",
+                        pos
+                    );
+                }
+                in_range
+            })
+            .and_then(|pos| {
+                pos.get_block(
+                    &state.terrain(),
+                    &state.ecs().read_resource(),
+                    &state.read_storage(),
+                )
+            })
+            .and_then(|block| block.get_sprite())
+    };
+
+    let knows_recipe = |state: &State, recipe: &str| {
+        let known = state
+            .ecs()
+            .read_storage::<comp::KnownRecipes>()
+            .get(entity)
+            .map_or(false, |known_recipes| known_recipes.knows(recipe));
+        if !known {
+            debug!(
+                ?entity,
+                "Failed to craft recipe {} as it is not known", recipe
+            );
+        }
+        known
+    };
+
     let mut inventories = state.ecs().write_storage::<comp::Inventory>();
     let mut inventory = if let Some(inventory) = inventories.get_mut(entity) {
         inventory
@@ -282,8 +363,22 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
                         );
                     }
 
+                    // Harvesting a crop that was planted by a player also yields seeds, and
+                    // leaves the soil tilled
+                    let crop = block
+                        .get_sprite()
+                        .filter(|_| ecs.write_resource::<PlantedCrops>().harvest(sprite_pos))
+                        .and_then(|sprite| default_crops().read().by_crop(sprite).cloned());
+
                     // If there are items to be reclaimed from the block, add it to the inventory
-                    if let Some(items) = comp::Item::try_reclaim_from_block(block) {
+                    if let Some(mut items) = comp::Item::try_reclaim_from_block(block) {
+                        if let Some(crop) = &crop {
+                            let (min, max) = crop.seeds_returned;
+                            let amount = rand::thread_rng().gen_range(min..=max);
+                            if amount > 0 {
+                                items.push((amount, comp::Item::new_from_asset_expect(&crop.seed)));
+                            }
+                        }
                         let msm = &MaterialStatManifest::load().read();
                         let ability_map = &AbilityMap::load().read();
                         for item in flatten_counted_items(&items, ability_map, msm) {
@@ -316,7 +411,13 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
                     }
 
                     // We made sure earlier the block was not already modified this tick
-                    block_change.set(sprite_pos, block.into_vacant());
+                    if crop.is_some() {
+                        let tilled_soil = block.into_vacant().with_sprite(SpriteKind::TilledSoil);
+                        block_change.set(sprite_pos, tilled_soil);
+                        persist_block(state, sprite_pos, tilled_soil);
+                    } else {
+                        block_change.set(sprite_pos, block.into_vacant());
+                    }
 
                     // If the block was a keyhole, remove nearby door blocks
                     // TODO: Abstract this code into a generalised way to do block updates?
@@ -473,6 +574,20 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
                         &state.ecs().read_resource::<MaterialStatManifest>(),
                     ) {
                         match &*item.kind() {
+                            ItemKind::Consumable {
+                                kind: item::ConsumableKind::Scroll,
+                                effects,
+                            } if !teaches_unknown_recipe(state, entity, effects) => {
+                                inventory.insert_or_stack_at(slot, item).expect(
+                                    "slot was just vacated of item, so it definitely fits there.",
+                                );
+                                send_notice(
+                                    state,
+                                    entity,
+                                    "You already know everything this scroll teaches.",
+                                );
+                                None
+                            },
                             ItemKind::Consumable { effects, .. } => {
                                 maybe_effect = Some(effects.clone());
                                 Some(InventoryUpdateEvent::Consumed(item.name().into_owned()))
@@ -784,52 +899,14 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
             let ability_map = &state.ecs().read_resource::<AbilityMap>();
             let msm = state.ecs().read_resource::<MaterialStatManifest>();
 
-            let get_craft_sprite = |state, sprite_pos: Option<VolumePos>| {
-                sprite_pos
-                    .filter(|pos| {
-                        let entity_cylinder = get_cylinder(state, entity);
-                        let in_range = within_pickup_range(entity_cylinder, || {
-                            pos.get_block_and_transform(
-                                &state.terrain(),
-                                &state.ecs().read_resource(),
-                                |e| {
-                                    state
-                                        .read_storage()
-                                        .get(e)
-                                        .copied()
-                                        .zip(state.read_storage().get(e).copied())
-                                },
-                                &state.read_storage(),
-                            )
-                            .map(|(mat, _, _)| mat.mul_point(Vec3::broadcast(0.5)))
-                        });
-                        if !in_range {
-                            debug!(
-                                ?entity_cylinder,
-                                "Failed to craft recipe as not within range of required sprite, \
-                                 sprite pos: {:?}",
-                                pos
-                            );
-                        }
-                        in_range
-                    })
-                    .and_then(|pos| {
-                        pos.get_block(
-                            &state.terrain(),
-                            &state.ecs().read_resource(),
-                            &state.read_storage(),
-                        )
-                    })
-                    .and_then(|block| block.get_sprite())
-            };
-
             let crafted_items = match craft_event {
                 CraftEvent::Simple {
-                    recipe,
+                    recipe: recipe_key,
                     slots,
                     amount,
                 } => recipe_book
-                    .get(&recipe)
+                    .get(&recipe_key)
+                    .filter(|_| knows_recipe(state, &recipe_key))
                     .filter(|r| {
                         if let Some(needed_sprite) = r.craft_sprite {
                             let sprite = get_craft_sprite(state, craft_sprite);
@@ -839,11 +916,27 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
                         }
                     })
                     .and_then(|r| {
+                        // Recipes made at a crafting station take time, so they are crafted
+                        // one at a time by the character state instead of all at once
+                        if let Some(craft_sprite) =
+                            craft_sprite.filter(|_| r.craft_sprite.is_some())
+                        {
+                            start_crafting(
+                                state,
+                                entity,
+                                recipe_key.clone(),
+                                craft_sprite,
+                                r.craft_duration(),
+                                amount,
+                            );
+                            return None;
+                        }
                         let items = (0..amount)
                             .filter_map(|_| {
                                 r.craft_simple(
                                     &mut inventory,
                                     slots.clone(),
+                                    craft_bonus(state, entity, r),
                                     &state.ecs().read_resource::<AbilityMap>(),
                                     &state.ecs().read_resource::<MaterialStatManifest>(),
                                 )
@@ -942,6 +1035,7 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
             // Attempt to insert items into inventory, dropping them if there is not enough
             // space
             let items_were_crafted = if let Some(crafted_items) = crafted_items {
+                record_crafted_items(state, entity, &crafted_items);
                 for item in crafted_items {
                     if let Err(item) = inventory.push(item) {
                         if let Some(pos) = state.read_component_copied::<comp::Pos>(entity) {
@@ -970,6 +1064,69 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
                 );
             }
         },
+        comp::InventoryManip::FinishCraft {
+            recipe,
+            craft_sprite,
+        } => {
+            let recipe_book = default_recipe_book().read();
+            let ability_map = &state.ecs().read_resource::<AbilityMap>();
+            let msm = state.ecs().read_resource::<MaterialStatManifest>();
+
+            let crafted_items = recipe_book
+                .get(&recipe)
+                .filter(|_| knows_recipe(state, &recipe))
+                .filter(|r| {
+                    r.craft_sprite.is_some()
+                        && r.craft_sprite == get_craft_sprite(state, Some(craft_sprite))
+                })
+                .and_then(|r| {
+                    // Earlier crafts of the batch may have used up some of the stacks that the
+                    // ingredients were in, so they're looked for again each time
+                    let slots = r.inventory_contains_ingredients(&inventory, 1).ok()?;
+                    r.craft_simple(
+                        &mut inventory,
+                        slots,
+                        craft_bonus(state, entity, r),
+                        ability_map,
+                        &msm,
+                    )
+                    .ok()
+                });
+
+            if let Some(crafted_items) = crafted_items {
+                record_crafted_items(state, entity, &crafted_items);
+                for item in crafted_items {
+                    if let Err(item) = inventory.push(item) {
+                        if let Some(pos) = state.read_component_copied::<comp::Pos>(entity) {
+                            dropped_items.push((
+                                pos,
+                                state
+                                    .read_component_copied::<comp::Ori>(entity)
+                                    .unwrap_or_default(),
+                                item,
+                            ));
+                        }
+                    }
+                }
+                drop(inventories);
+                let _ = state.ecs().write_storage().insert(
+                    entity,
+                    comp::InventoryUpdate::new(InventoryUpdateEvent::Craft),
+                );
+            } else {
+                drop(inventories);
+                // The rest of the batch can't be crafted either, since the ingredients ran
+                // out or the station is out of reach
+                if let Some(mut character_state) = state
+                    .ecs()
+                    .write_storage::<comp::CharacterState>()
+                    .get_mut(entity)
+                    .filter(|c| matches!(**c, comp::CharacterState::Crafting(_)))
+                {
+                    *character_state = comp::CharacterState::Idle(Default::default());
+                }
+            }
+        },
         comp::InventoryManip::Sort => {
             inventory.sort();
             drop(inventories);
@@ -978,6 +1135,522 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
             inventory.swap_equipped_weapons(time);
             drop(inventories);
         },
+        comp::InventoryManip::Plant { sprite_pos, seed } => {
+            let crop = inventory.get(seed).and_then(|item| {
+                default_crops()
+                    .read()
+                    .by_seed(item.item_definition_id().itemdef_id()?)
+                    .map(|spec| spec.crop)
+            });
+            // Seedlings can only grow in the open air
+            let is_open_air = state
+                .terrain()
+                .get(sprite_pos)
+                .map_or(false, |block| block.is_air());
+            let seedling = crop
+                .filter(|_| {
+                    is_open_air
+                        && state.can_set_block(sprite_pos)
+                        && within_sprite_range(
+                            state,
+                            get_cylinder(state, entity),
+                            sprite_pos,
+                            SpriteKind::TilledSoil,
+                        )
+                })
+                .map(Block::seedling);
+            if seedling.is_some() {
+                inventory.take(
+                    seed,
+                    &state.ecs().read_resource::<AbilityMap>(),
+                    &state.ecs().read_resource::<MaterialStatManifest>(),
+                );
+            }
+            drop(inventories);
+
+            if let Some(seedling) = seedling {
+                state.set_block(sprite_pos, seedling);
+                persist_block(state, sprite_pos, seedling);
+                state
+                    .ecs()
+                    .write_resource::<PlantedCrops>()
+                    .insert(sprite_pos);
+            }
+        },
+        comp::InventoryManip::OpenBank(bank_sprite) => {
+            drop(inventories);
+            if within_sprite_range(
+                state,
+                get_cylinder(state, entity),
+                bank_sprite,
+                SpriteKind::Bank,
+            ) {
+                send_bank(state, entity, bank_sprite);
+            }
+        },
+        comp::InventoryManip::BankDeposit { bank_sprite, slot } => {
+            if !within_sprite_range(
+                state,
+                get_cylinder(state, entity),
+                bank_sprite,
+                SpriteKind::Bank,
+            ) {
+                debug!(
+                    ?bank_sprite,
+                    "Failed to deposit item as not within range of bank"
+                );
+                return;
+            }
+            let mut banks = state.ecs().write_storage::<comp::Bank>();
+            let Some(bank) = banks.get_mut(entity) else { return };
+            let Some(item) = inventory.remove(slot) else { return };
+            // Either the whole stack is moved or nothing is, so that a failed deposit can
+            // never lose or duplicate items
+            if let Err(item) = bank.push(item) {
+                inventory
+                    .insert_at(slot, item)
+                    .expect("The item was just removed from this slot, so it must be empty.");
+                send_notice(state, entity, "Your bank is full.");
+            }
+            drop(banks);
+            drop(inventories);
+            send_bank(state, entity, bank_sprite);
+            state
+                .ecs()
+                .write_storage()
+                .insert(
+                    entity,
+                    comp::InventoryUpdate::new(InventoryUpdateEvent::Swapped),
+                )
+                .expect("We know entity exists since we got its inventory.");
+        },
+        comp::InventoryManip::BankWithdraw {
+            bank_sprite,
+            bank_slot,
+        } => {
+            if !within_sprite_range(
+                state,
+                get_cylinder(state, entity),
+                bank_sprite,
+                SpriteKind::Bank,
+            ) {
+                debug!(
+                    ?bank_sprite,
+                    "Failed to withdraw item as not within range of bank"
+                );
+                return;
+            }
+            let mut banks = state.ecs().write_storage::<comp::Bank>();
+            let Some(bank) = banks.get_mut(entity) else { return };
+            let Some(item) = bank.take(bank_slot) else { return };
+            if let Err(item) = inventory.push(item) {
+                bank.insert_at(bank_slot, item)
+                    .expect("The item was just taken from this slot, so it must be empty.");
+                send_notice(state, entity, "Your inventory is full.");
+            }
+            drop(banks);
+            drop(inventories);
+            send_bank(state, entity, bank_sprite);
+            state
+                .ecs()
+                .write_storage()
+                .insert(
+                    entity,
+                    comp::InventoryUpdate::new(InventoryUpdateEvent::Swapped),
+                )
+                .expect("We know entity exists since we got its inventory.");
+        },
+        comp::InventoryManip::OpenMailbox(mailbox_sprite) => {
+            drop(inventories);
+            if !within_sprite_range(
+                state,
+                get_cylinder(state, entity),
+                mailbox_sprite,
+                SpriteKind::Mailbox,
+            ) {
+                return;
+            }
+            let character_id = state
+                .ecs()
+                .read_storage::<comp::Presence>()
+                .get(entity)
+                .and_then(|presence| presence.kind.character_id());
+            if let Some(character_id) = character_id {
+                state
+                    .ecs()
+                    .write_resource::<CharacterUpdater>()
+                    .load_mailbox(entity, character_id, mailbox_sprite);
+            }
+        },
+        comp::InventoryManip::SendMail {
+            mailbox_sprite,
+            recipient,
+            body,
+            attachments,
+        } => {
+            if !within_sprite_range(
+                state,
+                get_cylinder(state, entity),
+                mailbox_sprite,
+                SpriteKind::Mailbox,
+            ) {
+                debug!(
+                    ?mailbox_sprite,
+                    "Failed to send mail as not within range of mailbox"
+                );
+                return;
+            }
+            let recipient = recipient.trim().to_string();
+            let is_valid = !recipient.is_empty()
+                && body.len() <= MAX_MAIL_BODY_LEN
+                && attachments.len() <= MAX_MAIL_ATTACHMENTS
+                && attachments.iter().collect::<HashSet<_>>().len() == attachments.len()
+                && attachments
+                    .iter()
+                    .all(|slot| inventory.get(*slot).is_some());
+            if !is_valid {
+                drop(inventories);
+                send_notice(state, entity, "That mail can't be sent.");
+                return;
+            }
+            // Only characters have a mailbox to send from
+            let is_character = state
+                .ecs()
+                .read_storage::<comp::Presence>()
+                .get(entity)
+                .map_or(false, |presence| presence.kind.character_id().is_some());
+            let sender_alias = state
+                .ecs()
+                .read_storage::<comp::Stats>()
+                .get(entity)
+                .map(|stats| stats.name.clone());
+            let Some(sender_alias) = sender_alias.filter(|_| is_character) else { return };
+
+            let attachments = attachments
+                .into_iter()
+                .filter_map(|slot| inventory.remove(slot))
+                .collect();
+            drop(inventories);
+            state
+                .ecs()
+                .write_storage()
+                .insert(
+                    entity,
+                    comp::InventoryUpdate::new(InventoryUpdateEvent::Swapped),
+                )
+                .expect("We know entity exists since we got its inventory.");
+
+            // The sender is persisted along with the mail, so that the attachments are
+            // never stored both in their inventory and in the mail
+            let Some(sender) = character_update_data(state, entity) else {
+                error!(?entity, "Failed to persist the sender of a mail");
+                return;
+            };
+            state.ecs().write_resource::<CharacterUpdater>().send_mail(
+                entity,
+                sender,
+                OutgoingMail {
+                    sender_alias,
+                    recipient_alias: recipient,
+                    body,
+                    attachments,
+                    sent_at: chrono::Utc::now().timestamp(),
+                },
+            );
+        },
+        comp::InventoryManip::ClaimMail {
+            mailbox_sprite,
+            mail_id,
+        } => {
+            if !within_sprite_range(
+                state,
+                get_cylinder(state, entity),
+                mailbox_sprite,
+                SpriteKind::Mailbox,
+            ) {
+                debug!(
+                    ?mailbox_sprite,
+                    "Failed to claim mail as not within range of mailbox"
+                );
+                return;
+            }
+            let mut mailboxes = state.ecs().write_storage::<comp::Mailbox>();
+            let Some(mailbox) = mailboxes.get_mut(entity) else { return };
+            let Some(mail) = mailbox.get(mail_id) else { return };
+            // Each attachment needs at most one slot, so checking this up front means that
+            // either all of the attachments are claimed or none are
+            if inventory.free_slots() < mail.attachments.len() {
+                drop(mailboxes);
+                drop(inventories);
+                send_notice(state, entity, "Your inventory is full.");
+                return;
+            }
+            let mail = mailbox
+                .take(mail_id)
+                .expect("We just checked that the mailbox has this mail.");
+            for item in mail.attachments {
+                if inventory.push(item).is_err() {
+                    error!("Failed to claim a mail attachment despite having enough free slots");
+                }
+            }
+            let mailbox = mailbox.clone();
+            drop(mailboxes);
+            drop(inventories);
+            state
+                .ecs()
+                .write_storage()
+                .insert(
+                    entity,
+                    comp::InventoryUpdate::new(InventoryUpdateEvent::Swapped),
+                )
+                .expect("We know entity exists since we got its inventory.");
+            if let Some(client) = state.ecs().read_storage::<Client>().get(entity) {
+                client.send_fallible(ServerGeneral::MailboxUpdate(mailbox_sprite, mailbox));
+            }
+
+            // The mailbox is only loaded for characters, so this always succeeds
+            if let Some(claimant) = character_update_data(state, entity) {
+                state
+                    .ecs()
+                    .write_resource::<CharacterUpdater>()
+                    .claim_mail(entity, claimant, mail_id);
+            }
+        },
+        comp::InventoryManip::OpenMarket(market_sprite) => {
+            drop(inventories);
+            if !within_sprite_range(
+                state,
+                get_cylinder(state, entity),
+                market_sprite,
+                SpriteKind::MarketBoard,
+            ) {
+                return;
+            }
+            let Some((site_id, _)) = market_site else {
+                send_notice(state, entity, "This market board doesn't belong to a settlement.");
+                return;
+            };
+            let character_id = state
+                .ecs()
+                .read_storage::<comp::Presence>()
+                .get(entity)
+                .and_then(|presence| presence.kind.character_id());
+            if let Some(character_id) = character_id {
+                state
+                    .ecs()
+                    .write_resource::<CharacterUpdater>()
+                    .load_market(entity, market_sprite, character_id, site_id);
+            }
+        },
+        comp::InventoryManip::PostSellOrder {
+            market_sprite,
+            slot,
+            quantity,
+            unit_price,
+        } => {
+            if !within_sprite_range(
+                state,
+                get_cylinder(state, entity),
+                market_sprite,
+                SpriteKind::MarketBoard,
+            ) {
+                debug!(
+                    ?market_sprite,
+                    "Failed to post sell order as not within range of market board"
+                );
+                return;
+            }
+            let Some((site_id, _)) = market_site else { return };
+            if quantity == 0 {
+                drop(inventories);
+                send_notice(state, entity, "You need to sell at least one item.");
+                return;
+            }
+            // Orders are matched by item definition, so only plain items can be sold
+            let item_definition_id = match inventory.get(slot).map(|item| item.item_definition_id())
+            {
+                Some(item::ItemDefinitionId::Simple(id)) if id != MARKET_CURRENCY => id.to_string(),
+                _ => {
+                    drop(inventories);
+                    send_notice(state, entity, "That item can't be sold on the market.");
+                    return;
+                },
+            };
+            if unit_price == 0 || unit_price.checked_mul(quantity).is_none() {
+                drop(inventories);
+                send_notice(state, entity, "That order can't be placed.");
+                return;
+            }
+            // Only characters can place orders, since they are paid by mail
+            let is_character = state
+                .ecs()
+                .read_storage::<comp::Presence>()
+                .get(entity)
+                .map_or(false, |presence| presence.kind.character_id().is_some());
+            if !is_character {
+                return;
+            }
+            let escrow = {
+                let ability_map = state.ecs().read_resource::<AbilityMap>();
+                let msm = state.ecs().read_resource::<MaterialStatManifest>();
+                inventory.take_amount(slot, quantity, &ability_map, &msm)
+            };
+            drop(inventories);
+            let Some(escrow) = escrow else {
+                send_notice(state, entity, "You don't have that many to sell.");
+                return;
+            };
+            state
+                .ecs()
+                .write_storage()
+                .insert(
+                    entity,
+                    comp::InventoryUpdate::new(InventoryUpdateEvent::Swapped),
+                )
+                .expect("We know entity exists since we got its inventory.");
+
+            post_market_order(state, entity, market_sprite, NewOrder {
+                site_id,
+                kind: OrderKind::Sell,
+                item_definition_id,
+                quantity,
+                unit_price,
+                escrow,
+                created_at: chrono::Utc::now().timestamp(),
+            });
+        },
+        comp::InventoryManip::PostBuyOrder {
+            market_sprite,
+            item_definition_id,
+            quantity,
+            unit_price,
+        } => {
+            if !within_sprite_range(
+                state,
+                get_cylinder(state, entity),
+                market_sprite,
+                SpriteKind::MarketBoard,
+            ) {
+                debug!(
+                    ?market_sprite,
+                    "Failed to post buy order as not within range of market board"
+                );
+                return;
+            }
+            let Some((site_id, _)) = market_site else { return };
+            let total = unit_price.checked_mul(quantity).filter(|total| {
+                *total > 0
+                    && item_definition_id != MARKET_CURRENCY
+                    && comp::Item::new_from_asset(&item_definition_id).is_ok()
+            });
+            let Some(total) = total else {
+                drop(inventories);
+                send_notice(state, entity, "That order can't be placed.");
+                return;
+            };
+            let is_character = state
+                .ecs()
+                .read_storage::<comp::Presence>()
+                .get(entity)
+                .map_or(false, |presence| presence.kind.character_id().is_some());
+            if !is_character {
+                return;
+            }
+            let coin_slots = inventory
+                .slots_with_id()
+                .filter_map(|(slot, item)| {
+                    item.as_ref()
+                        .filter(|item| {
+                            item.item_definition_id()
+                                == item::ItemDefinitionId::Simple(MARKET_CURRENCY)
+                        })
+                        .map(|item| (slot, item.amount()))
+                })
+                .collect::<Vec<_>>();
+            let owned_coins = coin_slots
+                .iter()
+                .map(|(_, amount)| u64::from(*amount))
+                .sum::<u64>();
+            if owned_coins < u64::from(total) {
+                drop(inventories);
+                send_notice(state, entity, "You don't have enough coins for that order.");
+                return;
+            }
+            // The coins may be spread over several stacks, which are combined into one
+            let mut escrow: Option<comp::Item> = None;
+            {
+                let ability_map = state.ecs().read_resource::<AbilityMap>();
+                let msm = state.ecs().read_resource::<MaterialStatManifest>();
+                let mut remaining = total;
+                for (slot, amount) in coin_slots {
+                    let taken = amount.min(remaining);
+                    if taken == 0 {
+                        break;
+                    }
+                    let Some(coins) = inventory.take_amount(slot, taken, &ability_map, &msm) else {
+                        continue;
+                    };
+                    remaining -= taken;
+                    match &mut escrow {
+                        Some(escrow) => {
+                            if escrow.increase_amount(taken).is_err() {
+                                error!("Failed to combine the coins for a buy order");
+                            }
+                        },
+                        None => escrow = Some(coins),
+                    }
+                }
+            }
+            drop(inventories);
+            let Some(escrow) = escrow else { return };
+            state
+                .ecs()
+                .write_storage()
+                .insert(
+                    entity,
+                    comp::InventoryUpdate::new(InventoryUpdateEvent::Swapped),
+                )
+                .expect("We know entity exists since we got its inventory.");
+
+            post_market_order(state, entity, market_sprite, NewOrder {
+                site_id,
+                kind: OrderKind::Buy,
+                item_definition_id,
+                quantity,
+                unit_price,
+                escrow,
+                created_at: chrono::Utc::now().timestamp(),
+            });
+        },
+        comp::InventoryManip::CancelMarketOrder {
+            market_sprite,
+            order_id,
+        } => {
+            drop(inventories);
+            if !within_sprite_range(
+                state,
+                get_cylinder(state, entity),
+                market_sprite,
+                SpriteKind::MarketBoard,
+            ) {
+                debug!(
+                    ?market_sprite,
+                    "Failed to cancel market order as not within range of market board"
+                );
+                return;
+            }
+            let character_id = state
+                .ecs()
+                .read_storage::<comp::Presence>()
+                .get(entity)
+                .and_then(|presence| presence.kind.character_id());
+            if let Some(character_id) = character_id {
+                state
+                    .ecs()
+                    .write_resource::<CharacterUpdater>()
+                    .cancel_market_order(entity, market_sprite, character_id, order_id);
+            }
+        },
     }
 
     // Drop items, Debug items should simply disappear when dropped
@@ -1060,6 +1733,46 @@ pub fn handle_inventory(server: &mut Server, entity: EcsEntity, manip: comp::Inv
     }
 }
 
+/// Puts the entity into the character state for crafting a recipe `amount`
+/// times at a crafting station.
+fn start_crafting(
+    state: &State,
+    entity: EcsEntity,
+    recipe: String,
+    craft_sprite: VolumePos,
+    craft_duration: Duration,
+    amount: u32,
+) {
+    if amount == 0 {
+        return;
+    }
+    let mut character_states = state.ecs().write_storage::<comp::CharacterState>();
+    if let Some(character_state) = character_states.get_mut(entity) {
+        *character_state = comp::CharacterState::Crafting(crafting::Data {
+            static_data: crafting::StaticData {
+                recipe,
+                craft_sprite,
+                craft_duration,
+                was_wielded: character_state.is_wield(),
+                was_sneak: character_state.is_stealthy(),
+            },
+            remaining: amount,
+            timer: Duration::default(),
+        });
+    }
+}
+
+/// How much the quality of an item crafted by the entity is improved by its
+/// skills and by luck.
+fn craft_bonus(state: &State, entity: EcsEntity, recipe: &recipe::Recipe) -> f32 {
+    let skill_bonus = state
+        .ecs()
+        .read_storage::<comp::SkillSet>()
+        .get(entity)
+        .map_or(0.0, |skill_set| recipe.skill_bonus(skill_set));
+    skill_bonus + rand::thread_rng().gen_range(-0.75..0.75)
+}
+
 fn within_pickup_range<S: FindDist<find_dist::Cylinder>>(
     entity_cylinder: Option<find_dist::Cylinder>,
     shape_fn: impl FnOnce() -> Option<S>,
@@ -1071,6 +1784,107 @@ fn within_pickup_range<S: FindDist<find_dist::Cylinder>>(
         .unwrap_or(false)
 }
 
+/// Writes a block change that should survive server restarts to terrain
+/// persistence, if it is enabled.
+fn persist_block(_state: &State, _pos: Vec3<i32>, _block: Block) {
+    #[cfg(feature = "persistent_world")]
+    if let Some(mut terrain_persistence) = _state.ecs().try_fetch_mut::<TerrainPersistence>() {
+        terrain_persistence.set_block(_pos, _block);
+    }
+}
+
+/// Whether the entity is close enough to use the sprite of the given kind at
+/// the given position, such as a bank or a mailbox.
+fn within_sprite_range(
+    state: &State,
+    entity_cylinder: Option<find_dist::Cylinder>,
+    sprite_pos: Vec3<i32>,
+    sprite_kind: SpriteKind,
+) -> bool {
+    let is_sprite = state
+        .terrain()
+        .get(sprite_pos)
+        .ok()
+        .and_then(|block| block.get_sprite())
+        == Some(sprite_kind);
+    is_sprite && within_pickup_range(entity_cylinder, || Some(sprite_pos.as_::<f32>() + 0.5))
+}
+
+fn send_bank(state: &State, entity: EcsEntity, bank_sprite: Vec3<i32>) {
+    if let (Some(client), Some(bank)) = (
+        state.ecs().read_storage::<Client>().get(entity),
+        state.ecs().read_storage::<comp::Bank>().get(entity),
+    ) {
+        client.send_fallible(ServerGeneral::BankUpdate(bank_sprite, bank.clone()));
+    }
+}
+
+/// Finds the site that a market board belongs to, which is the nearest site
+/// that the board is within, along with the name of the site.
+#[cfg(feature = "worldgen")]
+fn market_site(index: &world::IndexOwned, market_sprite: Vec3<i32>) -> Option<(SiteId, String)> {
+    let id = index.site_at(market_sprite.xy())?;
+    Some((id.id(), index.sites[id].name().to_string()))
+}
+
+#[cfg(not(feature = "worldgen"))]
+fn market_site(
+    _index: &crate::test_world::IndexOwned,
+    _market_sprite: Vec3<i32>,
+) -> Option<(SiteId, String)> {
+    None
+}
+
+/// Persists the character placing a market order along with the order, so that
+/// the escrowed items or coins are never stored both in their inventory and in
+/// the order.
+fn post_market_order(state: &State, entity: EcsEntity, market_sprite: Vec3<i32>, order: NewOrder) {
+    let Some(owner) = character_update_data(state, entity) else {
+        error!(?entity, "Failed to persist the owner of a market order");
+        return;
+    };
+    state
+        .ecs()
+        .write_resource::<CharacterUpdater>()
+        .post_market_order(entity, market_sprite, owner, order);
+}
+
+/// Whether reading a scroll with the given effects would teach the entity at
+/// least one recipe that it does not yet know.
+fn teaches_unknown_recipe(state: &State, entity: EcsEntity, effects: &item::Effects) -> bool {
+    let known_recipes = state.ecs().read_storage::<comp::KnownRecipes>();
+    let Some(known_recipes) = known_recipes.get(entity) else {
+        return false;
+    };
+    effects
+        .effects()
+        .iter()
+        .any(|effect| matches!(effect, Effect::LearnRecipe(recipe) if !known_recipes.knows(recipe)))
+}
+
+fn record_crafted_items(state: &State, entity: EcsEntity, crafted_items: &[comp::Item]) {
+    if let Some(statistics) = state
+        .ecs()
+        .write_storage::<comp::Statistics>()
+        .get_mut(entity)
+    {
+        statistics.items_crafted += crafted_items
+            .iter()
+            .map(|item| u64::from(item.amount()))
+            .sum::<u64>();
+    }
+}
+
+fn send_notice(state: &State, entity: EcsEntity, msg: &str) {
+    if let Some(client) = state.ecs().read_storage::<Client>().get(entity) {
+        // TODO: Localise
+        client.send_fallible(ServerGeneral::server_msg(
+            comp::ChatType::Meta,
+            msg.to_string(),
+        ));
+    }
+}
+
 fn announce_loot_to_group(
     group_id: &Group,
     ecs: &specs::World,
@@ -1139,3 +1953,235 @@ mod tests {
         },),);
     }
 }
+
+/// Handles the result of a mail request that was made by
+/// [`handle_inventory`] once the database has processed it.
+pub(crate) fn handle_mail_response(server: &mut Server, response: MailResponse) {
+    let state = server.state_mut();
+    match response {
+        MailResponse::Sent {
+            entity,
+            mail,
+            result: Ok(MailDelivery::Delivered(recipient_id)),
+        } => {
+            send_notice(
+                state,
+                entity,
+                &format!("Your mail to {} has been sent.", mail.recipient_alias),
+            );
+            let recipient = (
+                &state.ecs().entities(),
+                &state.ecs().read_storage::<comp::Presence>(),
+            )
+                .join()
+                .find(|(_, presence)| presence.kind.character_id() == Some(recipient_id))
+                .map(|(entity, _)| entity);
+            if let Some(recipient) = recipient {
+                send_notice(
+                    state,
+                    recipient,
+                    &format!("You have received mail from {}.", mail.sender_alias),
+                );
+            }
+        },
+        MailResponse::Sent {
+            entity,
+            mail,
+            result: Ok(MailDelivery::Returned(_)),
+        } => {
+            send_notice(
+                state,
+                entity,
+                &format!(
+                    "There is no one called {} to send mail to, so it has been returned to your \
+                     mailbox.",
+                    mail.recipient_alias
+                ),
+            );
+        },
+        MailResponse::Sent {
+            entity,
+            mail,
+            result: Err(e),
+        } => {
+            // Nothing was written, so the attachments are given back to the sender
+            warn!(
+                ?e,
+                "Failed to send mail, returning attachments to the sender"
+            );
+            let mut inventories = state.ecs().write_storage::<comp::Inventory>();
+            let Some(inventory) = inventories.get_mut(entity) else {
+                error!("Sender of a failed mail is gone, its attachments have been lost");
+                return;
+            };
+            for item in mail.attachments {
+                if let Err(item) = inventory.push(item) {
+                    error!(?item, "Failed to return a mail attachment to the sender");
+                }
+            }
+            drop(inventories);
+            let _ = state.ecs().write_storage().insert(
+                entity,
+                comp::InventoryUpdate::new(InventoryUpdateEvent::Swapped),
+            );
+            send_notice(state, entity, "Your mail could not be sent.");
+        },
+        MailResponse::Claimed {
+            entity,
+            mail_id,
+            result,
+        } => {
+            if let Err(e) = result {
+                error!(?e, ?mail_id, "Failed to claim mail");
+                send_notice(state, entity, "Your mail could not be claimed.");
+            }
+        },
+        MailResponse::Mailbox {
+            entity,
+            mailbox_sprite,
+            result,
+        } => match result {
+            Ok(mailbox) => {
+                if let Some(client) = state.ecs().read_storage::<Client>().get(entity) {
+                    client.send_fallible(ServerGeneral::MailboxUpdate(
+                        mailbox_sprite,
+                        mailbox.clone(),
+                    ));
+                }
+                let _ = state.ecs().write_storage().insert(entity, mailbox);
+            },
+            Err(e) => {
+                error!(?e, "Failed to load mailbox");
+                send_notice(state, entity, "Your mailbox could not be opened.");
+            },
+        },
+    }
+}
+
+/// Sends the open orders on a market board to the character viewing it, along
+/// with the prices of goods at the site as a reference.
+fn send_market(
+    server: &Server,
+    entity: EcsEntity,
+    market_sprite: Vec3<i32>,
+    orders: Vec<comp::MarketOrder>,
+) {
+    let Some((site_id, site_name)) = market_site(&server.index, market_sprite) else {
+        return;
+    };
+    #[cfg(feature = "worldgen")]
+    let prices = super::trade::site_prices_for_customer(
+        server.state.ecs(),
+        &server.index,
+        site_id,
+        Some(entity),
+    );
+    #[cfg(not(feature = "worldgen"))]
+    let prices = {
+        let _ = site_id;
+        None
+    };
+    if let Some(client) = server.state.ecs().read_storage::<Client>().get(entity) {
+        client.send_fallible(ServerGeneral::MarketUpdate(
+            market_sprite,
+            comp::MarketBoard {
+                site_name,
+                orders,
+                prices,
+            },
+        ));
+    }
+}
+
+pub(crate) fn handle_market_response(server: &mut Server, response: MarketResponse) {
+    match response {
+        MarketResponse::Posted {
+            entity,
+            market_sprite,
+            order: _,
+            result: Ok(MarketChange { orders, filled }),
+        } => {
+            send_market(server, entity, market_sprite, orders);
+            let state = server.state();
+            send_notice(state, entity, "Your order has been placed.");
+            for character_id in filled {
+                let owner = (
+                    &state.ecs().entities(),
+                    &state.ecs().read_storage::<comp::Presence>(),
+                )
+                    .join()
+                    .find(|(_, presence)| presence.kind.character_id() == Some(character_id))
+                    .map(|(entity, _)| entity);
+                if let Some(owner) = owner {
+                    send_notice(
+                        state,
+                        owner,
+                        "One of your market orders has been filled. The proceeds are waiting in \
+                         your mailbox.",
+                    );
+                }
+            }
+        },
+        MarketResponse::Posted {
+            entity,
+            market_sprite: _,
+            order,
+            result: Err(e),
+        } => {
+            // Nothing was written, so the escrowed items or coins are given back
+            warn!(
+                ?e,
+                "Failed to post market order, returning escrow to the owner"
+            );
+            let state = server.state();
+            let mut inventories = state.ecs().write_storage::<comp::Inventory>();
+            let Some(inventory) = inventories.get_mut(entity) else {
+                error!("Owner of a failed market order is gone, its escrow has been lost");
+                return;
+            };
+            if let Err(item) = inventory.push(order.escrow) {
+                error!(?item, "Failed to return the escrow of a market order");
+            }
+            drop(inventories);
+            let _ = state.ecs().write_storage().insert(
+                entity,
+                comp::InventoryUpdate::new(InventoryUpdateEvent::Swapped),
+            );
+            send_notice(state, entity, "Your order could not be placed.");
+        },
+        MarketResponse::Cancelled {
+            entity,
+            market_sprite,
+            result,
+        } => match result {
+            Ok(orders) => {
+                send_market(server, entity, market_sprite, orders);
+                send_notice(
+                    server.state(),
+                    entity,
+                    "Your order has been withdrawn, and what was left of it returned to your \
+                     mailbox.",
+                );
+            },
+            Err(e) => {
+                error!(?e, "Failed to cancel market order");
+                send_notice(server.state(), entity, "Your order could not be withdrawn.");
+            },
+        },
+        MarketResponse::Market {
+            entity,
+            market_sprite,
+            result,
+        } => match result {
+            Ok(orders) => send_market(server, entity, market_sprite, orders),
+            Err(e) => {
+                error!(?e, "Failed to load market");
+                send_notice(
+                    server.state(),
+                    entity,
+                    "The market board could not be opened.",
+                );
+            },
+        },
+    }
+}
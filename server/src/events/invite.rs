@@ -1,4 +1,10 @@
-use super::group_manip::{self, update_map_markers};
+#[cfg(feature = "worldgen")]
+use super::trade::site_prices_for_customer;
+use super::{
+    duel,
+    group_manip::{self, update_map_markers},
+    guild_manip,
+};
 use crate::{client::Client, Server};
 use common::{
     comp::{
@@ -67,38 +73,52 @@ pub fn handle_invite(server: &mut Server, inviter: Entity, invitee_uid: Uid, kin
         }
     }
 
-    if let InviteKind::Group = kind {
-        if !group_manip::can_invite(
-            state,
-            &clients,
-            &mut pending_invites,
-            max_group_size,
-            inviter,
-            invitee,
-        ) {
-            return;
-        }
-    } else {
-        // cancel current trades for inviter before inviting someone else to trade
-        let mut trades = state.ecs().write_resource::<Trades>();
-        if let Some(inviter_uid) = uids.get(inviter).copied() {
-            if let Some(active_trade) = trades.entity_trades.get(&inviter_uid).copied() {
-                trades
-                    .decline_trade(active_trade, inviter_uid)
-                    .and_then(|u| state.ecs().entity_from_uid(u.0))
-                    .map(|e| {
-                        if let Some(client) = clients.get(e) {
-                            client
-                                .send_fallible(ServerGeneral::FinishedTrade(TradeResult::Declined));
-                        }
-                        if let Some(agent) = agents.get_mut(e) {
-                            agent
-                                .inbox
-                                .push_back(AgentEvent::FinishedTrade(TradeResult::Declined));
-                        }
-                    });
+    match kind {
+        InviteKind::Group => {
+            if !group_manip::can_invite(
+                state,
+                &clients,
+                &mut pending_invites,
+                max_group_size,
+                inviter,
+                invitee,
+            ) {
+                return;
             }
-        };
+        },
+        InviteKind::Guild => {
+            if !guild_manip::can_invite(state, &clients, &mut pending_invites, inviter, invitee) {
+                return;
+            }
+        },
+        InviteKind::Duel => {
+            if !duel::can_invite(state, &clients, inviter, invitee) {
+                return;
+            }
+        },
+        InviteKind::Trade => {
+            // cancel current trades for inviter before inviting someone else to trade
+            let mut trades = state.ecs().write_resource::<Trades>();
+            if let Some(inviter_uid) = uids.get(inviter).copied() {
+                if let Some(active_trade) = trades.entity_trades.get(&inviter_uid).copied() {
+                    trades
+                        .decline_trade(active_trade, inviter_uid)
+                        .and_then(|u| state.ecs().entity_from_uid(u.0))
+                        .map(|e| {
+                            if let Some(client) = clients.get(e) {
+                                client.send_fallible(ServerGeneral::FinishedTrade(
+                                    TradeResult::Declined,
+                                ));
+                            }
+                            if let Some(agent) = agents.get_mut(e) {
+                                agent
+                                    .inbox
+                                    .push_back(AgentEvent::FinishedTrade(TradeResult::Declined));
+                            }
+                        });
+                }
+            };
+        },
     }
 
     if invites.contains(invitee) {
@@ -217,6 +237,8 @@ pub fn handle_invite_accept(server: &mut Server, entity: Entity) {
                     },
                 );
             },
+            InviteKind::Guild => guild_manip::handle_guild_invite_accepted(state, inviter, entity),
+            InviteKind::Duel => duel::handle_duel_invite_accepted(state, inviter, entity),
             InviteKind::Trade => {
                 if let (Some(inviter_uid), Some(invitee_uid)) =
                     (uids.get(inviter).copied(), uids.get(entity).copied())
@@ -245,15 +267,15 @@ pub fn handle_invite_accept(server: &mut Server, entity: Entity) {
                     let pricing = agents
                         .get(inviter)
                         .and_then(|a| {
-                            a.behavior
-                                .trade_site()
-                                .and_then(|id| index.get_site_prices(id))
+                            a.behavior.trade_site().and_then(|id| {
+                                site_prices_for_customer(state.ecs(), &index, id, Some(entity))
+                            })
                         })
                         .or_else(|| {
                             agents.get(entity).and_then(|a| {
-                                a.behavior
-                                    .trade_site()
-                                    .and_then(|id| index.get_site_prices(id))
+                                a.behavior.trade_site().and_then(|id| {
+                                    site_prices_for_customer(state.ecs(), &index, id, Some(inviter))
+                                })
                             })
                         });
                     #[cfg(not(feature = "worldgen"))]
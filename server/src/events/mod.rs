@@ -7,23 +7,24 @@ use crate::{
 use common::event::{EventBus, ServerEvent, ServerEventDiscriminants};
 use common_base::span;
 use entity_creation::{
-    handle_beam, handle_create_npc, handle_create_ship, handle_create_waypoint,
-    handle_initialize_character, handle_initialize_spectator, handle_loaded_character_data,
-    handle_shockwave, handle_shoot,
+    handle_beam, handle_create_dungeon_portal, handle_create_npc, handle_create_ship,
+    handle_create_waypoint, handle_initialize_character, handle_initialize_spectator,
+    handle_loaded_character_data, handle_shockwave, handle_shoot,
 };
 use entity_manipulation::{
     handle_aura, handle_bonk, handle_buff, handle_change_ability, handle_change_body,
     handle_combo_change, handle_delete, handle_destroy, handle_energy_change,
     handle_entity_attacked_hook, handle_explosion, handle_health_change, handle_knockback,
     handle_land_on_ground, handle_make_admin, handle_parry_hook, handle_poise,
-    handle_remove_light_emitter, handle_respawn, handle_stance_change, handle_teleport_to,
-    handle_update_map_marker,
+    handle_remove_light_emitter, handle_respawn, handle_stance_change, handle_taunt,
+    handle_teleport_to, handle_update_map_marker,
 };
 use group_manip::handle_group;
+use guild_manip::handle_guild;
 use information::handle_site_info;
 use interaction::{
-    handle_create_sprite, handle_lantern, handle_mine_block, handle_mount, handle_npc_interaction,
-    handle_sound, handle_unmount,
+    handle_create_sprite, handle_fish, handle_lantern, handle_mine_block, handle_mount,
+    handle_npc_interaction, handle_sound, handle_unmount,
 };
 use inventory_manip::handle_inventory;
 use invite::{handle_invite, handle_invite_response};
@@ -33,11 +34,14 @@ use trade::handle_process_trade_action;
 
 use crate::events::player::handle_character_delete;
 pub use group_manip::update_map_markers;
+pub(crate) use inventory_manip::{handle_mail_response, handle_market_response};
 pub(crate) use trade::cancel_trades_for;
 
+mod duel;
 mod entity_creation;
 mod entity_manipulation;
 mod group_manip;
+pub(crate) mod guild_manip;
 mod information;
 mod interaction;
 mod inventory_manip;
@@ -74,10 +78,19 @@ impl Server {
 
         use strum::VariantNames;
         let mut event_counts = vec![0u32; ServerEventDiscriminants::VARIANTS.len()];
+        let recording_replay = self
+            .state
+            .ecs()
+            .read_resource::<crate::sys::replay::ReplayRecorder>()
+            .enabled();
+        let mut replay_events = Vec::new();
 
         for event in events {
             // Count events by variant for metrics
             event_counts[ServerEventDiscriminants::from(&event) as usize] += 1;
+            if recording_replay {
+                replay_events.extend(crate::sys::replay::replay_event(self.state.ecs(), &event));
+            }
 
             match event {
                 ServerEvent::Explosion {
@@ -119,6 +132,7 @@ impl Server {
                 ServerEvent::Destroy { entity, cause } => handle_destroy(self, entity, cause),
                 ServerEvent::InventoryManip(entity, manip) => handle_inventory(self, entity, manip),
                 ServerEvent::GroupManip(entity, manip) => handle_group(self, entity, manip),
+                ServerEvent::GuildManip(entity, manip) => handle_guild(self, entity, manip),
                 ServerEvent::Respawn(entity) => handle_respawn(self, entity),
                 ServerEvent::LandOnGround {
                     entity,
@@ -179,6 +193,10 @@ impl Server {
                         pets,
                         active_abilities,
                         map_marker,
+                        known_recipes,
+                        statistics,
+                        achievements,
+                        bank,
                     ) = components;
                     let components = PersistedComponents {
                         body,
@@ -189,6 +207,10 @@ impl Server {
                         pets,
                         active_abilities,
                         map_marker,
+                        known_recipes,
+                        statistics,
+                        achievements,
+                        bank,
                     };
                     handle_loaded_character_data(self, entity, components, metadata);
                 },
@@ -206,6 +228,9 @@ impl Server {
                     driver,
                 } => handle_create_ship(self, pos, ori, ship, rtsim_entity, driver, Vec::new()),
                 ServerEvent::CreateWaypoint(pos) => handle_create_waypoint(self, pos),
+                ServerEvent::CreateDungeonPortal { pos, dungeon, exit } => {
+                    handle_create_dungeon_portal(self, pos, dungeon, exit)
+                },
                 ServerEvent::ClientDisconnect(entity, reason) => {
                     frontend_events.push(handle_client_disconnect(self, entity, reason, false))
                 },
@@ -244,6 +269,7 @@ impl Server {
                 ServerEvent::MineBlock { entity, pos, tool } => {
                     handle_mine_block(self, entity, pos, tool)
                 },
+                ServerEvent::Fish { entity, pos } => handle_fish(self, entity, pos),
                 ServerEvent::TeleportTo {
                     entity,
                     target,
@@ -265,6 +291,11 @@ impl Server {
                 ServerEvent::EntityAttackedHook { entity } => {
                     handle_entity_attacked_hook(self, entity)
                 },
+                ServerEvent::Taunt {
+                    entity,
+                    by,
+                    duration,
+                } => handle_taunt(self, entity, by, duration),
                 ServerEvent::ChangeAbility {
                     entity,
                     slot,
@@ -291,6 +322,11 @@ impl Server {
             }
         }
 
+        self.state
+            .ecs()
+            .write_resource::<crate::sys::replay::ReplayRecorder>()
+            .record_server_events(&event_counts, replay_events);
+
         {
             let server_event_metrics = self
                 .state
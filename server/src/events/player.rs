@@ -1,7 +1,11 @@
 use super::Event;
 use crate::{
-    client::Client, metrics::PlayerMetrics, persistence::character_updater::CharacterUpdater,
-    state_ext::StateExt, BattleModeBuffer, Server,
+    client::Client,
+    metrics::PlayerMetrics,
+    persistence::character_updater::{CharacterUpdateData, CharacterUpdater},
+    state_ext::StateExt,
+    sys::instance::DungeonInstances,
+    BattleModeBuffer, Server,
 };
 use common::{
     character::CharacterId,
@@ -48,6 +52,8 @@ pub fn handle_exit_ingame(server: &mut Server, entity: EcsEntity, skip_persisten
     span!(_guard, "handle_exit_ingame");
     let state = server.state_mut();
 
+    leave_dungeon_instance(state, entity);
+
     // Sync the player's character data to the database. This must be done before
     // removing any components from the entity
     let entity = if !skip_persistence {
@@ -69,6 +75,11 @@ pub fn handle_exit_ingame(server: &mut Server, entity: EcsEntity, skip_persisten
     // key could be switched from `Uid` to `Entity`)
     super::cancel_trades_for(state, entity);
 
+    let maybe_guild = state
+        .ecs()
+        .read_storage::<comp::Guild>()
+        .get(entity)
+        .map(|guild| guild.id);
     let maybe_admin = state.ecs().write_storage::<comp::Admin>().remove(entity);
     let maybe_group = state
         .ecs()
@@ -137,6 +148,11 @@ pub fn handle_exit_ingame(server: &mut Server, entity: EcsEntity, skip_persisten
             "Failed to delete entity when removing character"
         );
     }
+
+    // Let the rest of the guild know that the character went offline
+    if let Some(guild) = maybe_guild {
+        super::guild_manip::sync_guild(state, guild);
+    }
 }
 
 fn get_reason_str(reason: &comp::DisconnectReason) -> &str {
@@ -219,98 +235,178 @@ pub fn handle_client_disconnect(
         )));
     }
 
+    leave_dungeon_instance(state, entity);
+
     // Sync the player's character data to the database
     if !skip_persistence {
         entity = persist_entity(state, entity);
     }
 
+    let maybe_guild = state
+        .ecs()
+        .read_storage::<comp::Guild>()
+        .get(entity)
+        .map(|guild| guild.id);
+
     // Delete client entity
     if let Err(e) = server.state.delete_entity_recorded(entity) {
         error!(?e, ?entity, "Failed to delete disconnected client");
     }
 
+    // Let the rest of the guild know that the character went offline
+    if let Some(guild) = maybe_guild {
+        super::guild_manip::sync_guild(&server.state, guild);
+    }
+
     Event::ClientDisconnected { entity }
 }
 
-// When a player logs out, their data is queued for persistence in the next tick
-// of the persistence batch update. The player will be
-// temporarily unable to log in during this period to avoid
-// the race condition of their login fetching their old data
-// and overwriting the data saved here.
-fn persist_entity(state: &mut State, entity: EcsEntity) -> EcsEntity {
-    if let (
+/// Takes a snapshot of the persisted components of a character, or `None` if
+/// the entity is not controlling a character.
+pub(crate) fn character_update_data(
+    state: &State,
+    entity: EcsEntity,
+) -> Option<CharacterUpdateData> {
+    let presences = state.read_storage::<Presence>();
+    let skill_sets = state.read_storage::<comp::SkillSet>();
+    let inventories = state.read_storage::<comp::Inventory>();
+    let active_abilities = state.read_storage::<comp::ability::ActiveAbilities>();
+    let uids = state.read_storage::<Uid>();
+    let players = state.read_storage::<comp::Player>();
+    let (
         Some(presence),
         Some(skill_set),
         Some(inventory),
         Some(active_abilities),
         Some(player_uid),
         Some(player_info),
-        mut character_updater,
-        mut battlemode_buffer,
     ) = (
-        state.read_storage::<Presence>().get(entity),
-        state.read_storage::<comp::SkillSet>().get(entity),
-        state.read_storage::<comp::Inventory>().get(entity),
-        state
-            .read_storage::<comp::ability::ActiveAbilities>()
-            .get(entity),
-        state.read_storage::<Uid>().get(entity),
-        state.read_storage::<comp::Player>().get(entity),
-        state.ecs().fetch_mut::<CharacterUpdater>(),
-        state.ecs().fetch_mut::<BattleModeBuffer>(),
-    ) {
-        match presence.kind {
-            PresenceKind::Character(char_id) => {
-                let waypoint = state
-                    .ecs()
-                    .read_storage::<comp::Waypoint>()
-                    .get(entity)
-                    .cloned();
-                let map_marker = state
-                    .ecs()
-                    .read_storage::<comp::MapMarker>()
-                    .get(entity)
-                    .cloned();
-                // Store last battle mode change
-                if let Some(change) = player_info.last_battlemode_change {
-                    let mode = player_info.battle_mode;
-                    let save = (mode, change);
-                    battlemode_buffer.push(char_id, save);
-                }
+        presences.get(entity),
+        skill_sets.get(entity),
+        inventories.get(entity),
+        active_abilities.get(entity),
+        uids.get(entity),
+        players.get(entity),
+    ) else {
+        return None;
+    };
+    let PresenceKind::Character(char_id) = presence.kind else {
+        // Spectators and possessors are not persisted
+        return None;
+    };
 
-                // Get player's pets
-                let alignments = state.ecs().read_storage::<comp::Alignment>();
-                let bodies = state.ecs().read_storage::<comp::Body>();
-                let stats = state.ecs().read_storage::<comp::Stats>();
-                let pets = state.ecs().read_storage::<comp::Pet>();
-                let pets = (&alignments, &bodies, &stats, &pets)
-                    .join()
-                    .filter_map(|(alignment, body, stats, pet)| match alignment {
-                        // Don't try to persist non-tameable pets (likely spawned
-                        // using /spawn) since there isn't any code to handle
-                        // persisting them
-                        common::comp::Alignment::Owned(ref pet_owner)
-                            if pet_owner == player_uid && is_tameable(body) =>
-                        {
-                            Some(((*pet).clone(), *body, stats.clone()))
-                        },
-                        _ => None,
-                    })
-                    .collect();
-
-                character_updater.add_pending_logout_update((
-                    char_id,
-                    skill_set.clone(),
-                    inventory.clone(),
-                    pets,
-                    waypoint,
-                    active_abilities.clone(),
-                    map_marker,
-                ));
+    let waypoint = state
+        .ecs()
+        .read_storage::<comp::Waypoint>()
+        .get(entity)
+        .cloned();
+    let map_marker = state
+        .ecs()
+        .read_storage::<comp::MapMarker>()
+        .get(entity)
+        .cloned();
+    let known_recipes = state
+        .ecs()
+        .read_storage::<comp::KnownRecipes>()
+        .get(entity)
+        .cloned()
+        .unwrap_or_default();
+    let bank = state
+        .ecs()
+        .read_storage::<comp::Bank>()
+        .get(entity)
+        .map(|bank| (player_info.uuid().to_string(), bank.clone()));
+    let statistics = state
+        .ecs()
+        .read_storage::<comp::Statistics>()
+        .get(entity)
+        .cloned()
+        .unwrap_or_default();
+    let achievements = state
+        .ecs()
+        .read_storage::<comp::Achievements>()
+        .get(entity)
+        .map(|achievements| (player_info.uuid().to_string(), achievements.clone()));
+
+    // Get player's pets
+    let alignments = state.ecs().read_storage::<comp::Alignment>();
+    let bodies = state.ecs().read_storage::<comp::Body>();
+    let stats = state.ecs().read_storage::<comp::Stats>();
+    let pets = state.ecs().read_storage::<comp::Pet>();
+    let pets = (&alignments, &bodies, &stats, &pets)
+        .join()
+        .filter_map(|(alignment, body, stats, pet)| match alignment {
+            // Don't try to persist non-tameable pets (likely spawned
+            // using /spawn) since there isn't any code to handle
+            // persisting them
+            common::comp::Alignment::Owned(ref pet_owner)
+                if pet_owner == player_uid && is_tameable(body) =>
+            {
+                Some(((*pet).clone(), *body, stats.clone()))
             },
-            PresenceKind::Spectator => { /* Do nothing, spectators do not need persisting */ },
-            PresenceKind::Possessor => { /* Do nothing, possessor's are not persisted */ },
-        };
+            _ => None,
+        })
+        .collect();
+
+    Some((
+        char_id,
+        skill_set.clone(),
+        inventory.clone(),
+        pets,
+        waypoint,
+        active_abilities.clone(),
+        map_marker,
+        known_recipes,
+        statistics,
+        achievements,
+        bank,
+    ))
+}
+
+/// Take a player that is leaving the game out of any copy of a dungeon that
+/// they're in, so that they come back at its entrance rather than in a copy
+/// that may have been torn down by then.
+fn leave_dungeon_instance(state: &State, entity: EcsEntity) {
+    let ecs = state.ecs();
+    let mut instances = ecs.write_resource::<DungeonInstances>();
+    if let Some(uid) = ecs.read_storage::<Uid>().get(entity) {
+        instances.remove_player(*uid);
+    }
+    if let Some(pos) = ecs.write_storage::<comp::Pos>().get_mut(entity)
+        && let Some(entrance) = instances.surface_pos(pos.0)
+    {
+        pos.0 = entrance;
+    }
+    if let Some(waypoint) = ecs.write_storage::<comp::Waypoint>().get_mut(entity)
+        && let Some(entrance) = instances.surface_pos(waypoint.get_pos())
+    {
+        *waypoint = comp::Waypoint::new(entrance, *ecs.read_resource::<Time>());
+    }
+}
+
+// When a player logs out, their data is queued for persistence in the next tick
+// of the persistence batch update. The player will be
+// temporarily unable to log in during this period to avoid
+// the race condition of their login fetching their old data
+// and overwriting the data saved here.
+fn persist_entity(state: &mut State, entity: EcsEntity) -> EcsEntity {
+    if let Some(update_data) = character_update_data(state, entity) {
+        // Store last battle mode change
+        if let Some(player_info) = state.read_storage::<comp::Player>().get(entity)
+            && let Some(change) = player_info.last_battlemode_change
+        {
+            let mode = player_info.battle_mode;
+            let save = (mode, change);
+            state
+                .ecs()
+                .fetch_mut::<BattleModeBuffer>()
+                .push(update_data.0, save);
+        }
+
+        state
+            .ecs()
+            .fetch_mut::<CharacterUpdater>()
+            .add_pending_logout_update(update_data);
     }
 
     entity
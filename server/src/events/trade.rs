@@ -6,8 +6,12 @@ use common::{
             item::{tool::AbilityMap, ItemDefinitionIdOwned, MaterialStatManifest},
             Inventory,
         },
+        Reputation,
+    },
+    trade::{
+        PendingTrade, ReducedInventory, SiteId, SitePrices, TradeAction, TradeId, TradeResult,
+        Trades,
     },
-    trade::{PendingTrade, ReducedInventory, TradeAction, TradeId, TradeResult, Trades},
 };
 use common_net::{
     msg::ServerGeneral,
@@ -39,8 +43,14 @@ fn notify_agent_prices(
         if let AgentEvent::UpdatePendingTrade(boxval) = event {
             // Prefer using this Agent's price data, but use the counterparty's price
             // data if we don't have price data
+            // The counterparty's prices have already been adjusted for the reputation of the
+            // customer, so keep that adjustment
             let prices = site_id
                 .and_then(|site_id| index.get_site_prices(site_id))
+                .map(|prices| SitePrices {
+                    reputation_bonus: boxval.2.reputation_bonus,
+                    ..prices
+                })
                 .unwrap_or(boxval.2);
             // Box<(tid, pend, _, inventories)>) = event {
             agent
@@ -52,6 +62,27 @@ fn notify_agent_prices(
     }
 }
 
+/// Get the prices of goods at a site, adjusted for the reputation that the
+/// customer trading with the site's merchant has there.
+#[cfg(feature = "worldgen")]
+pub(super) fn site_prices_for_customer(
+    ecs: &specs::World,
+    index: &IndexOwned,
+    site: SiteId,
+    customer: Option<EcsEntity>,
+) -> Option<SitePrices> {
+    let reputation = customer
+        .and_then(|customer| {
+            ecs.read_storage::<Reputation>()
+                .get(customer)
+                .map(|reputation| reputation.toward_site(site))
+        })
+        .unwrap_or(0.0);
+    index
+        .get_site_prices(site)
+        .map(|prices| prices.with_reputation(reputation))
+}
+
 /// Invoked when the trade UI is up, handling item changes, accepts, etc
 pub(super) fn handle_process_trade_action(
     server: &mut Server,
@@ -123,10 +154,19 @@ pub(super) fn handle_process_trade_action(
                             #[cfg(feature = "worldgen")]
                             {
                                 prices = prices.or_else(|| {
-                                    agents
-                                        .get(e)
-                                        .and_then(|a| a.behavior.trade_site())
-                                        .and_then(|id| server.index.get_site_prices(id))
+                                    agents.get(e).and_then(|a| a.behavior.trade_site()).and_then(
+                                        |id| {
+                                            site_prices_for_customer(
+                                                server.state.ecs(),
+                                                &server.index,
+                                                id,
+                                                server
+                                                    .state
+                                                    .ecs()
+                                                    .entity_from_uid(parties[1 - i].0),
+                                            )
+                                        },
+                                    )
                                 });
                             }
                         }
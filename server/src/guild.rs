@@ -0,0 +1,567 @@
+//! Player guilds.
+//!
+//! The [`GuildManager`] is the authoritative record of guild membership while
+//! the server is running. It is loaded from the database at startup, and every
+//! change made to it is recorded as a [`GuildUpdate`] so that it can be written
+//! back through the [`CharacterUpdater`].
+//!
+//! [`CharacterUpdater`]: crate::persistence::character_updater::CharacterUpdater
+
+use common::{
+    character::CharacterId,
+    comp::{
+        guild::{GuildId, GuildMember, GuildPermission, MAX_GUILD_NAME_LEN},
+        Guild, GuildRank, GuildRoster,
+    },
+    uid::Uid,
+};
+use hashbrown::HashMap;
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct GuildMemberInfo {
+    pub name: String,
+    pub rank: GuildRank,
+    /// When the member joined, relative to other members of the guild.
+    pub joined: i64,
+}
+
+#[derive(Clone, Debug)]
+pub struct GuildInfo {
+    pub name: String,
+    pub members: HashMap<CharacterId, GuildMemberInfo>,
+}
+
+impl GuildInfo {
+    fn leader(&self) -> Option<CharacterId> {
+        self.members
+            .iter()
+            .find(|(_, member)| member.rank == GuildRank::Leader)
+            .map(|(character_id, _)| *character_id)
+    }
+
+    /// The member that should take over the guild if its leader leaves: the
+    /// highest-ranked, longest-standing member.
+    fn successor(&self) -> Option<CharacterId> {
+        self.members
+            .iter()
+            .filter(|(_, member)| member.rank != GuildRank::Leader)
+            .max_by_key(|(character_id, member)| (member.rank, -member.joined, -character_id.0))
+            .map(|(character_id, _)| *character_id)
+    }
+}
+
+/// A change to guild data that must be persisted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GuildUpdate {
+    Create {
+        guild: GuildId,
+        name: String,
+    },
+    Disband(GuildId),
+    SetMember {
+        character: CharacterId,
+        guild: GuildId,
+        rank: GuildRank,
+        joined: i64,
+    },
+    RemoveMember(CharacterId),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GuildError {
+    InvalidName,
+    NameTaken,
+    AlreadyInGuild,
+    NotInGuild,
+    TargetNotInGuild,
+    NotPermitted,
+    RankLimit,
+}
+
+impl fmt::Display for GuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GuildError::InvalidName => write!(
+                f,
+                "Guild names must be between 1 and {} characters long.",
+                MAX_GUILD_NAME_LEN
+            ),
+            GuildError::NameTaken => write!(f, "A guild with that name already exists."),
+            GuildError::AlreadyInGuild => write!(f, "Already a member of a guild."),
+            GuildError::NotInGuild => write!(f, "You are not a member of a guild."),
+            GuildError::TargetNotInGuild => write!(f, "They are not a member of your guild."),
+            GuildError::NotPermitted => write!(f, "Your rank does not permit you to do that."),
+            GuildError::RankLimit => write!(f, "Their rank cannot be changed any further."),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct GuildManager {
+    guilds: HashMap<GuildId, GuildInfo>,
+    members: HashMap<CharacterId, GuildId>,
+    next_id: i64,
+    next_joined: i64,
+    /// Changes that have not yet been sent to the database.
+    pending: Vec<GuildUpdate>,
+}
+
+impl GuildManager {
+    /// Create the manager from guild data loaded from the database, repairing
+    /// any guilds that were left empty or leaderless by character deletion.
+    pub fn from_loaded(
+        guilds: Vec<(GuildId, String)>,
+        members: Vec<(CharacterId, GuildId, String, GuildRank, i64)>,
+    ) -> Self {
+        let mut this = Self {
+            next_id: guilds.iter().map(|(id, _)| id.0 + 1).max().unwrap_or(1),
+            next_joined: members
+                .iter()
+                .map(|(_, _, _, _, joined)| joined + 1)
+                .max()
+                .unwrap_or(1),
+            guilds: guilds
+                .into_iter()
+                .map(|(id, name)| {
+                    (id, GuildInfo {
+                        name,
+                        members: HashMap::new(),
+                    })
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        for (character_id, guild_id, name, rank, joined) in members {
+            if let Some(guild) = this.guilds.get_mut(&guild_id) {
+                guild
+                    .members
+                    .insert(character_id, GuildMemberInfo { name, rank, joined });
+                this.members.insert(character_id, guild_id);
+            }
+        }
+
+        let guild_ids = this.guilds.keys().copied().collect::<Vec<_>>();
+        for guild_id in guild_ids {
+            this.ensure_leader(guild_id);
+        }
+
+        this
+    }
+
+    pub fn guild(&self, guild_id: GuildId) -> Option<&GuildInfo> { self.guilds.get(&guild_id) }
+
+    pub fn guild_of(&self, character_id: CharacterId) -> Option<GuildId> {
+        self.members.get(&character_id).copied()
+    }
+
+    /// The guild component that should be attached to the given character.
+    pub fn membership(&self, character_id: CharacterId) -> Option<Guild> {
+        let guild_id = self.guild_of(character_id)?;
+        let guild = self.guilds.get(&guild_id)?;
+        Some(Guild {
+            id: guild_id,
+            name: guild.name.clone(),
+            rank: guild.members.get(&character_id)?.rank,
+        })
+    }
+
+    pub fn members(&self, guild_id: GuildId) -> impl Iterator<Item = CharacterId> + '_ {
+        self.guilds
+            .get(&guild_id)
+            .into_iter()
+            .flat_map(|guild| guild.members.keys().copied())
+    }
+
+    /// Build the roster of a guild, using `online` to find the uids of members
+    /// that are currently playing.
+    pub fn roster(
+        &self,
+        guild_id: GuildId,
+        online: impl Fn(CharacterId) -> Option<Uid>,
+    ) -> Option<GuildRoster> {
+        let guild = self.guilds.get(&guild_id)?;
+        let mut members = guild
+            .members
+            .iter()
+            .map(|(character_id, member)| GuildMember {
+                name: member.name.clone(),
+                rank: member.rank,
+                uid: online(*character_id),
+            })
+            .collect::<Vec<_>>();
+        members.sort_by(|a, b| b.rank.cmp(&a.rank).then_with(|| a.name.cmp(&b.name)));
+        Some(GuildRoster {
+            id: guild_id,
+            name: guild.name.clone(),
+            members,
+        })
+    }
+
+    /// Take the changes that have been made since this was last called.
+    pub fn take_pending(&mut self) -> Vec<GuildUpdate> { std::mem::take(&mut self.pending) }
+
+    pub fn create(
+        &mut self,
+        founder: CharacterId,
+        founder_name: &str,
+        name: &str,
+    ) -> Result<GuildId, GuildError> {
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > MAX_GUILD_NAME_LEN {
+            return Err(GuildError::InvalidName);
+        }
+        if self
+            .guilds
+            .values()
+            .any(|guild| guild.name.eq_ignore_ascii_case(name))
+        {
+            return Err(GuildError::NameTaken);
+        }
+        if self.members.contains_key(&founder) {
+            return Err(GuildError::AlreadyInGuild);
+        }
+
+        let guild_id = GuildId(self.next_id);
+        self.next_id += 1;
+        self.guilds.insert(guild_id, GuildInfo {
+            name: name.to_string(),
+            members: HashMap::new(),
+        });
+        self.pending.push(GuildUpdate::Create {
+            guild: guild_id,
+            name: name.to_string(),
+        });
+        self.set_member(
+            guild_id,
+            founder,
+            founder_name.to_string(),
+            GuildRank::Leader,
+        );
+
+        Ok(guild_id)
+    }
+
+    /// Add a new member to the guild, as a recruit.
+    pub fn add_member(
+        &mut self,
+        guild_id: GuildId,
+        character_id: CharacterId,
+        name: &str,
+    ) -> Result<(), GuildError> {
+        if self.members.contains_key(&character_id) {
+            return Err(GuildError::AlreadyInGuild);
+        }
+        if !self.guilds.contains_key(&guild_id) {
+            return Err(GuildError::NotInGuild);
+        }
+        self.set_member(guild_id, character_id, name.to_string(), GuildRank::Recruit);
+        Ok(())
+    }
+
+    /// Remove a character from their guild, disbanding it if they were the
+    /// last member. Returns the guild that they left.
+    pub fn leave(&mut self, character_id: CharacterId) -> Option<GuildId> {
+        let guild_id = self.members.remove(&character_id)?;
+        self.pending.push(GuildUpdate::RemoveMember(character_id));
+        if let Some(guild) = self.guilds.get_mut(&guild_id) {
+            guild.members.remove(&character_id);
+        }
+        self.ensure_leader(guild_id);
+        Some(guild_id)
+    }
+
+    /// Check that `actor` may perform an action that requires the given
+    /// permission, returning their guild.
+    pub fn check_permission(
+        &self,
+        actor: CharacterId,
+        permission: GuildPermission,
+    ) -> Result<GuildId, GuildError> {
+        let guild_id = self.guild_of(actor).ok_or(GuildError::NotInGuild)?;
+        let rank = self
+            .rank_of(guild_id, actor)
+            .ok_or(GuildError::NotInGuild)?;
+        if rank.has_permission(permission) {
+            Ok(guild_id)
+        } else {
+            Err(GuildError::NotPermitted)
+        }
+    }
+
+    /// Remove `target` from the guild of `actor`.
+    pub fn kick(&mut self, actor: CharacterId, target: CharacterId) -> Result<GuildId, GuildError> {
+        let guild_id = self.check_permission(actor, GuildPermission::Kick)?;
+        let (actor_rank, target_rank) = self.ranks_of(guild_id, actor, target)?;
+        if !actor_rank.can_manage(target_rank) {
+            return Err(GuildError::NotPermitted);
+        }
+        self.leave(target);
+        Ok(guild_id)
+    }
+
+    /// Promote or demote `target`, returning their new rank.
+    pub fn change_rank(
+        &mut self,
+        actor: CharacterId,
+        target: CharacterId,
+        promote: bool,
+    ) -> Result<GuildRank, GuildError> {
+        let guild_id = self.check_permission(actor, GuildPermission::ChangeRank)?;
+        let (actor_rank, target_rank) = self.ranks_of(guild_id, actor, target)?;
+        if !actor_rank.can_manage(target_rank) {
+            return Err(GuildError::NotPermitted);
+        }
+        let new_rank = if promote {
+            target_rank.promoted()
+        } else {
+            target_rank.demoted()
+        }
+        .ok_or(GuildError::RankLimit)?;
+        // Only the leader may appoint officers
+        if new_rank >= actor_rank {
+            return Err(GuildError::NotPermitted);
+        }
+        self.set_rank(guild_id, target, new_rank);
+        Ok(new_rank)
+    }
+
+    /// Hand leadership of the guild from `actor` to `target`.
+    pub fn assign_leader(
+        &mut self,
+        actor: CharacterId,
+        target: CharacterId,
+    ) -> Result<GuildId, GuildError> {
+        let guild_id = self.guild_of(actor).ok_or(GuildError::NotInGuild)?;
+        let (actor_rank, _) = self.ranks_of(guild_id, actor, target)?;
+        if actor_rank != GuildRank::Leader || actor == target {
+            return Err(GuildError::NotPermitted);
+        }
+        self.set_rank(guild_id, actor, GuildRank::Officer);
+        self.set_rank(guild_id, target, GuildRank::Leader);
+        Ok(guild_id)
+    }
+
+    /// Disband the guild of `actor`, returning its former members.
+    pub fn disband(
+        &mut self,
+        actor: CharacterId,
+    ) -> Result<(GuildInfo, Vec<CharacterId>), GuildError> {
+        let guild_id = self.check_permission(actor, GuildPermission::Disband)?;
+        let guild = self.remove_guild(guild_id).ok_or(GuildError::NotInGuild)?;
+        let members = guild.members.keys().copied().collect();
+        Ok((guild, members))
+    }
+
+    fn rank_of(&self, guild_id: GuildId, character_id: CharacterId) -> Option<GuildRank> {
+        self.guilds
+            .get(&guild_id)?
+            .members
+            .get(&character_id)
+            .map(|member| member.rank)
+    }
+
+    fn ranks_of(
+        &self,
+        guild_id: GuildId,
+        actor: CharacterId,
+        target: CharacterId,
+    ) -> Result<(GuildRank, GuildRank), GuildError> {
+        let actor_rank = self
+            .rank_of(guild_id, actor)
+            .ok_or(GuildError::NotInGuild)?;
+        let target_rank = self
+            .rank_of(guild_id, target)
+            .ok_or(GuildError::TargetNotInGuild)?;
+        Ok((actor_rank, target_rank))
+    }
+
+    fn set_member(
+        &mut self,
+        guild_id: GuildId,
+        character_id: CharacterId,
+        name: String,
+        rank: GuildRank,
+    ) {
+        if let Some(guild) = self.guilds.get_mut(&guild_id) {
+            let joined = self.next_joined;
+            self.next_joined += 1;
+            guild
+                .members
+                .insert(character_id, GuildMemberInfo { name, rank, joined });
+            self.members.insert(character_id, guild_id);
+            self.pending.push(GuildUpdate::SetMember {
+                character: character_id,
+                guild: guild_id,
+                rank,
+                joined,
+            });
+        }
+    }
+
+    fn set_rank(&mut self, guild_id: GuildId, character_id: CharacterId, rank: GuildRank) {
+        if let Some(member) = self
+            .guilds
+            .get_mut(&guild_id)
+            .and_then(|guild| guild.members.get_mut(&character_id))
+        {
+            member.rank = rank;
+            self.pending.push(GuildUpdate::SetMember {
+                character: character_id,
+                guild: guild_id,
+                rank,
+                joined: member.joined,
+            });
+        }
+    }
+
+    fn remove_guild(&mut self, guild_id: GuildId) -> Option<GuildInfo> {
+        let guild = self.guilds.remove(&guild_id)?;
+        for character_id in guild.members.keys() {
+            self.members.remove(character_id);
+        }
+        self.pending.push(GuildUpdate::Disband(guild_id));
+        Some(guild)
+    }
+
+    /// Make sure that the guild has a leader, promoting a successor or
+    /// disbanding the guild if nobody is left.
+    fn ensure_leader(&mut self, guild_id: GuildId) {
+        let Some(guild) = self.guilds.get(&guild_id) else { return };
+        if guild.members.is_empty() {
+            self.remove_guild(guild_id);
+        } else if guild.leader().is_none() {
+            if let Some(successor) = guild.successor() {
+                self.set_rank(guild_id, successor, GuildRank::Leader);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager_with_guild() -> (GuildManager, GuildId) {
+        let mut manager = GuildManager::default();
+        let guild = manager.create(CharacterId(1), "Leader", "Testers").unwrap();
+        manager
+            .add_member(guild, CharacterId(2), "Officer")
+            .unwrap();
+        manager
+            .add_member(guild, CharacterId(3), "Recruit")
+            .unwrap();
+        manager
+            .change_rank(CharacterId(1), CharacterId(2), true)
+            .unwrap();
+        manager
+            .change_rank(CharacterId(1), CharacterId(2), true)
+            .unwrap();
+        (manager, guild)
+    }
+
+    #[test]
+    fn names_are_unique() {
+        let (mut manager, _) = manager_with_guild();
+        assert_eq!(
+            manager.create(CharacterId(4), "Other", "testers"),
+            Err(GuildError::NameTaken)
+        );
+        assert_eq!(
+            manager.create(CharacterId(4), "Other", "  "),
+            Err(GuildError::InvalidName)
+        );
+    }
+
+    #[test]
+    fn ranks_limit_management() {
+        let (mut manager, _) = manager_with_guild();
+        // Officers cannot appoint other officers, or touch the leader
+        assert_eq!(
+            manager.change_rank(CharacterId(2), CharacterId(3), true),
+            Ok(GuildRank::Member)
+        );
+        assert_eq!(
+            manager.change_rank(CharacterId(2), CharacterId(3), true),
+            Err(GuildError::NotPermitted)
+        );
+        assert_eq!(
+            manager.kick(CharacterId(2), CharacterId(1)),
+            Err(GuildError::NotPermitted)
+        );
+        // Recruits and members cannot kick
+        assert_eq!(
+            manager.kick(CharacterId(3), CharacterId(2)),
+            Err(GuildError::NotPermitted)
+        );
+    }
+
+    #[test]
+    fn leadership_passes_on() {
+        let (mut manager, guild) = manager_with_guild();
+        manager.leave(CharacterId(1));
+        assert_eq!(
+            manager.membership(CharacterId(2)).map(|g| g.rank),
+            Some(GuildRank::Leader)
+        );
+        manager.leave(CharacterId(2));
+        manager.leave(CharacterId(3));
+        assert!(manager.guild(guild).is_none());
+        assert!(
+            manager
+                .take_pending()
+                .contains(&GuildUpdate::Disband(guild))
+        );
+    }
+
+    #[test]
+    fn loading_repairs_leaderless_guilds() {
+        let manager = GuildManager::from_loaded(
+            vec![
+                (GuildId(3), "Orphans".to_string()),
+                (GuildId(5), "Empty".to_string()),
+            ],
+            vec![
+                (
+                    CharacterId(7),
+                    GuildId(3),
+                    "A".to_string(),
+                    GuildRank::Member,
+                    4,
+                ),
+                (
+                    CharacterId(8),
+                    GuildId(3),
+                    "B".to_string(),
+                    GuildRank::Officer,
+                    9,
+                ),
+            ],
+        );
+        assert_eq!(
+            manager.membership(CharacterId(8)).map(|g| g.rank),
+            Some(GuildRank::Leader)
+        );
+        assert!(manager.guild(GuildId(5)).is_none());
+        assert_eq!(manager.next_id, 6);
+        assert_eq!(manager.next_joined, 10);
+    }
+
+    #[test]
+    fn longest_standing_member_succeeds() {
+        let mut manager = GuildManager::default();
+        let guild = manager.create(CharacterId(1), "Leader", "Testers").unwrap();
+        manager
+            .add_member(guild, CharacterId(5), "Veteran")
+            .unwrap();
+        manager
+            .add_member(guild, CharacterId(4), "Newcomer")
+            .unwrap();
+        manager.leave(CharacterId(1));
+        assert_eq!(
+            manager.membership(CharacterId(5)).map(|g| g.rank),
+            Some(GuildRank::Leader)
+        );
+    }
+}
@@ -21,6 +21,7 @@ pub mod connection_handler;
 mod data_dir;
 pub mod error;
 pub mod events;
+pub mod guild;
 pub mod input;
 pub mod location;
 pub mod lod;
@@ -236,6 +237,9 @@ impl Server {
         debug!("Vacuuming database...");
         persistence::vacuum_database(&database_settings);
 
+        debug!("Loading guilds...");
+        let mut guild_manager = persistence::load_guilds(&database_settings);
+
         let database_settings = Arc::new(RwLock::new(database_settings));
 
         let registry = Arc::new(Registry::new());
@@ -281,6 +285,7 @@ impl Server {
             alt: Grid::new(Vec2::new(1, 1), 1),
             sites: Vec::new(),
             pois: Vec::new(),
+            factions: Default::default(),
             default_chunk: Arc::new(world.generate_oob_chunk()),
         };
 
@@ -303,6 +308,23 @@ impl Server {
             .ecs_mut()
             .insert(EventBus::<chunk_serialize::ChunkSendEntry>::default());
         state.ecs_mut().insert(Locations::default());
+        state
+            .ecs_mut()
+            .insert(sys::farming::PlantedCrops::default());
+        state
+            .ecs_mut()
+            .insert(sys::encounter::ArenaLockdowns::default());
+        state
+            .ecs_mut()
+            .insert(server_agent::squad::Squads::default());
+        state.ecs_mut().insert(sys::instance::DungeonInstances::new(
+            world.dungeon_layouts(index.as_index_ref()),
+        ));
+        state.ecs_mut().insert(sys::replay::ReplayRecorder::new(
+            &settings.replay_recording,
+            data_dir,
+            world.sim().map_size_lg(),
+        ));
         state.ecs_mut().insert(LoginProvider::new(
             settings.auth_server_address.clone(),
             Arc::clone(&runtime),
@@ -356,9 +378,12 @@ impl Server {
             state.ecs_mut().insert(receiver);
         }
 
-        state.ecs_mut().insert(CharacterUpdater::new(
-            Arc::<RwLock<DatabaseSettings>>::clone(&database_settings),
-        )?);
+        let mut character_updater =
+            CharacterUpdater::new(Arc::<RwLock<DatabaseSettings>>::clone(&database_settings))?;
+        // Persist any repairs that were made to guilds while loading them
+        character_updater.update_guilds(guild_manager.take_pending());
+        state.ecs_mut().insert(character_updater);
+        state.ecs_mut().insert(guild_manager);
 
         let ability_map = comp::item::tool::AbilityMap::<comp::AbilityItem>::load_expect_cloned(
             "common.abilities.ability_set_manifest",
@@ -376,6 +401,12 @@ impl Server {
         state
             .ecs_mut()
             .insert(sys::PersistenceScheduler::every(Duration::from_secs(10)));
+        state
+            .ecs_mut()
+            .insert(sys::StatisticsScheduler::every(Duration::from_secs(5)));
+        state
+            .ecs_mut()
+            .insert(sys::FarmingScheduler::every(Duration::from_secs(10)));
 
         // Server-only components
         state.ecs_mut().register::<RegionSubscription>();
@@ -389,6 +420,7 @@ impl Server {
         state.ecs_mut().register::<RepositionOnChunkLoad>();
         state.ecs_mut().register::<RtSimEntity>();
         state.ecs_mut().register::<RtSimVehicle>();
+        state.ecs_mut().register::<sys::instance::DungeonPortal>();
 
         // Load banned words list
         let banned_words = settings.moderation.load_banned_words(data_dir);
@@ -879,6 +911,10 @@ impl Server {
         let mut character_updater = self.state.ecs().write_resource::<CharacterUpdater>();
         let updater_messages: Vec<CharacterUpdaterMessage> = character_updater.messages().collect();
 
+        let mut mail_responses = Vec::new();
+        let mut market_responses = Vec::new();
+        let mut deleted_characters = Vec::new();
+
         // Get character-related database responses and notify the requesting client
         character_loader
             .messages()
@@ -887,6 +923,11 @@ impl Server {
                 CharacterUpdaterMessage::DatabaseBatchCompletion(batch_id) => {
                     character_updater.process_batch_completion(batch_id);
                 },
+                CharacterUpdaterMessage::CharactersDeleted(characters) => {
+                    deleted_characters.extend(characters)
+                },
+                CharacterUpdaterMessage::Mail(response) => mail_responses.push(response),
+                CharacterUpdaterMessage::Market(response) => market_responses.push(response),
                 CharacterUpdaterMessage::CharacterScreenResponse(response) => {
                     match response.response_kind {
                         CharacterScreenResponseKind::CharacterList(result) => match result {
@@ -943,6 +984,10 @@ impl Server {
                                         pets,
                                         active_abilities,
                                         map_marker,
+                                        known_recipes,
+                                        statistics,
+                                        achievements,
+                                        bank,
                                     } = character_data;
                                     let character_data = (
                                         body,
@@ -953,6 +998,10 @@ impl Server {
                                         pets,
                                         active_abilities,
                                         map_marker,
+                                        known_recipes,
+                                        statistics,
+                                        achievements,
+                                        bank,
                                     );
                                     ServerEvent::UpdateCharacterData {
                                         entity: response.target_entity,
@@ -991,6 +1040,16 @@ impl Server {
         drop(character_loader);
         drop(character_updater);
 
+        for response in mail_responses {
+            events::handle_mail_response(self, response);
+        }
+        for response in market_responses {
+            events::handle_market_response(self, response);
+        }
+        if !deleted_characters.is_empty() {
+            events::guild_manip::handle_characters_deleted(self, deleted_characters);
+        }
+
         {
             // Check for new chunks; cancel and regenerate all chunks if the asset has been
             // reloaded. Note that all of these assignments are no-ops, so the
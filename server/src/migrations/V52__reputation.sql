@@ -0,0 +1,12 @@
+-- Creates new reputation table
+CREATE TABLE "reputation" (
+      "entity_id" INT NOT NULL,
+      "reputation" TEXT NOT NULL,
+      PRIMARY KEY("entity_id"),
+      FOREIGN KEY("entity_id") REFERENCES "character"("character_id")
+);
+
+-- Everyone starts out as a stranger
+INSERT INTO reputation
+SELECT c.character_id, '{"sites":[],"factions":[]}'
+FROM character c
@@ -0,0 +1,15 @@
+-- Creates tables for player guilds and their membership
+CREATE TABLE "guild" (
+      "guild_id" INTEGER NOT NULL,
+      "name" TEXT NOT NULL UNIQUE,
+      PRIMARY KEY("guild_id")
+);
+
+CREATE TABLE "guild_member" (
+      "character_id" INT NOT NULL,
+      "guild_id" INT NOT NULL,
+      "rank" TEXT NOT NULL,
+      PRIMARY KEY("character_id"),
+      FOREIGN KEY("character_id") REFERENCES "character"("character_id"),
+      FOREIGN KEY("guild_id") REFERENCES "guild"("guild_id")
+);
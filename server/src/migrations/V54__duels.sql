@@ -0,0 +1,11 @@
+-- Creates a table recording the results of duels between characters
+CREATE TABLE "duel_result" (
+      "duel_result_id" INTEGER NOT NULL,
+      "winner_character_id" INT NOT NULL,
+      "loser_character_id" INT NOT NULL,
+      "outcome" TEXT NOT NULL,
+      "finished_at" INT NOT NULL,
+      PRIMARY KEY("duel_result_id"),
+      FOREIGN KEY("winner_character_id") REFERENCES "character"("character_id"),
+      FOREIGN KEY("loser_character_id") REFERENCES "character"("character_id")
+);
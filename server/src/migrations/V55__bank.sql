@@ -0,0 +1,8 @@
+-- Creates a table linking each player account to the pseudo-container item
+-- that holds the items in their bank
+CREATE TABLE "bank" (
+      "player_uuid" TEXT NOT NULL,
+      "bank_container_id" INT NOT NULL,
+      PRIMARY KEY("player_uuid"),
+      FOREIGN KEY("bank_container_id") REFERENCES "item"("item_id")
+);
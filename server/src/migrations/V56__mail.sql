@@ -0,0 +1,15 @@
+-- Creates a table of mail sent between characters. The items attached to a
+-- mail are stored in a pseudo-container item of their own.
+CREATE TABLE "mail" (
+      "mail_id" INTEGER NOT NULL,
+      "recipient_character_id" INT NOT NULL,
+      "sender_alias" TEXT NOT NULL,
+      "body" TEXT NOT NULL,
+      "sent_at" INT NOT NULL,
+      "attachment_container_id" INT,
+      PRIMARY KEY("mail_id"),
+      FOREIGN KEY("recipient_character_id") REFERENCES "character"("character_id"),
+      FOREIGN KEY("attachment_container_id") REFERENCES "item"("item_id")
+);
+
+CREATE INDEX "idx_mail_recipient" ON "mail" ("recipient_character_id");
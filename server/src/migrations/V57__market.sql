@@ -0,0 +1,21 @@
+-- Creates a table of the buy and sell orders on the market boards of sites.
+-- The items being sold, or the coins offered for the items being bought, are
+-- held in an escrow pseudo-container item of their own until the order is
+-- filled or cancelled.
+CREATE TABLE "market_order" (
+      "order_id" INTEGER NOT NULL,
+      "site_id" INT NOT NULL,
+      "character_id" INT NOT NULL,
+      "kind" TEXT NOT NULL,
+      "item_definition_id" TEXT NOT NULL,
+      "unit_price" INT NOT NULL,
+      "quantity" INT NOT NULL,
+      "created_at" INT NOT NULL,
+      "escrow_container_id" INT NOT NULL,
+      PRIMARY KEY("order_id"),
+      FOREIGN KEY("character_id") REFERENCES "character"("character_id"),
+      FOREIGN KEY("escrow_container_id") REFERENCES "item"("item_id")
+);
+
+CREATE INDEX "idx_market_order_site_item" ON "market_order" ("site_id", "item_definition_id");
+CREATE INDEX "idx_market_order_character" ON "market_order" ("character_id");
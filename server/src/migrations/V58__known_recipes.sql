@@ -0,0 +1,67 @@
+-- Creates new known_recipes table
+CREATE TABLE "known_recipes" (
+      "entity_id" INT NOT NULL,
+      "known_recipes" TEXT NOT NULL,
+      PRIMARY KEY("entity_id"),
+      FOREIGN KEY("entity_id") REFERENCES "character"("character_id")
+);
+
+-- Existing characters keep every recipe that they could craft before recipes
+-- had to be learned
+INSERT INTO known_recipes
+SELECT c.character_id, '{"recipes":[
+    "crafting_hammer","mortar_pestle","sewing_set","velorite_frag","potion_s","potion_m",
+    "cactus_colada","collar_basic","bomb_coconut","firework_blue","firework_green",
+    "firework_purple","firework_red","firework_white","firework_yellow",
+    "apple_shroom_curry","fish_cooked","bird_cooked","bird_large_cooked",
+    "beast_small_cooked","beast_large_cooked","tough_cooked","leather_strips",
+    "simple_leather","thick_leather","rigid_leather","gold_ingot","silver_ingot",
+    "cobalt_ingot","tin_ingot","orichalcum_ingot","copper_ingot","bloodsteel_ingot",
+    "iron_ingot","steel_ingot","bronze_ingot","diamonds","cotton","linen_flax","silk",
+    "lifecloth",
+    "moonweave","sunsilk","salad_plain","salad_tomato","apples_stick","mushroom_stick",
+    "sunflower_icetea","plain_cloth_glider","red_cloth_glider","leaves_glider",
+    "sand_raptor_wings","snow_raptor_wings","wood_raptor_wings","moonlit_love_glider",
+    "horizon_glider","winter_wings","healing_sceptre","burnt_drumstick","rawhide_back",
+    "rawhide_belt","rawhide_chest","rawhide_feet","rawhide_hands","rawhide_pants",
+    "rawhide_shoulder","leather_back","leather_belt","leather_chest","leather_feet",
+    "leather_hands","leather_pants","leather_shoulder","scale_back","scale_belt",
+    "scale_chest","scale_feet","scale_hands","scale_pants","scale_shoulder",
+    "carapace_back","carapace_belt","carapace_chest","carapace_feet","carapace_hands",
+    "carapace_pants","carapace_shoulder","primal_back","primal_belt","primal_chest",
+    "primal_feet","primal_hands","primal_pants","primal_shoulder","dragonscale_back",
+    "dragonscale_belt","dragonscale_chest","dragonscale_feet","dragonscale_hands",
+    "dragonscale_pants","dragonscale_shoulder","linen_back","linen_belt","linen_chest",
+    "linen_feet","linen_hands","linen_pants","linen_shoulder","woolen_back","woolen_belt",
+    "woolen_chest","woolen_feet","woolen_hands","woolen_pants","woolen_shoulder",
+    "silken_back","silken_belt","silken_chest","silken_feet","silken_hands",
+    "silken_pants","silken_shoulder","druid_back","druid_belt","druid_chest","druid_feet",
+    "druid_hands","druid_pants","druid_shoulder","moonweave_back","moonweave_belt",
+    "moonweave_chest","moonweave_feet","moonweave_hands","moonweave_pants",
+    "moonweave_shoulder","sunsilk_back","sunsilk_belt","sunsilk_chest","sunsilk_feet",
+    "sunsilk_hands","sunsilk_pants","sunsilk_shoulder","bronze_back","bronze_belt",
+    "bronze_chest","bronze_feet","bronze_hands","bronze_pants","bronze_shoulder",
+    "iron_back","iron_belt","iron_chest","iron_feet","iron_hands","iron_pants",
+    "iron_shoulder","steel_back","steel_belt","steel_chest","steel_feet","steel_hands",
+    "steel_pants","steel_shoulder","cobalt_back","cobalt_belt","cobalt_chest",
+    "cobalt_feet","cobalt_hands","cobalt_pants","cobalt_shoulder","bloodsteel_back",
+    "bloodsteel_belt","bloodsteel_chest","bloodsteel_feet","bloodsteel_hands",
+    "bloodsteel_pants","bloodsteel_shoulder","orichalcum_back","orichalcum_belt",
+    "orichalcum_chest","orichalcum_feet","orichalcum_hands","orichalcum_pants",
+    "orichalcum_shoulder","brinestone_back","brinestone_belt","brinestone_chest",
+    "brinestone_feet","brinestone_hands","brinestone_pants","brinestone_shoulder",
+    "brinestone_crown","seashell_necklace","red_cloth","tiny_red_pouch",
+    "tiny_leather_pouch","knitted_red_pouch","woven_red_bag","traveler_backpack",
+    "sturdy_red_backpack","troll_hide_pack","mindflayer_spellbag","abyssal_gorget",
+    "tin_pickaxe","steel_pickaxe","fang_necklace","honeycomb_pendant","diamond_ring",
+    "diamond_necklace","ruby_ring","ruby_necklace","emerald_ring","emerald_necklace",
+    "sapphire_ring","sapphire_necklace","topaz_ring","topaz_necklace","amethyst_ring",
+    "amethyst_necklace","merchant_turban","winged_coronet","black_lantern","polaris",
+    "short_hilt","medium_hilt","long_hilt","short_hammer_haft","medium_hammer_haft",
+    "long_hammer_haft","short_axe_haft","medium_axe_haft","long_axe_haft",
+    "short_bow_grip","medium_bow_grip","long_bow_grip","light_pyrocore","medium_pyrocore",
+    "heavy_pyrocore","light_biocore","medium_biocore","heavy_biocore","double_bass",
+    "flute","guitar","lyre","kalimba","lute","melodica","washboard","sitar",
+    "icy_talharpa","lockpick"
+]}'
+FROM character c
@@ -0,0 +1,20 @@
+-- Creates new statistics table
+CREATE TABLE "statistics" (
+      "entity_id" INT NOT NULL,
+      "statistics" TEXT NOT NULL,
+      PRIMARY KEY("entity_id"),
+      FOREIGN KEY("entity_id") REFERENCES "character"("character_id")
+);
+
+-- Statistics were not collected before, so existing characters start without
+INSERT INTO statistics
+SELECT c.character_id, '{"kills":[],"deaths":0,"distance_travelled":0.0,"items_crafted":0,"sites_visited":[],"dungeons_cleared":0}'
+FROM character c;
+
+-- Creates new achievement table, which is shared by all characters of an
+-- account
+CREATE TABLE "achievement" (
+      "player_uuid" TEXT NOT NULL,
+      "achievements" TEXT NOT NULL,
+      PRIMARY KEY("player_uuid")
+);
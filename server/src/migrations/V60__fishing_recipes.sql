@@ -0,0 +1,18 @@
+-- Existing characters learn the fishing recipes that new characters start with
+UPDATE known_recipes
+SET known_recipes = json_insert(
+    known_recipes,
+    '$.recipes[#]', 'fishing_rod',
+    '$.recipes[#]', 'carp_fillet',
+    '$.recipes[#]', 'trout_fillet',
+    '$.recipes[#]', 'salmon_fillet',
+    '$.recipes[#]', 'catfish_fillet',
+    '$.recipes[#]', 'cod_fillet',
+    '$.recipes[#]', 'icefish_fillet',
+    '$.recipes[#]', 'fish_stew'
+)
+WHERE NOT EXISTS (
+    SELECT 1
+    FROM json_each(known_recipes.known_recipes, '$.recipes')
+    WHERE value = 'fishing_rod'
+);
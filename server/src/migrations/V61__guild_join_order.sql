@@ -0,0 +1,4 @@
+-- Records the order in which guild members joined, so that leadership passes to
+-- the longest-standing member. Existing members are treated as having joined
+-- at the same time.
+ALTER TABLE guild_member ADD COLUMN joined INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,3 @@
+-- Reputation is derived from the sentiments that rtsim saves alongside its own
+-- data, so it no longer needs its own table.
+DROP TABLE reputation;
@@ -11,16 +11,21 @@ use crate::{
     comp::{self, Inventory},
     persistence::{
         character::conversions::{
+            convert_achievements_from_database, convert_achievements_to_database,
             convert_active_abilities_from_database, convert_active_abilities_to_database,
-            convert_body_from_database, convert_body_to_database_json,
-            convert_character_from_database, convert_inventory_from_database_items,
-            convert_items_to_database_items, convert_loadout_from_database_items,
+            convert_bank_from_database_items, convert_body_from_database,
+            convert_body_to_database_json, convert_character_from_database,
+            convert_container_items_to_database_items, convert_escrow_from_database_items,
+            convert_inventory_from_database_items, convert_items_to_database_items,
+            convert_known_recipes_from_database, convert_known_recipes_to_database,
+            convert_loadout_from_database_items, convert_mail_attachments_from_database_items,
             convert_skill_groups_to_database, convert_skill_set_from_database,
+            convert_statistics_from_database, convert_statistics_to_database,
             convert_stats_from_database, convert_waypoint_from_database_json,
             convert_waypoint_to_database_json,
         },
         character_loader::{CharacterCreationResult, CharacterDataResult, CharacterListResult},
-        character_updater::PetPersistenceData,
+        character_updater::{AchievementPersistenceData, BankPersistenceData, PetPersistenceData},
         error::PersistenceError::DatabaseError,
         EditableComponents, PersistedComponents,
     },
@@ -30,20 +35,23 @@ use common::{
     event::UpdateCharacterMetadata,
 };
 use core::ops::Range;
-use rusqlite::{types::Value, Connection, ToSql, Transaction};
+use rusqlite::{types::Value, Connection, OptionalExtension, ToSql, Transaction};
 use std::{num::NonZeroU64, rc::Rc};
 use tracing::{debug, error, trace, warn};
 
 /// Private module for very tightly coupled database conversion methods.  In
 /// general, these have many invariants that need to be maintained when they're
 /// called--do not assume it's safe to make these public!
-mod conversions;
+pub(super) mod conversions;
 
 pub(crate) type EntityId = i64;
 
 const CHARACTER_PSEUDO_CONTAINER_DEF_ID: &str = "veloren.core.pseudo_containers.character";
 const INVENTORY_PSEUDO_CONTAINER_DEF_ID: &str = "veloren.core.pseudo_containers.inventory";
 const LOADOUT_PSEUDO_CONTAINER_DEF_ID: &str = "veloren.core.pseudo_containers.loadout";
+const BANK_PSEUDO_CONTAINER_DEF_ID: &str = "veloren.core.pseudo_containers.bank";
+const MAIL_PSEUDO_CONTAINER_DEF_ID: &str = "veloren.core.pseudo_containers.mail";
+const MARKET_ESCROW_PSEUDO_CONTAINER_DEF_ID: &str = "veloren.core.pseudo_containers.market_escrow";
 const INVENTORY_PSEUDO_CONTAINER_POSITION: &str = "inventory";
 const LOADOUT_PSEUDO_CONTAINER_POSITION: &str = "loadout";
 const WORLD_PSEUDO_CONTAINER_ID: EntityId = 1;
@@ -125,6 +133,36 @@ pub fn load_character_data(
     let inventory_items = load_items(connection, character_containers.inventory_container_id)?;
     let loadout_items = load_items(connection, character_containers.loadout_container_id)?;
 
+    // The bank is shared by all characters of the player's account
+    let bank = match get_bank_container_id(connection, &requesting_player_uuid)? {
+        Some(bank_container_id) => convert_bank_from_database_items(
+            bank_container_id,
+            &load_items(connection, bank_container_id)?,
+        )?,
+        None => comp::Bank::default(),
+    };
+
+    // Achievements are shared by all characters of the player's account, and are
+    // only stored once the player has unlocked one
+    let mut stmt = connection.prepare_cached(
+        "
+            SELECT  achievements
+            FROM    achievement
+            WHERE   player_uuid = ?1",
+    )?;
+
+    let achievements = stmt
+        .query_row([&requesting_player_uuid], |row| {
+            Ok(Achievements {
+                player_uuid: requesting_player_uuid.clone(),
+                achievements: row.get(0)?,
+            })
+        })
+        .optional()?
+        .map_or_else(comp::Achievements::default, |achievements| {
+            convert_achievements_from_database(&achievements)
+        });
+
     let mut stmt = connection.prepare_cached(
         "
         SELECT  c.character_id,
@@ -261,6 +299,34 @@ pub fn load_character_data(
         })
     })?;
 
+    let mut stmt = connection.prepare_cached(
+        "
+            SELECT  known_recipes
+            FROM    known_recipes
+            WHERE   entity_id = ?1",
+    )?;
+
+    let known_recipes_data = stmt.query_row([char_id.0], |row| {
+        Ok(KnownRecipes {
+            entity_id: char_id.0,
+            known_recipes: row.get(0)?,
+        })
+    })?;
+
+    let mut stmt = connection.prepare_cached(
+        "
+            SELECT  statistics
+            FROM    statistics
+            WHERE   entity_id = ?1",
+    )?;
+
+    let statistics_data = stmt.query_row([char_id.0], |row| {
+        Ok(Statistics {
+            entity_id: char_id.0,
+            statistics: row.get(0)?,
+        })
+    })?;
+
     let (skill_set, skill_set_persistence_load_error) =
         convert_skill_set_from_database(&skill_group_data);
     let body = convert_body_from_database(&body_data.variant, &body_data.body_data)?;
@@ -279,6 +345,10 @@ pub fn load_character_data(
             pets,
             active_abilities: convert_active_abilities_from_database(&ability_set_data),
             map_marker: char_map_marker,
+            known_recipes: convert_known_recipes_from_database(&known_recipes_data),
+            statistics: convert_statistics_from_database(&statistics_data),
+            achievements,
+            bank,
         },
         UpdateCharacterMetadata {
             skill_set_persistence_load_error,
@@ -377,6 +447,10 @@ pub fn create_character(
         pets: _,
         active_abilities,
         map_marker,
+        known_recipes,
+        statistics,
+        achievements: _,
+        bank: _,
     } = persisted_components;
 
     // Fetch new entity IDs for character, inventory and loadout
@@ -512,6 +586,37 @@ pub fn create_character(
     ])?;
     drop(stmt);
 
+    let known_recipes =
+        convert_known_recipes_to_database(CharacterId(character_id), &known_recipes);
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        INSERT INTO known_recipes (entity_id,
+                                   known_recipes)
+        VALUES (?1, ?2)",
+    )?;
+
+    stmt.execute([
+        &character_id as &dyn ToSql,
+        &known_recipes.known_recipes as &dyn ToSql,
+    ])?;
+    drop(stmt);
+
+    let statistics = convert_statistics_to_database(CharacterId(character_id), &statistics);
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        INSERT INTO statistics (entity_id,
+                                statistics)
+        VALUES (?1, ?2)",
+    )?;
+
+    stmt.execute([
+        &character_id as &dyn ToSql,
+        &statistics.statistics as &dyn ToSql,
+    ])?;
+    drop(stmt);
+
     // Insert default inventory and loadout item records
     let mut inserts = Vec::new();
 
@@ -520,6 +625,7 @@ pub fn create_character(
             loadout_container_id,
             &inventory,
             inventory_container_id,
+            &[],
             &mut next_id,
         );
         inserts = inserts_;
@@ -602,12 +708,12 @@ pub fn edit_character(
     char_list.map(|list| (character_id, list))
 }
 
-/// Permanently deletes a character
+/// Permanently deletes a character, returning whether it was deleted
 pub fn delete_character(
     requesting_player_uuid: &str,
     char_id: CharacterId,
     transaction: &mut Transaction,
-) -> Result<(), PersistenceError> {
+) -> Result<bool, PersistenceError> {
     debug!(?requesting_player_uuid, ?char_id, "Deleting character");
 
     let mut stmt = transaction.prepare_cached(
@@ -627,7 +733,7 @@ pub fn delete_character(
     if result != 1 {
         // The character does not exist, or does not belong to the requesting player so
         // silently drop the request.
-        return Ok(());
+        return Ok(false);
     }
 
     // Delete skill groups
@@ -660,6 +766,57 @@ pub fn delete_character(
     stmt.execute([&char_id.0])?;
     drop(stmt);
 
+    // Delete known recipes
+    let mut stmt = transaction.prepare_cached(
+        "
+        DELETE
+        FROM    known_recipes
+        WHERE   entity_id = ?1",
+    )?;
+
+    stmt.execute([&char_id.0])?;
+    drop(stmt);
+
+    // Delete statistics
+    let mut stmt = transaction.prepare_cached(
+        "
+        DELETE
+        FROM    statistics
+        WHERE   entity_id = ?1",
+    )?;
+
+    stmt.execute([&char_id.0])?;
+    drop(stmt);
+
+    // Delete guild membership
+    let mut stmt = transaction.prepare_cached(
+        "
+        DELETE
+        FROM    guild_member
+        WHERE   character_id = ?1",
+    )?;
+
+    stmt.execute([&char_id.0])?;
+    drop(stmt);
+
+    // Delete duel results
+    let mut stmt = transaction.prepare_cached(
+        "
+        DELETE
+        FROM    duel_result
+        WHERE   winner_character_id = ?1
+        OR      loser_character_id = ?1",
+    )?;
+
+    stmt.execute([&char_id.0])?;
+    drop(stmt);
+
+    // Delete mail received by the character, along with its attachments
+    super::mail::delete_mailbox(char_id, transaction)?;
+
+    // Delete the character's market orders, along with whatever they hold
+    super::market::delete_orders(char_id, transaction)?;
+
     // Delete character
     let mut stmt = transaction.prepare_cached(
         "
@@ -714,7 +871,7 @@ pub fn delete_character(
         )));
     }
 
-    Ok(())
+    Ok(true)
 }
 
 /// Before creating a character, we ensure that the limit on the number of
@@ -808,6 +965,227 @@ fn get_pseudo_containers(
     Ok(character_containers)
 }
 
+/// Fetches the ID of the pseudo-container that holds a player's bank, if one
+/// has been created for them
+fn get_bank_container_id(
+    connection: &Connection,
+    player_uuid: &str,
+) -> Result<Option<EntityId>, PersistenceError> {
+    let mut stmt = connection.prepare_cached(
+        "
+        SELECT  bank_container_id
+        FROM    bank
+        WHERE   player_uuid = ?1",
+    )?;
+
+    Ok(stmt.query_row([player_uuid], |row| row.get(0)).optional()?)
+}
+
+/// Creates a pseudo-container that does not belong to any character, such as a
+/// bank.
+fn create_world_pseudo_container(
+    item_definition_id: &str,
+    transaction: &mut Transaction,
+) -> Result<EntityId, PersistenceError> {
+    let container_id = get_new_entity_ids(transaction, |next_id| next_id + 1)?.start;
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        INSERT INTO item (item_id,
+                          parent_container_item_id,
+                          item_definition_id,
+                          stack_size,
+                          position,
+                          properties)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+
+    stmt.execute([
+        &container_id as &dyn ToSql,
+        &WORLD_PSEUDO_CONTAINER_ID,
+        &item_definition_id,
+        &1,
+        &container_id.to_string(),
+        &String::new(),
+    ])?;
+
+    Ok(container_id)
+}
+
+/// Deletes a pseudo-container along with all of the items in it.
+pub(super) fn delete_container(
+    container_id: EntityId,
+    transaction: &mut Transaction,
+) -> Result<(), PersistenceError> {
+    let mut stmt = transaction.prepare_cached(
+        "
+        WITH RECURSIVE
+        parents AS (
+            SELECT  item_id
+            FROM    item
+            WHERE   item.item_id = ?1
+            UNION ALL
+            SELECT  item.item_id
+            FROM    item,
+                    parents
+            WHERE   item.parent_container_item_id = parents.item_id
+        )
+        DELETE
+        FROM    item
+        WHERE   EXISTS (SELECT 1 FROM parents WHERE parents.item_id = item.item_id)",
+    )?;
+
+    stmt.execute([&container_id])?;
+
+    Ok(())
+}
+
+/// Creates the pseudo-container that holds the items attached to a mail.
+pub(super) fn create_mail_container(
+    transaction: &mut Transaction,
+) -> Result<EntityId, PersistenceError> {
+    create_world_pseudo_container(MAIL_PSEUDO_CONTAINER_DEF_ID, transaction)
+}
+
+pub(super) fn load_mail_attachments(
+    connection: &Connection,
+    attachment_container_id: EntityId,
+) -> Result<Vec<comp::Item>, PersistenceError> {
+    let attachment_items = load_items(connection, attachment_container_id)?;
+    convert_mail_attachments_from_database_items(attachment_container_id, &attachment_items)
+}
+
+/// Creates the pseudo-container that holds the items or coins of a market
+/// order until the order is filled or cancelled.
+pub(super) fn create_escrow_container(
+    transaction: &mut Transaction,
+) -> Result<EntityId, PersistenceError> {
+    create_world_pseudo_container(MARKET_ESCROW_PSEUDO_CONTAINER_DEF_ID, transaction)
+}
+
+pub(super) fn load_escrow_item(
+    connection: &Connection,
+    escrow_container_id: EntityId,
+) -> Result<comp::Item, PersistenceError> {
+    let escrow_items = load_items(connection, escrow_container_id)?;
+    convert_escrow_from_database_items(escrow_container_id, &escrow_items)
+}
+
+/// Replaces the contents of pseudo-containers that do not belong to any
+/// character. Items that are moved from one of the containers to another keep
+/// their ids, and any items that are no longer in a container are deleted.
+pub(super) fn write_containers(
+    containers: &[(EntityId, &[comp::Item])],
+    transaction: &mut Transaction,
+) -> Result<(), PersistenceError> {
+    let containers = containers
+        .iter()
+        .map(|(container_id, items)| (*container_id, items.iter().enumerate().collect()))
+        .collect::<Vec<_>>();
+
+    let mut upserts = Vec::new();
+    get_new_entity_ids(transaction, |mut next_id| {
+        upserts = convert_container_items_to_database_items(&containers, &mut next_id);
+        next_id
+    })?;
+
+    let mut existing_item_ids = Vec::new();
+    for (container_id, _) in &containers {
+        existing_item_ids.push(Value::from(*container_id));
+        for it in load_items(transaction, *container_id)? {
+            existing_item_ids.push(Value::from(it.item_id));
+        }
+    }
+    let upserted_items: Vec<_> = upserts.into_iter().map(|pair| pair.model).collect();
+    delete_items_except(existing_item_ids, &upserted_items, transaction)?;
+    replace_items(&upserted_items, transaction)
+}
+
+/// Deletes the items in the given containers, other than the ones that are
+/// about to be upserted.
+fn delete_items_except(
+    container_ids: Vec<Value>,
+    upserted_items: &[Item],
+    transaction: &mut Transaction,
+) -> Result<usize, PersistenceError> {
+    let non_upserted_items = upserted_items
+        .iter()
+        .map(|item| Value::from(item.item_id))
+        .collect::<Vec<Value>>();
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        DELETE
+        FROM    item
+        WHERE   parent_container_item_id
+        IN      rarray(?1)
+        AND     item_id NOT IN rarray(?2)",
+    )?;
+    Ok(stmt.execute([Rc::new(container_ids), Rc::new(non_upserted_items)])?)
+}
+
+fn replace_items(items: &[Item], transaction: &mut Transaction) -> Result<(), PersistenceError> {
+    if items.is_empty() {
+        return Ok(());
+    }
+
+    // When moving inventory items around, foreign key constraints on
+    // `parent_container_item_id` can be temporarily violated by one
+    // upsert, but restored by another upsert. Deferred constraints
+    // allow SQLite to check this when committing the transaction.
+    // The `defer_foreign_keys` pragma treats the foreign key
+    // constraints as deferred for the next transaction (it turns itself
+    // off at the commit boundary). https://sqlite.org/foreignkeys.html#fk_deferred
+    transaction.pragma_update(None, "defer_foreign_keys", &"ON".to_string())?;
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        REPLACE
+        INTO    item (item_id,
+                      parent_container_item_id,
+                      item_definition_id,
+                      stack_size,
+                      position,
+                      properties)
+        VALUES  (?1, ?2, ?3, ?4, ?5, ?6)",
+    )?;
+
+    for item in items {
+        stmt.execute([
+            &item.item_id as &dyn ToSql,
+            &item.parent_container_item_id,
+            &item.item_definition_id,
+            &item.stack_size,
+            &item.position,
+            &item.properties,
+        ])?;
+    }
+
+    Ok(())
+}
+
+/// Creates the pseudo-container that holds a player's bank. This is done the
+/// first time that the player stores something in their bank.
+fn create_bank_container(
+    player_uuid: &str,
+    transaction: &mut Transaction,
+) -> Result<EntityId, PersistenceError> {
+    let bank_container_id =
+        create_world_pseudo_container(BANK_PSEUDO_CONTAINER_DEF_ID, transaction)?;
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        INSERT INTO bank (player_uuid,
+                          bank_container_id)
+        VALUES (?1, ?2)",
+    )?;
+
+    stmt.execute([&player_uuid as &dyn ToSql, &bank_container_id])?;
+    drop(stmt);
+
+    Ok(bank_container_id)
+}
+
 fn get_pseudo_container_id(
     connection: &Connection,
     character_id: CharacterId,
@@ -974,6 +1352,9 @@ fn delete_pets(
     Ok(())
 }
 
+/// Items that were moved out of the character's inventory into a new mail can
+/// be given as `mail_attachments`, along with the container created for them,
+/// so that they are written in the same pass as the inventory.
 #[allow(clippy::too_many_arguments)]
 pub fn update(
     char_id: CharacterId,
@@ -983,12 +1364,37 @@ pub fn update(
     char_waypoint: Option<comp::Waypoint>,
     active_abilities: comp::ability::ActiveAbilities,
     map_marker: Option<comp::MapMarker>,
+    known_recipes: comp::KnownRecipes,
+    statistics: comp::Statistics,
+    achievements: Option<AchievementPersistenceData>,
+    bank: Option<BankPersistenceData>,
+    world_container: Option<(EntityId, &[comp::Item])>,
     transaction: &mut Transaction,
 ) -> Result<(), PersistenceError> {
     // Run pet persistence
     update_pets(char_id, pets, transaction)?;
 
     let pseudo_containers = get_pseudo_containers(transaction, char_id)?;
+    // The bank is written in the same pass as the inventory, so that moving items
+    // between the two is atomic in the same way as a trade.
+    let bank_container_id = match &bank {
+        Some((player_uuid, bank)) => match get_bank_container_id(transaction, player_uuid)? {
+            Some(bank_container_id) => Some(bank_container_id),
+            None if !bank.is_empty() => Some(create_bank_container(player_uuid, transaction)?),
+            None => None,
+        },
+        None => None,
+    };
+    let mut containers = Vec::new();
+    if let Some(((_, bank), bank_container_id)) = bank.as_ref().zip(bank_container_id) {
+        containers.push((bank_container_id, bank.slots_with_id().collect()));
+    }
+    // Items that the character has just put into a container that does not belong
+    // to them, such as a mail or a market order, are moved in the same pass.
+    if let Some((container_id, items)) = world_container {
+        containers.push((container_id, items.iter().enumerate().collect()));
+    }
+
     let mut upserts = Vec::new();
     // First, get all the entity IDs for any new items, and identify which
     // slots to upsert and which ones to delete.
@@ -997,6 +1403,7 @@ pub fn update(
             pseudo_containers.loadout_container_id,
             &inventory,
             pseudo_containers.inventory_container_id,
+            &containers,
             &mut next_id,
         );
         upserts = upserts_;
@@ -1015,74 +1422,34 @@ pub fn update(
     for it in load_items(transaction, pseudo_containers.loadout_container_id)? {
         existing_item_ids.push(Value::from(it.item_id));
     }
+    if let Some(bank_container_id) = bank_container_id {
+        existing_item_ids.push(Value::from(bank_container_id));
+        for it in load_items(transaction, bank_container_id)? {
+            existing_item_ids.push(Value::from(it.item_id));
+        }
+    }
 
-    let non_upserted_items = upserts
-        .iter()
-        .map(|item_pair| Value::from(item_pair.model.item_id))
-        .collect::<Vec<Value>>();
+    let (upserted_items, _): (Vec<_>, Vec<_>) = upserts
+        .into_iter()
+        .map(|model_pair| {
+            debug_assert_eq!(
+                model_pair.model.item_id,
+                model_pair.comp.load().unwrap().get() as i64
+            );
+            (model_pair.model, model_pair.comp)
+        })
+        .unzip();
 
-    let mut stmt = transaction.prepare_cached(
-        "
-        DELETE
-        FROM    item
-        WHERE   parent_container_item_id
-        IN      rarray(?1)
-        AND     item_id NOT IN rarray(?2)",
-    )?;
-    let delete_count = stmt.execute([Rc::new(existing_item_ids), Rc::new(non_upserted_items)])?;
+    let delete_count = delete_items_except(existing_item_ids, &upserted_items, transaction)?;
     trace!("Deleted {} items", delete_count);
 
     // Upsert items
-    let expected_upsert_count = upserts.len();
-    if expected_upsert_count > 0 {
-        let (upserted_items, _): (Vec<_>, Vec<_>) = upserts
-            .into_iter()
-            .map(|model_pair| {
-                debug_assert_eq!(
-                    model_pair.model.item_id,
-                    model_pair.comp.load().unwrap().get() as i64
-                );
-                (model_pair.model, model_pair.comp)
-            })
-            .unzip();
-        trace!(
-            "Upserting items {:?} for character_id {}",
-            upserted_items,
-            char_id.0
-        );
-
-        // When moving inventory items around, foreign key constraints on
-        // `parent_container_item_id` can be temporarily violated by one
-        // upsert, but restored by another upsert. Deferred constraints
-        // allow SQLite to check this when committing the transaction.
-        // The `defer_foreign_keys` pragma treats the foreign key
-        // constraints as deferred for the next transaction (it turns itself
-        // off at the commit boundary). https://sqlite.org/foreignkeys.html#fk_deferred
-        transaction.pragma_update(None, "defer_foreign_keys", &"ON".to_string())?;
-
-        let mut stmt = transaction.prepare_cached(
-            "
-            REPLACE
-            INTO    item (item_id,
-                          parent_container_item_id,
-                          item_definition_id,
-                          stack_size,
-                          position,
-                          properties)
-            VALUES  (?1, ?2, ?3, ?4, ?5, ?6)",
-        )?;
-
-        for item in upserted_items.iter() {
-            stmt.execute([
-                &item.item_id as &dyn ToSql,
-                &item.parent_container_item_id,
-                &item.item_definition_id,
-                &item.stack_size,
-                &item.position,
-                &item.properties,
-            ])?;
-        }
-    }
+    trace!(
+        "Upserting items {:?} for character_id {}",
+        upserted_items,
+        char_id.0
+    );
+    replace_items(&upserted_items, transaction)?;
 
     let db_skill_groups = convert_skill_groups_to_database(char_id, char_skill_set.skill_groups());
 
@@ -1150,5 +1517,67 @@ pub fn update(
         )));
     }
 
+    let known_recipes = convert_known_recipes_to_database(char_id, &known_recipes);
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        UPDATE  known_recipes
+        SET     known_recipes = ?1
+        WHERE   entity_id = ?2
+    ",
+    )?;
+
+    let known_recipes_count = stmt.execute([
+        &known_recipes.known_recipes as &dyn ToSql,
+        &char_id.0 as &dyn ToSql,
+    ])?;
+
+    if known_recipes_count != 1 {
+        return Err(PersistenceError::OtherError(format!(
+            "Error updating known_recipes table for char_id {}",
+            char_id.0,
+        )));
+    }
+
+    let statistics = convert_statistics_to_database(char_id, &statistics);
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        UPDATE  statistics
+        SET     statistics = ?1
+        WHERE   entity_id = ?2
+    ",
+    )?;
+
+    let statistics_count = stmt.execute([
+        &statistics.statistics as &dyn ToSql,
+        &char_id.0 as &dyn ToSql,
+    ])?;
+
+    if statistics_count != 1 {
+        return Err(PersistenceError::OtherError(format!(
+            "Error updating statistics table for char_id {}",
+            char_id.0,
+        )));
+    }
+
+    if let Some((player_uuid, achievements)) = achievements {
+        // Players without achievements have no row, so that one is only created
+        // once they unlock their first achievement
+        if !achievements.is_empty() {
+            let achievements = convert_achievements_to_database(&player_uuid, &achievements);
+
+            let mut stmt = transaction.prepare_cached(
+                "
+                REPLACE
+                INTO    achievement (player_uuid,
+                                     achievements)
+                VALUES  (?1, ?2)",
+            )?;
+
+            stmt.execute([&achievements.player_uuid, &achievements.achievements])?;
+        }
+    }
+
     Ok(())
 }
@@ -1,24 +1,26 @@
 use crate::persistence::{
     character::EntityId,
-    models::{AbilitySets, Character, Item, SkillGroup},
+    models::{self, AbilitySets, Character, Item, SkillGroup},
 };
 
 use crate::persistence::{
     error::PersistenceError,
     json_models::{
-        self, CharacterPosition, DatabaseAbilitySet, DatabaseItemProperties, GenericBody,
-        HumanoidBody,
+        self, CharacterPosition, DatabaseAbilitySet, DatabaseAchievements, DatabaseItemProperties,
+        DatabaseKnownRecipes, DatabaseStatistics, GenericBody, HumanoidBody,
     },
 };
 use common::{
     character::CharacterId,
     comp::{
+        bank::BANK_SLOTS,
         inventory::{
             item::{tool::AbilityMap, Item as VelorenItem, MaterialStatManifest},
             loadout::{Loadout, LoadoutError},
             loadout_builder::LoadoutBuilder,
             slot::InvSlotId,
         },
+        mail::MAX_MAIL_ATTACHMENTS,
         skillset, Body as CompBody, Waypoint, *,
     },
     resources::Time,
@@ -29,6 +31,10 @@ use lazy_static::lazy_static;
 use std::{collections::VecDeque, str::FromStr, sync::Arc};
 use tracing::{trace, warn};
 
+/// The items in a pseudo-container other than a character's inventory or
+/// loadout, along with the slot that each item is in.
+pub type ContainerItems<'a> = (EntityId, Vec<(usize, &'a VelorenItem)>);
+
 #[derive(Debug)]
 pub struct ItemModelPair {
     pub comp: Arc<item::ItemId>,
@@ -52,10 +58,14 @@ lazy_static! {
 /// inventories. Although loadout items do store items inside them this does
 /// not currently utilise `parent_container_id` - all loadout items have the
 /// loadout pseudo-container as their parent.
+///
+/// Other containers, such as the bank, are converted alongside the inventory so
+/// that items moved between them keep their ids and are persisted in one pass.
 pub fn convert_items_to_database_items(
     loadout_container_id: EntityId,
     inventory: &Inventory,
     inventory_container_id: EntityId,
+    containers: &[ContainerItems<'_>],
     next_id: &mut i64,
 ) -> Vec<ItemModelPair> {
     let loadout = inventory
@@ -71,13 +81,54 @@ pub fn convert_items_to_database_items(
         )
     });
 
+    convert_slots_to_database_items(
+        inventory
+            .chain(loadout)
+            .chain(container_slots(containers))
+            .collect(),
+        [inventory_container_id, loadout_container_id]
+            .into_iter()
+            .chain(containers.iter().map(|(container_id, _)| *container_id)),
+        next_id,
+    )
+}
+
+/// Like [`convert_items_to_database_items`], but for containers that do not
+/// belong to a character, such as the ones that hold the items of market
+/// orders.
+pub fn convert_container_items_to_database_items(
+    containers: &[ContainerItems<'_>],
+    next_id: &mut i64,
+) -> Vec<ItemModelPair> {
+    convert_slots_to_database_items(
+        container_slots(containers).collect(),
+        containers.iter().map(|(container_id, _)| *container_id),
+        next_id,
+    )
+}
+
+fn container_slots<'a>(
+    containers: &'a [ContainerItems<'a>],
+) -> impl Iterator<Item = (String, Option<&'a VelorenItem>, EntityId)> {
+    containers.iter().flat_map(|(container_id, items)| {
+        items
+            .iter()
+            .map(move |(slot, item)| (slot.to_string(), Some(*item), *container_id))
+    })
+}
+
+fn convert_slots_to_database_items(
+    mut bfs_queue: VecDeque<(String, Option<&VelorenItem>, EntityId)>,
+    root_container_ids: impl IntoIterator<Item = EntityId>,
+    next_id: &mut i64,
+) -> Vec<ItemModelPair> {
     // Use Breadth-first search to recurse into containers/modular weapons to store
     // their parts
-    let mut bfs_queue: VecDeque<_> = inventory.chain(loadout).collect();
     let mut upserts = Vec::new();
     let mut depth = HashMap::new();
-    depth.insert(inventory_container_id, 0);
-    depth.insert(loadout_container_id, 0);
+    for container_id in root_container_ids {
+        depth.insert(container_id, 0);
+    }
     while let Some((position, item, parent_container_item_id)) = bfs_queue.pop_front() {
         // Construct new items.
         if let Some(item) = item {
@@ -365,35 +416,7 @@ pub fn convert_inventory_from_database_items(
     for (i, db_item) in inventory_items.iter().enumerate() {
         item_indices.insert(db_item.item_id, i);
 
-        let mut item = get_item_from_asset(db_item.item_definition_id.as_str())?;
-        let item_properties =
-            serde_json::de::from_str::<DatabaseItemProperties>(&db_item.properties)?;
-        json_models::apply_db_item_properties(&mut item, &item_properties);
-
-        // NOTE: Since this is freshly loaded, the atomic is *unique.*
-        let comp = item.get_item_id_for_database();
-
-        // Item ID
-        comp.store(Some(NonZeroU64::try_from(db_item.item_id as u64).map_err(
-            |_| PersistenceError::ConversionError("Item with zero item_id".to_owned()),
-        )?));
-
-        // Stack Size
-        if db_item.stack_size == 1 || item.is_stackable() {
-            // FIXME: On failure, collect the set of items that don't fit and return them
-            // (to be dropped next to the player) as this could be the result of
-            // a change in the max amount for that item.
-            item.set_amount(u32::try_from(db_item.stack_size).map_err(|_| {
-                PersistenceError::ConversionError(format!(
-                    "Invalid item stack size for stackable={}: {}",
-                    item.is_stackable(),
-                    &db_item.stack_size
-                ))
-            })?)
-            .map_err(|_| {
-                PersistenceError::ConversionError("Error setting amount for item".to_owned())
-            })?;
-        }
+        let item = convert_stackable_item_from_database(db_item)?;
 
         // Insert item into inventory
 
@@ -455,6 +478,152 @@ pub fn convert_inventory_from_database_items(
     Ok(inventory)
 }
 
+/// Loads an item along with its id and stack size, for items stored in
+/// containers where stacks are allowed.
+fn convert_stackable_item_from_database(db_item: &Item) -> Result<VelorenItem, PersistenceError> {
+    let mut item = get_item_from_asset(db_item.item_definition_id.as_str())?;
+    let item_properties = serde_json::de::from_str::<DatabaseItemProperties>(&db_item.properties)?;
+    json_models::apply_db_item_properties(&mut item, &item_properties);
+
+    // NOTE: Since this is freshly loaded, the atomic is *unique.*
+    let comp = item.get_item_id_for_database();
+
+    // Item ID
+    comp.store(Some(NonZeroU64::try_from(db_item.item_id as u64).map_err(
+        |_| PersistenceError::ConversionError("Item with zero item_id".to_owned()),
+    )?));
+
+    // Stack Size
+    if db_item.stack_size == 1 || item.is_stackable() {
+        // FIXME: On failure, collect the set of items that don't fit and return them
+        // (to be dropped next to the player) as this could be the result of
+        // a change in the max amount for that item.
+        item.set_amount(u32::try_from(db_item.stack_size).map_err(|_| {
+            PersistenceError::ConversionError(format!(
+                "Invalid item stack size for stackable={}: {}",
+                item.is_stackable(),
+                &db_item.stack_size
+            ))
+        })?)
+        .map_err(|_| {
+            PersistenceError::ConversionError("Error setting amount for item".to_owned())
+        })?;
+    }
+
+    Ok(item)
+}
+
+/// Loads the items of a pseudo-container whose positions are slot indices,
+/// such as a bank.
+///
+/// The caller is responsible for ensuring that `database_items` are
+/// topologically sorted, as for [`convert_inventory_from_database_items`].
+fn convert_slots_from_database_items(
+    container_id: i64,
+    database_items: &[Item],
+    num_slots: usize,
+) -> Result<Vec<Option<VelorenItem>>, PersistenceError> {
+    let mut slots: Vec<Option<VelorenItem>> =
+        std::iter::repeat_with(|| None).take(num_slots).collect();
+    let mut item_indices = HashMap::new();
+
+    for (i, db_item) in database_items.iter().enumerate() {
+        item_indices.insert(db_item.item_id, i);
+
+        let item = convert_stackable_item_from_database(db_item)?;
+
+        let slot = |s: &str| {
+            s.parse::<usize>().map_err(|_| {
+                PersistenceError::ConversionError(format!(
+                    "Failed to parse slot position: {:?}",
+                    &db_item.position
+                ))
+            })
+        };
+
+        if db_item.parent_container_item_id == container_id {
+            let slot = slot(&db_item.position)?;
+            match slots.get_mut(slot) {
+                Some(slot @ None) => *slot = Some(item),
+                // FIXME: As with inventories, items that no longer fit should be given back to
+                // the player rather than failing to load.
+                _ => {
+                    return Err(PersistenceError::ConversionError(format!(
+                        "Error inserting item into container {}, position: {}",
+                        container_id, slot
+                    )));
+                },
+            }
+        } else if let Some(&j) = item_indices.get(&db_item.parent_container_item_id) {
+            get_mutable_item(j, database_items, &item_indices, &mut slots, &|slots, s| {
+                slots.get_mut(slot(s).ok()?)?.as_mut()
+            })?
+            .persistence_access_add_component(item);
+        } else {
+            return Err(PersistenceError::ConversionError(format!(
+                "Couldn't find parent item {} before item {} in container {}",
+                db_item.parent_container_item_id, db_item.item_id, container_id
+            )));
+        }
+    }
+
+    // Some items may have had components added, so update the item config of each
+    // item to ensure that it correctly accounts for components that were added
+    slots.iter_mut().flatten().for_each(|item| {
+        item.update_item_state(&ABILITY_MAP, &MATERIAL_STATS_MANIFEST);
+    });
+
+    Ok(slots)
+}
+
+pub fn convert_bank_from_database_items(
+    bank_container_id: i64,
+    bank_items: &[Item],
+) -> Result<Bank, PersistenceError> {
+    let mut bank = Bank::default();
+    let slots = convert_slots_from_database_items(bank_container_id, bank_items, BANK_SLOTS)?;
+    for (slot, item) in slots.into_iter().enumerate() {
+        if let Some(item) = item {
+            bank.insert_at(slot, item).map_err(|_| {
+                PersistenceError::ConversionError(format!(
+                    "Error inserting item into bank, position: {}",
+                    slot
+                ))
+            })?;
+        }
+    }
+    Ok(bank)
+}
+
+pub fn convert_mail_attachments_from_database_items(
+    attachment_container_id: i64,
+    attachment_items: &[Item],
+) -> Result<Vec<VelorenItem>, PersistenceError> {
+    let slots = convert_slots_from_database_items(
+        attachment_container_id,
+        attachment_items,
+        MAX_MAIL_ATTACHMENTS,
+    )?;
+    Ok(slots.into_iter().flatten().collect())
+}
+
+/// Converts the item held by a market order, of which there is always exactly
+/// one.
+pub fn convert_escrow_from_database_items(
+    escrow_container_id: i64,
+    escrow_items: &[Item],
+) -> Result<VelorenItem, PersistenceError> {
+    convert_slots_from_database_items(escrow_container_id, escrow_items, 1)?
+        .pop()
+        .flatten()
+        .ok_or_else(|| {
+            PersistenceError::ConversionError(format!(
+                "Market escrow container {} is empty",
+                escrow_container_id
+            ))
+        })
+}
+
 pub fn convert_loadout_from_database_items(
     loadout_container_id: i64,
     database_items: &[Item],
@@ -740,3 +909,72 @@ pub fn convert_active_abilities_from_database(ability_sets: &AbilitySets) -> Act
         });
     json_models::active_abilities_from_db_model(ability_sets)
 }
+
+pub fn convert_known_recipes_to_database(
+    entity_id: CharacterId,
+    known_recipes: &KnownRecipes,
+) -> models::KnownRecipes {
+    let known_recipes = json_models::known_recipes_to_db_model(known_recipes);
+    models::KnownRecipes {
+        entity_id: entity_id.0,
+        known_recipes: serde_json::to_string(&known_recipes).unwrap_or_default(),
+    }
+}
+
+pub fn convert_known_recipes_from_database(known_recipes: &models::KnownRecipes) -> KnownRecipes {
+    serde_json::from_str::<DatabaseKnownRecipes>(&known_recipes.known_recipes)
+        .map(json_models::known_recipes_from_db_model)
+        .unwrap_or_else(|err| {
+            common_base::dev_panic!(format!(
+                "Failed to parse known recipes. Error: {:#?}\nKnown recipes:\n{:#?}",
+                err, known_recipes.known_recipes
+            ));
+            KnownRecipes::default()
+        })
+}
+
+pub fn convert_statistics_to_database(
+    entity_id: CharacterId,
+    statistics: &Statistics,
+) -> models::Statistics {
+    let statistics = json_models::statistics_to_db_model(statistics);
+    models::Statistics {
+        entity_id: entity_id.0,
+        statistics: serde_json::to_string(&statistics).unwrap_or_default(),
+    }
+}
+
+pub fn convert_statistics_from_database(statistics: &models::Statistics) -> Statistics {
+    serde_json::from_str::<DatabaseStatistics>(&statistics.statistics)
+        .map(json_models::statistics_from_db_model)
+        .unwrap_or_else(|err| {
+            common_base::dev_panic!(format!(
+                "Failed to parse statistics. Error: {:#?}\nStatistics:\n{:#?}",
+                err, statistics.statistics
+            ));
+            Statistics::default()
+        })
+}
+
+pub fn convert_achievements_to_database(
+    player_uuid: &str,
+    achievements: &Achievements,
+) -> models::Achievements {
+    let achievements = json_models::achievements_to_db_model(achievements);
+    models::Achievements {
+        player_uuid: player_uuid.to_string(),
+        achievements: serde_json::to_string(&achievements).unwrap_or_default(),
+    }
+}
+
+pub fn convert_achievements_from_database(achievements: &models::Achievements) -> Achievements {
+    serde_json::from_str::<DatabaseAchievements>(&achievements.achievements)
+        .map(json_models::achievements_from_db_model)
+        .unwrap_or_else(|err| {
+            common_base::dev_panic!(format!(
+                "Failed to parse achievements. Error: {:#?}\nAchievements:\n{:#?}",
+                err, achievements.achievements
+            ));
+            Achievements::default()
+        })
+}
@@ -1,7 +1,10 @@
 use crate::persistence::{
     character::{load_character_data, load_character_list},
     error::PersistenceError,
-    establish_connection, ConnectionMode, DatabaseSettings, PersistedComponents,
+    establish_connection,
+    mail::MailResponse,
+    market::MarketResponse,
+    ConnectionMode, DatabaseSettings, PersistedComponents,
 };
 use common::{
     character::{CharacterId, CharacterItem},
@@ -35,6 +38,10 @@ enum CharacterLoaderRequestKind {
 pub enum CharacterUpdaterMessage {
     CharacterScreenResponse(CharacterScreenResponse),
     DatabaseBatchCompletion(u64),
+    /// Characters that were deleted by a completed batch
+    CharactersDeleted(Vec<CharacterId>),
+    Mail(MailResponse),
+    Market(MarketResponse),
 }
 
 /// An event emitted from CharacterUpdater in response to a request made from
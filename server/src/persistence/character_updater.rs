@@ -1,13 +1,20 @@
-use crate::comp;
-use common::character::CharacterId;
+use crate::{comp, guild::GuildUpdate};
+use common::{
+    character::CharacterId,
+    comp::{mail::MailId, market::MarketOrderId},
+    trade::SiteId,
+};
 
 use crate::persistence::{
     character_loader::{
         CharacterScreenResponse, CharacterScreenResponseKind, CharacterUpdaterMessage,
     },
+    duel::DuelResult,
     error::PersistenceError,
-    establish_connection, ConnectionMode, DatabaseSettings, EditableComponents,
-    PersistedComponents, VelorenConnection,
+    establish_connection,
+    mail::{MailDelivery, MailResponse, OutgoingMail},
+    market::{MarketChange, MarketResponse, NewOrder},
+    ConnectionMode, DatabaseSettings, EditableComponents, PersistedComponents, VelorenConnection,
 };
 use crossbeam_channel::TryIter;
 use rusqlite::DropBehavior;
@@ -20,6 +27,7 @@ use std::{
     },
 };
 use tracing::{debug, error, info, trace, warn};
+use vek::Vec3;
 
 pub type CharacterUpdateData = (
     CharacterId,
@@ -29,10 +37,22 @@ pub type CharacterUpdateData = (
     Option<comp::Waypoint>,
     comp::ability::ActiveAbilities,
     Option<comp::MapMarker>,
+    comp::KnownRecipes,
+    comp::Statistics,
+    Option<AchievementPersistenceData>,
+    Option<BankPersistenceData>,
 );
 
 pub type PetPersistenceData = (comp::Pet, comp::Body, comp::Stats);
 
+/// The bank of the account that a character belongs to, along with the uuid of
+/// the account.
+pub type BankPersistenceData = (String, comp::Bank);
+
+/// The achievements of the account that a character belongs to, along with the
+/// uuid of the account.
+pub type AchievementPersistenceData = (String, comp::Achievements);
+
 #[allow(clippy::large_enum_variant)]
 enum CharacterUpdaterAction {
     BatchUpdate {
@@ -52,6 +72,41 @@ enum CharacterUpdaterAction {
         character_alias: String,
         editable_components: EditableComponents,
     },
+    UpdateGuilds(Vec<GuildUpdate>),
+    RecordDuel(DuelResult),
+    SendMail {
+        entity: Entity,
+        sender: Box<CharacterUpdateData>,
+        mail: OutgoingMail,
+    },
+    ClaimMail {
+        entity: Entity,
+        claimant: Box<CharacterUpdateData>,
+        mail_id: MailId,
+    },
+    LoadMailbox {
+        entity: Entity,
+        character_id: CharacterId,
+        mailbox_sprite: Vec3<i32>,
+    },
+    PostMarketOrder {
+        entity: Entity,
+        market_sprite: Vec3<i32>,
+        owner: Box<CharacterUpdateData>,
+        order: NewOrder,
+    },
+    CancelMarketOrder {
+        entity: Entity,
+        market_sprite: Vec3<i32>,
+        character_id: CharacterId,
+        order_id: MarketOrderId,
+    },
+    LoadMarket {
+        entity: Entity,
+        market_sprite: Vec3<i32>,
+        character_id: CharacterId,
+        site_id: SiteId,
+    },
     DisconnectedSuccess,
 }
 
@@ -94,6 +149,10 @@ pub struct CharacterUpdater {
     /// Pending actions to be performed during the next persistence batch, such
     /// as updates for recently logged out players and character deletions
     pending_database_actions: HashMap<CharacterId, DatabaseAction>,
+    /// The accounts of characters with pending logout updates, since the bank
+    /// shared by the account must be persisted before any of its characters
+    /// can be loaded again
+    pending_bank_accounts: HashMap<CharacterId, String>,
     /// Will disconnect all characters (without persistence) on the next tick if
     /// set to true
     disconnect_all_clients_requested: Arc<AtomicBool>,
@@ -127,14 +186,25 @@ impl CharacterUpdater {
                             }
                             conn.update_log_mode(&settings);
 
-                            if let Err(e) = execute_batch_update(updates.into_iter(), &mut conn) {
-                                error!(
-                                    ?e,
-                                    "Error during character batch update, disconnecting all \
-                                     clients to avoid loss of data integrity."
-                                );
-                                disconnect_all_clients_requested_clone
-                                    .store(true, Ordering::Relaxed);
+                            match execute_batch_update(updates.into_iter(), &mut conn) {
+                                Ok(deleted) => {
+                                    if !deleted.is_empty() {
+                                        if let Err(e) = response_tx.send(
+                                            CharacterUpdaterMessage::CharactersDeleted(deleted),
+                                        ) {
+                                            error!(?e, "Could not send CharactersDeleted message");
+                                        }
+                                    }
+                                },
+                                Err(e) => {
+                                    error!(
+                                        ?e,
+                                        "Error during character batch update, disconnecting all \
+                                         clients to avoid loss of data integrity."
+                                    );
+                                    disconnect_all_clients_requested_clone
+                                        .store(true, Ordering::Relaxed);
+                                },
                             };
 
                             if let Err(e) = response_tx
@@ -208,6 +278,142 @@ impl CharacterUpdater {
                                 ),
                             }
                         },
+                        CharacterUpdaterAction::UpdateGuilds(updates) => {
+                            conn.update_log_mode(&settings);
+
+                            if let Err(e) = execute_guild_update(updates, &mut conn) {
+                                error!(?e, "Error during guild update");
+                            }
+                        },
+                        CharacterUpdaterAction::RecordDuel(result) => {
+                            conn.update_log_mode(&settings);
+
+                            if let Err(e) = execute_record_duel(result, &mut conn) {
+                                error!(?e, "Error recording duel result");
+                            }
+                        },
+                        CharacterUpdaterAction::SendMail {
+                            entity,
+                            sender,
+                            mail,
+                        } => {
+                            conn.update_log_mode(&settings);
+
+                            let result = execute_send_mail(*sender, &mail, &mut conn);
+                            if let Err(e) = &result {
+                                error!(?e, "Error sending mail");
+                            }
+                            if let Err(e) = response_tx.send(CharacterUpdaterMessage::Mail(
+                                MailResponse::Sent {
+                                    entity,
+                                    mail,
+                                    result,
+                                },
+                            )) {
+                                error!(?e, "Could not send mail response");
+                            }
+                        },
+                        CharacterUpdaterAction::ClaimMail {
+                            entity,
+                            claimant,
+                            mail_id,
+                        } => {
+                            conn.update_log_mode(&settings);
+
+                            let result = execute_claim_mail(*claimant, mail_id, &mut conn);
+                            if let Err(e) = &result {
+                                error!(?e, "Error claiming mail");
+                            }
+                            if let Err(e) = response_tx.send(CharacterUpdaterMessage::Mail(
+                                MailResponse::Claimed {
+                                    entity,
+                                    mail_id,
+                                    result,
+                                },
+                            )) {
+                                error!(?e, "Could not send mail response");
+                            }
+                        },
+                        CharacterUpdaterAction::LoadMailbox {
+                            entity,
+                            character_id,
+                            mailbox_sprite,
+                        } => {
+                            let result = super::mail::load_mailbox(character_id, &conn.connection);
+                            if let Err(e) = response_tx.send(CharacterUpdaterMessage::Mail(
+                                MailResponse::Mailbox {
+                                    entity,
+                                    mailbox_sprite,
+                                    result,
+                                },
+                            )) {
+                                error!(?e, "Could not send mailbox");
+                            }
+                        },
+                        CharacterUpdaterAction::PostMarketOrder {
+                            entity,
+                            market_sprite,
+                            owner,
+                            order,
+                        } => {
+                            conn.update_log_mode(&settings);
+
+                            let result = execute_post_market_order(*owner, &order, &mut conn);
+                            if let Err(e) = &result {
+                                error!(?e, "Error posting market order");
+                            }
+                            if let Err(e) = response_tx.send(CharacterUpdaterMessage::Market(
+                                MarketResponse::Posted {
+                                    entity,
+                                    market_sprite,
+                                    order,
+                                    result,
+                                },
+                            )) {
+                                error!(?e, "Could not send market response");
+                            }
+                        },
+                        CharacterUpdaterAction::CancelMarketOrder {
+                            entity,
+                            market_sprite,
+                            character_id,
+                            order_id,
+                        } => {
+                            conn.update_log_mode(&settings);
+
+                            let result =
+                                execute_cancel_market_order(character_id, order_id, &mut conn);
+                            if let Err(e) = &result {
+                                error!(?e, "Error cancelling market order");
+                            }
+                            if let Err(e) = response_tx.send(CharacterUpdaterMessage::Market(
+                                MarketResponse::Cancelled {
+                                    entity,
+                                    market_sprite,
+                                    result,
+                                },
+                            )) {
+                                error!(?e, "Could not send market response");
+                            }
+                        },
+                        CharacterUpdaterAction::LoadMarket {
+                            entity,
+                            market_sprite,
+                            character_id,
+                            site_id,
+                        } => {
+                            let result =
+                                super::market::load_orders(site_id, character_id, &conn.connection);
+                            if let Err(e) = response_tx.send(CharacterUpdaterMessage::Market(
+                                MarketResponse::Market {
+                                    entity,
+                                    market_sprite,
+                                    result,
+                                },
+                            )) {
+                                error!(?e, "Could not send market");
+                            }
+                        },
                         CharacterUpdaterAction::DisconnectedSuccess => {
                             info!(
                                 "CharacterUpdater received DisconnectedSuccess event, resuming \
@@ -227,6 +433,7 @@ impl CharacterUpdater {
             response_rx,
             handle: Some(handle),
             pending_database_actions: HashMap::new(),
+            pending_bank_accounts: HashMap::new(),
             disconnect_all_clients_requested,
             last_pending_database_event_id: 0,
         })
@@ -256,6 +463,11 @@ impl CharacterUpdater {
             return;
         }
 
+        // The bank and achievements are shared by all characters of the account
+        if let Some((player_uuid, _)) = &update_data.11 {
+            self.pending_bank_accounts
+                .insert(update_data.0, player_uuid.clone());
+        }
         self.pending_database_actions.insert(
             update_data.0, // CharacterId
             DatabaseAction::New(DatabaseActionKind::UpdateCharacter(Box::new(update_data))),
@@ -266,12 +478,23 @@ impl CharacterUpdater {
         self.pending_database_actions.get(&character_id).is_some()
     }
 
+    /// Whether the bank of the given account has changes that are yet to be
+    /// persisted by a logout update.
+    pub fn has_pending_bank_update(&self, player_uuid: &str) -> bool {
+        self.pending_bank_accounts
+            .values()
+            .any(|account| account == player_uuid)
+    }
+
     pub fn process_batch_completion(&mut self, completed_batch_id: u64) {
         self.pending_database_actions.drain_filter(|_, event| {
             matches!(event, DatabaseAction::Submitted {
                     batch_id,
             } if completed_batch_id == *batch_id)
         });
+        let pending_database_actions = &self.pending_database_actions;
+        self.pending_bank_accounts
+            .retain(|character_id, _| pending_database_actions.contains_key(character_id));
         debug!(
             "Processed database batch completion - Batch ID: {}",
             completed_batch_id
@@ -405,17 +628,177 @@ impl CharacterUpdater {
             );
     }
 
+    /// Persists changes to guilds and their membership.
+    pub fn update_guilds(&mut self, updates: Vec<GuildUpdate>) {
+        if updates.is_empty() {
+            return;
+        }
+        if let Err(e) = self
+            .update_tx
+            .as_ref()
+            .unwrap()
+            .send(CharacterUpdaterAction::UpdateGuilds(updates))
+        {
+            error!(?e, "Could not send guild updates");
+        }
+    }
+
+    /// Records the result of a duel between two characters.
+    pub fn record_duel(&mut self, result: DuelResult) {
+        if let Err(e) = self
+            .update_tx
+            .as_ref()
+            .unwrap()
+            .send(CharacterUpdaterAction::RecordDuel(result))
+        {
+            error!(?e, "Could not send duel result");
+        }
+    }
+
+    /// Sends a mail on behalf of a character, persisting the sender along
+    /// with it so that the attachments cannot be duplicated or lost.
+    pub fn send_mail(&mut self, entity: Entity, sender: CharacterUpdateData, mail: OutgoingMail) {
+        if let Err(e) = self
+            .update_tx
+            .as_ref()
+            .unwrap()
+            .send(CharacterUpdaterAction::SendMail {
+                entity,
+                sender: Box::new(sender),
+                mail,
+            })
+        {
+            error!(?e, "Could not send mail request");
+        }
+    }
+
+    /// Removes a mail whose attachments have been moved into the claimant's
+    /// inventory, persisting the claimant along with it.
+    pub fn claim_mail(&mut self, entity: Entity, claimant: CharacterUpdateData, mail_id: MailId) {
+        if let Err(e) = self
+            .update_tx
+            .as_ref()
+            .unwrap()
+            .send(CharacterUpdaterAction::ClaimMail {
+                entity,
+                claimant: Box::new(claimant),
+                mail_id,
+            })
+        {
+            error!(?e, "Could not send mail claim request");
+        }
+    }
+
+    /// Loads the mailbox of a character. This goes through the updater rather
+    /// than the loader so that it sees the effects of any mail sent or claimed
+    /// before it was requested.
+    pub fn load_mailbox(
+        &mut self,
+        entity: Entity,
+        character_id: CharacterId,
+        mailbox_sprite: Vec3<i32>,
+    ) {
+        if let Err(e) = self
+            .update_tx
+            .as_ref()
+            .unwrap()
+            .send(CharacterUpdaterAction::LoadMailbox {
+                entity,
+                character_id,
+                mailbox_sprite,
+            })
+        {
+            error!(?e, "Could not send mailbox load request");
+        }
+    }
+
+    /// Puts an order on a market board on behalf of a character, persisting
+    /// the character along with it so that the escrowed items or coins cannot
+    /// be duplicated or lost.
+    pub fn post_market_order(
+        &mut self,
+        entity: Entity,
+        market_sprite: Vec3<i32>,
+        owner: CharacterUpdateData,
+        order: NewOrder,
+    ) {
+        if let Err(e) =
+            self.update_tx
+                .as_ref()
+                .unwrap()
+                .send(CharacterUpdaterAction::PostMarketOrder {
+                    entity,
+                    market_sprite,
+                    owner: Box::new(owner),
+                    order,
+                })
+        {
+            error!(?e, "Could not send market order");
+        }
+    }
+
+    /// Takes one of a character's orders off the market board that it was
+    /// placed on.
+    pub fn cancel_market_order(
+        &mut self,
+        entity: Entity,
+        market_sprite: Vec3<i32>,
+        character_id: CharacterId,
+        order_id: MarketOrderId,
+    ) {
+        if let Err(e) =
+            self.update_tx
+                .as_ref()
+                .unwrap()
+                .send(CharacterUpdaterAction::CancelMarketOrder {
+                    entity,
+                    market_sprite,
+                    character_id,
+                    order_id,
+                })
+        {
+            error!(?e, "Could not send market order cancellation");
+        }
+    }
+
+    /// Loads the open orders on the market board of a site. Like the mailbox,
+    /// this goes through the updater so that it sees any orders placed before
+    /// it was requested.
+    pub fn load_market(
+        &mut self,
+        entity: Entity,
+        market_sprite: Vec3<i32>,
+        character_id: CharacterId,
+        site_id: SiteId,
+    ) {
+        if let Err(e) = self
+            .update_tx
+            .as_ref()
+            .unwrap()
+            .send(CharacterUpdaterAction::LoadMarket {
+                entity,
+                market_sprite,
+                character_id,
+                site_id,
+            })
+        {
+            error!(?e, "Could not send market load request");
+        }
+    }
+
     /// Returns a non-blocking iterator over CharacterLoaderResponse messages
     pub fn messages(&self) -> TryIter<CharacterUpdaterMessage> { self.response_rx.try_iter() }
 }
 
+/// Returns the characters that were deleted by the batch
 fn execute_batch_update(
     updates: impl Iterator<Item = DatabaseActionKind>,
     connection: &mut VelorenConnection,
-) -> Result<(), PersistenceError> {
+) -> Result<Vec<CharacterId>, PersistenceError> {
     let mut transaction = connection.connection.transaction()?;
     transaction.set_drop_behavior(DropBehavior::Rollback);
     trace!("Transaction started for character batch update");
+    let mut deleted = Vec::new();
     updates.into_iter().try_for_each(|event| match event {
         DatabaseActionKind::UpdateCharacter(box (
             character_id,
@@ -425,6 +808,10 @@ fn execute_batch_update(
             waypoint,
             active_abilities,
             map_marker,
+            known_recipes,
+            statistics,
+            achievements,
+            bank,
         )) => super::character::update(
             character_id,
             stats,
@@ -433,6 +820,11 @@ fn execute_batch_update(
             waypoint,
             active_abilities,
             map_marker,
+            known_recipes,
+            statistics,
+            achievements,
+            bank,
+            None,
             &mut transaction,
         ),
         DatabaseActionKind::DeleteCharacter {
@@ -442,15 +834,107 @@ fn execute_batch_update(
             &requesting_player_uuid,
             character_id,
             &mut transaction,
-        ),
+        )
+        .map(|was_deleted| {
+            if was_deleted {
+                deleted.push(character_id);
+            }
+        }),
     })?;
 
     transaction.commit()?;
 
     trace!("Commit for character batch update completed");
+    Ok(deleted)
+}
+
+fn execute_guild_update(
+    updates: Vec<GuildUpdate>,
+    connection: &mut VelorenConnection,
+) -> Result<(), PersistenceError> {
+    let mut transaction = connection.connection.transaction()?;
+    transaction.set_drop_behavior(DropBehavior::Rollback);
+    super::guild::update_guilds(updates, &mut transaction)?;
+    transaction.commit()?;
+
+    trace!("Commit for guild update completed");
+    Ok(())
+}
+
+fn execute_record_duel(
+    result: DuelResult,
+    connection: &mut VelorenConnection,
+) -> Result<(), PersistenceError> {
+    let mut transaction = connection.connection.transaction()?;
+    transaction.set_drop_behavior(DropBehavior::Rollback);
+    super::duel::record_duel(result, &mut transaction)?;
+    transaction.commit()?;
+
+    trace!("Commit for duel result completed");
+    Ok(())
+}
+
+fn execute_send_mail(
+    sender: CharacterUpdateData,
+    mail: &OutgoingMail,
+    connection: &mut VelorenConnection,
+) -> Result<MailDelivery, PersistenceError> {
+    let mut transaction = connection.connection.transaction()?;
+    transaction.set_drop_behavior(DropBehavior::Rollback);
+    let delivery = super::mail::send_mail(sender, mail, &mut transaction)?;
+    transaction.commit()?;
+
+    trace!("Commit for sent mail completed");
+    Ok(delivery)
+}
+
+fn execute_claim_mail(
+    claimant: CharacterUpdateData,
+    mail_id: MailId,
+    connection: &mut VelorenConnection,
+) -> Result<(), PersistenceError> {
+    let mut transaction = connection.connection.transaction()?;
+    transaction.set_drop_behavior(DropBehavior::Rollback);
+    super::mail::claim_mail(claimant, mail_id, &mut transaction)?;
+    transaction.commit()?;
+
+    trace!("Commit for claimed mail completed");
     Ok(())
 }
 
+fn execute_post_market_order(
+    owner: CharacterUpdateData,
+    order: &NewOrder,
+    connection: &mut VelorenConnection,
+) -> Result<MarketChange, PersistenceError> {
+    let mut transaction = connection.connection.transaction()?;
+    transaction.set_drop_behavior(DropBehavior::Rollback);
+    let change = super::market::post_order(owner, order, &mut transaction)?;
+    transaction.commit()?;
+
+    trace!("Commit for market order completed");
+    Ok(change)
+}
+
+fn execute_cancel_market_order(
+    character_id: CharacterId,
+    order_id: MarketOrderId,
+    connection: &mut VelorenConnection,
+) -> Result<Vec<comp::MarketOrder>, PersistenceError> {
+    let mut transaction = connection.connection.transaction()?;
+    transaction.set_drop_behavior(DropBehavior::Rollback);
+    let orders = super::market::cancel_order(
+        character_id,
+        order_id,
+        chrono::Utc::now().timestamp(),
+        &mut transaction,
+    )?;
+    transaction.commit()?;
+
+    trace!("Commit for market order cancellation completed");
+    Ok(orders)
+}
+
 fn execute_character_create(
     entity: Entity,
     alias: String,
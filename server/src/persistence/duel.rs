@@ -0,0 +1,58 @@
+//! Database operations related to duels
+extern crate rusqlite;
+
+use super::error::PersistenceError;
+use common::character::CharacterId;
+use rusqlite::{ToSql, Transaction};
+
+/// How a duel was decided.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DuelOutcome {
+    /// The loser was beaten by the winner.
+    Defeat,
+    /// The loser left the arena, or was killed by something other than the
+    /// winner.
+    Forfeit,
+    /// Neither duellist won before the time limit. The winner and loser of a
+    /// draw are arbitrary.
+    Draw,
+}
+
+#[derive(Clone, Debug)]
+pub struct DuelResult {
+    pub winner: CharacterId,
+    pub loser: CharacterId,
+    pub outcome: DuelOutcome,
+    /// Unix timestamp of when the duel finished
+    pub finished_at: i64,
+}
+
+fn outcome_to_database(outcome: DuelOutcome) -> &'static str {
+    match outcome {
+        DuelOutcome::Defeat => "Defeat",
+        DuelOutcome::Forfeit => "Forfeit",
+        DuelOutcome::Draw => "Draw",
+    }
+}
+
+pub fn record_duel(
+    result: DuelResult,
+    transaction: &mut Transaction,
+) -> Result<(), PersistenceError> {
+    let mut stmt = transaction.prepare_cached(
+        "
+        INSERT INTO duel_result (winner_character_id,
+                                 loser_character_id,
+                                 outcome,
+                                 finished_at)
+        VALUES (?1, ?2, ?3, ?4)",
+    )?;
+    stmt.execute([
+        &result.winner.0 as &dyn ToSql,
+        &result.loser.0,
+        &outcome_to_database(result.outcome),
+        &result.finished_at,
+    ])?;
+
+    Ok(())
+}
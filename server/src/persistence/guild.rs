@@ -0,0 +1,165 @@
+//! Database operations related to guilds
+//!
+//! Guilds are loaded once when the server starts, after which the server's
+//! [`GuildManager`] is authoritative and changes to it are written back via
+//! the [`CharacterUpdater`].
+//!
+//! [`CharacterUpdater`]: super::character_updater::CharacterUpdater
+extern crate rusqlite;
+
+use super::error::PersistenceError;
+use crate::guild::{GuildManager, GuildUpdate};
+use common::{
+    character::CharacterId,
+    comp::{guild::GuildId, GuildRank},
+};
+use rusqlite::{Connection, ToSql, Transaction};
+use tracing::{debug, warn};
+
+fn rank_to_database(rank: GuildRank) -> &'static str {
+    match rank {
+        GuildRank::Recruit => "Recruit",
+        GuildRank::Member => "Member",
+        GuildRank::Officer => "Officer",
+        GuildRank::Leader => "Leader",
+    }
+}
+
+fn rank_from_database(rank: &str) -> Option<GuildRank> {
+    Some(match rank {
+        "Recruit" => GuildRank::Recruit,
+        "Member" => GuildRank::Member,
+        "Officer" => GuildRank::Officer,
+        "Leader" => GuildRank::Leader,
+        _ => return None,
+    })
+}
+
+pub fn load_guilds(connection: &Connection) -> Result<GuildManager, PersistenceError> {
+    let mut stmt = connection.prepare_cached(
+        "
+        SELECT  guild_id,
+                name
+        FROM    guild",
+    )?;
+
+    let guilds = stmt
+        .query_map([], |row| Ok((GuildId(row.get(0)?), row.get(1)?)))?
+        .collect::<Result<Vec<(GuildId, String)>, _>>()?;
+    drop(stmt);
+
+    let mut stmt = connection.prepare_cached(
+        "
+        SELECT  m.character_id,
+                m.guild_id,
+                c.alias,
+                m.rank,
+                m.joined
+        FROM    guild_member m
+        JOIN    character c ON c.character_id = m.character_id",
+    )?;
+
+    let members = stmt
+        .query_map([], |row| {
+            Ok((
+                CharacterId(row.get(0)?),
+                GuildId(row.get(1)?),
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, i64>(4)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .filter_map(|(character_id, guild_id, alias, rank, joined)| {
+            match rank_from_database(&rank) {
+                Some(rank) => Some((character_id, guild_id, alias, rank, joined)),
+                None => {
+                    warn!(
+                        ?character_id,
+                        ?rank,
+                        "Ignoring guild member with invalid rank"
+                    );
+                    None
+                },
+            }
+        })
+        .collect();
+
+    debug!("Loaded {} guilds", guilds.len());
+    Ok(GuildManager::from_loaded(guilds, members))
+}
+
+pub fn update_guilds(
+    updates: Vec<GuildUpdate>,
+    transaction: &mut Transaction,
+) -> Result<(), PersistenceError> {
+    for update in updates {
+        match update {
+            GuildUpdate::Create { guild, name } => {
+                let mut stmt = transaction.prepare_cached(
+                    "
+                    INSERT INTO guild (guild_id,
+                                       name)
+                    VALUES (?1, ?2)",
+                )?;
+                stmt.execute([&guild.0 as &dyn ToSql, &name])?;
+            },
+            GuildUpdate::Disband(guild) => {
+                let mut stmt = transaction.prepare_cached(
+                    "
+                    DELETE
+                    FROM    guild_member
+                    WHERE   guild_id = ?1",
+                )?;
+                stmt.execute([&guild.0])?;
+                drop(stmt);
+
+                let mut stmt = transaction.prepare_cached(
+                    "
+                    DELETE
+                    FROM    guild
+                    WHERE   guild_id = ?1",
+                )?;
+                stmt.execute([&guild.0])?;
+            },
+            GuildUpdate::SetMember {
+                character,
+                guild,
+                rank,
+                joined,
+            } => {
+                // The character may have been deleted since the guild manager last heard
+                // of it, in which case the membership is dropped rather than failing the
+                // whole batch. The manager removes it once the deletion has completed.
+                let mut stmt = transaction.prepare_cached(
+                    "
+                    REPLACE
+                    INTO    guild_member (character_id,
+                                          guild_id,
+                                          rank,
+                                          joined)
+                    SELECT  ?1, ?2, ?3, ?4
+                    WHERE   EXISTS (SELECT 1 FROM character WHERE character_id = ?1)",
+                )?;
+                stmt.execute([
+                    &character.0 as &dyn ToSql,
+                    &guild.0,
+                    &rank_to_database(rank),
+                    &joined,
+                ])?;
+            },
+            GuildUpdate::RemoveMember(character) => {
+                let mut stmt = transaction.prepare_cached(
+                    "
+                    DELETE
+                    FROM    guild_member
+                    WHERE   character_id = ?1",
+                )?;
+                stmt.execute([&character.0])?;
+            },
+        }
+    }
+
+    Ok(())
+}
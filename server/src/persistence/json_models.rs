@@ -1,4 +1,4 @@
-use common::comp;
+use common::comp::{self, item::CraftQuality};
 use common_base::dev_panic;
 use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
@@ -293,17 +293,42 @@ pub fn active_abilities_from_db_model(
 pub struct DatabaseItemProperties {
     #[serde(skip_serializing_if = "Option::is_none")]
     durability: Option<NonZeroU32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    craft_quality: Option<CraftQuality>,
 }
 
 pub fn item_properties_to_db_model(item: &comp::Item) -> DatabaseItemProperties {
     DatabaseItemProperties {
         durability: item.persistence_durability(),
+        craft_quality: item.persistence_craft_quality(),
     }
 }
 
 pub fn apply_db_item_properties(item: &mut comp::Item, properties: &DatabaseItemProperties) {
-    let DatabaseItemProperties { durability } = properties;
+    let DatabaseItemProperties {
+        durability,
+        craft_quality,
+    } = properties;
     item.persistence_set_durability(*durability);
+    item.persistence_set_craft_quality(*craft_quality);
+}
+
+/// Struct containing the recipes known by a character in the format that they
+/// get persisted to the database.
+#[derive(Serialize, Deserialize)]
+pub struct DatabaseKnownRecipes {
+    recipes: Vec<String>,
+}
+
+pub fn known_recipes_to_db_model(known_recipes: &comp::KnownRecipes) -> DatabaseKnownRecipes {
+    let mut recipes = known_recipes.iter().cloned().collect::<Vec<_>>();
+    // Sorted so that the stored value does not change between saves
+    recipes.sort_unstable();
+    DatabaseKnownRecipes { recipes }
+}
+
+pub fn known_recipes_from_db_model(known_recipes: DatabaseKnownRecipes) -> comp::KnownRecipes {
+    comp::KnownRecipes::new(known_recipes.recipes)
 }
 
 #[cfg(test)]
@@ -318,3 +343,63 @@ pub mod tests {
         );
     }
 }
+
+/// Struct containing the statistics of a character in the format that they
+/// get persisted to the database.
+#[derive(Serialize, Deserialize)]
+pub struct DatabaseStatistics {
+    kills: Vec<(String, u64)>,
+    deaths: u64,
+    distance_travelled: f64,
+    items_crafted: u64,
+    sites_visited: Vec<u64>,
+    dungeons_cleared: u64,
+}
+
+pub fn statistics_to_db_model(statistics: &comp::Statistics) -> DatabaseStatistics {
+    let mut kills = statistics
+        .kills
+        .iter()
+        .map(|(body, count)| (body.clone(), *count))
+        .collect::<Vec<_>>();
+    let mut sites_visited = statistics.sites_visited.iter().copied().collect::<Vec<_>>();
+    // Sorted so that the stored value does not change between saves
+    kills.sort_unstable();
+    sites_visited.sort_unstable();
+    DatabaseStatistics {
+        kills,
+        deaths: statistics.deaths,
+        distance_travelled: statistics.distance_travelled,
+        items_crafted: statistics.items_crafted,
+        sites_visited,
+        dungeons_cleared: statistics.dungeons_cleared,
+    }
+}
+
+pub fn statistics_from_db_model(statistics: DatabaseStatistics) -> comp::Statistics {
+    comp::Statistics {
+        kills: statistics.kills.into_iter().collect(),
+        deaths: statistics.deaths,
+        distance_travelled: statistics.distance_travelled,
+        items_crafted: statistics.items_crafted,
+        sites_visited: statistics.sites_visited.into_iter().collect(),
+        dungeons_cleared: statistics.dungeons_cleared,
+    }
+}
+
+/// Struct containing the achievements unlocked by a player in the format that
+/// they get persisted to the database.
+#[derive(Serialize, Deserialize)]
+pub struct DatabaseAchievements {
+    achievements: Vec<String>,
+}
+
+pub fn achievements_to_db_model(achievements: &comp::Achievements) -> DatabaseAchievements {
+    let mut achievements = achievements.iter().cloned().collect::<Vec<_>>();
+    achievements.sort_unstable();
+    DatabaseAchievements { achievements }
+}
+
+pub fn achievements_from_db_model(achievements: DatabaseAchievements) -> comp::Achievements {
+    comp::Achievements::new(achievements.achievements)
+}
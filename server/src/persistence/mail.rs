@@ -0,0 +1,327 @@
+//! Database operations related to mail
+extern crate rusqlite;
+
+use super::{
+    character::{create_mail_container, delete_container, load_mail_attachments, update, EntityId},
+    character_updater::CharacterUpdateData,
+    error::PersistenceError,
+};
+use common::{
+    character::CharacterId,
+    comp::{
+        self,
+        mail::{Mail, MailId},
+    },
+};
+use rusqlite::{Connection, OptionalExtension, ToSql, Transaction};
+use specs::Entity;
+use vek::Vec3;
+
+/// A mail that has been taken from its sender, but not yet stored in the
+/// database.
+#[derive(Debug)]
+pub struct OutgoingMail {
+    pub sender_alias: String,
+    pub recipient_alias: String,
+    pub body: String,
+    pub attachments: Vec<comp::Item>,
+    /// Unix timestamp of when the mail was sent
+    pub sent_at: i64,
+}
+
+/// The result of a mail request made to the
+/// [`CharacterUpdater`](super::character_updater::CharacterUpdater).
+#[derive(Debug)]
+pub enum MailResponse {
+    Sent {
+        entity: Entity,
+        mail: OutgoingMail,
+        result: Result<MailDelivery, PersistenceError>,
+    },
+    Claimed {
+        entity: Entity,
+        mail_id: MailId,
+        result: Result<(), PersistenceError>,
+    },
+    Mailbox {
+        entity: Entity,
+        mailbox_sprite: Vec3<i32>,
+        result: Result<comp::Mailbox, PersistenceError>,
+    },
+}
+
+/// Finds the character with the given alias. Aliases are not unique, so
+/// `None` is returned unless exactly one character has the alias.
+fn find_recipient(
+    alias: &str,
+    transaction: &mut Transaction,
+) -> Result<Option<CharacterId>, PersistenceError> {
+    let mut stmt = transaction.prepare_cached(
+        "
+        SELECT  character_id
+        FROM    character
+        WHERE   alias = ?1
+        LIMIT   2",
+    )?;
+    let character_ids = stmt
+        .query_map([alias], |row| row.get::<_, i64>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(match character_ids.as_slice() {
+        [character_id] => Some(CharacterId(*character_id)),
+        _ => None,
+    })
+}
+
+/// Where a sent mail ended up.
+#[derive(Copy, Clone, Debug)]
+pub enum MailDelivery {
+    Delivered(CharacterId),
+    /// No single character has the recipient's alias, so the mail was put in
+    /// the sender's own mailbox instead, to avoid losing its attachments.
+    Returned(CharacterId),
+}
+
+/// Stores a mail in the mailbox of its recipient. If the mail has attachments,
+/// this returns the container that they should be written to.
+pub(super) fn insert_mail(
+    recipient_id: CharacterId,
+    sender_alias: &str,
+    body: &str,
+    sent_at: i64,
+    has_attachments: bool,
+    transaction: &mut Transaction,
+) -> Result<Option<EntityId>, PersistenceError> {
+    let attachment_container_id = if has_attachments {
+        Some(create_mail_container(transaction)?)
+    } else {
+        None
+    };
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        INSERT INTO mail (recipient_character_id,
+                          sender_alias,
+                          body,
+                          sent_at,
+                          attachment_container_id)
+        VALUES (?1, ?2, ?3, ?4, ?5)",
+    )?;
+    stmt.execute([
+        &recipient_id.0 as &dyn ToSql,
+        &sender_alias,
+        &body,
+        &sent_at,
+        &attachment_container_id,
+    ])?;
+
+    Ok(attachment_container_id)
+}
+
+/// Stores a mail for its recipient, and persists its sender so that the
+/// attachments are moved out of their inventory in the same transaction.
+pub fn send_mail(
+    sender: CharacterUpdateData,
+    mail: &OutgoingMail,
+    transaction: &mut Transaction,
+) -> Result<MailDelivery, PersistenceError> {
+    let delivery = match find_recipient(&mail.recipient_alias, transaction)? {
+        Some(recipient_id) => MailDelivery::Delivered(recipient_id),
+        None => MailDelivery::Returned(sender.0),
+    };
+    let (MailDelivery::Delivered(recipient_id) | MailDelivery::Returned(recipient_id)) = delivery;
+
+    let attachment_container_id = insert_mail(
+        recipient_id,
+        &mail.sender_alias,
+        &mail.body,
+        mail.sent_at,
+        !mail.attachments.is_empty(),
+        transaction,
+    )?;
+
+    let (
+        character_id,
+        skill_set,
+        inventory,
+        pets,
+        waypoint,
+        active_abilities,
+        map_marker,
+        known_recipes,
+        statistics,
+        achievements,
+        bank,
+    ) = sender;
+    update(
+        character_id,
+        skill_set,
+        inventory,
+        pets,
+        waypoint,
+        active_abilities,
+        map_marker,
+        known_recipes,
+        statistics,
+        achievements,
+        bank,
+        attachment_container_id.map(|container_id| (container_id, mail.attachments.as_slice())),
+        transaction,
+    )?;
+
+    Ok(delivery)
+}
+
+/// Removes a mail from the claimant's mailbox, and persists the claimant so
+/// that the attachments are moved into their inventory in the same
+/// transaction.
+pub fn claim_mail(
+    claimant: CharacterUpdateData,
+    mail_id: MailId,
+    transaction: &mut Transaction,
+) -> Result<(), PersistenceError> {
+    let mut stmt = transaction.prepare_cached(
+        "
+        SELECT  attachment_container_id
+        FROM    mail
+        WHERE   mail_id = ?1
+        AND     recipient_character_id = ?2",
+    )?;
+    let attachment_container_id = stmt
+        .query_row([mail_id, claimant.0.0], |row| {
+            row.get::<_, Option<EntityId>>(0)
+        })
+        .optional()?
+        .ok_or_else(|| {
+            PersistenceError::OtherError(format!(
+                "Mail {} does not belong to character {}",
+                mail_id, claimant.0.0
+            ))
+        })?;
+    drop(stmt);
+
+    let (
+        character_id,
+        skill_set,
+        inventory,
+        pets,
+        waypoint,
+        active_abilities,
+        map_marker,
+        known_recipes,
+        statistics,
+        achievements,
+        bank,
+    ) = claimant;
+    update(
+        character_id,
+        skill_set,
+        inventory,
+        pets,
+        waypoint,
+        active_abilities,
+        map_marker,
+        known_recipes,
+        statistics,
+        achievements,
+        bank,
+        None,
+        transaction,
+    )?;
+
+    delete_mail(mail_id, attachment_container_id, transaction)
+}
+
+fn delete_mail(
+    mail_id: MailId,
+    attachment_container_id: Option<EntityId>,
+    transaction: &mut Transaction,
+) -> Result<(), PersistenceError> {
+    let mut stmt = transaction.prepare_cached(
+        "
+        DELETE
+        FROM    mail
+        WHERE   mail_id = ?1",
+    )?;
+    stmt.execute([mail_id])?;
+    drop(stmt);
+
+    if let Some(attachment_container_id) = attachment_container_id {
+        delete_container(attachment_container_id, transaction)?;
+    }
+
+    Ok(())
+}
+
+pub fn load_mailbox(
+    character_id: CharacterId,
+    connection: &Connection,
+) -> Result<comp::Mailbox, PersistenceError> {
+    let mut stmt = connection.prepare_cached(
+        "
+        SELECT  mail_id,
+                sender_alias,
+                body,
+                sent_at,
+                attachment_container_id
+        FROM    mail
+        WHERE   recipient_character_id = ?1
+        ORDER BY sent_at",
+    )?;
+    let rows = stmt
+        .query_map([character_id.0], |row| {
+            Ok((
+                row.get::<_, MailId>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, i64>(3)?,
+                row.get::<_, Option<EntityId>>(4)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mail = rows
+        .into_iter()
+        .map(|(id, sender, body, sent_at, attachment_container_id)| {
+            let attachments = match attachment_container_id {
+                Some(container_id) => load_mail_attachments(connection, container_id)?,
+                None => Vec::new(),
+            };
+            Ok(Mail {
+                id,
+                sender,
+                body,
+                sent_at,
+                attachments,
+            })
+        })
+        .collect::<Result<Vec<_>, PersistenceError>>()?;
+
+    Ok(comp::Mailbox { mail })
+}
+
+/// Deletes all of the mail received by a character, along with any unclaimed
+/// attachments.
+pub fn delete_mailbox(
+    character_id: CharacterId,
+    transaction: &mut Transaction,
+) -> Result<(), PersistenceError> {
+    let mut stmt = transaction.prepare_cached(
+        "
+        SELECT  mail_id,
+                attachment_container_id
+        FROM    mail
+        WHERE   recipient_character_id = ?1",
+    )?;
+    let mail = stmt
+        .query_map([character_id.0], |row| {
+            Ok((row.get::<_, MailId>(0)?, row.get::<_, Option<EntityId>>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    drop(stmt);
+
+    mail.into_iter()
+        .try_for_each(|(mail_id, attachment_container_id)| {
+            delete_mail(mail_id, attachment_container_id, transaction)
+        })
+}
@@ -0,0 +1,586 @@
+//! Database operations related to the market boards of sites
+//!
+//! The items and coins of open orders are held in escrow containers, and every
+//! change to an order happens in the same transaction as the change to the
+//! inventory of the character that placed it. Whatever an order yields, such
+//! as the items bought or the coins earned, is delivered by mail.
+extern crate rusqlite;
+
+use super::{
+    character::{
+        conversions::{ABILITY_MAP, MATERIAL_STATS_MANIFEST},
+        create_escrow_container, delete_container, load_escrow_item, update, write_containers,
+        EntityId,
+    },
+    character_updater::CharacterUpdateData,
+    error::PersistenceError,
+    mail::insert_mail,
+};
+use common::{
+    character::CharacterId,
+    comp::{
+        self,
+        market::{MarketOrder, MarketOrderId, OrderKind, MARKET_CURRENCY, MAX_MARKET_ORDERS},
+    },
+    trade::SiteId,
+};
+use rusqlite::{Connection, OptionalExtension, ToSql, Transaction};
+use specs::Entity;
+use std::slice;
+use vek::Vec3;
+
+/// The name that mail sent by the market is signed with.
+const MARKET_SENDER_ALIAS: &str = "Market";
+
+/// An order that has been taken from the character that placed it, but not
+/// yet stored in the database.
+#[derive(Debug)]
+pub struct NewOrder {
+    pub site_id: SiteId,
+    pub kind: OrderKind,
+    pub item_definition_id: String,
+    pub quantity: u32,
+    pub unit_price: u32,
+    /// For sell orders, the items being sold. For buy orders, the coins for the
+    /// whole order.
+    pub escrow: comp::Item,
+    /// Unix timestamp of when the order was placed
+    pub created_at: i64,
+}
+
+/// The open orders on a market board after a change to them, along with the
+/// characters whose orders were filled by it.
+#[derive(Debug)]
+pub struct MarketChange {
+    pub orders: Vec<MarketOrder>,
+    pub filled: Vec<CharacterId>,
+}
+
+/// The result of a market request made to the
+/// [`CharacterUpdater`](super::character_updater::CharacterUpdater).
+#[derive(Debug)]
+pub enum MarketResponse {
+    Posted {
+        entity: Entity,
+        market_sprite: Vec3<i32>,
+        order: NewOrder,
+        result: Result<MarketChange, PersistenceError>,
+    },
+    Cancelled {
+        entity: Entity,
+        market_sprite: Vec3<i32>,
+        result: Result<Vec<MarketOrder>, PersistenceError>,
+    },
+    Market {
+        entity: Entity,
+        market_sprite: Vec3<i32>,
+        result: Result<Vec<MarketOrder>, PersistenceError>,
+    },
+}
+
+/// An order as it is stored in the database.
+struct OrderRow {
+    order_id: MarketOrderId,
+    site_id: SiteId,
+    character_id: CharacterId,
+    kind: OrderKind,
+    item_definition_id: String,
+    unit_price: u32,
+    quantity: u32,
+    escrow_container_id: EntityId,
+}
+
+fn kind_to_database(kind: OrderKind) -> &'static str {
+    match kind {
+        OrderKind::Sell => "Sell",
+        OrderKind::Buy => "Buy",
+    }
+}
+
+fn kind_from_database(kind: &str) -> Result<OrderKind, PersistenceError> {
+    match kind {
+        "Sell" => Ok(OrderKind::Sell),
+        "Buy" => Ok(OrderKind::Buy),
+        _ => Err(PersistenceError::ConversionError(format!(
+            "Unknown market order kind: {}",
+            kind
+        ))),
+    }
+}
+
+const ORDER_COLUMNS: &str = "order_id,
+                site_id,
+                character_id,
+                kind,
+                item_definition_id,
+                unit_price,
+                quantity,
+                escrow_container_id";
+
+fn order_from_row(row: &rusqlite::Row) -> rusqlite::Result<OrderRow> {
+    let kind = kind_from_database(&row.get::<_, String>(3)?).map_err(|_| {
+        rusqlite::Error::InvalidColumnType(3, "kind".to_string(), rusqlite::types::Type::Text)
+    })?;
+    Ok(OrderRow {
+        order_id: row.get(0)?,
+        site_id: row.get::<_, i64>(1)? as SiteId,
+        character_id: CharacterId(row.get(2)?),
+        kind,
+        item_definition_id: row.get(4)?,
+        unit_price: row.get(5)?,
+        quantity: row.get(6)?,
+        escrow_container_id: row.get(7)?,
+    })
+}
+
+fn load_order(
+    order_id: MarketOrderId,
+    transaction: &mut Transaction,
+) -> Result<Option<OrderRow>, PersistenceError> {
+    let mut stmt = transaction.prepare_cached(&format!(
+        "
+        SELECT  {}
+        FROM    market_order
+        WHERE   order_id = ?1",
+        ORDER_COLUMNS
+    ))?;
+    Ok(stmt.query_row([order_id], order_from_row).optional()?)
+}
+
+/// Finds the best order that the given order can be filled against: the
+/// cheapest sell order for a buy order, or the most generous buy order for a
+/// sell order. Ties go to whichever order was placed first.
+fn find_counter_order(
+    order: &OrderRow,
+    transaction: &mut Transaction,
+) -> Result<Option<OrderRow>, PersistenceError> {
+    let (counter_kind, price_filter) = match order.kind {
+        OrderKind::Sell => (OrderKind::Buy, "unit_price >= ?4 ORDER BY unit_price DESC"),
+        OrderKind::Buy => (OrderKind::Sell, "unit_price <= ?4 ORDER BY unit_price ASC"),
+    };
+    let mut stmt = transaction.prepare_cached(&format!(
+        "
+        SELECT  {}
+        FROM    market_order
+        WHERE   site_id = ?1
+        AND     item_definition_id = ?2
+        AND     kind = ?3
+        AND     character_id != ?5
+        AND     {},
+                created_at,
+                order_id
+        LIMIT   1",
+        ORDER_COLUMNS, price_filter
+    ))?;
+    Ok(stmt
+        .query_row(
+            [
+                &(order.site_id as i64) as &dyn ToSql,
+                &order.item_definition_id,
+                &kind_to_database(counter_kind),
+                &order.unit_price,
+                &order.character_id.0,
+            ],
+            order_from_row,
+        )
+        .optional()?)
+}
+
+fn coins(amount: u32) -> Result<Option<comp::Item>, PersistenceError> {
+    if amount == 0 {
+        return Ok(None);
+    }
+    let mut coins = comp::Item::new_from_asset(MARKET_CURRENCY)
+        .map_err(|err| PersistenceError::AssetError(format!("{:?}", err)))?;
+    coins.set_amount(amount).map_err(|_| {
+        PersistenceError::OtherError(format!("Invalid amount of coins: {}", amount))
+    })?;
+    Ok(Some(coins))
+}
+
+fn checked_total(unit_price: u32, quantity: u32) -> Result<u32, PersistenceError> {
+    unit_price.checked_mul(quantity).ok_or_else(|| {
+        PersistenceError::OtherError(format!(
+            "Order of {} at {} each is too large",
+            quantity, unit_price
+        ))
+    })
+}
+
+/// Trades as many items as possible between a sell order and a buy order, at
+/// the given price per item. Both characters receive their share by mail, and
+/// the buyer is refunded the difference if they offered more than the price.
+fn fill(
+    sell: &OrderRow,
+    buy: &OrderRow,
+    unit_price: u32,
+    sent_at: i64,
+    transaction: &mut Transaction,
+) -> Result<(), PersistenceError> {
+    let quantity = sell.quantity.min(buy.quantity);
+    let invalid_amount = || PersistenceError::OtherError("Invalid market escrow".to_string());
+
+    let mut listed = load_escrow_item(transaction, sell.escrow_container_id)?;
+    let (bought, unsold) = if quantity == sell.quantity {
+        (listed, Vec::new())
+    } else {
+        let mut bought = listed.duplicate(&ABILITY_MAP, &MATERIAL_STATS_MANIFEST);
+        bought.set_amount(quantity).map_err(|_| invalid_amount())?;
+        listed
+            .set_amount(sell.quantity - quantity)
+            .map_err(|_| invalid_amount())?;
+        (bought, vec![listed])
+    };
+
+    let mut offered = load_escrow_item(transaction, buy.escrow_container_id)?;
+    let payment = checked_total(unit_price, quantity)?;
+    let kept = checked_total(buy.unit_price, buy.quantity - quantity)?;
+    let refund = offered
+        .amount()
+        .checked_sub(payment)
+        .and_then(|amount| amount.checked_sub(kept))
+        .ok_or_else(invalid_amount)?;
+    let unspent = if kept > 0 {
+        offered.set_amount(kept).map_err(|_| invalid_amount())?;
+        vec![offered]
+    } else {
+        Vec::new()
+    };
+
+    // TODO: Localise
+    let item_name = bought.name().into_owned();
+    let seller_mail = insert_mail(
+        sell.character_id,
+        MARKET_SENDER_ALIAS,
+        &format!("Sold {} x {} for {} coins.", quantity, item_name, payment),
+        sent_at,
+        true,
+        transaction,
+    )?
+    .expect("Mail with attachments always has an attachment container");
+    let buyer_mail = insert_mail(
+        buy.character_id,
+        MARKET_SENDER_ALIAS,
+        &format!("Bought {} x {} for {} coins.", quantity, item_name, payment),
+        sent_at,
+        true,
+        transaction,
+    )?
+    .expect("Mail with attachments always has an attachment container");
+
+    let earnings = coins(payment)?.into_iter().collect::<Vec<_>>();
+    let purchase = std::iter::once(bought)
+        .chain(coins(refund)?)
+        .collect::<Vec<_>>();
+    write_containers(
+        &[
+            (sell.escrow_container_id, &unsold),
+            (buy.escrow_container_id, &unspent),
+            (seller_mail, &earnings),
+            (buyer_mail, &purchase),
+        ],
+        transaction,
+    )?;
+
+    let mut stmt = transaction.prepare_cached(
+        "
+        UPDATE  market_order
+        SET     quantity = quantity - ?1
+        WHERE   order_id IN (?2, ?3)",
+    )?;
+    stmt.execute([i64::from(quantity), sell.order_id, buy.order_id])?;
+    drop(stmt);
+
+    for order in [sell, buy] {
+        if order.quantity == quantity {
+            delete_order(order.order_id, order.escrow_container_id, transaction)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Fills an order against the orders already on the market board, for as
+/// long as their prices overlap. The order that was placed first sets the
+/// price of each trade.
+fn match_order(
+    order_id: MarketOrderId,
+    sent_at: i64,
+    transaction: &mut Transaction,
+) -> Result<Vec<CharacterId>, PersistenceError> {
+    let mut filled = Vec::new();
+    // The order is deleted once it has been filled completely
+    while let Some(order) = load_order(order_id, transaction)? {
+        let Some(counter) = find_counter_order(&order, transaction)? else { break };
+        let unit_price = counter.unit_price;
+        match order.kind {
+            OrderKind::Sell => fill(&order, &counter, unit_price, sent_at, transaction)?,
+            OrderKind::Buy => fill(&counter, &order, unit_price, sent_at, transaction)?,
+        }
+        if !filled.contains(&counter.character_id) {
+            filled.push(counter.character_id);
+        }
+    }
+
+    Ok(filled)
+}
+
+/// Puts an order on a market board and fills it as far as possible, and
+/// persists the character that placed it so that the escrowed items or coins
+/// are moved out of their inventory in the same transaction.
+pub fn post_order(
+    owner: CharacterUpdateData,
+    order: &NewOrder,
+    transaction: &mut Transaction,
+) -> Result<MarketChange, PersistenceError> {
+    let owner_id = owner.0;
+    let mut stmt = transaction.prepare_cached(
+        "
+        SELECT  COUNT(1)
+        FROM    market_order
+        WHERE   character_id = ?1",
+    )?;
+    let open_orders: i64 = stmt.query_row([owner_id.0], |row| row.get(0))?;
+    drop(stmt);
+    if open_orders >= MAX_MARKET_ORDERS as i64 {
+        return Err(PersistenceError::OtherError(format!(
+            "Character {} already has {} open market orders",
+            owner_id.0, open_orders
+        )));
+    }
+
+    let escrow_container_id = create_escrow_container(transaction)?;
+    let mut stmt = transaction.prepare_cached(
+        "
+        INSERT INTO market_order (site_id,
+                                  character_id,
+                                  kind,
+                                  item_definition_id,
+                                  unit_price,
+                                  quantity,
+                                  created_at,
+                                  escrow_container_id)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+    )?;
+    stmt.execute([
+        &(order.site_id as i64) as &dyn ToSql,
+        &owner_id.0,
+        &kind_to_database(order.kind),
+        &order.item_definition_id,
+        &order.unit_price,
+        &order.quantity,
+        &order.created_at,
+        &escrow_container_id,
+    ])?;
+    drop(stmt);
+    let order_id = transaction.last_insert_rowid();
+
+    let (
+        character_id,
+        skill_set,
+        inventory,
+        pets,
+        waypoint,
+        active_abilities,
+        map_marker,
+        known_recipes,
+        statistics,
+        achievements,
+        bank,
+    ) = owner;
+    update(
+        character_id,
+        skill_set,
+        inventory,
+        pets,
+        waypoint,
+        active_abilities,
+        map_marker,
+        known_recipes,
+        statistics,
+        achievements,
+        bank,
+        Some((escrow_container_id, slice::from_ref(&order.escrow))),
+        transaction,
+    )?;
+
+    let filled = match_order(order_id, order.created_at, transaction)?;
+    let orders = load_orders(order.site_id, owner_id, transaction)?;
+
+    Ok(MarketChange { orders, filled })
+}
+
+/// Takes one of a character's orders off the market board, and mails whatever
+/// is left of it back to them. Returns the open orders on the same board.
+pub fn cancel_order(
+    character_id: CharacterId,
+    order_id: MarketOrderId,
+    sent_at: i64,
+    transaction: &mut Transaction,
+) -> Result<Vec<MarketOrder>, PersistenceError> {
+    let order = load_order(order_id, transaction)?
+        .filter(|order| order.character_id == character_id)
+        .ok_or_else(|| {
+            PersistenceError::OtherError(format!(
+                "Market order {} does not belong to character {}",
+                order_id, character_id.0
+            ))
+        })?;
+
+    let escrow = load_escrow_item(transaction, order.escrow_container_id)?;
+    // TODO: Localise
+    let body = match order.kind {
+        OrderKind::Sell => format!(
+            "Your listing of {} x {} was withdrawn.",
+            order.quantity,
+            escrow.name()
+        ),
+        OrderKind::Buy => format!(
+            "Your order for {} x {} was withdrawn.",
+            order.quantity,
+            comp::Item::new_from_asset(&order.item_definition_id).map_or_else(
+                |_| order.item_definition_id.clone(),
+                |item| { item.name().into_owned() }
+            )
+        ),
+    };
+    let mail_container_id = insert_mail(
+        character_id,
+        MARKET_SENDER_ALIAS,
+        &body,
+        sent_at,
+        true,
+        transaction,
+    )?
+    .expect("Mail with attachments always has an attachment container");
+    write_containers(
+        &[
+            (order.escrow_container_id, &[]),
+            (mail_container_id, slice::from_ref(&escrow)),
+        ],
+        transaction,
+    )?;
+    delete_order(order.order_id, order.escrow_container_id, transaction)?;
+
+    load_orders(order.site_id, character_id, transaction)
+}
+
+fn delete_order(
+    order_id: MarketOrderId,
+    escrow_container_id: EntityId,
+    transaction: &mut Transaction,
+) -> Result<(), PersistenceError> {
+    let mut stmt = transaction.prepare_cached(
+        "
+        DELETE
+        FROM    market_order
+        WHERE   order_id = ?1",
+    )?;
+    stmt.execute([order_id])?;
+    drop(stmt);
+
+    delete_container(escrow_container_id, transaction)
+}
+
+/// Loads the open orders on the market board of a site, as seen by the given
+/// character.
+pub fn load_orders(
+    site_id: SiteId,
+    viewer: CharacterId,
+    connection: &Connection,
+) -> Result<Vec<MarketOrder>, PersistenceError> {
+    let mut stmt = connection.prepare_cached(
+        "
+        SELECT  market_order.order_id,
+                market_order.kind,
+                character.alias,
+                market_order.character_id,
+                market_order.item_definition_id,
+                market_order.unit_price,
+                market_order.quantity,
+                market_order.created_at,
+                market_order.escrow_container_id
+        FROM    market_order
+        JOIN    character
+        ON      character.character_id = market_order.character_id
+        WHERE   market_order.site_id = ?1
+        ORDER BY market_order.item_definition_id,
+                 market_order.kind,
+                 market_order.unit_price",
+    )?;
+    let rows = stmt
+        .query_map([site_id as i64], |row| {
+            Ok((
+                row.get::<_, MarketOrderId>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, i64>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, u32>(5)?,
+                row.get::<_, u32>(6)?,
+                row.get::<_, i64>(7)?,
+                row.get::<_, EntityId>(8)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    rows.into_iter()
+        .map(
+            |(
+                id,
+                kind,
+                owner,
+                owner_id,
+                item_definition_id,
+                unit_price,
+                quantity,
+                created_at,
+                escrow_container_id,
+            )| {
+                let kind = kind_from_database(&kind)?;
+                let item = match kind {
+                    OrderKind::Sell => load_escrow_item(connection, escrow_container_id)?,
+                    OrderKind::Buy => comp::Item::new_from_asset(&item_definition_id)
+                        .map_err(|err| PersistenceError::AssetError(format!("{:?}", err)))?,
+                };
+                Ok(MarketOrder {
+                    id,
+                    kind,
+                    owner,
+                    is_own: owner_id == viewer.0,
+                    item,
+                    quantity,
+                    unit_price,
+                    created_at,
+                })
+            },
+        )
+        .collect()
+}
+
+/// Deletes all of the orders placed by a character, along with the items and
+/// coins held for them.
+pub fn delete_orders(
+    character_id: CharacterId,
+    transaction: &mut Transaction,
+) -> Result<(), PersistenceError> {
+    let mut stmt = transaction.prepare_cached(
+        "
+        SELECT  order_id,
+                escrow_container_id
+        FROM    market_order
+        WHERE   character_id = ?1",
+    )?;
+    let orders = stmt
+        .query_map([character_id.0], |row| {
+            Ok((row.get::<_, MarketOrderId>(0)?, row.get::<_, EntityId>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    drop(stmt);
+
+    orders
+        .into_iter()
+        .try_for_each(|(order_id, escrow_container_id)| {
+            delete_order(order_id, escrow_container_id, transaction)
+        })
+}
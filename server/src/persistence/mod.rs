@@ -4,11 +4,15 @@ pub(in crate::persistence) mod character;
 pub mod character_loader;
 pub mod character_updater;
 mod diesel_to_rusqlite;
+pub mod duel;
 pub mod error;
+mod guild;
 mod json_models;
+pub mod mail;
+pub mod market;
 mod models;
 
-use crate::persistence::character_updater::PetPersistenceData;
+use crate::{guild::GuildManager, persistence::character_updater::PetPersistenceData};
 use common::comp;
 use refinery::Report;
 use rusqlite::{Connection, OpenFlags};
@@ -32,6 +36,10 @@ pub struct PersistedComponents {
     pub pets: Vec<PetPersistenceData>,
     pub active_abilities: comp::ActiveAbilities,
     pub map_marker: Option<comp::MapMarker>,
+    pub known_recipes: comp::KnownRecipes,
+    pub statistics: comp::Statistics,
+    pub achievements: comp::Achievements,
+    pub bank: comp::Bank,
 }
 
 pub type EditableComponents = (comp::Body,);
@@ -183,6 +191,14 @@ pub fn vacuum_database(settings: &DatabaseSettings) {
     info!("Database vacuumed");
 }
 
+/// Loads all guilds from the database. This is executed during server startup,
+/// after which guilds are only written to.
+pub fn load_guilds(settings: &DatabaseSettings) -> GuildManager {
+    let conn = establish_connection(settings, ConnectionMode::ReadOnly);
+
+    guild::load_guilds(&conn).expect("Failed to load guilds, server startup aborted")
+}
+
 // These callbacks use info logging because they are never enabled by default,
 // only when explicitly turned on via CLI arguments or interactive CLI commands.
 // Setting them to anything other than info would remove the ability to get SQL
@@ -41,3 +41,18 @@ pub struct AbilitySets {
     pub entity_id: i64,
     pub ability_sets: String,
 }
+
+pub struct KnownRecipes {
+    pub entity_id: i64,
+    pub known_recipes: String,
+}
+
+pub struct Statistics {
+    pub entity_id: i64,
+    pub statistics: String,
+}
+
+pub struct Achievements {
+    pub player_uuid: String,
+    pub achievements: String,
+}
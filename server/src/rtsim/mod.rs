@@ -1,5 +1,7 @@
 pub mod event;
+pub mod reputation;
 pub mod rule;
+pub mod territory;
 pub mod tick;
 
 use atomicwrites::{AtomicFile, OverwriteBehavior};
@@ -7,7 +9,7 @@ use common::{
     grid::Grid,
     rtsim::{Actor, ChunkResource, RtSimEntity, RtSimVehicle, WorldSettings},
 };
-use common_ecs::dispatch;
+use common_ecs::{dispatch, System};
 use common_state::BlockDiff;
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use enum_map::EnumMap;
@@ -281,4 +283,6 @@ pub struct LoadedChunkState {
 
 pub fn add_server_systems(dispatch_builder: &mut DispatcherBuilder) {
     dispatch::<tick::Sys>(dispatch_builder, &[]);
+    dispatch::<reputation::Sys>(dispatch_builder, &[&tick::Sys::sys_name()]);
+    dispatch::<territory::Sys>(dispatch_builder, &[&tick::Sys::sys_name()]);
 }
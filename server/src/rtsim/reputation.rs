@@ -0,0 +1,115 @@
+use super::*;
+use crate::client::Client;
+use common::{
+    comp::{
+        self,
+        reputation::{FactionStanding, SiteStanding},
+        Presence, PresenceKind,
+    },
+    rtsim::FactionId,
+    store::Id,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::ServerGeneral;
+use rtsim::data::Reputation;
+use specs::{Entities, Join, ReadExpect, ReadStorage, WriteStorage};
+use world::site::Site as WorldSite;
+
+/// How many ticks should pass between copying character reputations out of
+/// rtsim. Reputations change slowly, so there's no need to do this often.
+const REPUTATION_SYNC_TICKS: u64 = 150;
+
+/// The site that identifies a faction to players: the most populous site that
+/// it holds.
+pub(super) fn capital(data: &Data, faction: FactionId) -> Option<Id<WorldSite>> {
+    data.factions
+        .get(faction)?
+        .territory
+        .iter()
+        .filter_map(|site| {
+            let site = data.sites.get(*site)?;
+            Some((site, site.world_site?))
+        })
+        .max_by_key(|(site, world_site)| (site.population.len(), world_site.id()))
+        .map(|(_, world_site)| world_site)
+}
+
+/// Convert the reputation of a character, as understood by rtsim, into
+/// standings that make sense to the rest of the game.
+fn standings(data: &Data, index: IndexRef, reputation: &Reputation) -> comp::Reputation {
+    let world_site = |site| data.sites.get(site).and_then(|site| site.world_site);
+    let site_name = |world_site| index.sites.get(world_site).name().to_string();
+
+    let mut sites = reputation
+        .sites
+        .iter()
+        .filter_map(|(site, value)| {
+            let world_site = world_site(*site)?;
+            Some(SiteStanding {
+                site: world_site.id(),
+                name: site_name(world_site),
+                value: *value,
+            })
+        })
+        .collect::<Vec<_>>();
+    sites.sort_by(|a, b| b.value.abs().total_cmp(&a.value.abs()));
+
+    let mut factions = reputation
+        .factions
+        .iter()
+        .filter_map(|(faction, value)| {
+            data.factions.get(*faction)?;
+            let capital = capital(data, *faction);
+            Some(FactionStanding {
+                capital: capital.map(|ws| ws.id()),
+                capital_name: capital.map(site_name),
+                value: *value,
+            })
+        })
+        .collect::<Vec<_>>();
+    factions.sort_by(|a, b| b.value.abs().total_cmp(&a.value.abs()));
+
+    comp::Reputation { sites, factions }
+}
+
+/// Keeps the reputations of player characters up to date with the sentiments
+/// held toward them by rtsim NPCs, informing clients of any changes.
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        ReadExpect<'a, RtSim>,
+        ReadExpect<'a, world::IndexOwned>,
+        ReadStorage<'a, Presence>,
+        ReadStorage<'a, Client>,
+        WriteStorage<'a, comp::Reputation>,
+    );
+
+    const NAME: &'static str = "rtsim::reputation";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (entities, rtsim, index, presences, clients, mut reputations): Self::SystemData,
+    ) {
+        let data = rtsim.state.data();
+        if data.tick % REPUTATION_SYNC_TICKS != 0 {
+            return;
+        }
+
+        for (entity, presence, client) in (&entities, &presences, &clients).join() {
+            let PresenceKind::Character(character) = presence.kind else { continue };
+            let reputation = data
+                .reputations
+                .of(character)
+                .map(|reputation| standings(&data, index.as_index_ref(), reputation))
+                .unwrap_or_default();
+            if reputations.get(entity) != Some(&reputation) {
+                client.send_fallible(ServerGeneral::ReputationUpdate(reputation.clone()));
+                let _ = reputations.insert(entity, reputation);
+            }
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use super::{reputation::capital, *};
+use crate::client::Client;
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::{
+    world_msg::{FactionStance, SiteFactions},
+    ServerGeneral, WorldMapMsg,
+};
+use hashbrown::HashMap;
+use rtsim::data::Stance;
+use specs::{Join, ReadExpect, ReadStorage, WriteExpect};
+
+/// How many ticks should pass between checking whether sites have changed
+/// hands. Sites are only captured by raids, so there's no need to do this
+/// often.
+const TERRITORY_SYNC_TICKS: u64 = 150;
+
+/// Which factions hold the sites of the world, and how they get along, in a
+/// form that can be shown on the map.
+fn site_factions(data: &Data) -> SiteFactions {
+    let capitals = data
+        .factions
+        .keys()
+        .filter_map(|faction| Some((faction, capital(data, faction)?.id())))
+        .collect::<HashMap<_, _>>();
+
+    let owners = data
+        .sites
+        .values()
+        .filter_map(|site| Some((site.world_site?.id(), *capitals.get(&site.faction?)?)))
+        .collect();
+
+    let mut relations = data
+        .factions
+        .iter()
+        .flat_map(|(faction, info)| {
+            let capitals = &capitals;
+            info.relations.iter().filter_map(move |(other, stance)| {
+                let stance = match stance {
+                    Stance::Alliance => FactionStance::Alliance,
+                    Stance::War => FactionStance::War,
+                    Stance::Neutral => return None,
+                };
+                Some((*capitals.get(&faction)?, *capitals.get(other)?, stance))
+            })
+        })
+        .collect::<Vec<_>>();
+    relations.sort_by_key(|(from, to, _)| (*from, *to));
+
+    SiteFactions { owners, relations }
+}
+
+/// Keeps the faction information on the world map up to date as sites change
+/// hands and factions go to war, informing clients of any changes.
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        ReadExpect<'a, RtSim>,
+        WriteExpect<'a, WorldMapMsg>,
+        ReadStorage<'a, Client>,
+    );
+
+    const NAME: &'static str = "rtsim::territory";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(_job: &mut Job<Self>, (rtsim, mut map, clients): Self::SystemData) {
+        let data = rtsim.state.data();
+        if data.tick % TERRITORY_SYNC_TICKS != 0 {
+            return;
+        }
+
+        let factions = site_factions(&data);
+        if map.factions == factions {
+            return;
+        }
+
+        let mut lazy_msg = None;
+        for client in clients.join() {
+            if lazy_msg.is_none() {
+                lazy_msg =
+                    Some(client.prepare(ServerGeneral::SiteFactionsUpdate(factions.clone())));
+            }
+            lazy_msg.as_ref().map(|msg| client.send_prepared(msg));
+        }
+        map.factions = factions;
+    }
+}
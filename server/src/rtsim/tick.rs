@@ -12,6 +12,7 @@ use common::{
     terrain::CoordinateConversions,
     trade::{Good, SiteInformation},
     util::Dir,
+    weather::WeatherGrid,
     LoadoutBuilder,
 };
 use common_ecs::{Job, Origin, Phase, System};
@@ -24,6 +25,9 @@ use std::{sync::Arc, time::Duration};
 use tracing::error;
 use world::site::settlement::trader_loadout;
 
+/// How many ticks should pass between copying the weather into rtsim.
+const WEATHER_SYNC_TICKS: u64 = 150;
+
 fn humanoid_config(profession: &Profession) -> &'static str {
     match profession {
         Profession::Farmer => "common.entity.village.farmer",
@@ -215,6 +219,7 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, RtSimVehicle>,
         WriteStorage<'a, comp::Agent>,
         ReadStorage<'a, Presence>,
+        ReadExpect<'a, WeatherGrid>,
     );
 
     const NAME: &'static str = "rtsim::tick";
@@ -237,6 +242,7 @@ impl<'a> System<'a> for Sys {
             rtsim_vehicles,
             mut agents,
             presences,
+            weather_grid,
         ): Self::SystemData,
     ) {
         let mut emitter = server_event_bus.emitter();
@@ -262,6 +268,12 @@ impl<'a> System<'a> for Sys {
                         .push((*character, wpos.0));
                 }
             }
+
+            // Update weather (i.e: so that rtsim knows when it's raining)
+            // The weather changes slowly, so there's no need to do this every tick
+            if data.tick % WEATHER_SYNC_TICKS == 0 {
+                *rtsim.state.resource_mut::<WeatherGrid>() = weather_grid.clone();
+            }
         }
 
         // Tick rtsim
@@ -312,6 +324,9 @@ impl<'a> System<'a> for Sys {
                                 alignment,
                                 scale,
                                 loot,
+                                is_boss,
+                                encounter,
+                                squad: _,
                             } => NpcBuilder::new(stats, body, alignment)
                                 .with_skill_set(skill_set)
                                 .with_health(health)
@@ -323,6 +338,8 @@ impl<'a> System<'a> for Sys {
                                 }))
                                 .with_scale(scale)
                                 .with_loot(loot)
+                                .with_boss(is_boss)
+                                .with_encounter(encounter)
                                 .with_rtsim(RtSimEntity(npc_id)),
                             // EntityConfig can't represent Waypoints at all
                             // as of now, and if someone will try to spawn
@@ -372,6 +389,9 @@ impl<'a> System<'a> for Sys {
                         alignment,
                         scale,
                         loot,
+                        is_boss,
+                        encounter,
+                        squad: _,
                     } => ServerEvent::CreateNpc {
                         pos,
                         npc: NpcBuilder::new(stats, body, alignment)
@@ -385,6 +405,8 @@ impl<'a> System<'a> for Sys {
                             }))
                             .with_scale(scale)
                             .with_loot(loot)
+                            .with_boss(is_boss)
+                            .with_encounter(encounter)
                             .with_rtsim(RtSimEntity(npc_id)),
                     },
                     // EntityConfig can't represent Waypoints at all
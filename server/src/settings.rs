@@ -95,6 +95,54 @@ impl Default for GameplaySettings {
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DungeonInstanceSettings {
+    /// Whether dungeons have portals that take groups to their own copy of
+    /// the dungeon
+    pub enabled: bool,
+    /// How many copies of dungeons may exist at once across the server
+    pub max_instances: usize,
+    /// How long a group may spend in their copy of a dungeon
+    pub time_limit: Duration,
+    /// How long a group may stay in their copy of a dungeon once its bosses
+    /// are defeated
+    pub completion_grace: Duration,
+}
+
+impl Default for DungeonInstanceSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_instances: 8,
+            time_limit: Duration::from_secs(60 * 60),
+            completion_grace: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReplaySettings {
+    /// Whether the server records replays of its simulation into the
+    /// `replays` directory of its data dir. Of the server events, only
+    /// knockbacks and teleports are recorded in full; for the rest only how
+    /// many were emitted is kept, so replays can't show nondeterminism in how
+    /// they are handled.
+    pub enabled: bool,
+    /// How long each replay file covers before a new one is started
+    pub segment_length: Duration,
+}
+
+impl Default for ReplaySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            segment_length: Duration::from_secs(10 * 60),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ModerationSettings {
     #[serde(default)]
@@ -185,6 +233,10 @@ pub struct Settings {
     #[serde(default)]
     pub gameplay: GameplaySettings,
     #[serde(default)]
+    pub dungeon_instances: DungeonInstanceSettings,
+    #[serde(default)]
+    pub replay_recording: ReplaySettings,
+    #[serde(default)]
     pub moderation: ModerationSettings,
 
     #[serde(default)]
@@ -217,6 +269,8 @@ impl Default for Settings {
             max_player_for_kill_broadcast: None,
             experimental_terrain_persistence: false,
             gameplay: GameplaySettings::default(),
+            dungeon_instances: DungeonInstanceSettings::default(),
+            replay_recording: ReplaySettings::default(),
             moderation: ModerationSettings::default(),
             world: WorldSettings::default(),
         }
@@ -24,6 +24,7 @@ use common::{
     effect::Effect,
     link::{Link, LinkHandle},
     mounting::{Mounting, VolumeMounting},
+    recipe::default_recipe_book,
     resources::{Secs, Time, TimeOfDay},
     rtsim::{Actor, RtSimEntity},
     slowjob::SlowJobPool,
@@ -255,6 +256,16 @@ impl StateExt for State {
                         )
                     });
             },
+            Effect::LearnRecipe(recipe) => {
+                let mut known_recipes = self.ecs().write_storage::<comp::KnownRecipes>();
+                if let Some(known_recipes) = known_recipes.get_mut(entity)
+                    && default_recipe_book().read().get(&recipe).is_some()
+                    && known_recipes.learn(recipe)
+                    && let Some(client) = self.ecs().read_storage::<Client>().get(entity)
+                {
+                    client.send_fallible(ServerGeneral::KnownRecipesUpdate(known_recipes.clone()));
+                }
+            },
         }
     }
 
@@ -688,6 +699,10 @@ impl StateExt for State {
             pets,
             active_abilities,
             map_marker,
+            known_recipes,
+            statistics,
+            achievements,
+            bank,
         } = components;
 
         if let Some(player_uid) = self.read_component_copied::<Uid>(entity) {
@@ -740,6 +755,34 @@ impl StateExt for State {
                 self.write_component_ignore_entity_dead(entity, map_marker);
             }
 
+            if let Some(client) = self.ecs().read_storage::<Client>().get(entity) {
+                client.send_fallible(ServerGeneral::KnownRecipesUpdate(known_recipes.clone()));
+            }
+            self.write_component_ignore_entity_dead(entity, known_recipes);
+            if let Some(client) = self.ecs().read_storage::<Client>().get(entity) {
+                client.send_fallible(ServerGeneral::StatisticsUpdate(statistics.clone()));
+                client.send_fallible(ServerGeneral::AchievementsUpdate(achievements.clone()));
+            }
+            self.write_component_ignore_entity_dead(entity, statistics);
+            self.write_component_ignore_entity_dead(entity, achievements);
+            self.write_component_ignore_entity_dead(entity, bank);
+
+            // Give the character their guild membership, and let the rest of the guild know
+            // that they're online
+            let guild = match self.ecs().read_storage::<Presence>().get(entity) {
+                Some(Presence {
+                    kind: PresenceKind::Character(char_id),
+                    ..
+                }) => self
+                    .ecs()
+                    .read_resource::<crate::guild::GuildManager>()
+                    .guild_of(*char_id),
+                _ => None,
+            };
+            if let Some(guild) = guild {
+                crate::events::guild_manip::sync_guild(self, guild);
+            }
+
             let player_pos = self.ecs().read_storage::<comp::Pos>().get(entity).copied();
             if let Some(player_pos) = player_pos {
                 trace!(
@@ -848,7 +891,7 @@ impl StateExt for State {
     }
 
     /// Send the chat message to the proper players. Say and region are limited
-    /// by location. Faction and group are limited by component.
+    /// by location. Guild and group are limited by component.
     fn send_chat(&self, msg: comp::UnresolvedChatMsg) {
         let ecs = self.ecs();
         let is_within =
@@ -1007,14 +1050,14 @@ impl StateExt for State {
                         }
                     }
                 },
-                comp::ChatType::FactionMeta(s) | comp::ChatType::Faction(_, s) => {
-                    for (client, faction) in (
+                comp::ChatType::GuildMeta(s) | comp::ChatType::Guild(_, s) => {
+                    for (client, guild) in (
                         &ecs.read_storage::<Client>(),
-                        &ecs.read_storage::<comp::Faction>(),
+                        &ecs.read_storage::<comp::Guild>(),
                     )
                         .join()
                     {
-                        if s == &faction.0 {
+                        if s == &guild.name {
                             client.send_fallible(ServerGeneral::ChatMsg(resolved_msg.clone()));
                         }
                     }
@@ -5,6 +5,7 @@ use common::{
             TRADE_INTERACTION_TIME,
         },
         dialogue::Subject,
+        threat::THREAT_SWITCH_RATIO,
         Agent, Alignment, BehaviorCapability, BehaviorState, Body, BuffKind, ControlAction,
         ControlEvent, Controller, InputKind, InventoryEvent, Pos, UtteranceKind,
     },
@@ -85,6 +86,7 @@ impl BehaviorTree {
                 react_on_dangerous_fall,
                 react_if_on_fire,
                 target_if_attacked,
+                target_by_threat,
                 process_inbox_sound_and_hurt,
                 process_inbox_interaction,
                 do_target_tree_if_target_else_do_idle_tree,
@@ -164,6 +166,7 @@ impl BehaviorTree {
             tree: vec![
                 set_owner_if_no_target,
                 handle_rtsim_actions,
+                keep_squad_formation,
                 handle_timed_events,
             ],
         }
@@ -265,16 +268,9 @@ fn target_if_attacked(bdata: &mut BehaviorData) -> bool {
 
                         bdata.agent.awareness.change_by(1.0);
 
-                        // Determine whether the new target should be a priority
-                        // over the old one (i.e: because it's either close or
-                        // because they attacked us).
-                        if bdata.agent.target.map_or(true, |target| {
-                            bdata.agent_data.is_more_dangerous_than_target(
-                                attacker,
-                                target,
-                                bdata.read_data,
-                            )
-                        }) {
+                        // Switching between targets that we're already fighting is
+                        // decided by `target_by_threat`
+                        if bdata.agent.target.map_or(true, |target| !target.aggro_on) {
                             bdata.agent.target = Some(Target {
                                 target: attacker,
                                 hostile: true,
@@ -307,6 +303,49 @@ fn target_if_attacked(bdata: &mut BehaviorData) -> bool {
     false
 }
 
+/// Switch to whoever has built up the most threat against us. We only move on
+/// from our current target for someone with clearly more threat, unless we were
+/// taunted.
+fn target_by_threat(bdata: &mut BehaviorData) -> bool {
+    let time = bdata.read_data.time.0;
+    let threat = &bdata.agent.threat;
+    let Some(top) = threat.highest(time) else {
+        return false;
+    };
+    let current = bdata
+        .agent
+        .target
+        .and_then(|target| bdata.read_data.uids.get(target.target).copied());
+    let taunted = threat.taunted_by(time) == Some(top);
+    if current == Some(top)
+        || (!taunted
+            && current.map_or(false, |current| {
+                threat.get(top) < threat.get(current) * THREAT_SWITCH_RATIO
+            }))
+    {
+        return false;
+    }
+
+    if let Some(attacker) = bdata
+        .read_data
+        .uid_allocator
+        .retrieve_entity_internal(top.into())
+        && !is_dead_or_invulnerable(attacker, bdata.read_data)
+    {
+        if taunted {
+            bdata.agent.awareness.set_maximally_aware();
+        }
+        bdata.agent.target = Some(Target {
+            target: attacker,
+            hostile: true,
+            selected_at: time,
+            aggro_on: true,
+            last_known_pos: bdata.read_data.positions.get(attacker).map(|pos| pos.0),
+        });
+    }
+    false
+}
+
 /// If the agent has a target, do the target tree, else do the idle tree
 ///
 /// This function will never stop the BehaviorTree
@@ -531,6 +570,29 @@ fn handle_rtsim_actions(bdata: &mut BehaviorData) -> bool {
     }
 }
 
+/// Squad members that aren't busy follow their leader in formation, keeping an
+/// eye out for enemies while they catch up
+fn keep_squad_formation(bdata: &mut BehaviorData) -> bool {
+    if bdata.agent.target.is_some()
+        || !bdata
+            .agent_data
+            .follow_squad_leader(bdata.agent, bdata.controller, bdata.read_data)
+    {
+        return false;
+    }
+
+    if bdata.rng.gen::<f32>() < 0.1 {
+        bdata.agent_data.choose_target(
+            bdata.agent,
+            bdata.controller,
+            bdata.read_data,
+            bdata.event_emitter,
+            AgentData::is_enemy,
+        );
+    }
+    true
+}
+
 /// Handle timed events, like looking at the player we are talking to
 fn handle_timed_events(bdata: &mut BehaviorData) -> bool {
     let timeout = if bdata.agent.behavior.is(BehaviorState::TRADING) {
@@ -758,7 +820,8 @@ fn do_combat(bdata: &mut BehaviorData) -> bool {
             }
             let aggro_on = *aggro_on;
 
-            if agent_data.below_flee_health(agent) {
+            if agent_data.below_flee_health(agent) || agent_data.squad_retreating(agent, read_data)
+            {
                 let flee_timer_done = agent.action_state.timers
                     [ActionStateBehaviorTreeTimers::TimerBehaviorTree as usize]
                     > FLEE_DURATION;
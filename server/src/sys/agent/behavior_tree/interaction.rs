@@ -1,6 +1,6 @@
 use common::{
     comp::{
-        agent::{AgentEvent, Target, TimerAction},
+        agent::{AgentEvent, Sound, SoundKind, Target, TimerAction},
         compass::{Direction, Distance},
         dialogue::Subject,
         inventory::item::{ItemTag, MaterialStatManifest},
@@ -16,7 +16,7 @@ use common::{
 use rand::{thread_rng, Rng};
 use specs::saveload::Marker;
 
-use crate::sys::agent::util::get_entity_by_id;
+use crate::sys::agent::util::{get_entity_by_id, is_dead_or_invulnerable};
 
 use super::{BehaviorData, BehaviorTree};
 
@@ -34,6 +34,7 @@ pub fn process_inbox_sound_and_hurt(bdata: &mut BehaviorData) -> bool {
             let sound = bdata.agent.inbox.pop_front();
             match sound {
                 Some(AgentEvent::ServerSound(sound)) => {
+                    answer_squad_call(bdata, &sound);
                     bdata.agent.sounds_heard.push(sound);
                 },
                 Some(AgentEvent::Hurt) => {
@@ -53,6 +54,38 @@ pub fn process_inbox_sound_and_hurt(bdata: &mut BehaviorData) -> bool {
     false
 }
 
+/// When a squad mate cries out, join them in fighting whoever the squad is
+/// fighting
+fn answer_squad_call(bdata: &mut BehaviorData, sound: &Sound) {
+    if !matches!(sound.kind, SoundKind::Utterance(..))
+        || bdata.agent.target.map_or(false, |target| target.hostile)
+    {
+        return;
+    }
+    let Some(caller) = sound
+        .source
+        .and_then(|source| get_entity_by_id(source.id(), bdata.read_data))
+    else {
+        return;
+    };
+
+    if bdata
+        .agent_data
+        .is_squad_mate(bdata.agent, caller, bdata.read_data)
+        && let Some(target) = bdata.agent_data.squad_target(bdata.agent, bdata.read_data)
+        && !is_dead_or_invulnerable(target, bdata.read_data)
+    {
+        bdata.agent.awareness.set_maximally_aware();
+        bdata.agent.target = Some(Target::new(
+            target,
+            true,
+            bdata.read_data.time.0,
+            true,
+            bdata.read_data.positions.get(target).map(|pos| pos.0),
+        ));
+    }
+}
+
 /// If we receive a new interaction, start the interaction timer
 pub fn process_inbox_interaction(bdata: &mut BehaviorData) -> bool {
     if BehaviorTree::interaction(bdata.agent).run(bdata) {
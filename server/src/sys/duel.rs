@@ -0,0 +1,156 @@
+use crate::{
+    client::Client,
+    persistence::{
+        character_updater::CharacterUpdater,
+        duel::{DuelOutcome, DuelResult},
+    },
+};
+use common::{
+    comp::{
+        duel::{Duel, DuelPhase},
+        ChatType, Health, Player, Pos, Presence, PresenceKind, Stats,
+    },
+    resources::Time,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::ServerGeneral;
+use hashbrown::HashSet;
+use specs::{Entities, Entity, Join, Read, ReadStorage, WriteExpect, WriteStorage};
+
+/// This system starts duels once their countdown has finished, and ends them
+/// once they have been decided
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, Time>,
+        WriteStorage<'a, Duel>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, Health>,
+        ReadStorage<'a, Stats>,
+        ReadStorage<'a, Presence>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Client>,
+        WriteExpect<'a, CharacterUpdater>,
+    );
+
+    const NAME: &'static str = "duel";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            entities,
+            time,
+            mut duels,
+            positions,
+            healths,
+            stats,
+            presences,
+            players,
+            clients,
+            mut character_updater,
+        ): Self::SystemData,
+    ) {
+        let notify = |entity: Entity, msg: &str| {
+            if let Some(client) = clients.get(entity) {
+                client.send_fallible(ServerGeneral::server_msg(ChatType::Meta, msg));
+            }
+        };
+
+        let mut started = Vec::new();
+        let mut abandoned = Vec::new();
+        // (winner, loser, outcome)
+        let mut finished = Vec::new();
+        let mut decided = HashSet::new();
+        for (entity, duel) in (&entities, &duels).join() {
+            // The opponent may have logged out or otherwise gone away
+            if !duels
+                .get(duel.opponent)
+                .map_or(false, |other| other.opponent == entity)
+            {
+                abandoned.push(entity);
+                continue;
+            }
+            if decided.contains(&entity) {
+                continue;
+            }
+
+            let beaten = duel.phase == DuelPhase::Defeated;
+            let dead = healths.get(entity).map_or(true, |health| health.is_dead);
+            let left_arena = positions
+                .get(entity)
+                .map_or(true, |pos| !duel.within_arena(pos.0));
+            let outcome = if beaten {
+                Some((duel.opponent, entity, DuelOutcome::Defeat))
+            } else if dead || left_arena {
+                Some((duel.opponent, entity, DuelOutcome::Forfeit))
+            } else if duel.timed_out(*time) {
+                Some((entity, duel.opponent, DuelOutcome::Draw))
+            } else {
+                None
+            };
+
+            if let Some(outcome) = outcome {
+                decided.insert(entity);
+                decided.insert(duel.opponent);
+                finished.push(outcome);
+            } else if duel.phase == DuelPhase::Countdown && time.0 >= duel.start.0 {
+                started.push(entity);
+            }
+        }
+
+        for entity in started {
+            if let Some(duel) = duels.get_mut(entity) {
+                duel.phase = DuelPhase::Fighting;
+                notify(entity, "Fight!");
+            }
+        }
+
+        for entity in abandoned {
+            duels.remove(entity);
+            notify(entity, "Your opponent has left, the duel is over.");
+        }
+
+        for (winner, loser, outcome) in finished {
+            duels.remove(winner);
+            duels.remove(loser);
+
+            let name = |entity| stats.get(entity).map_or("", |stats| stats.name.as_str());
+            // TODO: Localise
+            let msg = match outcome {
+                DuelOutcome::Defeat => {
+                    format!("[{}] defeated [{}] in a duel", name(winner), name(loser))
+                },
+                DuelOutcome::Forfeit => format!(
+                    "[{}] forfeited their duel against [{}]",
+                    name(loser),
+                    name(winner)
+                ),
+                DuelOutcome::Draw => format!(
+                    "The duel between [{}] and [{}] ended in a draw",
+                    name(winner),
+                    name(loser)
+                ),
+            };
+            for (client, _) in (&clients, &players).join() {
+                client.send_fallible(ServerGeneral::server_msg(ChatType::Meta, msg.as_str()));
+            }
+
+            let character_of = |entity| match presences.get(entity)?.kind {
+                PresenceKind::Character(character_id) => Some(character_id),
+                PresenceKind::Spectator | PresenceKind::Possessor => None,
+            };
+            if let (Some(winner), Some(loser)) = (character_of(winner), character_of(loser)) {
+                character_updater.record_duel(DuelResult {
+                    winner,
+                    loser,
+                    outcome,
+                    finished_at: chrono::Utc::now().timestamp(),
+                });
+            }
+        }
+    }
+}
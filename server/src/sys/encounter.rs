@@ -0,0 +1,229 @@
+use common::{
+    assets::AssetExt,
+    comp::{
+        agent::ActionState, encounter::PhaseSpec, Agent, Alignment, CharacterState, Encounter,
+        Health, Player, Pos,
+    },
+    event::{EventBus, ServerEvent},
+    resources::Time,
+    tactic::TacticSpec,
+    terrain::{Block, TerrainGrid},
+    uid::Uid,
+    vol::ReadVol,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_state::BlockChange;
+use hashbrown::HashMap;
+use specs::{
+    Entities, Entity as EcsEntity, Join, Read, ReadExpect, ReadStorage, Write, WriteStorage,
+};
+use vek::*;
+
+/// The blocks that were replaced to seal off the arenas of ongoing boss
+/// fights, by the boss whose arena they seal.
+#[derive(Default)]
+pub struct ArenaLockdowns(HashMap<EcsEntity, Vec<(Vec3<i32>, Block)>>);
+
+/// This system runs scripted boss encounters. It moves bosses through the
+/// phases of their fight, seals off their arena while players are fighting
+/// them, and resets the fight once no living player is left in the arena.
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, Time>,
+        Read<'a, EventBus<ServerEvent>>,
+        ReadExpect<'a, TerrainGrid>,
+        Write<'a, BlockChange>,
+        Write<'a, ArenaLockdowns>,
+        ReadStorage<'a, Uid>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Alignment>,
+        ReadStorage<'a, CharacterState>,
+        WriteStorage<'a, Encounter>,
+        WriteStorage<'a, Health>,
+        WriteStorage<'a, Pos>,
+        WriteStorage<'a, Agent>,
+    );
+
+    const NAME: &'static str = "encounter";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            entities,
+            time,
+            server_bus,
+            terrain,
+            mut block_change,
+            mut lockdowns,
+            uids,
+            players,
+            alignments,
+            char_states,
+            mut encounters,
+            mut healths,
+            mut positions,
+            mut agents,
+        ): Self::SystemData,
+    ) {
+        let mut server_emitter = server_bus.emitter();
+
+        let player_positions = (&players, &positions, &healths)
+            .join()
+            .filter(|(_, _, health)| !health.is_dead)
+            .map(|(_, pos, _)| pos.0)
+            .collect::<Vec<_>>();
+
+        for (entity, uid, encounter, health, pos, agent, char_state) in (
+            &entities,
+            &uids,
+            &mut encounters,
+            &mut healths,
+            &mut positions,
+            &mut agents,
+            char_states.maybe(),
+        )
+            .join()
+        {
+            if health.is_dead {
+                continue;
+            }
+
+            let spec = encounter.spec.read();
+            let home = match encounter.home {
+                Some(home) => home,
+                None => {
+                    encounter.home = Some(pos.0);
+                    if let Some(phase) = spec.phases.first() {
+                        enter_phase(agent, phase);
+                    }
+                    pos.0
+                },
+            };
+            let players_in_arena = player_positions
+                .iter()
+                .any(|pos| pos.distance_squared(home) < spec.arena_radius.powi(2));
+
+            match encounter.started {
+                None if players_in_arena => {
+                    encounter.started = Some(*time);
+                    encounter.phase_started = *time;
+                    if let Some(lockdown) = &spec.lockdown {
+                        let barrier = lockdown.barrier();
+                        lockdowns.0.entry(entity).or_insert_with(|| {
+                            arena_wall(&terrain, home, spec.arena_radius, lockdown.height)
+                                .into_iter()
+                                .filter_map(|pos| {
+                                    let old = *terrain.get(pos).ok()?;
+                                    block_change.try_set(pos, barrier)?;
+                                    Some((pos, old))
+                                })
+                                .collect()
+                        });
+                    }
+                },
+                None => {},
+                Some(_) if !players_in_arena => {
+                    // Everyone fighting the boss left or died, so the fight
+                    // starts over
+                    health.revive();
+                    pos.0 = home;
+                    agent.target = None;
+                    agent.action_state = ActionState::default();
+                    encounter.started = None;
+                    encounter.phase = 0;
+                    if let Some(phase) = spec.phases.first() {
+                        enter_phase(agent, phase);
+                    }
+
+                    for (add, alignment) in (&entities, &alignments).join() {
+                        if *alignment == Alignment::Owned(*uid) {
+                            server_emitter.emit(ServerEvent::Delete(add));
+                        }
+                    }
+                },
+                Some(_) => {
+                    if let Some(next) = spec.phases.get(encounter.phase + 1)
+                        && next.trigger.map_or(false, |trigger| {
+                            trigger.is_triggered(
+                                health.fraction(),
+                                time.0 - encounter.phase_started.0,
+                            )
+                        })
+                    {
+                        encounter.phase += 1;
+                        encounter.phase_started = *time;
+                        enter_phase(agent, next);
+                    }
+                },
+            }
+
+            // Adds have been summoned once the boss starts casting its summon
+            if let Some(condition) = spec
+                .phases
+                .get(encounter.phase)
+                .and_then(|phase| phase.summon_condition)
+                && matches!(char_state, Some(CharacterState::BasicSummon(_)))
+            {
+                agent.action_state.conditions[condition] = false;
+            }
+        }
+
+        // Open up the arenas of fights that are over
+        lockdowns.0.retain(|entity, blocks| {
+            let fighting = entities.is_alive(*entity)
+                && healths.get(*entity).map_or(false, |health| !health.is_dead)
+                && encounters
+                    .get(*entity)
+                    .map_or(false, |encounter| encounter.started.is_some());
+            if !fighting {
+                for (pos, block) in blocks.drain(..) {
+                    block_change.set(pos, block);
+                }
+            }
+            fighting
+        });
+    }
+}
+
+fn enter_phase(agent: &mut Agent, phase: &PhaseSpec) {
+    agent.tactic = phase.tactic.as_deref().map(TacticSpec::load_expect);
+    if let Some(condition) = phase.summon_condition {
+        agent.action_state.conditions[condition] = true;
+    }
+}
+
+/// Finds the open blocks that need to be filled to wall off the edge of an
+/// arena. The wall follows the floor, so that it has no gaps on uneven ground.
+fn arena_wall(terrain: &TerrainGrid, home: Vec3<f32>, radius: f32, height: u32) -> Vec<Vec3<i32>> {
+    let center = home.map(|e| e.floor() as i32);
+    let extent = radius.ceil() as i32 + 1;
+    let is_open = |pos: Vec3<i32>| terrain.get(pos).map_or(false, |block| !block.is_solid());
+
+    let mut wall = Vec::new();
+    for x in -extent..=extent {
+        for y in -extent..=extent {
+            let dist = ((x * x + y * y) as f32).sqrt();
+            if (dist - radius).abs() > 0.5 {
+                continue;
+            }
+
+            let column = center + Vec3::new(x, y, 0);
+            let base = (1..=height as i32)
+                .map(|depth| column - Vec3::unit_z() * depth)
+                .find(|pos| !is_open(*pos))
+                .map_or(column, |floor| floor + Vec3::unit_z());
+            wall.extend(
+                (0..height as i32)
+                    .map(|z| base + Vec3::unit_z() * z)
+                    .filter(|pos| is_open(*pos)),
+            );
+        }
+    }
+
+    wall
+}
@@ -0,0 +1,156 @@
+use super::terrain::TerrainPersistenceData;
+use crate::sys::SysScheduler;
+use common::{
+    farming::{default_crops, GROWTH_STAGES, RAIN_GROWTH_BONUS},
+    resources::TimeOfDay,
+    terrain::{Block, TerrainGrid},
+    vol::ReadVol,
+    weather::WeatherGrid,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_state::BlockChange;
+use hashbrown::{HashMap, HashSet};
+use specs::{Read, ReadExpect, Write};
+use vek::*;
+
+/// The most in-game time, in seconds, that crops can grow by at once. This
+/// stops crops from maturing instantly when the time of day is changed.
+const MAX_GROWTH_STEP: f64 = 3600.0;
+
+/// The seedlings in loaded chunks, along with their progress towards their
+/// next growth stage, and the crops in loaded chunks that were grown from them.
+#[derive(Default)]
+pub struct PlantedCrops {
+    crops: HashMap<Vec3<i32>, f32>,
+    /// Mature crops that were planted by players, as opposed to wild crops
+    grown: HashSet<Vec3<i32>>,
+    /// The time of day at which crops last grew
+    last_growth: Option<f64>,
+}
+
+impl PlantedCrops {
+    pub fn insert(&mut self, pos: Vec3<i32>) { self.crops.entry(pos).or_default(); }
+
+    /// Remembers the seedlings and grown crops among blocks changed by players
+    pub fn extend(&mut self, blocks: impl IntoIterator<Item = (Vec3<i32>, Block)>) {
+        let crops = default_crops().read();
+        for (pos, block) in blocks {
+            if block.get_growth().is_some() {
+                self.insert(pos);
+            } else if block
+                .get_sprite()
+                .map_or(false, |sprite| crops.by_crop(sprite).is_some())
+            {
+                self.grown.insert(pos);
+            }
+        }
+    }
+
+    /// Forgets a crop that is being harvested, returning whether it was
+    /// planted by a player
+    pub fn harvest(&mut self, pos: Vec3<i32>) -> bool { self.grown.remove(&pos) }
+}
+
+/// This system grows seedlings over in-game time. Crops grow faster in the
+/// rain, and turn into their mature, harvestable crop after their final growth
+/// stage.
+///
+/// Crops only grow while their chunk is loaded. When it is unloaded, they are
+/// forgotten until the chunk is loaded again from terrain persistence.
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Read<'a, TimeOfDay>,
+        ReadExpect<'a, WeatherGrid>,
+        ReadExpect<'a, TerrainGrid>,
+        Write<'a, BlockChange>,
+        Write<'a, PlantedCrops>,
+        TerrainPersistenceData<'a>,
+        Write<'a, SysScheduler<Self>>,
+    );
+
+    const NAME: &'static str = "farming";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            time_of_day,
+            weather_grid,
+            terrain,
+            mut block_change,
+            mut planted_crops,
+            mut _terrain_persistence,
+            mut scheduler,
+        ): Self::SystemData,
+    ) {
+        if !scheduler.should_run() {
+            return;
+        }
+
+        let elapsed = planted_crops
+            .last_growth
+            .replace(time_of_day.0)
+            .map_or(0.0, |last| {
+                (time_of_day.0 - last).clamp(0.0, MAX_GROWTH_STEP)
+            });
+
+        let crops = default_crops().read();
+        let PlantedCrops {
+            crops: seedlings,
+            grown,
+            ..
+        } = &mut *planted_crops;
+        // Forget about grown crops that were destroyed or unloaded
+        grown.retain(|pos| {
+            terrain
+                .get(*pos)
+                .ok()
+                .and_then(|block| block.get_sprite())
+                .map_or(false, |sprite| crops.by_crop(sprite).is_some())
+        });
+        seedlings.retain(|pos, progress| {
+            // Forget about seedlings that were harvested, destroyed or unloaded
+            let Some((block, (stage, crop))) = terrain
+                .get(*pos)
+                .ok()
+                .and_then(|block| Some((*block, block.get_growth()?)))
+            else {
+                return false;
+            };
+            let Some(spec) = crops.by_crop(crop) else { return false };
+
+            let rain = weather_grid.get_interpolated(pos.xy().as_()).rain;
+            *progress += (elapsed / spec.stage_duration()) as f32
+                * (1.0 + rain.clamp(0.0, 1.0) * RAIN_GROWTH_BONUS);
+            if *progress < 1.0 {
+                return true;
+            }
+
+            let stages = progress.floor();
+            let stage = stage.saturating_add(stages as u8);
+            let (new_block, mature) = if stage >= GROWTH_STAGES {
+                (Block::air(crop), true)
+            } else {
+                (block.with_growth_stage(stage).unwrap_or(block), false)
+            };
+
+            if block_change.try_set(*pos, new_block).is_none() {
+                // The block was already changed this tick, so try again later
+                return true;
+            }
+            *progress -= stages;
+            #[cfg(feature = "persistent_world")]
+            if let Some(terrain_persistence) = _terrain_persistence.as_mut() {
+                terrain_persistence.set_block(*pos, new_block);
+            }
+
+            if mature {
+                grown.insert(*pos);
+            }
+            !mature
+        });
+    }
+}
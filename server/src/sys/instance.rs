@@ -0,0 +1,633 @@
+#[cfg(not(feature = "worldgen"))]
+use crate::test_world::{IndexOwned, World};
+#[cfg(feature = "worldgen")]
+use world::{IndexOwned, World};
+
+use crate::{
+    chunk_serialize::ChunkSendEntry, client::Client, settings::Settings, sys::terrain::NpcData,
+};
+use common::{
+    comp::{group::Group, Alignment, Boss, ChatType, ForceUpdate, Health, Player, Pos},
+    event::{Emitter, EventBus, NpcBuilder, ServerEvent},
+    generation::DungeonLayout,
+    resources::Time,
+    slowjob::SlowJobPool,
+    terrain::{TerrainChunk, TerrainGrid},
+    uid::Uid,
+    vol::{ReadVol, WriteVol},
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::ServerGeneral;
+use hashbrown::HashSet;
+use specs::{
+    Component, DenseVecStorage, Entities, Entity as EcsEntity, Join, Read, ReadExpect, ReadStorage,
+    WriteExpect, WriteStorage,
+};
+use std::sync::Arc;
+use vek::*;
+
+/// How close a player has to get to a portal to step through it
+const PORTAL_RANGE: f32 = 2.5;
+/// How far players may be from the portal when their copy of the dungeon is
+/// ready and still be taken into it
+const GATHER_RANGE: f32 = 24.0;
+/// How many blocks of space are left between copies of a dungeon
+const INSTANCE_GAP: i32 = 32;
+/// How many freshly generated chunks are written into a copy of a dungeon each
+/// tick
+const COPY_CHUNKS_PER_TICK: usize = 2;
+/// How long after a copy of a dungeon is populated before it can count as
+/// cleared, so that its bosses have had time to spawn
+const SPAWN_GRACE: f64 = 10.0;
+/// The lowest that copies of dungeons may reach, so that they stay within the
+/// range of heights that terrain is expected to have
+const MIN_INSTANCE_Z: i32 = -4096;
+
+/// A portal that takes groups to their own copy of a dungeon, or out of it
+pub struct DungeonPortal {
+    /// The origin of the dungeon the portal belongs to
+    pub dungeon: Vec2<i32>,
+    /// Whether the portal leads out of a copy of the dungeon
+    pub exit: bool,
+}
+
+impl Component for DungeonPortal {
+    type Storage = DenseVecStorage<Self>;
+}
+
+enum InstanceState {
+    /// The dungeon is being copied from fresh copies of the chunks it lies in,
+    /// some of which are still being generated and some of which are waiting
+    /// to be written into the copy
+    Building {
+        pending: HashSet<Vec2<i32>>,
+        generated: Vec<(Vec2<i32>, TerrainChunk)>,
+    },
+    /// The group is exploring the dungeon
+    Active { since: f64, cleared: Option<f64> },
+}
+
+/// A private copy of a dungeon that a group is exploring. Copies lie directly
+/// below the dungeon they are made from, and are copied from freshly generated
+/// terrain every time they are used so that they start out untouched by
+/// players.
+struct Instance {
+    /// Tells chunks generated for this copy apart from those generated for
+    /// copies that have since been torn down
+    id: u64,
+    /// Index of the dungeon in [`DungeonInstances::layouts`]
+    dungeon: usize,
+    /// Which of the copies below the dungeon this is, starting at 0 for the
+    /// one closest to it
+    slot: i32,
+    group: Option<Group>,
+    /// The players allowed into this copy of the dungeon
+    players: Vec<Uid>,
+    state: InstanceState,
+}
+
+impl Instance {
+    fn slot_z_offset(layout: &DungeonLayout, slot: i32) -> i32 {
+        -(layout.bounds.size().d + INSTANCE_GAP) * (slot + 1)
+    }
+
+    /// Whether a copy of the dungeon in the given slot would stay above
+    /// [`MIN_INSTANCE_Z`]
+    fn slot_fits(layout: &DungeonLayout, slot: i32) -> bool {
+        layout.bounds.min.z + Self::slot_z_offset(layout, slot) >= MIN_INSTANCE_Z
+    }
+
+    fn z_offset(&self, layout: &DungeonLayout) -> i32 { Self::slot_z_offset(layout, self.slot) }
+
+    fn bounds(&self, layout: &DungeonLayout) -> Aabb<i32> {
+        let offset = Vec3::unit_z() * self.z_offset(layout);
+        Aabb {
+            min: layout.bounds.min + offset,
+            max: layout.bounds.max + offset,
+        }
+    }
+}
+
+type GeneratedChunk = (u64, Vec2<i32>, TerrainChunk);
+
+/// The copies of dungeons currently in use
+pub struct DungeonInstances {
+    layouts: Vec<DungeonLayout>,
+    instances: Vec<Instance>,
+    next_id: u64,
+    chunk_tx: crossbeam_channel::Sender<GeneratedChunk>,
+    chunk_rx: crossbeam_channel::Receiver<GeneratedChunk>,
+    /// Dungeons whose entrance portal has been asked for but not yet created
+    requested_portals: HashSet<Vec2<i32>>,
+    /// Players who were standing at a portal last tick, who have to step away
+    /// before they can use a portal again
+    at_portal: HashSet<EcsEntity>,
+}
+
+impl DungeonInstances {
+    pub fn new(layouts: Vec<DungeonLayout>) -> Self {
+        let (chunk_tx, chunk_rx) = crossbeam_channel::unbounded();
+        Self {
+            layouts,
+            instances: Vec::new(),
+            next_id: 0,
+            chunk_tx,
+            chunk_rx,
+            requested_portals: HashSet::new(),
+            at_portal: HashSet::new(),
+        }
+    }
+
+    /// The entrance of the dungeon if `pos` lies within a copy of it, which is
+    /// where players in the copy are put when they leave the game so that they
+    /// don't come back to a copy that may have been torn down.
+    pub fn surface_pos(&self, pos: Vec3<f32>) -> Option<Vec3<f32>> {
+        let pos = pos.map(|e| e.floor() as i32);
+        self.instances.iter().find_map(|instance| {
+            let layout = &self.layouts[instance.dungeon];
+            instance
+                .bounds(layout)
+                .contains_point(pos)
+                .then_some(layout.entrance)
+        })
+    }
+
+    /// Forget a player that has left the game, so that copies of dungeons
+    /// are only kept for players that are still online.
+    pub fn remove_player(&mut self, uid: Uid) {
+        for instance in &mut self.instances {
+            instance.players.retain(|player| *player != uid);
+        }
+    }
+}
+
+/// This system gives groups their own copy of a dungeon when they step
+/// through the portal at its entrance, and sends them back out once they
+/// have cleared it or run out of time.
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, Time>,
+        Read<'a, Settings>,
+        Read<'a, EventBus<ServerEvent>>,
+        ReadExpect<'a, Arc<World>>,
+        ReadExpect<'a, IndexOwned>,
+        ReadExpect<'a, SlowJobPool>,
+        ReadExpect<'a, EventBus<ChunkSendEntry>>,
+        WriteExpect<'a, TerrainGrid>,
+        WriteExpect<'a, DungeonInstances>,
+        ReadStorage<'a, DungeonPortal>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Uid>,
+        ReadStorage<'a, Group>,
+        ReadStorage<'a, Alignment>,
+        ReadStorage<'a, Health>,
+        ReadStorage<'a, Boss>,
+        ReadStorage<'a, Client>,
+        WriteStorage<'a, Pos>,
+        WriteStorage<'a, ForceUpdate>,
+    );
+
+    const NAME: &'static str = "instance";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            entities,
+            time,
+            settings,
+            server_bus,
+            world,
+            index,
+            slow_jobs,
+            chunk_send_bus,
+            mut terrain,
+            mut instances,
+            portals,
+            players,
+            uids,
+            groups,
+            alignments,
+            healths,
+            bosses,
+            clients,
+            mut positions,
+            mut force_updates,
+        ): Self::SystemData,
+    ) {
+        let settings = &settings.dungeon_instances;
+        if !settings.enabled {
+            return;
+        }
+        let mut server_emitter = server_bus.emitter();
+        let mut chunk_send_emitter = chunk_send_bus.emitter();
+        let DungeonInstances {
+            layouts,
+            instances,
+            next_id,
+            chunk_tx,
+            chunk_rx,
+            requested_portals,
+            at_portal,
+        } = &mut *instances;
+
+        // Hand freshly generated chunks to the copies they were generated for
+        while let Ok((id, key, chunk)) = chunk_rx.try_recv() {
+            if let Some(InstanceState::Building { pending, generated }) = instances
+                .iter_mut()
+                .find(|instance| instance.id == id)
+                .map(|instance| &mut instance.state)
+            {
+                pending.remove(&key);
+                generated.push((key, chunk));
+            }
+        }
+
+        let notify = |entity, msg: &str| {
+            if let Some(client) = clients.get(entity) {
+                client.send_fallible(ServerGeneral::server_msg(ChatType::Meta, msg));
+            }
+        };
+
+        // Put portals at the entrances of dungeons whose entrance is loaded
+        let entrance_portals = (&portals, &positions)
+            .join()
+            .filter(|(portal, _)| !portal.exit)
+            .map(|(portal, _)| portal.dungeon)
+            .collect::<HashSet<_>>();
+        requested_portals.retain(|dungeon| !entrance_portals.contains(dungeon));
+        for layout in layouts.iter() {
+            let pos = portal_pos(layout);
+            if terrain
+                .get_key_real(terrain.pos_key(pos.map(|e| e.floor() as i32)))
+                .is_none()
+            {
+                // The portal may have been unloaded before it was created
+                requested_portals.remove(&layout.origin);
+                continue;
+            }
+            if !entrance_portals.contains(&layout.origin)
+                && !requested_portals.contains(&layout.origin)
+            {
+                server_emitter.emit(ServerEvent::CreateDungeonPortal {
+                    pos,
+                    dungeon: layout.origin,
+                    exit: false,
+                });
+                requested_portals.insert(layout.origin);
+            }
+        }
+
+        // Players step through portals as they walk up to them
+        let portal_positions = (&portals, &positions)
+            .join()
+            .map(|(portal, pos)| (portal.dungeon, portal.exit, pos.0))
+            .collect::<Vec<_>>();
+        let mut now_at_portal = HashSet::new();
+        let mut teleports = Vec::new();
+        for (entity, _, uid, pos, health) in
+            (&entities, &players, &uids, &positions, &healths).join()
+        {
+            if health.is_dead {
+                continue;
+            }
+            let Some((dungeon, exit)) = portal_positions
+                .iter()
+                .find(|(_, _, portal_pos)| portal_pos.distance_squared(pos.0) < PORTAL_RANGE.powi(2))
+                .map(|(dungeon, exit, _)| (*dungeon, *exit))
+            else {
+                continue;
+            };
+            now_at_portal.insert(entity);
+            if at_portal.contains(&entity) {
+                continue;
+            }
+            let Some(dungeon) = layouts.iter().position(|layout| layout.origin == dungeon) else {
+                continue;
+            };
+            let layout = &layouts[dungeon];
+
+            if exit {
+                teleports.push((entity, layout.entrance));
+                continue;
+            }
+
+            let group = groups.get(entity).copied();
+            let existing = instances.iter_mut().find(|instance| {
+                instance.dungeon == dungeon
+                    && (instance.players.contains(uid)
+                        || group.map_or(false, |group| instance.group == Some(group)))
+            });
+            match existing {
+                Some(instance) => {
+                    if !instance.players.contains(uid) {
+                        instance.players.push(*uid);
+                    }
+                    match instance.state {
+                        InstanceState::Building { .. } => notify(
+                            entity,
+                            "Your group's copy of this dungeon is still being prepared.",
+                        ),
+                        InstanceState::Active { .. } => {
+                            let offset = Vec3::unit_z() * instance.z_offset(layout) as f32;
+                            teleports.push((entity, layout.top_floor + offset));
+                        },
+                    }
+                },
+                None => {
+                    let slot = (0..)
+                        .find(|slot| {
+                            !instances.iter().any(|instance| {
+                                instance.dungeon == dungeon && instance.slot == *slot
+                            })
+                        })
+                        .unwrap_or_default();
+                    if instances.len() >= settings.max_instances
+                        || !Instance::slot_fits(layout, slot)
+                    {
+                        notify(
+                            entity,
+                            "Every copy of a dungeon is in use right now, try again later.",
+                        );
+                        continue;
+                    }
+                    // Generate the chunks the dungeon lies in afresh, so that the copy
+                    // doesn't pick up anything that players have done to the original
+                    let id = *next_id;
+                    *next_id += 1;
+                    let min_key = terrain.pos_key(layout.bounds.min);
+                    let max_key = terrain.pos_key(layout.bounds.max - 1);
+                    let mut pending = HashSet::new();
+                    for x in min_key.x..=max_key.x {
+                        for y in min_key.y..=max_key.y {
+                            let key = Vec2::new(x, y);
+                            pending.insert(key);
+                            let world = Arc::clone(&world);
+                            let index = index.clone();
+                            let chunk_tx = chunk_tx.clone();
+                            slow_jobs.spawn("CHUNK_GENERATOR", move || {
+                                if let Ok((chunk, _)) = world.generate_chunk(
+                                    index.as_index_ref(),
+                                    key,
+                                    None,
+                                    || false,
+                                    None,
+                                ) {
+                                    let _ = chunk_tx.send((id, key, chunk));
+                                }
+                            });
+                        }
+                    }
+                    instances.push(Instance {
+                        id,
+                        dungeon,
+                        slot,
+                        group,
+                        players: vec![*uid],
+                        state: InstanceState::Building {
+                            pending,
+                            generated: Vec::new(),
+                        },
+                    });
+                    notify(
+                        entity,
+                        &format!(
+                            "Preparing a copy of {} for your group, stay close to the portal.",
+                            layout.name
+                        ),
+                    );
+                },
+            }
+        }
+        *at_portal = now_at_portal;
+
+        let mut rng = rand::thread_rng();
+        instances.retain_mut(|instance| {
+            let layout = &layouts[instance.dungeon];
+            let z_offset = instance.z_offset(layout);
+            let bounds = instance.bounds(layout);
+            let contains = |pos: Vec3<f32>| bounds.contains_point(pos.map(|e| e.floor() as i32));
+            let members = || {
+                (&entities, &uids, &positions)
+                    .join()
+                    .filter(|(entity, uid, _)| {
+                        instance.players.contains(uid)
+                            || instance
+                                .group
+                                .map_or(false, |group| groups.get(*entity) == Some(&group))
+                    })
+                    .map(|(entity, _, pos)| (entity, pos.0))
+                    .collect::<Vec<_>>()
+            };
+
+            match &mut instance.state {
+                InstanceState::Building { pending, generated } => {
+                    // Give up on copies that nobody who asked for them is still around for
+                    let members = members();
+                    if members.is_empty() {
+                        return false;
+                    }
+
+                    // Write the dungeon's part of each fresh chunk into the copy. Only the
+                    // group is sent the changed chunks, since nobody else can see them.
+                    let mut copied = 0;
+                    generated.retain(|(key, fresh)| {
+                        if copied >= COPY_CHUNKS_PER_TICK {
+                            return true;
+                        }
+                        // Wait for the part of the world the copy is in to be loaded
+                        let Some(chunk) = terrain.get_key_arc_real(*key) else {
+                            return true;
+                        };
+                        let mut chunk = TerrainChunk::clone(chunk);
+                        let chunk_min = TerrainGrid::key_chunk(*key);
+                        let chunk_max = TerrainGrid::key_chunk(*key + 1);
+                        let min = Vec2::<i32>::from(layout.bounds.min).map2(chunk_min, i32::max);
+                        let max = Vec2::<i32>::from(layout.bounds.max).map2(chunk_max, i32::min);
+                        for x in min.x..max.x {
+                            for y in min.y..max.y {
+                                for z in layout.bounds.min.z..layout.bounds.max.z {
+                                    let pos = Vec3::new(x - chunk_min.x, y - chunk_min.y, z);
+                                    if let Ok(block) = fresh.get(pos) {
+                                        let _ = chunk.set(pos + Vec3::unit_z() * z_offset, *block);
+                                    }
+                                }
+                            }
+                        }
+                        terrain.insert(*key, Arc::new(chunk));
+                        for (entity, _) in &members {
+                            chunk_send_emitter.emit(ChunkSendEntry {
+                                entity: *entity,
+                                chunk_key: *key,
+                            });
+                        }
+                        copied += 1;
+                        false
+                    });
+
+                    if pending.is_empty() && generated.is_empty() {
+                        for entity in world.dungeon_entities(
+                            index.as_index_ref(),
+                            layout.origin,
+                            z_offset,
+                            &mut rng,
+                        ) {
+                            spawn_instance_npc(
+                                &mut server_emitter,
+                                NpcData::from_entity_info(entity),
+                            );
+                        }
+                        let offset = Vec3::unit_z() * z_offset as f32;
+                        server_emitter.emit(ServerEvent::CreateDungeonPortal {
+                            pos: layout.top_floor + offset + Vec3::new(0.0, 6.0, 0.0),
+                            dungeon: layout.origin,
+                            exit: true,
+                        });
+                        let entry = portal_pos(layout);
+                        for (entity, pos) in members {
+                            if pos.distance_squared(entry) < GATHER_RANGE.powi(2) {
+                                teleports.push((entity, layout.top_floor + offset));
+                            }
+                        }
+                        instance.state = InstanceState::Active {
+                            since: time.0,
+                            cleared: None,
+                        };
+                    }
+                    true
+                },
+                InstanceState::Active { since, cleared } => {
+                    let members = members();
+                    let inside = members
+                        .iter()
+                        .filter(|(_, pos)| contains(*pos))
+                        .collect::<Vec<_>>();
+                    let message = |msg: &str| {
+                        for (entity, _) in &inside {
+                            notify(*entity, msg);
+                        }
+                    };
+
+                    // Give the group's members a moment to arrive before giving up on them
+                    let populated = time.0 - *since > SPAWN_GRACE;
+                    if populated && cleared.is_none() {
+                        let boss_alive = (&bosses, &positions, &healths)
+                            .join()
+                            .any(|(_, pos, health)| !health.is_dead && contains(pos.0));
+                        if !boss_alive {
+                            *cleared = Some(time.0);
+                            message(&format!(
+                                "{} has been cleared! You will be returned to the surface in {} \
+                                 minutes.",
+                                layout.name,
+                                settings.completion_grace.as_secs() / 60
+                            ));
+                        }
+                    }
+
+                    let timed_out = time.0 - *since > settings.time_limit.as_secs_f64();
+                    let finished = cleared.map_or(false, |cleared| {
+                        time.0 - cleared > settings.completion_grace.as_secs_f64()
+                    });
+                    let abandoned = populated && inside.is_empty();
+                    if !(timed_out || finished || abandoned) {
+                        return true;
+                    }
+                    if timed_out && !finished {
+                        message("You have run out of time in this dungeon.");
+                    }
+
+                    // Send players and their pets back to the surface, and clear out everything
+                    // else so that the copy can be used again
+                    for (entity, pos, alignment) in
+                        (&entities, &positions, alignments.maybe()).join()
+                    {
+                        if !contains(pos.0) {
+                            continue;
+                        }
+                        if players.contains(entity)
+                            || matches!(alignment, Some(Alignment::Owned(_)))
+                        {
+                            teleports.push((entity, layout.entrance));
+                        } else {
+                            server_emitter.emit(ServerEvent::Delete(entity));
+                        }
+                    }
+                    false
+                },
+            }
+        });
+
+        // Pets go wherever their owners are taken, so that they can come along into
+        // the copy as well as out of it
+        let owners = teleports
+            .iter()
+            .filter_map(|(entity, target)| {
+                Some((*uids.get(*entity)?, positions.get(*entity)?.0, *target))
+            })
+            .collect::<Vec<_>>();
+        for (entity, pos, alignment) in (&entities, &positions, &alignments).join() {
+            let Alignment::Owned(owner) = alignment else { continue };
+            if players.contains(entity) {
+                continue;
+            }
+            if let Some((_, _, target)) = owners.iter().find(|(uid, owner_pos, _)| {
+                uid == owner && owner_pos.distance_squared(pos.0) < GATHER_RANGE.powi(2)
+            }) {
+                teleports.push((entity, *target));
+            }
+        }
+
+        for (entity, target) in teleports {
+            if let Some(pos) = positions.get_mut(entity) {
+                pos.0 = target;
+                force_updates
+                    .get_mut(entity)
+                    .map(|force_update| force_update.update());
+            }
+        }
+    }
+}
+
+/// The portal stands a little way from the dungeon's waypoint, so that
+/// players returning to the entrance don't step straight back through it
+fn portal_pos(layout: &DungeonLayout) -> Vec3<f32> { layout.entrance + Vec3::new(6.0, 0.0, 0.0) }
+
+fn spawn_instance_npc(server_emitter: &mut Emitter<'_, ServerEvent>, data: NpcData) {
+    if let NpcData::Data {
+        pos,
+        stats,
+        skill_set,
+        health,
+        poise,
+        inventory,
+        agent,
+        body,
+        alignment,
+        scale,
+        loot,
+        is_boss,
+        encounter,
+        squad: _,
+    } = data
+    {
+        server_emitter.emit(ServerEvent::CreateNpc {
+            pos,
+            npc: NpcBuilder::new(stats, body, alignment)
+                .with_skill_set(skill_set)
+                .with_health(health)
+                .with_poise(poise)
+                .with_inventory(inventory)
+                .with_agent(agent)
+                .with_scale(scale)
+                .with_loot(loot)
+                .with_boss(is_boss)
+                .with_encounter(encounter),
+        });
+    }
+}
@@ -1,7 +1,11 @@
 pub mod agent;
 pub mod chunk_send;
 pub mod chunk_serialize;
+pub mod duel;
+pub mod encounter;
 pub mod entity_sync;
+pub mod farming;
+pub mod instance;
 pub mod invite_timeout;
 pub mod loot;
 pub mod metrics;
@@ -9,10 +13,14 @@ pub mod msg;
 pub mod object;
 pub mod persistence;
 pub mod pets;
+pub mod replay;
 pub mod sentinel;
+pub mod squad;
+pub mod statistics;
 pub mod subscription;
 pub mod terrain;
 pub mod terrain_sync;
+pub mod threat;
 pub mod waypoint;
 pub mod wiring;
 
@@ -25,6 +33,8 @@ use std::{
 };
 
 pub type PersistenceScheduler = SysScheduler<persistence::Sys>;
+pub type StatisticsScheduler = SysScheduler<statistics::Sys>;
+pub type FarmingScheduler = SysScheduler<farming::Sys>;
 
 pub fn add_server_systems(dispatch_builder: &mut DispatcherBuilder) {
     dispatch::<melee::Sys>(dispatch_builder, &[&projectile::Sys::sys_name()]);
@@ -33,7 +43,14 @@ pub fn add_server_systems(dispatch_builder: &mut DispatcherBuilder) {
     dispatch::<terrain::Sys>(dispatch_builder, &[&msg::terrain::Sys::sys_name()]);
     dispatch::<waypoint::Sys>(dispatch_builder, &[]);
     dispatch::<invite_timeout::Sys>(dispatch_builder, &[]);
+    dispatch::<duel::Sys>(dispatch_builder, &[]);
     dispatch::<persistence::Sys>(dispatch_builder, &[]);
+    dispatch::<statistics::Sys>(dispatch_builder, &[]);
+    dispatch::<farming::Sys>(dispatch_builder, &[]);
+    dispatch::<encounter::Sys>(dispatch_builder, &[]);
+    dispatch::<threat::Sys>(dispatch_builder, &[]);
+    dispatch::<squad::Sys>(dispatch_builder, &[]);
+    dispatch::<instance::Sys>(dispatch_builder, &[]);
     dispatch::<object::Sys>(dispatch_builder, &[]);
     dispatch::<wiring::Sys>(dispatch_builder, &[]);
     // no dependency, as we only work once per sec anyway.
@@ -47,6 +64,8 @@ pub fn run_sync_systems(ecs: &mut specs::World) {
     // If I'm not mistaken, these two could be ran in parallel
     run_now::<sentinel::Sys>(ecs);
     run_now::<subscription::Sys>(ecs);
+    // Needs the changes recorded by the sentinel
+    run_now::<replay::Sys>(ecs);
 
     // Sync
     run_now::<terrain_sync::Sys>(ecs);
@@ -88,6 +88,7 @@ impl Sys {
                     if presences.contains(entity) {
                         debug!("player already ingame, aborting");
                     } else if character_updater.has_pending_database_action(character_id)
+                        || character_updater.has_pending_bank_update(&player.uuid().to_string())
                     {
                         debug!("player recently logged out pending persistence, aborting");
                         client.send(ServerGeneral::CharacterDataLoadResult(Err(
@@ -1,6 +1,6 @@
 #[cfg(feature = "persistent_world")]
 use crate::TerrainPersistence;
-use crate::{client::Client, Settings};
+use crate::{client::Client, sys::replay::ReplayRecorder, Settings};
 use common::{
     comp::{
         Admin, AdminRole, CanBuild, ControlEvent, Controller, ForceUpdate, Health, Ori, Player,
@@ -340,6 +340,7 @@ impl<'a> System<'a> for Sys {
         TerrainPersistenceData<'a>,
         ReadStorage<'a, Player>,
         ReadStorage<'a, Admin>,
+        ReadExpect<'a, ReplayRecorder>,
     );
 
     const NAME: &'static str = "msg::in_game";
@@ -372,6 +373,7 @@ impl<'a> System<'a> for Sys {
             mut terrain_persistence,
             players,
             admins,
+            replay_recorder,
         ): Self::SystemData,
     ) {
         let time_for_vd_changes = Instant::now();
@@ -426,6 +428,7 @@ impl<'a> System<'a> for Sys {
                     let mut clearable_maybe_presence = maybe_presence.as_deref_mut();
                     let mut skill_set = skill_set.map(Cow::Borrowed);
                     let _ = super::try_recv_all(client, 2, |client, msg| {
+                        replay_recorder.record_client_input(entity, &msg);
                         Self::handle_client_in_game_msg(
                             server_emitter,
                             entity,
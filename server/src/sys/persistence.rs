@@ -1,14 +1,18 @@
-use crate::{persistence::character_updater, sys::SysScheduler};
+use crate::{
+    persistence::character_updater,
+    sys::{instance::DungeonInstances, SysScheduler},
+};
 use common::{
     comp::{
         pet::{is_tameable, Pet},
-        ActiveAbilities, Alignment, Body, Inventory, MapMarker, Presence, PresenceKind, SkillSet,
-        Stats, Waypoint,
+        Achievements, ActiveAbilities, Alignment, Bank, Body, Inventory, KnownRecipes, MapMarker,
+        Player, Presence, PresenceKind, SkillSet, Statistics, Stats, Waypoint,
     },
+    resources::Time,
     uid::Uid,
 };
 use common_ecs::{Job, Origin, Phase, System};
-use specs::{Join, ReadStorage, Write, WriteExpect};
+use specs::{Join, Read, ReadExpect, ReadStorage, Write, WriteExpect};
 
 #[derive(Default)]
 pub struct Sys;
@@ -26,6 +30,13 @@ impl<'a> System<'a> for Sys {
         ReadStorage<'a, Pet>,
         ReadStorage<'a, Stats>,
         ReadStorage<'a, ActiveAbilities>,
+        ReadStorage<'a, KnownRecipes>,
+        ReadStorage<'a, Statistics>,
+        ReadStorage<'a, Achievements>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Bank>,
+        Read<'a, Time>,
+        ReadExpect<'a, DungeonInstances>,
         WriteExpect<'a, character_updater::CharacterUpdater>,
         Write<'a, SysScheduler<Self>>,
     );
@@ -48,6 +59,13 @@ impl<'a> System<'a> for Sys {
             pets,
             stats,
             active_abilities,
+            known_recipes,
+            statistics,
+            achievements,
+            players,
+            banks,
+            time,
+            instances,
             mut updater,
             mut scheduler,
         ): Self::SystemData,
@@ -62,6 +80,11 @@ impl<'a> System<'a> for Sys {
                     player_waypoints.maybe(),
                     &active_abilities,
                     map_markers.maybe(),
+                    known_recipes.maybe(),
+                    statistics.maybe(),
+                    achievements.maybe(),
+                    players.maybe(),
+                    banks.maybe(),
                 )
                     .join()
                     .filter_map(
@@ -73,6 +96,11 @@ impl<'a> System<'a> for Sys {
                             waypoint,
                             active_abilities,
                             map_marker,
+                            known_recipes,
+                            statistics,
+                            achievements,
+                            player,
+                            bank,
                         )| match presence.kind {
                             PresenceKind::Character(id) => {
                                 let pets = (&alignments, &bodies, &stats, &pets)
@@ -95,9 +123,23 @@ impl<'a> System<'a> for Sys {
                                     skill_set.clone(),
                                     inventory.clone(),
                                     pets,
-                                    waypoint.cloned(),
+                                    // Players in a copy of a dungeon come back at its entrance
+                                    waypoint.map(|waypoint| {
+                                        match instances.surface_pos(waypoint.get_pos()) {
+                                            Some(entrance) => Waypoint::new(entrance, *time),
+                                            None => *waypoint,
+                                        }
+                                    }),
                                     active_abilities.clone(),
                                     map_marker.cloned(),
+                                    known_recipes.cloned().unwrap_or_default(),
+                                    statistics.cloned().unwrap_or_default(),
+                                    player.zip(achievements).map(|(player, achievements)| {
+                                        (player.uuid().to_string(), achievements.clone())
+                                    }),
+                                    player.zip(bank).map(|(player, bank)| {
+                                        (player.uuid().to_string(), bank.clone())
+                                    }),
                                 ))
                             },
                             PresenceKind::Spectator | PresenceKind::Possessor => None,
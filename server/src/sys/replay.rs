@@ -0,0 +1,290 @@
+use super::sentinel::{TrackedStorages, UpdateTrackers};
+use crate::settings::ReplaySettings;
+use common::{
+    comp::{Controller, Ori, Pos, Vel},
+    event::{ServerEvent, ServerEventDiscriminants},
+    resources::{DeltaTime, Time},
+    terrain::{MapSizeLg, TerrainChunk, TerrainGrid},
+    uid::Uid,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::{
+    msg::ClientGeneral,
+    replay::{
+        EntityPhysics, ReplayError, ReplayEvent, ReplayFrame, ReplayHeader, ReplaySnapshot,
+        ReplayWriter, REPLAY_VERSION,
+    },
+};
+use common_state::TerrainChanges;
+use hashbrown::HashSet;
+use specs::{
+    Entities, Entity as EcsEntity, Join, Read, ReadExpect, ReadStorage, World, WorldExt,
+    WriteExpect,
+};
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use strum::VariantNames;
+use tracing::{info, warn};
+use vek::*;
+
+/// A replay file that is being written to
+struct Segment {
+    writer: ReplayWriter<BufWriter<File>>,
+    started: Instant,
+    /// The entities that existed at the end of the last recorded tick, used to
+    /// work out which ones have been deleted since
+    known_entities: HashSet<u64>,
+}
+
+/// Records the simulation of the server into replay files, which can be
+/// re-simulated by the `replay` binary of `common-systems`.
+pub struct ReplayRecorder {
+    enabled: bool,
+    dir: PathBuf,
+    segment_length: Duration,
+    map_size_lg: MapSizeLg,
+    segment: Option<Segment>,
+    tick: u64,
+    /// Messages received from clients during this tick
+    client_inputs: parking_lot::Mutex<Vec<(EcsEntity, ClientGeneral)>>,
+    /// How many server events of each kind were handled during this tick
+    server_events: Vec<(u16, u32)>,
+    /// The server events handled during this tick that moved entities
+    events: Vec<ReplayEvent>,
+}
+
+/// The payload of a server event, if it's one that the replay runner applies
+/// again when re-simulating
+pub fn replay_event(ecs: &World, event: &ServerEvent) -> Option<ReplayEvent> {
+    let uid = |entity| ecs.read_storage::<Uid>().get(entity).copied();
+    match event {
+        ServerEvent::Knockback { entity, impulse } => Some(ReplayEvent::Knockback {
+            entity: uid(*entity)?,
+            impulse: *impulse,
+        }),
+        ServerEvent::TeleportTo {
+            entity,
+            target,
+            max_range,
+        } => Some(ReplayEvent::TeleportTo {
+            entity: uid(*entity)?,
+            target: *target,
+            max_range: *max_range,
+        }),
+        _ => None,
+    }
+}
+
+impl ReplayRecorder {
+    pub fn new(settings: &ReplaySettings, data_dir: &Path, map_size_lg: MapSizeLg) -> Self {
+        Self {
+            enabled: settings.enabled,
+            dir: data_dir.join("replays"),
+            segment_length: settings.segment_length,
+            map_size_lg,
+            segment: None,
+            tick: 0,
+            client_inputs: parking_lot::Mutex::new(Vec::new()),
+            server_events: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    pub fn enabled(&self) -> bool { self.enabled }
+
+    pub fn record_client_input(&self, entity: EcsEntity, msg: &ClientGeneral) {
+        if self.enabled {
+            self.client_inputs.lock().push((entity, msg.clone()));
+        }
+    }
+
+    /// Takes the number of server events of each kind handled this tick,
+    /// indexed by [`ServerEventDiscriminants`], along with the events that
+    /// moved entities
+    pub fn record_server_events(&mut self, counts: &[u32], events: Vec<ReplayEvent>) {
+        if self.enabled {
+            self.events = events;
+            self.server_events = counts
+                .iter()
+                .enumerate()
+                .filter(|(_, count)| **count > 0)
+                .map(|(kind, count)| (kind as u16, *count))
+                .collect();
+        }
+    }
+
+    fn start_segment(&self) -> Result<ReplayWriter<BufWriter<File>>, ReplayError> {
+        fs::create_dir_all(&self.dir)?;
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_secs());
+        let path = self.dir.join(format!("replay-{}.bin", secs));
+        info!(?path, "Recording replay");
+
+        let mut writer = ReplayWriter::new(BufWriter::new(File::create(path)?));
+        writer.write(&ReplayHeader {
+            version: REPLAY_VERSION,
+            map_size_lg: self.map_size_lg.vec(),
+            server_events: ServerEventDiscriminants::VARIANTS
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+        })?;
+        Ok(writer)
+    }
+}
+
+/// Writes what happened during the tick to the current replay. This must run
+/// after the sentinel system has recorded changes to components, and before
+/// the terrain changes of the tick are cleared.
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    #[allow(clippy::type_complexity)]
+    type SystemData = (
+        Entities<'a>,
+        TrackedStorages<'a>,
+        ReadExpect<'a, UpdateTrackers>,
+        ReadExpect<'a, TerrainGrid>,
+        Read<'a, TerrainChanges>,
+        Read<'a, Time>,
+        Read<'a, DeltaTime>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, Vel>,
+        ReadStorage<'a, Ori>,
+        ReadStorage<'a, Controller>,
+        WriteExpect<'a, ReplayRecorder>,
+    );
+
+    const NAME: &'static str = "replay";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            entities,
+            tracked_storages,
+            trackers,
+            terrain,
+            terrain_changes,
+            time,
+            dt,
+            positions,
+            velocities,
+            orientations,
+            controllers,
+            mut recorder,
+        ): Self::SystemData,
+    ) {
+        if !recorder.enabled {
+            return;
+        }
+        let recorder = &mut *recorder;
+        recorder.tick += 1;
+        let client_inputs = std::mem::take(&mut *recorder.client_inputs.lock());
+        let server_events = std::mem::take(&mut recorder.server_events);
+        let events = std::mem::take(&mut recorder.events);
+
+        let uids = &tracked_storages.uid;
+        let physics = (uids, &positions, &velocities, &orientations)
+            .join()
+            .map(|(uid, pos, vel, ori)| (*uid, *pos, *vel, *ori))
+            .collect::<Vec<EntityPhysics>>();
+        let controllers = (uids, &controllers)
+            .join()
+            .map(|(uid, controller)| (*uid, controller.clone()))
+            .collect::<Vec<_>>();
+        let current_entities = uids.join().map(|uid| uid.0).collect::<HashSet<_>>();
+
+        let result = if let Some(segment) = recorder
+            .segment
+            .as_mut()
+            .filter(|segment| segment.started.elapsed() < recorder.segment_length)
+        {
+            let deleted_entities = segment
+                .known_entities
+                .difference(&current_entities)
+                .copied()
+                .collect();
+            let (entity_sync, comp_sync) =
+                trackers.create_replay_sync_packages(&tracked_storages, deleted_entities);
+            let chunk = |key: &Vec2<i32>| {
+                terrain
+                    .get_key(*key)
+                    .map(|chunk| (*key, TerrainChunk::clone(chunk)))
+            };
+            segment.known_entities = current_entities;
+            segment.writer.write(&ReplayFrame {
+                tick: recorder.tick,
+                dt: dt.0,
+                time: time.0,
+                client_inputs: client_inputs
+                    .into_iter()
+                    .filter_map(|(entity, msg)| Some((*uids.get(entity)?, msg)))
+                    .collect(),
+                server_events,
+                events,
+                entity_sync,
+                comp_sync,
+                physics,
+                controllers,
+                new_chunks: terrain_changes
+                    .new_chunks
+                    .iter()
+                    .filter_map(chunk)
+                    .collect(),
+                removed_chunks: terrain_changes.removed_chunks.iter().copied().collect(),
+                block_changes: terrain_changes
+                    .modified_blocks
+                    .iter()
+                    .map(|(pos, block)| (*pos, *block))
+                    .collect(),
+            })
+        } else {
+            // Start a new replay with a snapshot of the world in place of this tick
+            if let Some(mut segment) = recorder.segment.take() {
+                if let Err(error) = segment.writer.flush() {
+                    warn!(?error, "Failed to finish replay");
+                }
+            }
+            recorder.start_segment().and_then(|mut writer| {
+                writer.write(&ReplaySnapshot {
+                    tick: recorder.tick,
+                    time: time.0,
+                    chunks: terrain
+                        .iter()
+                        .map(|(key, chunk)| (key, TerrainChunk::clone(chunk)))
+                        .collect(),
+                    entities: (&entities, uids)
+                        .join()
+                        .map(|(entity, uid)| {
+                            tracked_storages.create_replay_entity_package(entity, *uid)
+                        })
+                        .collect(),
+                    physics,
+                    controllers,
+                })?;
+                recorder.segment = Some(Segment {
+                    writer,
+                    started: Instant::now(),
+                    known_entities: current_entities,
+                });
+                Ok(())
+            })
+        };
+
+        if let Err(error) = result {
+            warn!(
+                ?error,
+                "Failed to record replay, recording has been stopped"
+            );
+            recorder.enabled = false;
+            recorder.segment = None;
+        }
+    }
+}
@@ -137,6 +137,25 @@ macro_rules! trackers {
 
                 comp_sync_package
             }
+
+            /// Create a package containing every synced component of this entity, including
+            /// those that are only synced for the client's entity. Used for recording replays.
+            pub fn create_replay_entity_package(
+                &self,
+                entity: EcsEntity,
+                uid: Uid,
+            ) -> EntityPackage<EcsCompPacket> {
+                let mut comps = Vec::new();
+                $(
+                    self
+                        .$component_name
+                        .get(entity)
+                        .cloned()
+                        .map(|c| comps.push(c.into()));
+                )*
+
+                EntityPackage { uid: uid.0, comps }
+            }
         }
 
         /// Contains an [`UpdateTracker`] for every synced component (that uses this method of
@@ -267,6 +286,28 @@ macro_rules! trackers {
                 comp_sync_package
             }
 
+            /// Create a [`EntitySyncPackage`] and a [`CompSyncPackage`] with the updates of
+            /// every synced component of every entity. Used for recording replays.
+            pub fn create_replay_sync_packages(
+                &self,
+                comps: &TrackedStorages,
+                deleted_entities: Vec<u64>,
+            ) -> (EntitySyncPackage, CompSyncPackage<EcsCompPacket>) {
+                let entity_sync_package =
+                    EntitySyncPackage::new(&comps.uid, &self.uid, &comps.uid, deleted_entities);
+                let mut comp_sync_package = CompSyncPackage::new();
+
+                $(
+                    comp_sync_package.add_component_updates(
+                        &comps.uid,
+                        &self.$component_name,
+                        &comps.$component_name,
+                        &comps.uid,
+                    );
+                )*
+
+                (entity_sync_package, comp_sync_package)
+            }
         }
     }
 }
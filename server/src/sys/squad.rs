@@ -0,0 +1,91 @@
+use common::{
+    comp::{Agent, Health},
+    resources::Time,
+    uid::Uid,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use server_agent::{
+    consts::{SQUAD_DISBAND_DELAY, SQUAD_RETREAT_MORALE},
+    squad::Squads,
+};
+use specs::{Entities, Join, Read, ReadStorage, Write};
+
+/// This system keeps track of who is in each squad of NPCs, who the squad is
+/// fighting and whether the squad has taken enough of a beating to retreat.
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, Time>,
+        Write<'a, Squads>,
+        ReadStorage<'a, Agent>,
+        ReadStorage<'a, Health>,
+        ReadStorage<'a, Uid>,
+    );
+
+    const NAME: &'static str = "squad";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (entities, time, mut squads, agents, healths, uids): Self::SystemData,
+    ) {
+        for squad in squads.iter_mut() {
+            squad.members.clear();
+            squad.morale = 0.0;
+        }
+
+        for (entity, agent, health) in (&entities, &agents, healths.maybe()).join() {
+            if health.map_or(false, |health| health.is_dead) {
+                continue;
+            }
+            if let Some(squad) = agent.squad.and_then(|id| squads.get_mut(id)) {
+                squad.members.push(entity);
+                squad.morale += health.map_or(1.0, |health| health.fraction());
+            }
+        }
+
+        for squad in squads.iter_mut() {
+            if squad.members.is_empty() {
+                squad.target = None;
+                squad.retreating = false;
+                squad.empty_since.get_or_insert(time.0);
+                continue;
+            }
+            squad.empty_since = None;
+            // Uids are handed out in the order that entities are spawned in, unlike
+            // entity ids which get reused, so this makes the oldest member the leader
+            squad
+                .members
+                .sort_by_key(|member| uids.get(*member).copied());
+            squad.size = squad.size.max(squad.members.len());
+            // Fallen members count as having no health left
+            squad.morale /= squad.size as f32;
+
+            // Fight whoever the leader is fighting, or else whoever any member is
+            let hostile_target = |member| {
+                agents
+                    .get(member)
+                    .and_then(|agent| agent.target)
+                    .filter(|target| target.hostile && target.aggro_on)
+                    .map(|target| target.target)
+            };
+            squad.target = squad
+                .leader()
+                .and_then(hostile_target)
+                .or_else(|| squad.members.iter().copied().find_map(hostile_target));
+
+            // Once a squad starts retreating it keeps going until the fight is over
+            squad.retreating =
+                squad.target.is_some() && (squad.retreating || squad.morale < SQUAD_RETREAT_MORALE);
+        }
+
+        squads.retain(|squad| {
+            squad
+                .empty_since
+                .map_or(true, |since| time.0 - since < SQUAD_DISBAND_DELAY)
+        });
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(not(feature = "worldgen"))]
+use crate::test_world::IndexOwned;
+use crate::{client::Client, sys::SysScheduler};
+use common::{
+    comp::{achievement::default_achievements, Achievements, Last, Pos, Statistics, Vel},
+    link::Is,
+    mounting::Rider,
+    resources::DeltaTime,
+    trade::SiteId,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use common_net::msg::{Notification, ServerGeneral};
+use specs::{Entities, Join, Read, ReadExpect, ReadStorage, Write, WriteStorage};
+use vek::*;
+#[cfg(feature = "worldgen")]
+use world::IndexOwned;
+
+/// This system collects the statistics of characters that aren't fed by
+/// server events, such as the distance that they have travelled, and unlocks
+/// achievements once their requirements are met.
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, DeltaTime>,
+        ReadExpect<'a, IndexOwned>,
+        ReadStorage<'a, Pos>,
+        ReadStorage<'a, Vel>,
+        ReadStorage<'a, Is<Rider>>,
+        ReadStorage<'a, Client>,
+        WriteStorage<'a, Statistics>,
+        WriteStorage<'a, Achievements>,
+        WriteStorage<'a, Last<Statistics>>,
+        Write<'a, SysScheduler<Self>>,
+    );
+
+    const NAME: &'static str = "statistics";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (
+            entities,
+            dt,
+            index,
+            positions,
+            velocities,
+            is_riders,
+            clients,
+            mut statistics,
+            mut achievements,
+            mut last_statistics,
+            mut scheduler,
+        ): Self::SystemData,
+    ) {
+        // Teleporting doesn't change the velocity of an entity, so it isn't counted
+        // as travelled distance. Neither is riding a mount.
+        for (vel, statistics, _) in (&velocities, &mut statistics, !&is_riders).join() {
+            statistics.distance_travelled += f64::from(vel.0.magnitude() * dt.0);
+        }
+
+        if scheduler.should_run() {
+            let achievement_defs = default_achievements().read();
+            for (entity, pos, statistics, achievements, client) in (
+                &entities,
+                &positions,
+                &mut statistics,
+                &mut achievements,
+                &clients,
+            )
+                .join()
+            {
+                if let Some(site) = site_at(&index, pos.0.xy().as_()) {
+                    statistics.sites_visited.insert(site);
+                }
+
+                let mut unlocked_any = false;
+                for achievement in &achievement_defs.0 {
+                    if !achievements.has_unlocked(&achievement.id)
+                        && achievement.requirement.is_met(statistics)
+                        && achievements.unlock(achievement.id.clone())
+                    {
+                        unlocked_any = true;
+                        client.send_fallible(ServerGeneral::Notification(
+                            Notification::AchievementUnlocked(achievement.id.clone()),
+                        ));
+                    }
+                }
+                if unlocked_any {
+                    client.send_fallible(ServerGeneral::AchievementsUpdate(achievements.clone()));
+                }
+                // Only tell the client about statistics that changed since they were last sent
+                if last_statistics
+                    .get(entity)
+                    .map_or(true, |last| last.0 != *statistics)
+                {
+                    client.send_fallible(ServerGeneral::StatisticsUpdate(statistics.clone()));
+                    let _ = last_statistics.insert(entity, Last(statistics.clone()));
+                }
+            }
+        }
+    }
+}
+
+/// Finds the site that the given position is within, if any
+#[cfg(feature = "worldgen")]
+fn site_at(index: &IndexOwned, pos: Vec2<i32>) -> Option<SiteId> {
+    index.site_at(pos).map(|id| id.id())
+}
+
+#[cfg(not(feature = "worldgen"))]
+fn site_at(_index: &IndexOwned, _pos: Vec2<i32>) -> Option<SiteId> { None }
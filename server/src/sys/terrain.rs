@@ -7,7 +7,8 @@ use world::{IndexOwned, World};
 
 use crate::{
     chunk_generator::ChunkGenerator, chunk_serialize::ChunkSendEntry, client::Client,
-    presence::RepositionOnChunkLoad, rtsim, settings::Settings, ChunkRequest, Tick,
+    presence::RepositionOnChunkLoad, rtsim, settings::Settings, sys::farming::PlantedCrops,
+    ChunkRequest, Tick,
 };
 use common::{
     calendar::Calendar,
@@ -31,6 +32,7 @@ use comp::Behavior;
 use core::cmp::Reverse;
 use itertools::Itertools;
 use rayon::{iter::Either, prelude::*};
+use server_agent::squad::Squads;
 use specs::{
     storage::GenericReadStorage, Entities, Entity, Join, ParJoin, Read, ReadExpect, ReadStorage,
     Write, WriteExpect, WriteStorage,
@@ -76,6 +78,8 @@ impl<'a> System<'a> for Sys {
         Write<'a, Vec<ChunkRequest>>,
         RtSimData<'a>,
         TerrainPersistenceData<'a>,
+        Write<'a, PlantedCrops>,
+        Write<'a, Squads>,
         WriteStorage<'a, Pos>,
         ReadStorage<'a, Presence>,
         ReadStorage<'a, Client>,
@@ -108,6 +112,8 @@ impl<'a> System<'a> for Sys {
             mut chunk_requests,
             mut rtsim,
             mut _terrain_persistence,
+            mut _planted_crops,
+            mut squads,
             mut positions,
             presences,
             clients,
@@ -162,6 +168,8 @@ impl<'a> System<'a> for Sys {
             #[cfg(feature = "persistent_world")]
             if let Some(terrain_persistence) = _terrain_persistence.as_mut() {
                 terrain_persistence.apply_changes(key, &mut chunk);
+                // Keep growing the crops that were planted in this chunk
+                _planted_crops.extend(terrain_persistence.changed_blocks(key));
             }
 
             // Arcify the chunk
@@ -208,7 +216,11 @@ impl<'a> System<'a> for Sys {
                         alignment,
                         scale,
                         loot,
+                        is_boss,
+                        encounter,
+                        squad,
                     } => {
+                        let squad = squad.map(|origin| squads.join(origin));
                         server_emitter.emit(ServerEvent::CreateNpc {
                             pos,
                             npc: NpcBuilder::new(stats, body, alignment)
@@ -216,10 +228,12 @@ impl<'a> System<'a> for Sys {
                                 .with_health(health)
                                 .with_poise(poise)
                                 .with_inventory(inventory)
-                                .with_agent(agent)
+                                .with_agent(agent.map(|agent| agent.with_squad(squad)))
                                 .with_scale(scale)
                                 .with_anchor(comp::Anchor::Chunk(key))
-                                .with_loot(loot),
+                                .with_loot(loot)
+                                .with_boss(is_boss)
+                                .with_encounter(encounter),
                         });
                     },
                 }
@@ -413,6 +427,9 @@ pub enum NpcData {
         alignment: comp::Alignment,
         scale: comp::Scale,
         loot: LootSpec<String>,
+        is_boss: bool,
+        encounter: Option<comp::Encounter>,
+        squad: Option<Vec2<i32>>,
     },
     Waypoint(Vec3<f32>),
 }
@@ -427,6 +444,10 @@ impl NpcData {
             alignment,
             no_flee,
             idle_wander_factor,
+            tactic,
+            is_boss,
+            encounter,
+            squad,
             // stats
             body,
             name,
@@ -522,6 +543,7 @@ impl NpcData {
             agent
                 .with_no_flee_if(matches!(agent_mark, Some(agent::Mark::Guard)) || no_flee)
                 .with_idle_wander_factor(idle_wander_factor)
+                .with_tactic(tactic.as_deref())
         });
 
         let agent = if matches!(alignment, comp::Alignment::Enemy)
@@ -544,6 +566,9 @@ impl NpcData {
             alignment,
             scale: comp::Scale(scale),
             loot,
+            is_boss,
+            encounter: encounter.as_deref().map(comp::Encounter::new),
+            squad,
         }
     }
 }
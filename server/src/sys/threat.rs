@@ -0,0 +1,57 @@
+use common::{
+    comp::{Agent, Health, ThreatList},
+    resources::{DeltaTime, Time},
+    uid::UidAllocator,
+};
+use common_ecs::{Job, Origin, Phase, System};
+use specs::{saveload::MarkerAllocator, Entities, Join, Read, ReadStorage, WriteStorage};
+
+/// This system lets the threat built up against agents fade over time, forgets
+/// the threat of entities that have died or left, and keeps the threat lists
+/// that are shown to players up to date.
+#[derive(Default)]
+pub struct Sys;
+impl<'a> System<'a> for Sys {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, DeltaTime>,
+        Read<'a, Time>,
+        Read<'a, UidAllocator>,
+        ReadStorage<'a, Health>,
+        WriteStorage<'a, Agent>,
+        WriteStorage<'a, ThreatList>,
+    );
+
+    const NAME: &'static str = "threat";
+    const ORIGIN: Origin = Origin::Server;
+    const PHASE: Phase = Phase::Create;
+
+    fn run(
+        _job: &mut Job<Self>,
+        (entities, dt, time, uid_allocator, healths, mut agents, mut threat_lists): Self::SystemData,
+    ) {
+        for (entity, agent) in (&entities, &mut agents).join() {
+            let threat = &mut agent.threat;
+            threat.decay(dt.0, time.0);
+            threat.retain(|uid| {
+                uid_allocator
+                    .retrieve_entity_internal(uid.into())
+                    .map_or(false, |entity| {
+                        healths.get(entity).map_or(true, |health| !health.is_dead)
+                    })
+            });
+
+            // Only write to the list when it changes, since it's synced to clients
+            if threat.is_empty() {
+                if threat_lists.contains(entity) {
+                    threat_lists.remove(entity);
+                }
+            } else {
+                let list = threat.to_list(time.0);
+                if threat_lists.get(entity) != Some(&list) {
+                    let _ = threat_lists.insert(entity, list);
+                }
+            }
+        }
+    }
+}
@@ -85,6 +85,18 @@ impl TerrainPersistence {
         self.cached_chunks.limiter_mut().remove_blocks(removed);
     }
 
+    /// Get the persisted blocks of a chunk, along with their world positions.
+    pub fn changed_blocks(
+        &mut self,
+        key: Vec2<i32>,
+    ) -> impl Iterator<Item = (Vec3<i32>, Block)> + '_ {
+        let chunk_pos = key * TerrainChunk::RECT_SIZE.map(|e| e as i32);
+        self.load_chunk(key)
+            .chunk
+            .blocks()
+            .map(move |(rpos, block)| (chunk_pos + rpos, block))
+    }
+
     /// Maintain terrain persistence (writing changes changes back to
     /// filesystem, etc.)
     pub fn maintain(&mut self) {
@@ -1,6 +1,6 @@
 use common::{
     calendar::Calendar,
-    generation::{ChunkSupplement, EntityInfo},
+    generation::{ChunkSupplement, DungeonLayout, EntityInfo},
     resources::TimeOfDay,
     terrain::{
         Block, BlockKind, MapSizeLg, SpriteKind, TerrainChunk, TerrainChunkMeta, TerrainChunkSize,
@@ -44,6 +44,18 @@ impl World {
 
     pub fn generate_oob_chunk(&self) -> TerrainChunk { TerrainChunk::water(0) }
 
+    pub fn dungeon_layouts(&self, _index: IndexRef) -> Vec<DungeonLayout> { Vec::new() }
+
+    pub fn dungeon_entities(
+        &self,
+        _index: IndexRef,
+        _origin: Vec2<i32>,
+        _z_offset: i32,
+        _rng: &mut impl Rng,
+    ) -> Vec<EntityInfo> {
+        Vec::new()
+    }
+
     pub fn generate_chunk(
         &self,
         _index: IndexRef,
@@ -120,6 +120,33 @@ impl Animation for ConsumeAnimation {
                 next.shoulder_r.position =
                     Vec3::new(s_a.shoulder.0, s_a.shoulder.1, s_a.shoulder.2);
             },
+            Some(ItemUseKind::Consumable(ConsumableKind::Scroll)) => {
+                let (move1, move2, move3) = match stage_section {
+                    Some(StageSection::Buildup) => (anim_time, 0.0, 0.0),
+                    Some(StageSection::Action) => (1.0, (anim_time * 2.0).sin(), 0.0),
+                    Some(StageSection::Recover) => (1.0, 1.0, anim_time.powf(0.25)),
+                    _ => (0.0, 0.0, 0.0),
+                };
+                let pullback = 1.0 - move3;
+                let move2 = move2 * pullback;
+                let move1 = move1 * pullback;
+                next.head.orientation =
+                    Quaternion::rotation_x(move1 * -0.4) * Quaternion::rotation_z(move2 * 0.1);
+
+                next.hand_l.position = Vec3::new(
+                    -s_a.hand.0 + move1 * 3.0,
+                    s_a.hand.1 + move1 * 6.0,
+                    s_a.hand.2 + move1 * 5.0,
+                );
+                next.hand_l.orientation = Quaternion::rotation_x(move1 * 1.4);
+
+                next.hand_r.position = Vec3::new(
+                    s_a.hand.0 + move1 * -3.0,
+                    s_a.hand.1 + move1 * 6.0,
+                    s_a.hand.2 + move1 * 5.0,
+                );
+                next.hand_r.orientation = Quaternion::rotation_x(move1 * 1.4);
+            },
             _ => {},
         }
 
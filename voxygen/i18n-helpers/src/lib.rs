@@ -53,7 +53,7 @@ pub fn localize_chat_message(
             .into_owned(),
         ChatType::CommandError => localization.get_content(msg.content()),
         ChatType::CommandInfo => localization.get_content(msg.content()),
-        ChatType::FactionMeta(_) => localization.get_content(msg.content()),
+        ChatType::GuildMeta(_) => localization.get_content(msg.content()),
         ChatType::GroupMeta(_) => localization.get_content(msg.content()),
         ChatType::Tell(from, to) => {
             let from_alias = name_format(from);
@@ -73,7 +73,7 @@ pub fn localize_chat_message(
         },
         ChatType::Say(uid) => message_format(uid, msg.content(), None),
         ChatType::Group(uid, s) => message_format(uid, msg.content(), Some(s)),
-        ChatType::Faction(uid, s) => message_format(uid, msg.content(), Some(s)),
+        ChatType::Guild(uid, s) => message_format(uid, msg.content(), Some(s)),
         ChatType::Region(uid) => message_format(uid, msg.content(), None),
         ChatType::World(uid) => message_format(uid, msg.content(), None),
         // NPCs can't talk. Should be filtered by hud/mod.rs for voxygen and
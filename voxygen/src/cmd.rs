@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{path::Path, str::FromStr, time::SystemTime};
 
 use crate::{
     render::ExperimentalShader, session::settings_change::change_render_mode, GlobalState,
@@ -14,6 +14,8 @@ pub enum ClientChatCommand {
     ExperimentalShader,
     Help,
     Mute,
+    Record,
+    Replay,
     Unmute,
 }
 
@@ -44,6 +46,28 @@ impl ClientChatCommand {
                 "Mutes chat messages from a player.",
                 None,
             ),
+            ClientChatCommand::Record => cmd(
+                vec![],
+                "Starts or stops recording the session, which can then be watched again from the \
+                 main menu.",
+                None,
+            ),
+            ClientChatCommand::Replay => cmd(
+                vec![
+                    Enum(
+                        "action",
+                        ["pause", "resume", "speed", "seek"]
+                            .iter()
+                            .map(|action| action.to_string())
+                            .collect(),
+                        Optional,
+                    ),
+                    Float("value", 1.0, Optional),
+                ],
+                "Controls the recording being watched. Speed takes a multiplier and seek the \
+                 number of seconds since the start of the recording.",
+                None,
+            ),
             ClientChatCommand::Unmute => cmd(
                 vec![PlayerName(Required)],
                 "Unmutes a player muted with the 'mute' command.",
@@ -57,6 +81,8 @@ impl ClientChatCommand {
             ClientChatCommand::ExperimentalShader => "experimental_shader",
             ClientChatCommand::Help => "help",
             ClientChatCommand::Mute => "mute",
+            ClientChatCommand::Record => "record",
+            ClientChatCommand::Replay => "replay",
             ClientChatCommand::Unmute => "unmute",
         }
     }
@@ -205,6 +231,8 @@ fn run_client_command(
         ClientChatCommand::ExperimentalShader => handle_experimental_shader,
         ClientChatCommand::Help => handle_help,
         ClientChatCommand::Mute => handle_mute,
+        ClientChatCommand::Record => handle_record,
+        ClientChatCommand::Replay => handle_replay,
         ClientChatCommand::Unmute => handle_unmute,
     };
 
@@ -212,7 +240,7 @@ fn run_client_command(
 }
 
 fn handle_help(
-    client: &Client,
+    client: &mut Client,
     _global_state: &mut GlobalState,
     args: Vec<String>,
 ) -> Result<String, String> {
@@ -249,7 +277,7 @@ fn handle_help(
 }
 
 fn handle_mute(
-    client: &Client,
+    client: &mut Client,
     global_state: &mut GlobalState,
     args: Vec<String>,
 ) -> Result<String, String> {
@@ -282,7 +310,7 @@ fn handle_mute(
 }
 
 fn handle_unmute(
-    client: &Client,
+    client: &mut Client,
     global_state: &mut GlobalState,
     args: Vec<String>,
 ) -> Result<String, String> {
@@ -312,8 +340,80 @@ fn handle_unmute(
     }
 }
 
+fn handle_record(
+    client: &mut Client,
+    global_state: &mut GlobalState,
+    _args: Vec<String>,
+) -> Result<String, String> {
+    if let Some(path) = client.recording().map(Path::to_path_buf) {
+        client
+            .stop_recording()
+            .map_err(|e| format!("Failed to finish the recording: {:?}", e))?;
+        Ok(format!("Stopped recording, saved to {}.", path.display()))
+    } else {
+        let dir = global_state.recordings_dir();
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Couldn't create the folder for recordings: {}", e))?;
+        let path = dir.join(format!(
+            "recording_{}.bin",
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map(|d| d.as_millis())
+                .unwrap_or(0)
+        ));
+        client
+            .start_recording(path.clone())
+            .map_err(|e| format!("Failed to start recording: {:?}", e))?;
+        Ok(format!("Started recording to {}.", path.display()))
+    }
+}
+
+fn handle_replay(
+    client: &mut Client,
+    _global_state: &mut GlobalState,
+    args: Vec<String>,
+) -> Result<String, String> {
+    let playback = client
+        .playback_mut()
+        .ok_or("You can only use this while watching a recording.")?;
+    match parse_cmd_args!(args, String, f32) {
+        (Some(action), value) => match (action.as_str(), value) {
+            ("pause", _) => {
+                playback.set_paused(true);
+                Ok("Paused the recording.".to_string())
+            },
+            ("resume", _) => {
+                playback.set_paused(false);
+                Ok("Resumed the recording.".to_string())
+            },
+            ("speed", Some(speed)) => {
+                playback.set_speed(speed);
+                Ok(format!(
+                    "Playing the recording at {}x speed.",
+                    playback.speed()
+                ))
+            },
+            ("seek", Some(secs)) => {
+                let time = playback.start() + secs as f64;
+                client
+                    .seek_playback(time)
+                    .map_err(|e| format!("Failed to seek: {:?}", e))?;
+                Ok(format!("Jumped to {:.0}s.", secs))
+            },
+            _ => Err("You must specify a value to change the speed or seek.".to_string()),
+        },
+        (None, _) => Ok(format!(
+            "Watching {:.0}s of {:.0}s at {}x speed{}.",
+            playback.time() - playback.start(),
+            playback.end() - playback.start(),
+            playback.speed(),
+            if playback.paused() { ", paused" } else { "" }
+        )),
+    }
+}
+
 fn handle_experimental_shader(
-    _client: &Client,
+    _client: &mut Client,
     global_state: &mut GlobalState,
     args: Vec<String>,
 ) -> Result<String, String> {
@@ -759,7 +759,7 @@ fn render_chat_mode(chat_mode: &ChatMode, imgs: &Imgs) -> (Color, conrod_core::i
         ChatMode::World => (WORLD_COLOR, imgs.chat_world_small),
         ChatMode::Say => (SAY_COLOR, imgs.chat_say_small),
         ChatMode::Region => (REGION_COLOR, imgs.chat_region_small),
-        ChatMode::Faction(_) => (FACTION_COLOR, imgs.chat_faction_small),
+        ChatMode::Guild(_) => (FACTION_COLOR, imgs.chat_faction_small),
         ChatMode::Group(_) => (GROUP_COLOR, imgs.chat_group_small),
         ChatMode::Tell(_) => (TELL_COLOR, imgs.chat_tell_small),
     }
@@ -773,12 +773,12 @@ fn render_chat_line(chat_type: &ChatType<String>, imgs: &Imgs) -> (Color, conrod
         ChatType::CommandError => (ERROR_COLOR, imgs.chat_command_error_small),
         ChatType::CommandInfo => (INFO_COLOR, imgs.chat_command_info_small),
         ChatType::GroupMeta(_) => (GROUP_COLOR, imgs.chat_group_small),
-        ChatType::FactionMeta(_) => (FACTION_COLOR, imgs.chat_faction_small),
+        ChatType::GuildMeta(_) => (FACTION_COLOR, imgs.chat_faction_small),
         ChatType::Kill(_, _) => (KILL_COLOR, imgs.chat_kill_small),
         ChatType::Tell(_from, _to) => (TELL_COLOR, imgs.chat_tell_small),
         ChatType::Say(_uid) => (SAY_COLOR, imgs.chat_say_small),
         ChatType::Group(_uid, _s) => (GROUP_COLOR, imgs.chat_group_small),
-        ChatType::Faction(_uid, _s) => (FACTION_COLOR, imgs.chat_faction_small),
+        ChatType::Guild(_uid, _s) => (FACTION_COLOR, imgs.chat_faction_small),
         ChatType::Region(_uid) => (REGION_COLOR, imgs.chat_region_small),
         ChatType::World(_uid) => (WORLD_COLOR, imgs.chat_world_small),
         ChatType::Npc(_uid) => panic!("NPCs can't talk!"), // Should be filtered by hud/mod.rs
@@ -592,6 +592,8 @@ impl<'a> Widget for Crafting<'a> {
             .client
             .recipe_book()
             .iter()
+            // Recipes that have not been learned yet are not shown
+            .filter(|(name, _)| self.client.known_recipes().knows(name))
             .filter(|(_, recipe)| match search_filter {
                 SearchFilter::None => {
                     let output_name = recipe.output.0.name().to_lowercase();
@@ -24,6 +24,7 @@ use common::{
     comp::{
         self,
         ability::{Ability, ActiveAbilities, AuxiliaryAbility, MAX_ABILITIES},
+        achievement::default_achievements,
         inventory::{
             item::{
                 item_key::ItemKey,
@@ -36,6 +37,7 @@ use common::{
             self, AxeSkill, BowSkill, ClimbSkill, GeneralSkill, HammerSkill, MiningSkill,
             RollSkill, SceptreSkill, Skill, StaffSkill, SwimSkill, SwordSkill, SKILL_MODIFIERS,
         },
+        reputation::Standing,
         skillset::{SkillGroupKind, SkillSet},
         Body, Energy, Health, Inventory, Poise,
     },
@@ -217,13 +219,22 @@ widget_ids! {
         // Stats
         stat_names[],
         stat_values[],
+        // Reputation
+        reputation_empty,
+        reputation_names[],
+        reputation_values[],
+        // Achievements
+        statistic_names[],
+        statistic_values[],
+        achievement_names[],
+        achievement_values[],
     }
 }
 
 #[derive(WidgetCommon)]
 pub struct Diary<'a> {
     show: &'a Show,
-    _client: &'a Client,
+    client: &'a Client,
     global_state: &'a GlobalState,
     skill_set: &'a SkillSet,
     active_abilities: &'a ActiveAbilities,
@@ -269,7 +280,7 @@ impl Default for DiaryShow {
 impl<'a> Diary<'a> {
     pub fn new(
         show: &'a Show,
-        _client: &'a Client,
+        client: &'a Client,
         global_state: &'a GlobalState,
         skill_set: &'a SkillSet,
         active_abilities: &'a ActiveAbilities,
@@ -291,7 +302,7 @@ impl<'a> Diary<'a> {
     ) -> Self {
         Self {
             show,
-            _client,
+            client,
             global_state,
             skill_set,
             active_abilities,
@@ -334,8 +345,20 @@ const TREES: [&str; 8] = [
 ];
 
 // Possible future sections: Bestiary ("Pokedex" of fought enemies), Weapon and
-// armour catalogue, Achievements...
-const SECTIONS: [&str; 3] = ["Skill-Trees", "Abilities", "Stats"];
+// armour catalogue...
+const SECTIONS: [&str; 5] = [
+    "Skill-Trees",
+    "Abilities",
+    "Stats",
+    "Reputation",
+    "Achievements",
+];
+
+/// The maximum number of standings shown in the reputation section.
+const MAX_STANDINGS: usize = 16;
+
+/// The maximum number of achievements shown in the achievements section.
+const MAX_ACHIEVEMENTS: usize = 16;
 
 pub enum Event {
     Close,
@@ -350,6 +373,8 @@ pub enum DiarySection {
     SkillTrees,
     AbilitySelection,
     Stats,
+    Reputation,
+    Achievements,
 }
 
 pub struct DiaryState {
@@ -469,6 +494,8 @@ impl<'a> Widget for Diary<'a> {
                 "Abilities" => "List of your currently available abilities.",
                 "Skill-Trees" => "",
                 "Stats" => "",
+                "Reputation" => "How the people of the world regard you.",
+                "Achievements" => "Your deeds so far, and the feats yet to be achieved.",
                 _ => "",
             };
             let btn_img = {
@@ -476,6 +503,8 @@ impl<'a> Widget for Diary<'a> {
                     "Abilities" => self.imgs.spellbook_ico,
                     "Skill-Trees" => self.imgs.skilltree_ico,
                     "Stats" => self.imgs.stats_ico,
+                    "Reputation" => self.imgs.quest_ico,
+                    "Achievements" => self.imgs.stats_ico,
                     _ => self.imgs.nothing,
                 };
                 if i == 0 {
@@ -1348,6 +1377,203 @@ impl<'a> Widget for Diary<'a> {
                     number.set(state.ids.stat_values[i], ui);
                 }
 
+                events
+            },
+            DiarySection::Reputation => {
+                let reputation = self.client.reputation();
+
+                // Background Art
+                Image::new(self.imgs.book_bg)
+                    .w_h(299.0 * 4.0, 184.0 * 4.0)
+                    .mid_top_with_margin_on(state.ids.content_align, 4.0)
+                    .set(state.ids.spellbook_art, ui);
+
+                let standings = reputation
+                    .sites
+                    .iter()
+                    .map(|standing| (standing.name.clone(), standing.value))
+                    .chain(reputation.factions.iter().map(|standing| {
+                        let name = self.localized_strings.get_msg_ctx(
+                            "hud-diary-reputation-faction",
+                            &i18n::fluent_args! {
+                                "capital" => standing.capital_name.as_deref().unwrap_or("?"),
+                            },
+                        );
+                        (name.into_owned(), standing.value)
+                    }))
+                    .take(MAX_STANDINGS)
+                    .collect::<Vec<_>>();
+
+                if standings.is_empty() {
+                    Text::new(&self.localized_strings.get_msg("hud-diary-reputation-none"))
+                        .top_left_with_margins_on(state.ids.spellbook_art, 20.0, 20.0)
+                        .font_id(self.fonts.cyri.conrod_id)
+                        .font_size(self.fonts.cyri.scale(29))
+                        .color(BLACK)
+                        .set(state.ids.reputation_empty, ui);
+                }
+
+                state.update(|s| {
+                    s.ids
+                        .reputation_names
+                        .resize(standings.len(), &mut ui.widget_id_generator());
+                    s.ids
+                        .reputation_values
+                        .resize(standings.len(), &mut ui.widget_id_generator());
+                });
+                for (i, (name, value)) in standings.iter().enumerate() {
+                    let mut txt = Text::new(name)
+                        .font_id(self.fonts.cyri.conrod_id)
+                        .font_size(self.fonts.cyri.scale(29))
+                        .color(BLACK);
+                    if i == 0 {
+                        txt = txt.top_left_with_margins_on(state.ids.spellbook_art, 20.0, 20.0);
+                    } else {
+                        txt = txt.down_from(state.ids.reputation_names[i - 1], 10.0);
+                    };
+                    txt.set(state.ids.reputation_names[i], ui);
+
+                    let standing = self
+                        .localized_strings
+                        .get_msg(Standing::from_value(*value).i18n_key());
+                    let mut number = Text::new(&standing)
+                        .font_id(self.fonts.cyri.conrod_id)
+                        .font_size(self.fonts.cyri.scale(29))
+                        .color(BLACK);
+                    if i == 0 {
+                        number = number.right_from(state.ids.reputation_names[i], 265.0);
+                    } else {
+                        number = number.down_from(state.ids.reputation_values[i - 1], 10.0);
+                    };
+                    number.set(state.ids.reputation_values[i], ui);
+                }
+
+                events
+            },
+            DiarySection::Achievements => {
+                let statistics = self.client.statistics();
+                let achievements = self.client.achievements();
+
+                // Background Art
+                Image::new(self.imgs.book_bg)
+                    .w_h(299.0 * 4.0, 184.0 * 4.0)
+                    .mid_top_with_margin_on(state.ids.content_align, 4.0)
+                    .set(state.ids.spellbook_art, ui);
+
+                // Statistics on the left page
+                let stats = [
+                    (
+                        "hud-diary-statistics-kills",
+                        statistics.total_kills().to_string(),
+                    ),
+                    ("hud-diary-statistics-deaths", statistics.deaths.to_string()),
+                    (
+                        "hud-diary-statistics-distance_travelled",
+                        format!("{:.1} km", statistics.distance_travelled / 1000.0),
+                    ),
+                    (
+                        "hud-diary-statistics-items_crafted",
+                        statistics.items_crafted.to_string(),
+                    ),
+                    (
+                        "hud-diary-statistics-sites_visited",
+                        statistics.sites_visited.len().to_string(),
+                    ),
+                    (
+                        "hud-diary-statistics-dungeons_cleared",
+                        statistics.dungeons_cleared.to_string(),
+                    ),
+                ];
+
+                state.update(|s| {
+                    s.ids
+                        .statistic_names
+                        .resize(stats.len(), &mut ui.widget_id_generator());
+                    s.ids
+                        .statistic_values
+                        .resize(stats.len(), &mut ui.widget_id_generator());
+                });
+                for (i, (key, value)) in stats.iter().enumerate() {
+                    let name = self.localized_strings.get_msg(key);
+                    let mut txt = Text::new(&name)
+                        .font_id(self.fonts.cyri.conrod_id)
+                        .font_size(self.fonts.cyri.scale(29))
+                        .color(BLACK);
+                    if i == 0 {
+                        txt = txt.top_left_with_margins_on(state.ids.spellbook_art, 20.0, 20.0);
+                    } else {
+                        txt = txt.down_from(state.ids.statistic_names[i - 1], 10.0);
+                    };
+                    txt.set(state.ids.statistic_names[i], ui);
+
+                    let mut number = Text::new(value)
+                        .font_id(self.fonts.cyri.conrod_id)
+                        .font_size(self.fonts.cyri.scale(29))
+                        .color(BLACK);
+                    if i == 0 {
+                        number =
+                            number.top_left_with_margins_on(state.ids.spellbook_art, 20.0, 400.0);
+                    } else {
+                        number = number.down_from(state.ids.statistic_values[i - 1], 10.0);
+                    };
+                    number.set(state.ids.statistic_values[i], ui);
+                }
+
+                // Achievements, along with the progress towards those that are still
+                // locked, on the right page
+                let achievement_defs = default_achievements().read();
+                let achievement_rows = achievement_defs
+                    .0
+                    .iter()
+                    .take(MAX_ACHIEVEMENTS)
+                    .map(|achievement| {
+                        let title = self
+                            .localized_strings
+                            .get_msg(&format!("hud-achievement-{}", achievement.id));
+                        let progress = if achievements.has_unlocked(&achievement.id) {
+                            self.localized_strings
+                                .get_msg("hud-diary-achievement-unlocked")
+                        } else {
+                            let (current, required) = achievement.requirement.progress(statistics);
+                            Cow::Owned(format!("{:.0} / {:.0}", current.min(required), required))
+                        };
+                        (title, progress)
+                    })
+                    .collect::<Vec<_>>();
+
+                state.update(|s| {
+                    s.ids
+                        .achievement_names
+                        .resize(achievement_rows.len(), &mut ui.widget_id_generator());
+                    s.ids
+                        .achievement_values
+                        .resize(achievement_rows.len(), &mut ui.widget_id_generator());
+                });
+                for (i, (title, progress)) in achievement_rows.iter().enumerate() {
+                    let mut txt = Text::new(title)
+                        .font_id(self.fonts.cyri.conrod_id)
+                        .font_size(self.fonts.cyri.scale(29))
+                        .color(BLACK);
+                    if i == 0 {
+                        txt = txt.top_left_with_margins_on(state.ids.spellbook_art, 20.0, 620.0);
+                    } else {
+                        txt = txt.down_from(state.ids.achievement_names[i - 1], 10.0);
+                    };
+                    txt.set(state.ids.achievement_names[i], ui);
+
+                    let mut number = Text::new(progress)
+                        .font_id(self.fonts.cyri.conrod_id)
+                        .font_size(self.fonts.cyri.scale(29))
+                        .color(BLACK);
+                    if i == 0 {
+                        number =
+                            number.top_left_with_margins_on(state.ids.spellbook_art, 20.0, 1000.0);
+                    } else {
+                        number = number.down_from(state.ids.achievement_values[i - 1], 10.0);
+                    };
+                    number.set(state.ids.achievement_values[i], ui);
+                }
+
                 events
             },
         }
@@ -1373,6 +1599,8 @@ fn section_from_str(string: &str) -> Option<DiarySection> {
         "Abilities" => Some(DiarySection::AbilitySelection),
         "Skill-Trees" => Some(DiarySection::SkillTrees),
         "Stats" => Some(DiarySection::Stats),
+        "Reputation" => Some(DiarySection::Reputation),
+        "Achievements" => Some(DiarySection::Achievements),
         _ => None,
     }
 }
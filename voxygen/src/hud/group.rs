@@ -27,8 +27,9 @@ use conrod_core::{
     widget::{self, Button, Image, Rectangle, Scrollbar, Text},
     widget_ids, Color, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
 };
+use hashbrown::HashMap;
 use i18n::Localization;
-use specs::{saveload::MarkerAllocator, WorldExt};
+use specs::{saveload::MarkerAllocator, Join, WorldExt};
 
 widget_ids! {
     pub struct Ids {
@@ -60,6 +61,7 @@ widget_ids! {
         dead_txt[],
         health_txt[],
         combat_rating_indicators[],
+        aggro_txt[],
         timeout_bg,
         timeout,
     }
@@ -366,6 +368,13 @@ impl<'a> Widget for Group<'a> {
                         .resize(group_size, &mut ui.widget_id_generator())
                 })
             };
+            if state.ids.aggro_txt.len() < group_size {
+                state.update(|s| {
+                    s.ids
+                        .aggro_txt
+                        .resize(group_size, &mut ui.widget_id_generator())
+                })
+            };
             let client_state = self.client.state();
             let stats = client_state.ecs().read_storage::<Stats>();
             let skill_sets = client_state.ecs().read_storage::<common::comp::SkillSet>();
@@ -377,6 +386,17 @@ impl<'a> Widget for Group<'a> {
             let bodies = client_state.ecs().read_storage::<common::comp::Body>();
             let poises = client_state.ecs().read_storage::<common::comp::Poise>();
             let stances = client_state.ecs().read_storage::<common::comp::Stance>();
+            // How many enemies are focused on each entity
+            let mut aggro_counts = HashMap::<Uid, usize>::new();
+            for threat_list in client_state
+                .ecs()
+                .read_storage::<common::comp::ThreatList>()
+                .join()
+            {
+                if let Some(top) = threat_list.top() {
+                    *aggro_counts.entry(top).or_default() += 1;
+                }
+            }
 
             // Keep track of the total number of widget ids we are using for buffs
             let mut total_buff_count = 0;
@@ -499,6 +519,20 @@ impl<'a> Widget for Group<'a> {
                         .top_left_with_margins_on(state.ids.member_panels_frame[i], -20.0, 2.0)
                         .color(Some(indicator_col))
                         .set(state.ids.combat_rating_indicators[i], ui);
+                    // Number of enemies focused on the member
+                    if let Some(count) = aggro_counts.get(&uid) {
+                        Text::new(&self.localized_strings.get_msg_ctx(
+                            "hud-group-aggro",
+                            &i18n::fluent_args! {
+                                "count" => *count,
+                            },
+                        ))
+                        .top_right_with_margins_on(state.ids.member_panels_frame[i], -18.0, 2.0)
+                        .font_size(14)
+                        .font_id(self.fonts.cyri.conrod_id)
+                        .color(ERROR_COLOR)
+                        .set(state.ids.aggro_txt[i], ui);
+                    }
                     // Panel Text
                     Text::new(&char_name)
                      .top_left_with_margins_on(state.ids.member_panels_frame[i], -22.0, 22.0)
@@ -815,6 +849,18 @@ impl<'a> Widget for Group<'a> {
                         "name" => name,
                     },
                 ),
+                InviteKind::Guild => self.localized_strings.get_msg_ctx(
+                    "hud-group-invite_to_join_guild",
+                    &i18n::fluent_args! {
+                        "name" => &name,
+                    },
+                ),
+                InviteKind::Duel => self.localized_strings.get_msg_ctx(
+                    "hud-group-invite_to_duel",
+                    &i18n::fluent_args! {
+                        "name" => &name,
+                    },
+                ),
                 InviteKind::Trade => self.localized_strings.get_msg_ctx(
                     "hud-group-invite_to_trade",
                     &i18n::fluent_args! {
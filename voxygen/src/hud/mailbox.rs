@@ -0,0 +1,437 @@
+use conrod_core::{
+    color,
+    position::Relative,
+    widget::{self, Button, Image, Rectangle, Scrollbar, Text, TextEdit},
+    widget_ids, Color, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use vek::*;
+
+use client::Client;
+use common::comp::{
+    inventory::item::{ItemDesc, MaterialStatManifest, Quality},
+    mail::{MailId, MAX_MAIL_ATTACHMENTS, MAX_MAIL_BODY_LEN},
+    slot::InvSlotId,
+};
+use i18n::Localization;
+
+use crate::ui::{
+    fonts::Fonts,
+    slot::{ContentSize, SlotMaker},
+    ImageFrame, ItemTooltip, ItemTooltipManager, ItemTooltipable,
+};
+
+use super::{
+    img_ids::{Imgs, ImgsRot},
+    item_imgs::ItemImgs,
+    slots::{MailSlot, SlotManager},
+    HudInfo, TEXT_COLOR, UI_HIGHLIGHT_0, UI_MAIN,
+};
+
+const MAIL_HEIGHT: f64 = 60.0;
+
+/// The mail being written, kept between frames.
+#[derive(Default)]
+pub struct MailboxShow {
+    pub recipient: String,
+    pub body: String,
+    pub attachments: Vec<InvSlotId>,
+}
+
+pub enum Event {
+    Close,
+    Claim(MailId),
+    Send {
+        recipient: String,
+        body: String,
+        attachments: Vec<InvSlotId>,
+    },
+}
+
+widget_ids! {
+    pub struct Ids {
+        bg,
+        bg_frame,
+        title_bg,
+        title,
+        close,
+        mail_align,
+        mail_scrollbar,
+        no_mail,
+        mail_frames[],
+        mail_senders[],
+        mail_bodies[],
+        mail_claims[],
+        recipient_label,
+        recipient_bg,
+        recipient_input,
+        body_label,
+        body_bg,
+        body_input,
+        attachment_align,
+        attachments[],
+        send,
+    }
+}
+
+#[derive(WidgetCommon)]
+pub struct Mailbox<'a> {
+    client: &'a Client,
+    info: &'a HudInfo,
+    imgs: &'a Imgs,
+    item_imgs: &'a ItemImgs,
+    fonts: &'a Fonts,
+    rot_imgs: &'a ImgsRot,
+    item_tooltip_manager: &'a mut ItemTooltipManager,
+    slot_manager: &'a mut SlotManager,
+    localized_strings: &'a Localization,
+    msm: &'a MaterialStatManifest,
+    pulse: f32,
+    show: &'a mut MailboxShow,
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> Mailbox<'a> {
+    pub fn new(
+        client: &'a Client,
+        info: &'a HudInfo,
+        imgs: &'a Imgs,
+        item_imgs: &'a ItemImgs,
+        fonts: &'a Fonts,
+        rot_imgs: &'a ImgsRot,
+        item_tooltip_manager: &'a mut ItemTooltipManager,
+        slot_manager: &'a mut SlotManager,
+        localized_strings: &'a Localization,
+        msm: &'a MaterialStatManifest,
+        pulse: f32,
+        show: &'a mut MailboxShow,
+    ) -> Self {
+        Self {
+            client,
+            info,
+            imgs,
+            item_imgs,
+            fonts,
+            rot_imgs,
+            item_tooltip_manager,
+            slot_manager,
+            localized_strings,
+            msm,
+            pulse,
+            show,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+impl<'a> Widget for Mailbox<'a> {
+    type Event = Vec<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("Mailbox::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+        let mut events = Vec::new();
+
+        let Some((_, mailbox)) = self.client.mailbox() else {
+            events.push(Event::Close);
+            return events;
+        };
+        let inventories = self.client.inventories();
+        let Some(inventory) = inventories.get(self.info.viewpoint_entity) else {
+            return events;
+        };
+
+        // Forget attachments that were moved or used since they were added
+        self.show
+            .attachments
+            .retain(|slot| inventory.get(*slot).is_some());
+
+        if state.ids.mail_frames.len() < mailbox.mail.len() {
+            state.update(|s| {
+                let mut id_gen = ui.widget_id_generator();
+                let len = mailbox.mail.len();
+                s.ids.mail_frames.resize(len, &mut id_gen);
+                s.ids.mail_senders.resize(len, &mut id_gen);
+                s.ids.mail_bodies.resize(len, &mut id_gen);
+                s.ids.mail_claims.resize(len, &mut id_gen);
+            });
+        }
+        if state.ids.attachments.len() < MAX_MAIL_ATTACHMENTS {
+            state.update(|s| {
+                s.ids
+                    .attachments
+                    .resize(MAX_MAIL_ATTACHMENTS, &mut ui.widget_id_generator())
+            });
+        }
+
+        // Background
+        Image::new(self.imgs.inv_middle_bg_bag)
+            .w_h(424.0, 460.0)
+            .color(Some(UI_MAIN))
+            .mid_bottom_with_margin_on(ui.window, 295.0)
+            .set(state.ids.bg, ui);
+        Image::new(self.imgs.inv_middle_frame)
+            .w_h(424.0, 460.0)
+            .middle_of(state.ids.bg)
+            .color(Some(UI_HIGHLIGHT_0))
+            .set(state.ids.bg_frame, ui);
+
+        // Title
+        Text::new(&self.localized_strings.get_msg("hud-mail-title"))
+            .mid_top_with_margin_on(state.ids.bg_frame, 9.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(Color::Rgba(0.0, 0.0, 0.0, 1.0))
+            .set(state.ids.title_bg, ui);
+        Text::new(&self.localized_strings.get_msg("hud-mail-title"))
+            .top_left_with_margins_on(state.ids.title_bg, 2.0, 2.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(TEXT_COLOR)
+            .set(state.ids.title, ui);
+
+        // Received mail
+        Rectangle::fill_with([380.0, 190.0], color::TRANSPARENT)
+            .mid_top_with_margin_on(state.ids.bg, 50.0)
+            .scroll_kids_vertically()
+            .set(state.ids.mail_align, ui);
+
+        if mailbox.mail.is_empty() {
+            Text::new(&self.localized_strings.get_msg("hud-mail-empty"))
+                .mid_top_with_margin_on(state.ids.mail_align, 10.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(TEXT_COLOR)
+                .set(state.ids.no_mail, ui);
+        }
+
+        for (i, mail) in mailbox.mail.iter().enumerate() {
+            Rectangle::fill_with([370.0, MAIL_HEIGHT - 4.0], Color::Rgba(0.0, 0.0, 0.0, 0.3))
+                .top_left_with_margins_on(state.ids.mail_align, i as f64 * MAIL_HEIGHT, 0.0)
+                .parent(state.ids.mail_align)
+                .set(state.ids.mail_frames[i], ui);
+            Text::new(
+                &self
+                    .localized_strings
+                    .get_msg_ctx("hud-mail-from", &i18n::fluent_args! {
+                        "sender" => mail.sender.as_str(),
+                        "attachments" => mail.attachments.len(),
+                    }),
+            )
+            .top_left_with_margins_on(state.ids.mail_frames[i], 4.0, 6.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.mail_senders[i], ui);
+            Text::new(&mail.body)
+                .down_from(state.ids.mail_senders[i], 4.0)
+                .w(280.0)
+                .h(30.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(12))
+                .color(TEXT_COLOR)
+                .set(state.ids.mail_bodies[i], ui);
+            if Button::image(self.imgs.button)
+                .w_h(70.0, 24.0)
+                .hover_image(self.imgs.button_hover)
+                .press_image(self.imgs.button_press)
+                .mid_right_with_margin_on(state.ids.mail_frames[i], 6.0)
+                .label(&self.localized_strings.get_msg("hud-mail-claim"))
+                .label_font_size(self.fonts.cyri.scale(12))
+                .label_color(TEXT_COLOR)
+                .label_font_id(self.fonts.cyri.conrod_id)
+                .label_y(Relative::Scalar(2.0))
+                .set(state.ids.mail_claims[i], ui)
+                .was_clicked()
+            {
+                events.push(Event::Claim(mail.id));
+            }
+        }
+        Scrollbar::y_axis(state.ids.mail_align)
+            .thickness(5.0)
+            .rgba(0.33, 0.33, 0.33, 1.0)
+            .set(state.ids.mail_scrollbar, ui);
+
+        // New mail
+        Text::new(&self.localized_strings.get_msg("hud-mail-recipient"))
+            .top_left_with_margins_on(state.ids.bg, 255.0, 22.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.recipient_label, ui);
+        Rectangle::fill([290.0, 22.0])
+            .top_right_with_margins_on(state.ids.bg, 252.0, 22.0)
+            .color(Color::Rgba(0.0, 0.0, 0.0, 0.5))
+            .set(state.ids.recipient_bg, ui);
+        if let Some(recipient) = TextEdit::new(&self.show.recipient)
+            .middle_of(state.ids.recipient_bg)
+            .w_h(280.0, 20.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.recipient_input, ui)
+        {
+            self.show.recipient = recipient;
+        }
+
+        Text::new(&self.localized_strings.get_msg("hud-mail-body"))
+            .top_left_with_margins_on(state.ids.bg, 285.0, 22.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.body_label, ui);
+        Rectangle::fill([290.0, 60.0])
+            .top_right_with_margins_on(state.ids.bg, 282.0, 22.0)
+            .color(Color::Rgba(0.0, 0.0, 0.0, 0.5))
+            .set(state.ids.body_bg, ui);
+        if let Some(mut body) = TextEdit::new(&self.show.body)
+            .middle_of(state.ids.body_bg)
+            .w_h(280.0, 56.0)
+            .wrap_by_word()
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(12))
+            .color(TEXT_COLOR)
+            .set(state.ids.body_input, ui)
+        {
+            while body.len() > MAX_MAIL_BODY_LEN {
+                body.pop();
+            }
+            self.show.body = body;
+        }
+
+        // Attachments
+        let item_tooltip = ItemTooltip::new(
+            {
+                // Edge images [t, b, r, l]
+                // Corner images [tr, tl, br, bl]
+                let edge = &self.rot_imgs.tt_side;
+                let corner = &self.rot_imgs.tt_corner;
+                ImageFrame::new(
+                    [edge.cw180, edge.none, edge.cw270, edge.cw90],
+                    [corner.none, corner.cw270, corner.cw90, corner.cw180],
+                    Color::Rgba(0.08, 0.07, 0.04, 1.0),
+                    5.0,
+                )
+            },
+            self.client,
+            self.info,
+            self.imgs,
+            self.item_imgs,
+            self.pulse,
+            self.msm,
+            self.localized_strings,
+        )
+        .title_font_size(self.fonts.cyri.scale(20))
+        .parent(ui.window)
+        .desc_font_size(self.fonts.cyri.scale(12))
+        .font_id(self.fonts.cyri.conrod_id)
+        .desc_text_color(TEXT_COLOR);
+
+        Rectangle::fill_with(
+            [MAX_MAIL_ATTACHMENTS as f64 * 40.0, 40.0],
+            color::TRANSPARENT,
+        )
+        .top_left_with_margins_on(state.ids.bg, 355.0, 22.0)
+        .set(state.ids.attachment_align, ui);
+
+        let mut slot_maker = SlotMaker {
+            empty_slot: self.imgs.inv_slot,
+            filled_slot: self.imgs.inv_slot,
+            selected_slot: self.imgs.inv_slot_sel,
+            background_color: Some(UI_MAIN),
+            content_size: ContentSize {
+                width_height_ratio: 1.0,
+                max_fraction: 0.75,
+            },
+            selected_content_scale: 1.067,
+            amount_font: self.fonts.cyri.conrod_id,
+            amount_margins: Vec2::new(-4.0, 0.0),
+            amount_font_size: self.fonts.cyri.scale(12),
+            amount_text_color: TEXT_COLOR,
+            content_source: inventory,
+            image_source: self.item_imgs,
+            slot_manager: Some(self.slot_manager),
+            pulse: self.pulse,
+        };
+
+        for i in 0..MAX_MAIL_ATTACHMENTS {
+            let slot = self.show.attachments.get(i).copied();
+            let slot_widget = slot_maker
+                .fabricate(MailSlot { index: i, slot }, [40.0; 2])
+                .top_left_with_margins_on(state.ids.attachment_align, 0.0, i as f64 * 40.0);
+            if let Some(item) = slot.and_then(|slot| inventory.get(slot)) {
+                let quality_col_img = match item.quality() {
+                    Quality::Low => self.imgs.inv_slot_grey,
+                    Quality::Common => self.imgs.inv_slot_common,
+                    Quality::Moderate => self.imgs.inv_slot_green,
+                    Quality::High => self.imgs.inv_slot_blue,
+                    Quality::Epic => self.imgs.inv_slot_purple,
+                    Quality::Legendary => self.imgs.inv_slot_gold,
+                    Quality::Artifact => self.imgs.inv_slot_orange,
+                    _ => self.imgs.inv_slot_red,
+                };
+
+                slot_widget
+                    .filled_slot(quality_col_img)
+                    .with_item_tooltip(
+                        self.item_tooltip_manager,
+                        core::iter::once(item as &dyn ItemDesc),
+                        &None,
+                        &item_tooltip,
+                    )
+                    .set(state.ids.attachments[i], ui);
+            } else {
+                slot_widget.set(state.ids.attachments[i], ui);
+            }
+        }
+
+        if Button::image(self.imgs.button)
+            .w_h(31.0 * 5.0, 12.0 * 2.0)
+            .hover_image(self.imgs.button_hover)
+            .press_image(self.imgs.button_press)
+            .mid_bottom_with_margin_on(state.ids.bg, 40.0)
+            .label(&self.localized_strings.get_msg("hud-mail-send"))
+            .label_font_size(self.fonts.cyri.scale(14))
+            .label_color(TEXT_COLOR)
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .label_y(Relative::Scalar(2.0))
+            .set(state.ids.send, ui)
+            .was_clicked()
+            && !self.show.recipient.trim().is_empty()
+        {
+            events.push(Event::Send {
+                recipient: core::mem::take(&mut self.show.recipient),
+                body: core::mem::take(&mut self.show.body),
+                attachments: core::mem::take(&mut self.show.attachments),
+            });
+        }
+
+        // Close button
+        if Button::image(self.imgs.close_btn)
+            .w_h(24.0, 25.0)
+            .hover_image(self.imgs.close_btn_hover)
+            .press_image(self.imgs.close_btn_press)
+            .top_right_with_margins_on(state.ids.bg, 0.0, 0.0)
+            .set(state.ids.close, ui)
+            .was_clicked()
+        {
+            events.push(Event::Close);
+        }
+
+        events
+    }
+}
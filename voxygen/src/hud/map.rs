@@ -18,7 +18,7 @@ use common::{
     trade::Good,
     vol::RectVolSize,
 };
-use common_net::msg::world_msg::{PoiKind, SiteId, SiteKind};
+use common_net::msg::world_msg::{FactionStance, PoiKind, SiteId, SiteKind};
 use conrod_core::{
     color,
     input::MouseButton as ConrodMouseButton,
@@ -170,6 +170,45 @@ pub enum Event {
     RemoveMarker,
 }
 
+/// Describe which faction holds the site, and how it gets along with others
+fn get_site_faction(client: &Client, site: SiteId, i18n: &Localization) -> String {
+    let factions = client.site_factions();
+    let Some(&capital) = factions.owners.get(&site) else {
+        return String::new();
+    };
+    let name = |site| {
+        client
+            .sites()
+            .get(&site)
+            .and_then(|site_rich| site_rich.site.name.clone())
+    };
+
+    let mut result = String::from("\n\n");
+    result += &if capital == site {
+        i18n.get_msg("hud-map-faction_capital")
+    } else {
+        i18n.get_msg_ctx("hud-map-faction_held_by", &i18n::fluent_args! {
+            "capital" => name(capital).unwrap_or_default()
+        })
+    };
+    for (stance, key) in [
+        (FactionStance::Alliance, "hud-map-faction_allies"),
+        (FactionStance::War, "hud-map-faction_enemies"),
+    ] {
+        let names = factions
+            .related(capital, stance)
+            .filter_map(name)
+            .collect::<Vec<_>>();
+        if !names.is_empty() {
+            result += "\n";
+            result += &i18n.get_msg_ctx(key, &i18n::fluent_args! {
+                "factions" => names.join(", ")
+            });
+        }
+    }
+    result
+}
+
 fn get_site_economy(site_rich: &SiteInfoRich) -> String {
     if SHOW_ECONOMY {
         let site = &site_rich.site;
@@ -953,7 +992,9 @@ impl<'a> Widget for Map<'a> {
                 SiteKind::Bridge => (None, i18n.get_msg("hud-map-bridge")),
                 SiteKind::Adlet => (Some(1), i18n.get_msg("hud-map-adlet")),
             };
-            let desc = desc.into_owned() + &get_site_economy(site_rich);
+            let desc = desc.into_owned()
+                + &get_site_faction(self.client, site.id, i18n)
+                + &get_site_economy(site_rich);
             let site_btn = Button::image(match &site.kind {
                 SiteKind::Town => self.imgs.mmap_site_town,
                 SiteKind::ChapelSite => self.imgs.mmap_site_sea_chapel,
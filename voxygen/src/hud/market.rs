@@ -0,0 +1,545 @@
+use conrod_core::{
+    color,
+    position::Relative,
+    widget::{self, Button, Image, Rectangle, Scrollbar, Text, TextEdit},
+    widget_ids, Color, Colorable, Labelable, Positionable, Sizeable, Widget, WidgetCommon,
+};
+use vek::*;
+
+use client::Client;
+use common::comp::{
+    inventory::item::{ItemDefinitionId, ItemDesc, MaterialStatManifest, Quality},
+    market::{MarketOrderId, OrderKind},
+    slot::InvSlotId,
+    Item,
+};
+use i18n::Localization;
+
+use crate::ui::{
+    fonts::Fonts,
+    slot::{ContentSize, SlotMaker},
+    ImageFrame, ItemTooltip, ItemTooltipManager, ItemTooltipable,
+};
+
+use super::{
+    img_ids::{Imgs, ImgsRot},
+    item_imgs::ItemImgs,
+    slots::{MarketSlot, SlotManager},
+    util, HudInfo, TEXT_COLOR, TEXT_GRAY_COLOR, UI_HIGHLIGHT_0, UI_MAIN,
+};
+
+const ORDER_HEIGHT: f64 = 50.0;
+
+/// The order being written, kept between frames.
+#[derive(Default)]
+pub struct MarketShow {
+    pub slot: Option<InvSlotId>,
+    pub quantity: String,
+    pub unit_price: String,
+}
+
+pub enum Event {
+    Close,
+    Sell {
+        slot: InvSlotId,
+        quantity: u32,
+        unit_price: u32,
+    },
+    Buy {
+        item_definition_id: String,
+        quantity: u32,
+        unit_price: u32,
+    },
+    Cancel(MarketOrderId),
+}
+
+widget_ids! {
+    pub struct Ids {
+        bg,
+        bg_frame,
+        title_bg,
+        title,
+        close,
+        order_align,
+        order_scrollbar,
+        no_orders,
+        order_frames[],
+        order_items[],
+        order_owners[],
+        order_actions[],
+        slot,
+        quantity_label,
+        quantity_bg,
+        quantity_input,
+        price_label,
+        price_bg,
+        price_input,
+        reference,
+        sell,
+        buy,
+    }
+}
+
+#[derive(WidgetCommon)]
+pub struct Market<'a> {
+    client: &'a Client,
+    info: &'a HudInfo,
+    imgs: &'a Imgs,
+    item_imgs: &'a ItemImgs,
+    fonts: &'a Fonts,
+    rot_imgs: &'a ImgsRot,
+    item_tooltip_manager: &'a mut ItemTooltipManager,
+    slot_manager: &'a mut SlotManager,
+    localized_strings: &'a Localization,
+    msm: &'a MaterialStatManifest,
+    pulse: f32,
+    show: &'a mut MarketShow,
+    #[conrod(common_builder)]
+    common: widget::CommonBuilder,
+}
+
+impl<'a> Market<'a> {
+    pub fn new(
+        client: &'a Client,
+        info: &'a HudInfo,
+        imgs: &'a Imgs,
+        item_imgs: &'a ItemImgs,
+        fonts: &'a Fonts,
+        rot_imgs: &'a ImgsRot,
+        item_tooltip_manager: &'a mut ItemTooltipManager,
+        slot_manager: &'a mut SlotManager,
+        localized_strings: &'a Localization,
+        msm: &'a MaterialStatManifest,
+        pulse: f32,
+        show: &'a mut MarketShow,
+    ) -> Self {
+        Self {
+            client,
+            info,
+            imgs,
+            item_imgs,
+            fonts,
+            rot_imgs,
+            item_tooltip_manager,
+            slot_manager,
+            localized_strings,
+            msm,
+            pulse,
+            show,
+            common: widget::CommonBuilder::default(),
+        }
+    }
+}
+
+pub struct State {
+    ids: Ids,
+}
+
+/// Only items with a simple definition can be traded on the market.
+fn simple_id(item: &Item) -> Option<String> {
+    match item.item_definition_id() {
+        ItemDefinitionId::Simple(id) => Some(id.to_owned()),
+        _ => None,
+    }
+}
+
+impl<'a> Widget for Market<'a> {
+    type Event = Vec<Event>;
+    type State = State;
+    type Style = ();
+
+    fn init_state(&self, id_gen: widget::id::Generator) -> Self::State {
+        State {
+            ids: Ids::new(id_gen),
+        }
+    }
+
+    fn style(&self) -> Self::Style {}
+
+    fn update(self, args: widget::UpdateArgs<Self>) -> Self::Event {
+        common_base::prof_span!("Market::update");
+        let widget::UpdateArgs { state, ui, .. } = args;
+        let mut events = Vec::new();
+
+        let Some((_, market)) = self.client.market() else {
+            events.push(Event::Close);
+            return events;
+        };
+        let inventories = self.client.inventories();
+        let Some(inventory) = inventories.get(self.info.viewpoint_entity) else {
+            return events;
+        };
+
+        // Forget the chosen item if it was moved or used since
+        if self
+            .show
+            .slot
+            .map_or(false, |slot| inventory.get(slot).is_none())
+        {
+            self.show.slot = None;
+        }
+
+        if state.ids.order_frames.len() < market.orders.len() {
+            state.update(|s| {
+                let mut id_gen = ui.widget_id_generator();
+                let len = market.orders.len();
+                s.ids.order_frames.resize(len, &mut id_gen);
+                s.ids.order_items.resize(len, &mut id_gen);
+                s.ids.order_owners.resize(len, &mut id_gen);
+                s.ids.order_actions.resize(len, &mut id_gen);
+            });
+        }
+
+        // Background
+        Image::new(self.imgs.inv_middle_bg_bag)
+            .w_h(424.0, 460.0)
+            .color(Some(UI_MAIN))
+            .mid_bottom_with_margin_on(ui.window, 295.0)
+            .set(state.ids.bg, ui);
+        Image::new(self.imgs.inv_middle_frame)
+            .w_h(424.0, 460.0)
+            .middle_of(state.ids.bg)
+            .color(Some(UI_HIGHLIGHT_0))
+            .set(state.ids.bg_frame, ui);
+
+        // Title
+        let title = self
+            .localized_strings
+            .get_msg_ctx("hud-market-title", &i18n::fluent_args! {
+                "site" => market.site_name.as_str(),
+            });
+        Text::new(&title)
+            .mid_top_with_margin_on(state.ids.bg_frame, 9.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(Color::Rgba(0.0, 0.0, 0.0, 1.0))
+            .set(state.ids.title_bg, ui);
+        Text::new(&title)
+            .top_left_with_margins_on(state.ids.title_bg, 2.0, 2.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(20))
+            .color(TEXT_COLOR)
+            .set(state.ids.title, ui);
+
+        // Open orders
+        Rectangle::fill_with([380.0, 230.0], color::TRANSPARENT)
+            .mid_top_with_margin_on(state.ids.bg, 50.0)
+            .scroll_kids_vertically()
+            .set(state.ids.order_align, ui);
+
+        if market.orders.is_empty() {
+            Text::new(&self.localized_strings.get_msg("hud-market-empty"))
+                .mid_top_with_margin_on(state.ids.order_align, 10.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(14))
+                .color(TEXT_COLOR)
+                .set(state.ids.no_orders, ui);
+        }
+
+        for (i, order) in market.orders.iter().enumerate() {
+            Rectangle::fill_with([370.0, ORDER_HEIGHT - 4.0], Color::Rgba(0.0, 0.0, 0.0, 0.3))
+                .top_left_with_margins_on(state.ids.order_align, i as f64 * ORDER_HEIGHT, 0.0)
+                .parent(state.ids.order_align)
+                .set(state.ids.order_frames[i], ui);
+            let kind_key = match order.kind {
+                OrderKind::Sell => "hud-market-selling",
+                OrderKind::Buy => "hud-market-buying",
+            };
+            Text::new(
+                &self
+                    .localized_strings
+                    .get_msg_ctx(kind_key, &i18n::fluent_args! {
+                        "quantity" => order.quantity,
+                        "item" => order.item.name(),
+                        "price" => order.unit_price,
+                    }),
+            )
+            .top_left_with_margins_on(state.ids.order_frames[i], 4.0, 6.0)
+            .w(280.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.order_items[i], ui);
+            let mut owner = self
+                .localized_strings
+                .get_msg_ctx("hud-market-owner", &i18n::fluent_args! {
+                    "owner" => order.owner.as_str(),
+                })
+                .into_owned();
+            if let Some((buy, sell, _)) = util::price_desc(
+                &market.prices,
+                order.item.item_definition_id(),
+                self.localized_strings,
+            ) {
+                owner = format!("{}  ({}, {})", owner, buy, sell);
+            }
+            Text::new(&owner)
+                .down_from(state.ids.order_items[i], 4.0)
+                .w(280.0)
+                .font_id(self.fonts.cyri.conrod_id)
+                .font_size(self.fonts.cyri.scale(12))
+                .color(TEXT_GRAY_COLOR)
+                .set(state.ids.order_owners[i], ui);
+
+            // Own orders can be withdrawn, and the orders of others can be taken
+            // up by placing an opposite order at the same price
+            let action = if order.is_own {
+                Some(("hud-market-withdraw", Event::Cancel(order.id)))
+            } else {
+                match order.kind {
+                    OrderKind::Sell => simple_id(&order.item).map(|item_definition_id| {
+                        ("hud-market-buy", Event::Buy {
+                            item_definition_id,
+                            quantity: order.quantity,
+                            unit_price: order.unit_price,
+                        })
+                    }),
+                    OrderKind::Buy => inventory
+                        .get_slot_of_item(&order.item)
+                        .and_then(|slot| Some((slot, inventory.get(slot)?.amount())))
+                        .map(|(slot, amount)| {
+                            ("hud-market-sell", Event::Sell {
+                                slot,
+                                quantity: amount.min(order.quantity),
+                                unit_price: order.unit_price,
+                            })
+                        }),
+                }
+            };
+            if let Some((label, event)) = action {
+                if Button::image(self.imgs.button)
+                    .w_h(70.0, 24.0)
+                    .hover_image(self.imgs.button_hover)
+                    .press_image(self.imgs.button_press)
+                    .mid_right_with_margin_on(state.ids.order_frames[i], 6.0)
+                    .label(&self.localized_strings.get_msg(label))
+                    .label_font_size(self.fonts.cyri.scale(12))
+                    .label_color(TEXT_COLOR)
+                    .label_font_id(self.fonts.cyri.conrod_id)
+                    .label_y(Relative::Scalar(2.0))
+                    .set(state.ids.order_actions[i], ui)
+                    .was_clicked()
+                {
+                    events.push(event);
+                }
+            }
+        }
+        Scrollbar::y_axis(state.ids.order_align)
+            .thickness(5.0)
+            .rgba(0.33, 0.33, 0.33, 1.0)
+            .set(state.ids.order_scrollbar, ui);
+
+        // New order
+        let item_tooltip = ItemTooltip::new(
+            {
+                // Edge images [t, b, r, l]
+                // Corner images [tr, tl, br, bl]
+                let edge = &self.rot_imgs.tt_side;
+                let corner = &self.rot_imgs.tt_corner;
+                ImageFrame::new(
+                    [edge.cw180, edge.none, edge.cw270, edge.cw90],
+                    [corner.none, corner.cw270, corner.cw90, corner.cw180],
+                    Color::Rgba(0.08, 0.07, 0.04, 1.0),
+                    5.0,
+                )
+            },
+            self.client,
+            self.info,
+            self.imgs,
+            self.item_imgs,
+            self.pulse,
+            self.msm,
+            self.localized_strings,
+        )
+        .title_font_size(self.fonts.cyri.scale(20))
+        .parent(ui.window)
+        .desc_font_size(self.fonts.cyri.scale(12))
+        .font_id(self.fonts.cyri.conrod_id)
+        .desc_text_color(TEXT_COLOR);
+
+        let mut slot_maker = SlotMaker {
+            empty_slot: self.imgs.inv_slot,
+            filled_slot: self.imgs.inv_slot,
+            selected_slot: self.imgs.inv_slot_sel,
+            background_color: Some(UI_MAIN),
+            content_size: ContentSize {
+                width_height_ratio: 1.0,
+                max_fraction: 0.75,
+            },
+            selected_content_scale: 1.067,
+            amount_font: self.fonts.cyri.conrod_id,
+            amount_margins: Vec2::new(-4.0, 0.0),
+            amount_font_size: self.fonts.cyri.scale(12),
+            amount_text_color: TEXT_COLOR,
+            content_source: inventory,
+            image_source: self.item_imgs,
+            slot_manager: Some(self.slot_manager),
+            pulse: self.pulse,
+        };
+
+        let item = self.show.slot.and_then(|slot| inventory.get(slot));
+        let slot_widget = slot_maker
+            .fabricate(
+                MarketSlot {
+                    slot: self.show.slot,
+                },
+                [40.0; 2],
+            )
+            .top_left_with_margins_on(state.ids.bg, 298.0, 22.0);
+        if let Some(item) = item {
+            let quality_col_img = match item.quality() {
+                Quality::Low => self.imgs.inv_slot_grey,
+                Quality::Common => self.imgs.inv_slot_common,
+                Quality::Moderate => self.imgs.inv_slot_green,
+                Quality::High => self.imgs.inv_slot_blue,
+                Quality::Epic => self.imgs.inv_slot_purple,
+                Quality::Legendary => self.imgs.inv_slot_gold,
+                Quality::Artifact => self.imgs.inv_slot_orange,
+                _ => self.imgs.inv_slot_red,
+            };
+
+            slot_widget
+                .filled_slot(quality_col_img)
+                .with_item_tooltip(
+                    self.item_tooltip_manager,
+                    core::iter::once(item as &dyn ItemDesc),
+                    &None,
+                    &item_tooltip,
+                )
+                .set(state.ids.slot, ui);
+        } else {
+            slot_widget.set(state.ids.slot, ui);
+        }
+
+        Text::new(&self.localized_strings.get_msg("hud-market-quantity"))
+            .top_left_with_margins_on(state.ids.bg, 300.0, 80.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.quantity_label, ui);
+        Rectangle::fill([80.0, 22.0])
+            .top_left_with_margins_on(state.ids.bg, 297.0, 150.0)
+            .color(Color::Rgba(0.0, 0.0, 0.0, 0.5))
+            .set(state.ids.quantity_bg, ui);
+        if let Some(quantity) = TextEdit::new(&self.show.quantity)
+            .middle_of(state.ids.quantity_bg)
+            .w_h(70.0, 20.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.quantity_input, ui)
+        {
+            self.show.quantity = quantity.chars().filter(char::is_ascii_digit).collect();
+        }
+
+        Text::new(&self.localized_strings.get_msg("hud-market-price"))
+            .top_left_with_margins_on(state.ids.bg, 300.0, 245.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.price_label, ui);
+        Rectangle::fill([80.0, 22.0])
+            .top_right_with_margins_on(state.ids.bg, 297.0, 22.0)
+            .color(Color::Rgba(0.0, 0.0, 0.0, 0.5))
+            .set(state.ids.price_bg, ui);
+        if let Some(unit_price) = TextEdit::new(&self.show.unit_price)
+            .middle_of(state.ids.price_bg)
+            .w_h(70.0, 20.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(14))
+            .color(TEXT_COLOR)
+            .set(state.ids.price_input, ui)
+        {
+            self.show.unit_price = unit_price.chars().filter(char::is_ascii_digit).collect();
+        }
+
+        // What merchants of the site would pay for the chosen item
+        if let Some((buy, sell, _)) = item.and_then(|item| {
+            util::price_desc(
+                &market.prices,
+                item.item_definition_id(),
+                self.localized_strings,
+            )
+        }) {
+            Text::new(&self.localized_strings.get_msg_ctx(
+                "hud-market-reference",
+                &i18n::fluent_args! {
+                    "buy" => buy,
+                    "sell" => sell,
+                },
+            ))
+            .top_left_with_margins_on(state.ids.bg, 330.0, 80.0)
+            .font_id(self.fonts.cyri.conrod_id)
+            .font_size(self.fonts.cyri.scale(12))
+            .color(TEXT_GRAY_COLOR)
+            .set(state.ids.reference, ui);
+        }
+
+        let quantity = self.show.quantity.parse::<u32>().ok().filter(|q| *q > 0);
+        let unit_price = self.show.unit_price.parse::<u32>().ok().filter(|p| *p > 0);
+
+        if Button::image(self.imgs.button)
+            .w_h(31.0 * 4.0, 12.0 * 2.0)
+            .hover_image(self.imgs.button_hover)
+            .press_image(self.imgs.button_press)
+            .bottom_left_with_margins_on(state.ids.bg, 40.0, 60.0)
+            .label(&self.localized_strings.get_msg("hud-market-sell"))
+            .label_font_size(self.fonts.cyri.scale(14))
+            .label_color(TEXT_COLOR)
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .label_y(Relative::Scalar(2.0))
+            .set(state.ids.sell, ui)
+            .was_clicked()
+        {
+            if let (Some(slot), Some(quantity), Some(unit_price)) =
+                (self.show.slot, quantity, unit_price)
+            {
+                events.push(Event::Sell {
+                    slot,
+                    quantity,
+                    unit_price,
+                });
+                *self.show = MarketShow::default();
+            }
+        }
+
+        if Button::image(self.imgs.button)
+            .w_h(31.0 * 4.0, 12.0 * 2.0)
+            .hover_image(self.imgs.button_hover)
+            .press_image(self.imgs.button_press)
+            .bottom_right_with_margins_on(state.ids.bg, 40.0, 60.0)
+            .label(&self.localized_strings.get_msg("hud-market-buy"))
+            .label_font_size(self.fonts.cyri.scale(14))
+            .label_color(TEXT_COLOR)
+            .label_font_id(self.fonts.cyri.conrod_id)
+            .label_y(Relative::Scalar(2.0))
+            .set(state.ids.buy, ui)
+            .was_clicked()
+        {
+            if let (Some(item_definition_id), Some(quantity), Some(unit_price)) =
+                (item.and_then(simple_id), quantity, unit_price)
+            {
+                events.push(Event::Buy {
+                    item_definition_id,
+                    quantity,
+                    unit_price,
+                });
+                *self.show = MarketShow::default();
+            }
+        }
+
+        // Close button
+        if Button::image(self.imgs.close_btn)
+            .w_h(24.0, 25.0)
+            .hover_image(self.imgs.close_btn_hover)
+            .press_image(self.imgs.close_btn_press)
+            .top_right_with_margins_on(state.ids.bg, 0.0, 0.0)
+            .set(state.ids.close, ui)
+            .was_clicked()
+        {
+            events.push(Event::Close);
+        }
+
+        events
+    }
+}
@@ -1,5 +1,6 @@
 mod animation;
 mod bag;
+mod bank;
 mod buffs;
 mod buttons;
 mod change_notification;
@@ -10,7 +11,9 @@ mod esc_menu;
 mod group;
 mod hotbar;
 mod loot_scroller;
+mod mailbox;
 mod map;
+mod market;
 mod minimap;
 mod overhead;
 mod overitem;
@@ -101,6 +104,8 @@ use common::{
             ItemDesc, MaterialStatManifest, Quality,
         },
         loot_owner::LootOwnerKind,
+        mail::{MailId, MAX_MAIL_ATTACHMENTS},
+        market::MarketOrderId,
         pet::is_mountable,
         skillset::{skills::Skill, SkillGroupKind, SkillsPersistenceError},
         BuffData, BuffKind, Health, Item, MapMarkerChange, PresenceKind,
@@ -317,6 +322,9 @@ widget_ids! {
         minimap,
         prompt_dialog,
         bag,
+        bank,
+        mailbox,
+        market,
         trade,
         social,
         quest,
@@ -704,6 +712,28 @@ pub enum Event {
     SortInventory,
     ChangeHotbarState(Box<HotbarState>),
     TradeAction(TradeAction),
+    BankDeposit(InvSlotId),
+    BankWithdraw(usize),
+    CloseBank,
+    SendMail {
+        recipient: String,
+        body: String,
+        attachments: Vec<InvSlotId>,
+    },
+    ClaimMail(MailId),
+    CloseMailbox,
+    PostSellOrder {
+        slot: InvSlotId,
+        quantity: u32,
+        unit_price: u32,
+    },
+    PostBuyOrder {
+        item_definition_id: String,
+        quantity: u32,
+        unit_price: u32,
+    },
+    CancelMarketOrder(MarketOrderId),
+    CloseMarket,
     Ability(usize, bool),
     Logout,
     Quit,
@@ -895,6 +925,9 @@ pub struct Show {
     bag_details: bool,
     trade: bool,
     trade_details: bool,
+    bank: bool,
+    mailbox: bool,
+    market: bool,
     social: bool,
     diary: bool,
     group: bool,
@@ -908,6 +941,8 @@ pub struct Show {
     settings_tab: SettingsTab,
     diary_fields: diary::DiaryShow,
     crafting_fields: crafting::CraftingShow,
+    mailbox_fields: mailbox::MailboxShow,
+    market_fields: market::MarketShow,
     social_search_key: Option<String>,
     want_grab: bool,
     stats: bool,
@@ -943,6 +978,39 @@ impl Show {
         }
     }
 
+    fn bank(&mut self, open: bool) {
+        if !self.esc_menu {
+            self.bag = open;
+            self.bank = open;
+            self.map = false;
+            self.want_grab = !self.any_window_requires_cursor();
+        }
+    }
+
+    fn mailbox(&mut self, open: bool) {
+        if !self.esc_menu {
+            self.bag = open;
+            self.mailbox = open;
+            self.map = false;
+            if !open {
+                self.mailbox_fields = mailbox::MailboxShow::default();
+            }
+            self.want_grab = !self.any_window_requires_cursor();
+        }
+    }
+
+    fn market(&mut self, open: bool) {
+        if !self.esc_menu {
+            self.bag = open;
+            self.market = open;
+            self.map = false;
+            if !open {
+                self.market_fields = market::MarketShow::default();
+            }
+            self.want_grab = !self.any_window_requires_cursor();
+        }
+    }
+
     fn map(&mut self, open: bool) {
         if !self.esc_menu {
             self.map = open;
@@ -1078,6 +1146,9 @@ impl Show {
     fn any_window_requires_cursor(&self) -> bool {
         self.bag
             || self.trade
+            || self.bank
+            || self.mailbox
+            || self.market
             || self.esc_menu
             || self.map
             || self.social
@@ -1093,6 +1164,9 @@ impl Show {
         if self.any_window_requires_cursor() {
             self.bag = false;
             self.trade = false;
+            self.bank = false;
+            self.mailbox = false;
+            self.market = false;
             self.esc_menu = false;
             self.help = false;
             self.intro = false;
@@ -1395,6 +1469,9 @@ impl Hud {
                 bag_details: false,
                 trade: false,
                 trade_details: false,
+                bank: false,
+                mailbox: false,
+                market: false,
                 esc_menu: false,
                 open_windows: Windows::None,
                 map: false,
@@ -1410,6 +1487,8 @@ impl Hud {
                 settings_tab: SettingsTab::Interface,
                 diary_fields: diary::DiaryShow::default(),
                 crafting_fields: crafting::CraftingShow::default(),
+                mailbox_fields: mailbox::MailboxShow::default(),
+                market_fields: market::MarketShow::default(),
                 social_search_key: None,
                 want_grab: true,
                 ingame: true,
@@ -1548,6 +1627,53 @@ impl Hud {
                 self.show.toggle_trade();
             }
 
+            // Close the bank once the player walks away from it
+            let bank_in_range =
+                client
+                    .bank()
+                    .zip(client.position())
+                    .map_or(false, |((bank_pos, _), pos)| {
+                        pos.distance_squared(bank_pos.as_::<f32>() + 0.5) < MAX_PICKUP_RANGE.powi(2)
+                    });
+            if bank_in_range != self.show.bank {
+                self.show.bank(bank_in_range);
+            }
+            if !bank_in_range && client.bank().is_some() {
+                events.push(Event::CloseBank);
+            }
+
+            // Likewise for the mailbox
+            let mailbox_in_range =
+                client
+                    .mailbox()
+                    .zip(client.position())
+                    .map_or(false, |((mailbox_pos, _), pos)| {
+                        pos.distance_squared(mailbox_pos.as_::<f32>() + 0.5)
+                            < MAX_PICKUP_RANGE.powi(2)
+                    });
+            if mailbox_in_range != self.show.mailbox {
+                self.show.mailbox(mailbox_in_range);
+            }
+            if !mailbox_in_range && client.mailbox().is_some() {
+                events.push(Event::CloseMailbox);
+            }
+
+            // Likewise for the market board
+            let market_in_range =
+                client
+                    .market()
+                    .zip(client.position())
+                    .map_or(false, |((market_pos, _), pos)| {
+                        pos.distance_squared(market_pos.as_::<f32>() + 0.5)
+                            < MAX_PICKUP_RANGE.powi(2)
+                    });
+            if market_in_range != self.show.market {
+                self.show.market(market_in_range);
+            }
+            if !market_in_range && client.market().is_some() {
+                events.push(Event::CloseMarket);
+            }
+
             //self.input = client.read_storage::<comp::ControllerInputs>();
             if let Some(health) = healths.get(me) {
                 // Hurt Frame
@@ -2063,10 +2189,11 @@ impl Hud {
 
                 let interaction_text = || match interaction {
                     BlockInteraction::Collect => {
-                        vec![(
-                            Some(GameInput::Interact),
-                            i18n.get_msg("hud-collect").to_string(),
-                        )]
+                        let key = match block.get_sprite() {
+                            Some(SpriteKind::TilledSoil) => "hud-plant",
+                            _ => "hud-collect",
+                        };
+                        vec![(Some(GameInput::Interact), i18n.get_msg(key).to_string())]
                     },
                     BlockInteraction::Craft(_) => {
                         vec![(
@@ -3193,6 +3320,113 @@ impl Hud {
             }
         }
 
+        // Bank window
+        if self.show.bank {
+            if let Some(bank::Event::Close) = bank::Bank::new(
+                client,
+                &info,
+                &self.imgs,
+                &self.item_imgs,
+                &self.fonts,
+                &self.rot_imgs,
+                item_tooltip_manager,
+                &mut self.slot_manager,
+                i18n,
+                &msm,
+                self.pulse,
+            )
+            .set(self.ids.bank, ui_widgets)
+            {
+                self.show.bank(false);
+                events.push(Event::CloseBank);
+            }
+        }
+
+        // Mailbox window
+        if self.show.mailbox {
+            for event in mailbox::Mailbox::new(
+                client,
+                &info,
+                &self.imgs,
+                &self.item_imgs,
+                &self.fonts,
+                &self.rot_imgs,
+                item_tooltip_manager,
+                &mut self.slot_manager,
+                i18n,
+                &msm,
+                self.pulse,
+                &mut self.show.mailbox_fields,
+            )
+            .set(self.ids.mailbox, ui_widgets)
+            {
+                match event {
+                    mailbox::Event::Close => {
+                        self.show.mailbox(false);
+                        events.push(Event::CloseMailbox);
+                    },
+                    mailbox::Event::Claim(mail_id) => events.push(Event::ClaimMail(mail_id)),
+                    mailbox::Event::Send {
+                        recipient,
+                        body,
+                        attachments,
+                    } => events.push(Event::SendMail {
+                        recipient,
+                        body,
+                        attachments,
+                    }),
+                }
+            }
+        }
+
+        // Market window
+        if self.show.market {
+            for event in market::Market::new(
+                client,
+                &info,
+                &self.imgs,
+                &self.item_imgs,
+                &self.fonts,
+                &self.rot_imgs,
+                item_tooltip_manager,
+                &mut self.slot_manager,
+                i18n,
+                &msm,
+                self.pulse,
+                &mut self.show.market_fields,
+            )
+            .set(self.ids.market, ui_widgets)
+            {
+                match event {
+                    market::Event::Close => {
+                        self.show.market(false);
+                        events.push(Event::CloseMarket);
+                    },
+                    market::Event::Sell {
+                        slot,
+                        quantity,
+                        unit_price,
+                    } => events.push(Event::PostSellOrder {
+                        slot,
+                        quantity,
+                        unit_price,
+                    }),
+                    market::Event::Buy {
+                        item_definition_id,
+                        quantity,
+                        unit_price,
+                    } => events.push(Event::PostBuyOrder {
+                        item_definition_id,
+                        quantity,
+                        unit_price,
+                    }),
+                    market::Event::Cancel(order_id) => {
+                        events.push(Event::CancelMarketOrder(order_id))
+                    },
+                }
+            }
+        }
+
         // Buffs
         if let (Some(player_buffs), Some(health), Some(energy)) = (
             buffs.get(info.viewpoint_entity),
@@ -3766,7 +4000,7 @@ impl Hud {
 
         // Maintain slot manager
         'slot_events: for event in self.slot_manager.maintain(ui_widgets) {
-            use slots::{AbilitySlot, InventorySlot, SlotKind::*};
+            use slots::{AbilitySlot, InventorySlot, MailSlot, MarketSlot, SlotKind::*};
             let to_slot = |slot_kind| match slot_kind {
                 Inventory(InventorySlot {
                     slot, ours: true, ..
@@ -3777,6 +4011,9 @@ impl Hud {
                 Trade(_) => None,
                 Ability(_) => None,
                 Crafting(_) => None,
+                Bank(_) => None,
+                Mail(_) => None,
+                Market(_) => None,
             };
             match event {
                 slot::Event::Dragged(a, b) => {
@@ -3826,6 +4063,45 @@ impl Hud {
                                 }
                             }
                         }
+                    } else if let (
+                        Inventory(InventorySlot {
+                            slot, ours: true, ..
+                        }),
+                        Bank(_),
+                    ) = (a, b)
+                    {
+                        events.push(Event::BankDeposit(slot));
+                    } else if let (Bank(b), Inventory(InventorySlot { ours: true, .. })) = (a, b) {
+                        events.push(Event::BankWithdraw(b.index));
+                    } else if let (
+                        Inventory(InventorySlot {
+                            slot, ours: true, ..
+                        }),
+                        Mail(_),
+                    ) = (a, b)
+                    {
+                        // Attach item to the mail being written
+                        let attachments = &mut self.show.mailbox_fields.attachments;
+                        if !attachments.contains(&slot) && attachments.len() < MAX_MAIL_ATTACHMENTS
+                        {
+                            attachments.push(slot);
+                        }
+                    } else if let (Mail(m), Inventory(_)) = (a, b) {
+                        // Remove item from the mail being written
+                        if let Some(slot) = m.slot {
+                            self.show.mailbox_fields.attachments.retain(|s| *s != slot);
+                        }
+                    } else if let (
+                        Inventory(InventorySlot {
+                            slot, ours: true, ..
+                        }),
+                        Market(_),
+                    ) = (a, b)
+                    {
+                        // Choose the item of the order being written
+                        self.show.market_fields.slot = Some(slot);
+                    } else if let (Market(_), Inventory(_)) = (a, b) {
+                        self.show.market_fields.slot = None;
                     } else if let (Ability(a), Ability(b)) = (a, b) {
                         match (a, b) {
                             (AbilitySlot::Ability(ability), AbilitySlot::Slot(index)) => {
@@ -3914,6 +4190,14 @@ impl Hud {
                     } else if let Crafting(c) = from {
                         // Remove item from crafting input
                         self.show.crafting_fields.recipe_inputs.remove(&c.index);
+                    } else if let Mail(MailSlot {
+                        slot: Some(slot), ..
+                    }) = from
+                    {
+                        // Remove item from the mail being written
+                        self.show.mailbox_fields.attachments.retain(|s| *s != slot);
+                    } else if let Market(MarketSlot { slot: Some(_) }) = from {
+                        self.show.market_fields.slot = None;
                     }
                 },
                 slot::Event::SplitDropped(from) => {
@@ -4550,6 +4834,15 @@ impl Hud {
                     self.ui.focus_widget(None);
                 } else if self.show.trade {
                     self.events.push(Event::TradeAction(TradeAction::Decline));
+                } else if self.show.bank {
+                    self.show.bank(false);
+                    self.events.push(Event::CloseBank);
+                } else if self.show.mailbox {
+                    self.show.mailbox(false);
+                    self.events.push(Event::CloseMailbox);
+                } else if self.show.market {
+                    self.show.market(false);
+                    self.events.push(Event::CloseMarket);
                 } else {
                     // Close windows on esc
                     if self.show.bag {
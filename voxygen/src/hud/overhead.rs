@@ -686,7 +686,7 @@ fn bubble_color(bubble: &SpeechBubble, dark_mode: bool) -> (Color, Color) {
         SpeechBubbleType::Say => SAY_COLOR,
         SpeechBubbleType::Region => REGION_COLOR,
         SpeechBubbleType::Group => GROUP_COLOR,
-        SpeechBubbleType::Faction => FACTION_COLOR,
+        SpeechBubbleType::Guild => FACTION_COLOR,
         SpeechBubbleType::World
         | SpeechBubbleType::Quest
         | SpeechBubbleType::Trade
@@ -706,7 +706,7 @@ fn bubble_icon(sb: &SpeechBubble, imgs: &Imgs) -> conrod_core::image::Id {
         SpeechBubbleType::Say => imgs.chat_say_small,
         SpeechBubbleType::Region => imgs.chat_region_small,
         SpeechBubbleType::Group => imgs.chat_group_small,
-        SpeechBubbleType::Faction => imgs.chat_faction_small,
+        SpeechBubbleType::Guild => imgs.chat_faction_small,
         SpeechBubbleType::World => imgs.chat_world_small,
         SpeechBubbleType::Quest => imgs.nothing, // TODO not implemented
         SpeechBubbleType::Trade => imgs.nothing, // TODO not implemented
@@ -119,7 +119,7 @@ impl<'a> Widget for Popup<'a> {
             }
         }
 
-        // Push waypoint to message queue
+        // Push notifications to message queue
         for notification in self.new_notifications {
             match notification {
                 Notification::WaypointSaved => {
@@ -131,6 +131,23 @@ impl<'a> Widget for Popup<'a> {
                         s.infos.push_back(text.to_string());
                     });
                 },
+                Notification::AchievementUnlocked(achievement) => {
+                    state.update(|s| {
+                        if s.infos.is_empty() {
+                            s.last_info_update = Instant::now();
+                        }
+                        let title = self
+                            .i18n
+                            .get_msg(&format!("hud-achievement-{}", achievement));
+                        let text = self.i18n.get_msg_ctx(
+                            "hud-achievement_unlocked",
+                            &i18n::fluent_args! {
+                                "achievement" => &*title,
+                            },
+                        );
+                        s.infos.push_back(text.to_string());
+                    });
+                },
             }
         }
 
@@ -467,7 +467,7 @@ impl<'a> Widget for Chat<'a> {
                 .right_from(state.ids.text_messages_group, 5.0)
                 .set(state.ids.icon_messages_group, ui);
 
-            //Messages - faction
+            //Messages - guild
             if chat_tab.filter.message_faction
                 != create_toggle(
                     chat_tab.filter.message_faction,
@@ -480,8 +480,8 @@ impl<'a> Widget for Chat<'a> {
                 updated_chat_tab.filter.message_faction = !chat_tab.filter.message_faction;
             }
 
-            let faction_text = self.localized_strings.get_msg("hud-settings-faction");
-            create_toggle_text(&faction_text, !chat_tab.filter.message_all)
+            let guild_text = self.localized_strings.get_msg("hud-settings-guild");
+            create_toggle_text(&guild_text, !chat_tab.filter.message_all)
                 .right_from(state.ids.btn_messages_faction, 5.0)
                 .set(state.ids.text_messages_faction, ui);
 
@@ -10,8 +10,8 @@ use common::{
         ability::{Ability, AbilityInput, AuxiliaryAbility},
         item::tool::{AbilityContext, ToolKind},
         slot::{InvSlotId, Slot},
-        ActiveAbilities, Body, CharacterState, Combo, Energy, Inventory, Item, ItemKey, SkillSet,
-        Stance,
+        ActiveAbilities, Bank, Body, CharacterState, Combo, Energy, Inventory, Item, ItemKey,
+        SkillSet, Stance,
     },
     recipe::ComponentRecipeBook,
 };
@@ -29,6 +29,9 @@ pub enum SlotKind {
     Trade(TradeSlot),
     Ability(AbilitySlot),
     Crafting(CraftSlot),
+    Bank(BankSlot),
+    Mail(MailSlot),
+    Market(MarketSlot),
     /* Spellbook(SpellbookSlot), TODO */
 }
 
@@ -116,6 +119,85 @@ impl SlotKey<Inventory, ItemImgs> for TradeSlot {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BankSlot {
+    pub index: usize,
+}
+
+impl SlotKey<Bank, ItemImgs> for BankSlot {
+    type ImageKey = ItemKey;
+
+    fn image_key(&self, source: &Bank) -> Option<(Self::ImageKey, Option<Color>)> {
+        source.get(self.index).map(|i| (i.into(), None))
+    }
+
+    fn amount(&self, source: &Bank) -> Option<u32> {
+        source
+            .get(self.index)
+            .map(|item| item.amount())
+            .filter(|amount| *amount > 1)
+    }
+
+    fn image_ids(key: &Self::ImageKey, source: &ItemImgs) -> Vec<image::Id> {
+        source.img_ids_or_not_found_img(key.clone())
+    }
+}
+
+/// A slot for attaching an item in the inventory to a mail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MailSlot {
+    pub index: usize,
+    pub slot: Option<InvSlotId>,
+}
+
+impl SlotKey<Inventory, ItemImgs> for MailSlot {
+    type ImageKey = ItemKey;
+
+    fn image_key(&self, source: &Inventory) -> Option<(Self::ImageKey, Option<Color>)> {
+        self.slot
+            .and_then(|slot| source.get(slot))
+            .map(|i| (i.into(), None))
+    }
+
+    fn amount(&self, source: &Inventory) -> Option<u32> {
+        self.slot
+            .and_then(|slot| source.get(slot))
+            .map(|item| item.amount())
+            .filter(|amount| *amount > 1)
+    }
+
+    fn image_ids(key: &Self::ImageKey, source: &ItemImgs) -> Vec<image::Id> {
+        source.img_ids_or_not_found_img(key.clone())
+    }
+}
+
+/// A slot for choosing an item in the inventory to put up on a market board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MarketSlot {
+    pub slot: Option<InvSlotId>,
+}
+
+impl SlotKey<Inventory, ItemImgs> for MarketSlot {
+    type ImageKey = ItemKey;
+
+    fn image_key(&self, source: &Inventory) -> Option<(Self::ImageKey, Option<Color>)> {
+        self.slot
+            .and_then(|slot| source.get(slot))
+            .map(|i| (i.into(), None))
+    }
+
+    fn amount(&self, source: &Inventory) -> Option<u32> {
+        self.slot
+            .and_then(|slot| source.get(slot))
+            .map(|item| item.amount())
+            .filter(|amount| *amount > 1)
+    }
+
+    fn image_ids(key: &Self::ImageKey, source: &ItemImgs) -> Vec<image::Id> {
+        source.img_ids_or_not_found_img(key.clone())
+    }
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub enum HotbarImage {
     Item(ItemKey),
@@ -352,6 +434,18 @@ impl From<CraftSlot> for SlotKind {
     fn from(craft: CraftSlot) -> Self { Self::Crafting(craft) }
 }
 
+impl From<BankSlot> for SlotKind {
+    fn from(bank: BankSlot) -> Self { Self::Bank(bank) }
+}
+
+impl From<MailSlot> for SlotKind {
+    fn from(mail: MailSlot) -> Self { Self::Mail(mail) }
+}
+
+impl From<MarketSlot> for SlotKind {
+    fn from(market: MarketSlot) -> Self { Self::Market(market) }
+}
+
 impl SumSlot for SlotKind {
     fn drag_size(&self) -> Option<[f64; 2]> {
         Some(match self {
@@ -5,7 +5,7 @@ use common::{
         item::{
             armor::{Armor, ArmorKind, Protection},
             tool::{Hands, Tool, ToolKind},
-            Effects, Item, ItemDefinitionId, ItemDesc, ItemKind, MaterialKind,
+            CraftQuality, Effects, Item, ItemDefinitionId, ItemDesc, ItemKind, MaterialKind,
             MaterialStatManifest,
         },
         BuffKind,
@@ -100,6 +100,15 @@ pub fn material_kind_text<'a>(kind: &MaterialKind, i18n: &'a Localization) -> Co
     }
 }
 
+pub fn craft_quality_text<'a>(quality: CraftQuality, i18n: &'a Localization) -> Cow<'a, str> {
+    match quality {
+        CraftQuality::Crude => i18n.get_msg("common-craft_quality-crude"),
+        CraftQuality::Standard => i18n.get_msg("common-craft_quality-standard"),
+        CraftQuality::Fine => i18n.get_msg("common-craft_quality-fine"),
+        CraftQuality::Masterwork => i18n.get_msg("common-craft_quality-masterwork"),
+    }
+}
+
 pub fn stats_count(item: &dyn ItemDesc, msm: &MaterialStatManifest) -> usize {
     let mut count = match &*item.kind() {
         ItemKind::Armor(armor) => {
@@ -153,7 +162,24 @@ pub fn consumable_desc(effects: &Effects, i18n: &Localization) -> Vec<String> {
             descriptions.push(i18n.get_msg("buff-mysterious").into_owned());
         },
         Effects::All(_) | Effects::One(_) => {
+            // Recipe scrolls are described as a whole rather than listing every recipe
+            let learned_recipes = effects
+                .effects()
+                .iter()
+                .filter(|effect| matches!(effect, Effect::LearnRecipe(_)))
+                .count();
+            if learned_recipes > 0 {
+                descriptions.push(
+                    i18n.get_msg_ctx("buff-stat-learn_recipes", &i18n::fluent_args! {
+                        "count" => learned_recipes
+                    })
+                    .into_owned(),
+                );
+            }
             for effect in effects.effects() {
+                if matches!(effect, Effect::LearnRecipe(_)) {
+                    continue;
+                }
                 let mut description = String::new();
                 if let Effect::Buff(buff) = effect {
                     let strength = buff.data.strength;
@@ -109,6 +109,10 @@ impl GlobalState {
     #[cfg(not(feature = "singleplayer"))]
     pub fn paused(&self) -> bool { false }
 
+    /// The folder that recordings of sessions are saved to and played back
+    /// from
+    pub fn recordings_dir(&self) -> PathBuf { self.userdata_dir.join("voxygen").join("recordings") }
+
     #[cfg(feature = "singleplayer")]
     pub fn unpause(&self) { self.singleplayer.as_ref().map(|s| s.pause(false)); }
 
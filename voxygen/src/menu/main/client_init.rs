@@ -5,6 +5,7 @@ use client::{
 };
 use crossbeam_channel::{unbounded, Receiver, Sender, TryRecvError};
 use std::{
+    path::PathBuf,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -121,6 +122,35 @@ impl ClientInit {
         }
     }
 
+    /// Sets up a client that plays back a recording instead of connecting to a
+    /// server.
+    pub fn new_playback(path: PathBuf, runtime: Arc<runtime::Runtime>) -> Self {
+        let (tx, rx) = unbounded();
+        let (trust_tx, _) = unbounded();
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        let runtime2 = Arc::clone(&runtime);
+
+        runtime.spawn(async move {
+            let result = Client::new_playback(path, Arc::clone(&runtime2))
+                .await
+                .map_err(|error| Error::ClientError {
+                    error,
+                    mismatched_server_info: None,
+                });
+            let _ = tx.send(Msg::Done(result));
+
+            // Safe drop runtime
+            tokio::task::block_in_place(move || drop(runtime2));
+        });
+
+        ClientInit {
+            rx,
+            trust_tx,
+            cancel,
+        }
+    }
+
     /// Poll if the thread is complete.
     /// Returns None if the thread is still running, otherwise returns the
     /// Result of client creation.
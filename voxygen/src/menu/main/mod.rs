@@ -7,6 +7,7 @@ use super::char_selection::CharSelectionState;
 use crate::singleplayer::Singleplayer;
 use crate::{
     render::{Drawer, GlobalsBindGroup},
+    session::SessionState,
     settings::Settings,
     window::Event,
     Direction, GlobalState, PlayState, PlayStateResult,
@@ -17,11 +18,11 @@ use client::{
     Client, ServerInfo,
 };
 use client_init::{ClientInit, Error as InitError, Msg as InitMsg};
-use common::comp;
+use common::{comp, event::UpdateCharacterMetadata};
 use common_base::span;
 use i18n::LocalizationHandle;
 use scene::Scene;
-use std::sync::Arc;
+use std::{cell::RefCell, rc::Rc, sync::Arc};
 use tokio::runtime;
 use tracing::error;
 use ui::{Event as MainMenuEvent, MainMenuUi};
@@ -226,8 +227,13 @@ impl PlayState for MainMenuState {
             None => {},
         }
 
-        // Tick the client to keep the connection alive if we are waiting on pipelines
-        if let InitState::Pipeline(client) = &mut self.init {
+        // Tick the client to keep the connection alive if we are waiting on pipelines.
+        // Recordings have no connection, and shouldn't start playing before they can be
+        // watched.
+        if let Some(client) = match &mut self.init {
+            InitState::Pipeline(client) if client.playback().is_none() => Some(client),
+            _ => None,
+        } {
             match client.tick(
                 comp::ControllerInputs::default(),
                 global_state.clock.dt(),
@@ -275,10 +281,20 @@ impl PlayState for MainMenuState {
                     core::mem::replace(&mut self.init, InitState::None)
                 {
                     self.main_menu_ui.connected();
-                    return PlayStateResult::Push(Box::new(CharSelectionState::new(
-                        global_state,
-                        std::rc::Rc::new(std::cell::RefCell::new(*client)),
-                    )));
+                    let client = Rc::new(RefCell::new(*client));
+                    // There are no characters to select when watching a recording
+                    return if client.borrow().playback().is_some() {
+                        PlayStateResult::Push(Box::new(SessionState::new(
+                            global_state,
+                            UpdateCharacterMetadata::default(),
+                            client,
+                        )))
+                    } else {
+                        PlayStateResult::Push(Box::new(CharSelectionState::new(
+                            global_state,
+                            client,
+                        )))
+                    };
                 }
             }
         }
@@ -375,6 +391,14 @@ impl PlayState for MainMenuState {
                         .client()
                         .map(|init| init.auth_trust(auth_server, trust));
                 },
+                MainMenuEvent::WatchReplay(path) => {
+                    if let InitState::None = self.init {
+                        self.init = InitState::Client(ClientInit::new_playback(
+                            path,
+                            Arc::clone(&global_state.tokio_runtime),
+                        ));
+                    }
+                },
                 MainMenuEvent::DeleteServer { server_index } => {
                     let net_settings = &mut global_state.settings.networking;
                     net_settings.servers.remove(server_index);
@@ -493,6 +517,9 @@ fn get_client_msg_error(
             Error::Other(e) => {
                 format!("{}: {}", localization.get_msg("common-error"), e)
             },
+            Error::Replay(e) => {
+                format!("{}: {}", localization.get_msg("main-login-replay_error"), e)
+            },
             Error::AuthClientError(e) => match e {
                 // TODO: remove parentheses
                 client::AuthClientError::RequestError(e) => format!(
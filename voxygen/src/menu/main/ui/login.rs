@@ -26,6 +26,7 @@ pub struct Screen {
     quit_button: button::State,
     // settings_button: button::State,
     servers_button: button::State,
+    replays_button: button::State,
     credits_button: button::State,
     language_select_button: button::State,
 
@@ -39,6 +40,7 @@ impl Screen {
     pub fn new() -> Self {
         Self {
             servers_button: Default::default(),
+            replays_button: Default::default(),
             credits_button: Default::default(),
             // settings_button: Default::default(),
             quit_button: Default::default(),
@@ -92,6 +94,13 @@ impl Screen {
                 button_style,
                 Some(Message::OpenLanguageMenu),
             ),
+            neat_button(
+                &mut self.replays_button,
+                i18n.get_msg("main-replays"),
+                FILL_FRAC_ONE,
+                button_style,
+                Some(Message::ShowReplays),
+            ),
             neat_button(
                 &mut self.credits_button,
                 i18n.get_msg("main-credits"),
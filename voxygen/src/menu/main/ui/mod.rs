@@ -3,6 +3,7 @@ mod connecting;
 //mod disclaimer;
 mod credits;
 mod login;
+mod replays;
 mod servers;
 
 use crate::{
@@ -24,7 +25,7 @@ use keyboard_keynames::key_layout::KeyLayout;
 use crate::settings::Settings;
 use common::assets::{self, AssetExt};
 use rand::{seq::SliceRandom, thread_rng};
-use std::time::Duration;
+use std::{fs, path::PathBuf, time::Duration};
 use tracing::warn;
 
 // TODO: what is this? (showed up in rebase)
@@ -94,6 +95,7 @@ pub enum Event {
     DeleteServer {
         server_index: usize,
     },
+    WatchReplay(PathBuf),
 }
 
 pub struct LoginInfo {
@@ -123,6 +125,9 @@ enum Screen {
     Servers {
         screen: servers::Screen,
     },
+    Replays {
+        screen: replays::Screen,
+    },
     Connecting {
         screen: connecting::Screen,
         connection_state: ConnectionState,
@@ -147,6 +152,11 @@ struct Controls {
     selected_server_index: Option<usize>,
     login_info: LoginInfo,
 
+    // The folder recordings are listed from, and the recordings that were found there
+    recordings_dir: PathBuf,
+    replays: Vec<PathBuf>,
+    selected_replay_index: Option<usize>,
+
     is_selecting_language: bool,
     selected_language_index: Option<usize>,
 
@@ -160,6 +170,7 @@ enum Message {
     Quit,
     Back,
     ShowServers,
+    ShowReplays,
     ShowCredits,
     #[cfg(feature = "singleplayer")]
     Singleplayer,
@@ -171,6 +182,8 @@ enum Message {
     Password(String),
     Server(String),
     ServerChanged(usize),
+    ReplayChanged(usize),
+    WatchReplay,
     FocusPassword,
     CancelConnect,
     TrustPromptAdd,
@@ -189,6 +202,7 @@ impl Controls {
         i18n: LocalizationHandle,
         settings: &Settings,
         server: Option<String>,
+        recordings_dir: PathBuf,
     ) -> Self {
         let version = common::util::DISPLAY_VERSION_LONG.clone();
         let alpha = format!("Veloren {}", common::util::DISPLAY_VERSION.as_str());
@@ -237,6 +251,10 @@ impl Controls {
             selected_server_index,
             login_info,
 
+            recordings_dir,
+            replays: Vec::new(),
+            selected_replay_index: None,
+
             is_selecting_language: false,
             selected_language_index,
 
@@ -320,6 +338,14 @@ impl Controls {
                 &self.i18n.read(),
                 button_style,
             ),
+            Screen::Replays { screen } => screen.view(
+                &self.fonts,
+                &self.imgs,
+                &self.replays,
+                self.selected_replay_index,
+                &self.i18n.read(),
+                button_style,
+            ),
             Screen::Connecting {
                 screen,
                 connection_state,
@@ -373,6 +399,24 @@ impl Controls {
                     };
                 }
             },
+            Message::ShowReplays => {
+                if matches!(&self.screen, Screen::Login { .. }) {
+                    self.replays = fs::read_dir(&self.recordings_dir)
+                        .map(|entries| {
+                            entries
+                                .filter_map(|entry| Some(entry.ok()?.path()))
+                                .filter(|path| path.extension().map_or(false, |ext| ext == "bin"))
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    // Newest first, as recordings are named after when they were started
+                    self.replays.sort_unstable_by(|a, b| b.cmp(a));
+                    self.selected_replay_index = None;
+                    self.screen = Screen::Replays {
+                        screen: replays::Screen::new(),
+                    };
+                }
+            },
             Message::ShowCredits => {
                 self.screen = Screen::Credits {
                     screen: credits::Screen::new(),
@@ -412,6 +456,19 @@ impl Controls {
                 self.selected_server_index = Some(new_value);
                 self.login_info.server = servers[new_value].clone();
             },
+            Message::ReplayChanged(new_value) => self.selected_replay_index = Some(new_value),
+            Message::WatchReplay => {
+                if let Some(replay) = self
+                    .selected_replay_index
+                    .and_then(|index| self.replays.get(index))
+                {
+                    events.push(Event::WatchReplay(replay.clone()));
+                    self.screen = Screen::Connecting {
+                        screen: connecting::Screen::new(ui),
+                        connection_state: ConnectionState::InProgress,
+                    };
+                }
+            },
             Message::FocusPassword => {
                 if let Screen::Login { screen, .. } = &mut self.screen {
                     screen.banner.password = text_input::State::focused();
@@ -560,6 +617,7 @@ impl MainMenuUi {
             global_state.i18n,
             &global_state.settings,
             server,
+            global_state.recordings_dir(),
         );
 
         Self { ui, controls }
@@ -0,0 +1,162 @@
+use super::{Imgs, Message, FILL_FRAC_ONE};
+use crate::ui::{
+    fonts::IcedFonts as Fonts,
+    ice::{component::neat_button, style, Element},
+};
+use i18n::Localization;
+use iced::{
+    button, scrollable, Align, Button, Column, Container, Length, Row, Scrollable, Space, Text,
+};
+use std::path::PathBuf;
+
+pub struct Screen {
+    back_button: button::State,
+    watch_button: button::State,
+    replay_buttons: Vec<button::State>,
+    replays_list: scrollable::State,
+}
+
+impl Screen {
+    pub fn new() -> Self {
+        Self {
+            back_button: Default::default(),
+            watch_button: Default::default(),
+            replay_buttons: vec![],
+            replays_list: Default::default(),
+        }
+    }
+
+    pub(super) fn view(
+        &mut self,
+        fonts: &Fonts,
+        imgs: &Imgs,
+        replays: &[PathBuf],
+        selected_replay_index: Option<usize>,
+        i18n: &Localization,
+        button_style: style::button::Style,
+    ) -> Element<Message> {
+        let title = Text::new(i18n.get_msg("main-replays-select_replay"))
+            .size(fonts.cyri.scale(35))
+            .width(Length::Fill)
+            .horizontal_alignment(iced::HorizontalAlignment::Center);
+
+        let back_button = Container::new(
+            Container::new(neat_button(
+                &mut self.back_button,
+                i18n.get_msg("common-back"),
+                FILL_FRAC_ONE,
+                button_style,
+                Some(Message::Back),
+            ))
+            .max_width(200),
+        )
+        .width(Length::Fill)
+        .align_x(Align::Center);
+
+        let watch_button = Container::new(
+            Container::new(neat_button(
+                &mut self.watch_button,
+                i18n.get_msg("main-replays-watch"),
+                FILL_FRAC_ONE,
+                button_style,
+                selected_replay_index.map(|_| Message::WatchReplay),
+            ))
+            .max_width(200),
+        )
+        .width(Length::Fill)
+        .align_x(Align::Center);
+
+        let mut list = Scrollable::new(&mut self.replays_list)
+            .spacing(8)
+            .align_items(Align::Start)
+            .width(Length::Fill)
+            .height(Length::Fill);
+
+        // Reset button states if recordings were added / removed
+        if self.replay_buttons.len() != replays.len() {
+            self.replay_buttons = vec![Default::default(); replays.len()];
+        }
+
+        if replays.is_empty() {
+            list = list.push(
+                Text::new(i18n.get_msg("main-replays-none"))
+                    .size(fonts.cyri.scale(20))
+                    .width(Length::Fill)
+                    .horizontal_alignment(iced::HorizontalAlignment::Center),
+            );
+        }
+
+        let list_items =
+            self.replay_buttons
+                .iter_mut()
+                .zip(replays)
+                .enumerate()
+                .map(|(i, (state, replay))| {
+                    let color = if Some(i) == selected_replay_index {
+                        (97, 255, 18)
+                    } else {
+                        (97, 97, 25)
+                    };
+                    let name = replay
+                        .file_stem()
+                        .map(|name| name.to_string_lossy())
+                        .unwrap_or_default();
+                    let button = Button::new(
+                        state,
+                        Row::with_children(vec![
+                            Space::new(Length::FillPortion(5), Length::Units(0)).into(),
+                            Text::new(name)
+                                .size(fonts.cyri.scale(25))
+                                .width(Length::FillPortion(95))
+                                .vertical_alignment(iced::VerticalAlignment::Center)
+                                .into(),
+                        ]),
+                    )
+                    .style(
+                        style::button::Style::new(imgs.selection)
+                            .hover_image(imgs.selection_hover)
+                            .press_image(imgs.selection_press)
+                            .image_color(vek::Rgba::new(color.0, color.1, color.2, 255)),
+                    )
+                    .min_height(80)
+                    .on_press(Message::ReplayChanged(i));
+                    Row::with_children(vec![
+                        Space::new(Length::FillPortion(3), Length::Units(0)).into(),
+                        button.width(Length::FillPortion(92)).into(),
+                        Space::new(Length::FillPortion(5), Length::Units(0)).into(),
+                    ])
+                });
+
+        for item in list_items {
+            list = list.push(item);
+        }
+
+        Container::new(
+            Container::new(
+                Column::with_children(vec![
+                    title.into(),
+                    list.into(),
+                    Row::with_children(vec![watch_button.into(), back_button.into()])
+                        .width(Length::Fill)
+                        .into(),
+                ])
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .spacing(10)
+                .padding(20),
+            )
+            .style(
+                style::container::Style::color_with_double_cornerless_border(
+                    (22, 18, 16, 255).into(),
+                    (11, 11, 11, 255).into(),
+                    (54, 46, 38, 255).into(),
+                ),
+            )
+            .max_width(500),
+        )
+        .width(Length::Fill)
+        .align_x(Align::Center)
+        .padding(80)
+        .into()
+    }
+}
@@ -1561,6 +1561,21 @@ impl FigureMgr {
                                 skeleton_attr,
                             )
                         },
+                        CharacterState::Crafting(s) => {
+                            let sprite_pos = s.static_data.craft_sprite.pos;
+                            anim::character::CollectAnimation::update_skeleton(
+                                &target_base,
+                                (
+                                    pos.0,
+                                    time,
+                                    Some(StageSection::Action),
+                                    anim::vek::Vec3::from(sprite_pos.map(|x| x as f32)),
+                                ),
+                                s.timer.as_secs_f32(),
+                                &mut state_animation_rate,
+                                skeleton_attr,
+                            )
+                        },
                         CharacterState::Boost(_) => {
                             anim::character::AlphaAnimation::update_skeleton(
                                 &target_base,
@@ -13,7 +13,7 @@ use common::{
     consts::{MAX_PICKUP_RANGE, MAX_SPRITE_MOUNT_RANGE},
     link::Is,
     mounting::{Mount, Rider, VolumePos, VolumeRider},
-    terrain::{Block, TerrainGrid, UnlockKind},
+    terrain::{Block, SpriteKind, TerrainGrid, UnlockKind},
     uid::{Uid, UidAllocator},
     util::find_dist::{Cube, Cylinder, FindDist},
     vol::ReadVol,
@@ -73,7 +73,15 @@ impl Interactable {
                         // unlock_condition method so we hack around that by
                         // saying that it is a regular collectible sprite if
                         // `unlock_condition` returns UnlockKind::Free and the cfg was `None`.
-                        if sprite_cfg.is_some() || !matches!(&unlock_condition, UnlockKind::Free) {
+                        // Banks, mailboxes and market boards are always opened rather than
+                        // collected.
+                        if sprite_cfg.is_some()
+                            || !matches!(&unlock_condition, UnlockKind::Free)
+                            || matches!(
+                                sprite,
+                                SpriteKind::Bank | SpriteKind::Mailbox | SpriteKind::MarketBoard
+                            )
+                        {
                             Some(unlock_condition)
                         } else {
                             None
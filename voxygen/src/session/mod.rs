@@ -290,6 +290,8 @@ impl SessionState {
                     // not be grammatical in some languages)
                     let kind_str = match kind {
                         InviteKind::Group => "Group",
+                        InviteKind::Guild => "Guild",
+                        InviteKind::Duel => "Duel",
                         InviteKind::Trade => "Trade",
                     };
                     let target_name = match client.player_list().get(&target) {
@@ -1702,6 +1704,56 @@ impl PlayState for SessionState {
                     HudEvent::TradeAction(action) => {
                         self.client.borrow_mut().perform_trade_action(action);
                     },
+                    HudEvent::BankDeposit(slot) => {
+                        self.client.borrow_mut().bank_deposit(slot);
+                    },
+                    HudEvent::BankWithdraw(bank_slot) => {
+                        self.client.borrow_mut().bank_withdraw(bank_slot);
+                    },
+                    HudEvent::CloseBank => {
+                        self.client.borrow_mut().close_bank();
+                    },
+                    HudEvent::SendMail {
+                        recipient,
+                        body,
+                        attachments,
+                    } => {
+                        self.client
+                            .borrow_mut()
+                            .send_mail(recipient, body, attachments);
+                    },
+                    HudEvent::ClaimMail(mail_id) => {
+                        self.client.borrow_mut().claim_mail(mail_id);
+                    },
+                    HudEvent::CloseMailbox => {
+                        self.client.borrow_mut().close_mailbox();
+                    },
+                    HudEvent::PostSellOrder {
+                        slot,
+                        quantity,
+                        unit_price,
+                    } => {
+                        self.client
+                            .borrow_mut()
+                            .post_sell_order(slot, quantity, unit_price);
+                    },
+                    HudEvent::PostBuyOrder {
+                        item_definition_id,
+                        quantity,
+                        unit_price,
+                    } => {
+                        self.client.borrow_mut().post_buy_order(
+                            item_definition_id,
+                            quantity,
+                            unit_price,
+                        );
+                    },
+                    HudEvent::CancelMarketOrder(order_id) => {
+                        self.client.borrow_mut().cancel_market_order(order_id);
+                    },
+                    HudEvent::CloseMarket => {
+                        self.client.borrow_mut().close_market();
+                    },
                     HudEvent::Ability(i, state) => {
                         self.client.borrow_mut().handle_input(
                             InputKind::Ability(i),
@@ -32,12 +32,12 @@ impl ChatFilter {
             },
             ChatType::CommandInfo | ChatType::CommandError => true,
             ChatType::Kill(_, u) => self.death_all || self.death_group && group_members.contains(u),
-            ChatType::GroupMeta(_) => true,   //todo
-            ChatType::FactionMeta(_) => true, //todo
+            ChatType::GroupMeta(_) => true, //todo
+            ChatType::GuildMeta(_) => true, //todo
             ChatType::Tell(..) => true,
             ChatType::Say(_) => self.message_all || self.message_say,
             ChatType::Group(..) => self.message_all || self.message_group,
-            ChatType::Faction(..) => self.message_all || self.message_faction,
+            ChatType::Guild(..) => self.message_all || self.message_faction,
             ChatType::Region(_) => self.message_all || self.message_region,
             ChatType::World(_) => self.message_all || self.message_world,
             ChatType::Npc(..) => true,
@@ -9,8 +9,8 @@ use client::Client;
 use common::{
     comp::{
         item::{
-            armor::Protection, item_key::ItemKey, modular::ModularComponent, Item, ItemDesc,
-            ItemKind, ItemTag, MaterialStatManifest, Quality,
+            armor::Protection, item_key::ItemKey, modular::ModularComponent, CraftQuality, Item,
+            ItemDesc, ItemKind, ItemTag, MaterialStatManifest, Quality,
         },
         Energy,
     },
@@ -482,6 +482,15 @@ impl<'a> Widget for ItemTooltip<'a> {
         } else {
             item_kind
         };
+        // Only crafted items that turned out better or worse than usual are marked
+        let subtitle = match item.craft_quality() {
+            CraftQuality::Standard => subtitle,
+            craft_quality => format!(
+                "{}, {}",
+                subtitle,
+                util::craft_quality_text(craft_quality, i18n)
+            ),
+        };
 
         let style = self.style.desc;
 
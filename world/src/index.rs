@@ -5,12 +5,13 @@ use crate::{
 };
 use common::{
     assets::{AssetExt, AssetHandle},
-    store::Store,
+    store::{Id, Store},
     trade::{SiteId, SitePrices},
 };
 use core::ops::Deref;
 use noise::{Fbm, Seedable, SuperSimplex};
 use std::sync::Arc;
+use vek::*;
 
 const WORLD_COLORS_MANIFEST: &str = "world.style.colors";
 const WORLD_FEATURES_MANIFEST: &str = "world.features";
@@ -92,6 +93,18 @@ impl Index {
             .map(|i| self.sites.get(i))
             .map(|s| s.economy.get_site_prices())
     }
+
+    /// Finds the site that the given position is within, picking the nearest
+    /// one if it's within several
+    pub fn site_at(&self, pos: Vec2<i32>) -> Option<Id<Site>> {
+        self.sites
+            .iter()
+            .filter(|(_, site)| {
+                site.get_origin().as_::<f32>().distance_squared(pos.as_()) <= site.radius().powi(2)
+            })
+            .min_by_key(|(_, site)| site.get_origin().distance_squared(pos))
+            .map(|(id, _)| id)
+    }
 }
 
 impl IndexOwned {
@@ -525,6 +525,12 @@ pub fn apply_wildlife_supplement<'a, R: Rng>(
                     (wpos2d.map(|e| e as f32) + 0.5).with_z(desired_alt),
                     dynamic_rng,
                 );
+                // Packs fight and roam together
+                let entity = if group_size > 1 {
+                    entity.with_squad(wpos2d)
+                } else {
+                    entity
+                };
                 for e in 0..group_size {
                     // Choose a nearby position
                     let offs_wpos2d = (Vec2::new(
@@ -46,7 +46,7 @@ use crate::{
 use common::{
     assets,
     calendar::Calendar,
-    generation::{ChunkSupplement, EntityInfo},
+    generation::{ChunkSupplement, DungeonLayout, EntityInfo},
     lod,
     resources::TimeOfDay,
     rtsim::ChunkResource,
@@ -136,6 +136,42 @@ impl World {
         // TODO
     }
 
+    fn dungeons<'a>(index: IndexRef<'a>) -> impl Iterator<Item = &'a site2::plot::Dungeon> {
+        index
+            .index
+            .sites
+            .values()
+            .filter_map(|site| match &site.kind {
+                SiteKind::Dungeon(site2) => Some(site2),
+                _ => None,
+            })
+            .flat_map(|site2| site2.plots())
+            .filter_map(|plot| match plot.kind() {
+                site2::plot::PlotKind::Dungeon(dungeon) => Some(dungeon),
+                _ => None,
+            })
+    }
+
+    /// The layouts of all dungeons in the world
+    pub fn dungeon_layouts(&self, index: IndexRef) -> Vec<DungeonLayout> {
+        Self::dungeons(index).map(|dungeon| dungeon.layout()).collect()
+    }
+
+    /// The entities that live in the dungeon at `origin`, moved up or down by
+    /// `z_offset`
+    pub fn dungeon_entities(
+        &self,
+        index: IndexRef,
+        origin: Vec2<i32>,
+        z_offset: i32,
+        rng: &mut impl Rng,
+    ) -> Vec<EntityInfo> {
+        Self::dungeons(index)
+            .find(|dungeon| dungeon.get_origin() == origin)
+            .map(|dungeon| dungeon.instance_entities(rng, z_offset))
+            .unwrap_or_default()
+    }
+
     pub fn get_map_data(&self, index: IndexRef, threadpool: &rayon::ThreadPool) -> WorldMapMsg {
         prof_span!("World::get_map_data");
         threadpool.install(|| {
@@ -1688,6 +1688,7 @@ impl WorldSim {
             sites: Vec::new(),                   // Will be substituted later
             pois: Vec::new(),                    // Will be substituted later
             possible_starting_sites: Vec::new(), // Will be substituted later
+            factions: Default::default(),        // Will be substituted later
             default_chunk: Arc::new(self.generate_oob_chunk()),
         }
     }
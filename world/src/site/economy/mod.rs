@@ -328,6 +328,7 @@ impl Economy {
                 });
                 prices.iter().map(|(g, v)| (Good::from(g), *v)).collect()
             },
+            reputation_bonus: 0.0,
         }
     }
 
@@ -706,6 +706,10 @@ impl Settlement {
                                         }
                                     });
                                 }
+                                // Leave some of the furrow tilled, so that crops can be planted
+                                if surface_sprite.is_none() && roll(10, 4) == 0 {
+                                    surface_sprite = Some(SpriteKind::TilledSoil);
+                                }
                             } else if roll(0, 20) == 0 {
                                 surface_sprite = Some(SpriteKind::ShortGrass);
                             } else if roll(1, 30) == 0 {
@@ -1201,6 +1201,23 @@ impl Site {
                                     .with_alignment(Alignment::Tame),
                             );
                         }
+                        // Every plaza has a market board at its centre
+                        if wpos2d == twpos_center
+                            && matches!(tile.kind, TileKind::Plaza)
+                            && tile.plot.map_or(false, |p| self.plot(p).root_tile == tpos)
+                        {
+                            let mut placed = false;
+                            for z in -8..6 {
+                                canvas.map(Vec3::new(wpos2d.x, wpos2d.y, alt + z), |b| {
+                                    if !placed && !b.is_filled() {
+                                        placed = true;
+                                        b.with_sprite(SpriteKind::MarketBoard)
+                                    } else {
+                                        b
+                                    }
+                                });
+                            }
+                        }
                     }
                 });
             },
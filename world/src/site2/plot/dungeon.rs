@@ -9,7 +9,7 @@ use crate::{
 use common::{
     assets::{self, AssetExt, AssetHandle},
     astar::Astar,
-    generation::{ChunkSupplement, EntityInfo},
+    generation::{ChunkSupplement, DungeonLayout, EntityInfo},
     store::{Id, Store},
     terrain::{
         BiomeKind, Block, BlockKind, SpriteKind, Structure, StructuresGroup, TerrainChunkSize,
@@ -128,6 +128,55 @@ impl Dungeon {
 
     pub fn difficulty(&self) -> u32 { self.difficulty }
 
+    fn waypoint_pos(&self) -> Vec3<f32> {
+        let pos = self.origin.map2(FLOOR_SIZE, |e, sz| e + sz / 2);
+        Vec3::new(pos.x as f32, pos.y as f32, self.alt as f32) + 5.0
+    }
+
+    pub fn layout(&self) -> DungeonLayout {
+        let top = self.alt + ALT_OFFSET;
+        let bottom = top - self.floors.iter().map(Floor::total_depth).sum::<i32>();
+        let top_floor = self
+            .floors
+            .first()
+            .map_or(top, |floor| top - floor.total_depth());
+        // The first floor's stairs come down in the middle tile, so players
+        // arrive one tile to the side of them
+        let arrival = self.origin + TILE_SIZE / 2 + Vec2::new(TILE_SIZE, 0);
+        let half_size = FLOOR_SIZE * TILE_SIZE / 2;
+
+        DungeonLayout {
+            origin: self.origin,
+            name: self.name.clone(),
+            entrance: self.waypoint_pos(),
+            top_floor: arrival.with_z(top_floor + 1).map(|e| e as f32),
+            bounds: Aabb {
+                // Boss rooms and lava pits dig below the floor they are on
+                min: (self.origin - half_size).with_z(bottom - 8),
+                max: (self.origin + half_size).with_z(top),
+            },
+        }
+    }
+
+    /// All entities in the dungeon, as if every chunk of it was loaded at
+    /// once, moved up or down by `z_offset`.
+    pub fn instance_entities(&self, dynamic_rng: &mut impl Rng, z_offset: i32) -> Vec<EntityInfo> {
+        let half_size = FLOOR_SIZE * TILE_SIZE / 2;
+        let area = Aabr {
+            min: -half_size,
+            max: half_size,
+        };
+
+        let mut supplement = ChunkSupplement::default();
+        let mut z = self.alt + ALT_OFFSET;
+        for floor in &self.floors {
+            z -= floor.total_depth();
+            let origin = Vec3::new(self.origin.x, self.origin.y, z + z_offset);
+            floor.apply_supplement(dynamic_rng, area, origin, &mut supplement);
+        }
+        supplement.entities
+    }
+
     pub fn apply_supplement(
         &self,
         // NOTE: Used only for dynamic elements like chests and entities!
@@ -144,10 +193,7 @@ impl Dungeon {
         // Add waypoint
         let pos = self.origin.map2(FLOOR_SIZE, |e, sz| e + sz / 2);
         if area.contains_point(pos - self.origin) {
-            supplement.add_entity(
-                EntityInfo::at(Vec3::new(pos.x as f32, pos.y as f32, self.alt as f32) + 5.0)
-                    .into_waypoint(),
-            );
+            supplement.add_entity(EntityInfo::at(self.waypoint_pos()).into_waypoint());
         }
 
         let mut z = self.alt + ALT_OFFSET;
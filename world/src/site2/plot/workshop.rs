@@ -129,6 +129,8 @@ impl Structure for Workshop {
             SpriteKind::Anvil,
             SpriteKind::DismantlingBench,
             SpriteKind::RepairBench,
+            SpriteKind::Bank,
+            SpriteKind::Mailbox,
         ];
         'outer: for d in 0..3 {
             for dir in CARDINALS {